@@ -38,6 +38,9 @@ const PROBE: &str = r#"
 "#;
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(error_generic_member_access)");
+    println!("cargo:rustc-check-cfg=cfg(thiserror_nightly_testing)");
+
     match compile_probe() {
         Some(status) if status.success() => println!("cargo:rustc-cfg=error_generic_member_access"),
         _ => {}
@@ -0,0 +1,33 @@
+// Errors derived in a crate of their own, so that the conformance tests call
+// their throw methods from outside it, as downstream crates do.
+
+use std::io;
+use std::num::{ParseIntError, TryFromIntError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[throws(vis = "pub")]
+pub enum StoreError {
+    #[error("failed to open {path}")]
+    Open { path: String, source: io::Error },
+    #[error("corrupt record {1}")]
+    Corrupt(#[source] ParseIntError, u64),
+    #[error("record out of range")]
+    Range(#[from] TryFromIntError),
+}
+
+#[derive(Error, Debug)]
+#[throws(vis = "pub")]
+#[error("replica {replica} failed")]
+pub struct ReplicaError {
+    pub replica: u32,
+    pub source: StoreError,
+}
+
+#[derive(Error, Debug)]
+#[throws(vis = "pub")]
+#[error("cache miss")]
+pub struct CacheError<K: std::fmt::Debug> {
+    pub key: K,
+    pub source: io::Error,
+}
@@ -0,0 +1,134 @@
+//! Generated tests for the `throw_*` methods of errors deriving
+//! `thiserror::Error`.
+//!
+//! Every source-carrying struct and enum variant gets a throw method on
+//! `Result<T, Source>`. Whether that method resolves, against which source
+//! type, and which variant it builds are easy to break without noticing
+//! until a downstream crate fails to compile. The [`throws!`] macro turns a
+//! list of expected throw methods into one test per method.
+//!
+//! ```
+//! # fn main() {}
+//! use std::io;
+//! use thiserror::Error;
+//!
+//! #[derive(Error, Debug)]
+//! pub enum QueryError {
+//!     #[error("failed to connect")]
+//!     Connect { source: io::Error },
+//!     #[error("failed to read {path}")]
+//!     Read { path: String, source: io::Error },
+//! }
+//!
+//! #[cfg(test)]
+//! mod query_error {
+//!     use super::*;
+//!
+//!     thiserror_conformance::throws! {
+//!         QueryError {
+//!             throw_connect()
+//!                 on io::Error = io::Error::from(io::ErrorKind::ConnectionRefused)
+//!                 => QueryError::Connect { .. },
+//!             throw_read("/etc/app.toml".to_owned())
+//!                 on io::Error = io::Error::from(io::ErrorKind::NotFound)
+//!                 => QueryError::Read { .. },
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Throw methods live on traits private to the module of the derive, so the
+//! macro needs to be invoked in that module or one nested inside it. Each
+//! generated test is named after its throw method.
+//!
+//! Regressions in visibility and coherence only show up outside the crate
+//! that derives the error, though. With `#[throws(vis = "pub")]` the traits
+//! are public, and the tests are best generated in a separate crate, such as
+//! an integration test of a crate depending on the one with the errors, with
+//! the traits imported:
+//!
+//! ```
+//! # const IGNORE: &str = stringify! {
+//! use app_errors::{QueryError, QueryErrorConnectThrows, QueryErrorReadThrows};
+//! use std::io;
+//!
+//! thiserror_conformance::throws! {
+//!     QueryError {
+//!         throw_connect()
+//!             on io::Error = io::Error::from(io::ErrorKind::ConnectionRefused)
+//!             => QueryError::Connect { .. },
+//!         throw_read("/etc/app.toml".to_owned())
+//!             on io::Error = io::Error::from(io::ErrorKind::NotFound)
+//!             => QueryError::Read { .. },
+//!     }
+//! }
+//! # };
+//! ```
+
+#![doc(html_root_url = "https://docs.rs/thiserror-conformance/1.0.50")]
+
+/// Generates a test for each listed throw method of an error type.
+///
+/// Each entry names the throw method with its arguments, the source type it
+/// is expected to be implemented for, a source value, and a pattern for the
+/// variant it must produce. The generated test checks that:
+///
+/// - the method is callable on `Result<T, Source>` and returns
+///   `Result<T, Error>`,
+/// - an `Ok` value passes through untouched,
+/// - an `Err` becomes an error matching the pattern,
+/// - the original source is reported by `Error::source()`.
+#[macro_export]
+macro_rules! throws {
+    (
+        $error:ty {
+            $(
+                $method:ident ( $($arg:expr),* $(,)? )
+                    on $source_ty:ty = $source:expr
+                    => $variant:pat
+            ),* $(,)?
+        }
+    ) => {
+        $(
+            #[test]
+            fn $method() {
+                let ok: ::core::result::Result<u8, $source_ty> = ::core::result::Result::Ok(1);
+                let ok: ::core::result::Result<u8, $error> = ok.$method($($arg),*);
+                ::core::assert!(
+                    ::core::matches!(ok, ::core::result::Result::Ok(1)),
+                    "{} did not pass an Ok value through",
+                    ::core::stringify!($method),
+                );
+
+                let source: $source_ty = $source;
+                let expected = ::std::string::ToString::to_string(&source);
+                let err: ::core::result::Result<u8, $source_ty> = ::core::result::Result::Err(source);
+                let error = match err.$method($($arg),*) {
+                    ::core::result::Result::Ok(_) => {
+                        ::core::panic!("{} turned an Err into Ok", ::core::stringify!($method))
+                    }
+                    ::core::result::Result::Err(error) => error,
+                };
+                ::core::assert!(
+                    ::core::matches!(error, $variant),
+                    "{} produced {:?}, expected {}",
+                    ::core::stringify!($method),
+                    error,
+                    ::core::stringify!($variant),
+                );
+                let error: &$error = &error;
+                match ::std::error::Error::source(error) {
+                    ::core::option::Option::Some(source) => ::core::assert_eq!(
+                        expected,
+                        ::std::string::ToString::to_string(source),
+                        "{} reported a different source",
+                        ::core::stringify!($method),
+                    ),
+                    ::core::option::Option::None => {
+                        ::core::panic!("{} lost its source", ::core::stringify!($method))
+                    }
+                }
+            }
+        )*
+    };
+}
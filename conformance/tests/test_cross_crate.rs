@@ -0,0 +1,51 @@
+// Throw methods of errors derived in another crate, called through their
+// public traits the way a downstream crate calls them.
+
+use std::io;
+use std::num::{ParseIntError, TryFromIntError};
+use thiserror_conformance_fixture::{
+    CacheError, CacheErrorThrows, ReplicaError, ReplicaErrorThrows, StoreError,
+    StoreErrorCorruptThrows, StoreErrorOpenThrows, StoreErrorRangeThrows,
+};
+
+mod store_error {
+    use super::*;
+
+    thiserror_conformance::throws! {
+        StoreError {
+            throw_open("/var/db".to_owned())
+                on io::Error = io::Error::from(io::ErrorKind::PermissionDenied)
+                => StoreError::Open { .. },
+            throw_corrupt(9)
+                on ParseIntError = "x".parse::<u64>().unwrap_err()
+                => StoreError::Corrupt(_, 9),
+            throw_range()
+                on TryFromIntError = u8::try_from(300).unwrap_err()
+                => StoreError::Range(_),
+        }
+    }
+}
+
+mod replica_error {
+    use super::*;
+
+    thiserror_conformance::throws! {
+        ReplicaError {
+            throw_replica(2)
+                on StoreError = StoreError::from(u8::try_from(-1).unwrap_err())
+                => ReplicaError { replica: 2, .. },
+        }
+    }
+}
+
+mod cache_error {
+    use super::*;
+
+    thiserror_conformance::throws! {
+        CacheError<u64> {
+            throw_cache(42)
+                on io::Error = io::Error::from(io::ErrorKind::NotFound)
+                => CacheError { key: 42, .. },
+        }
+    }
+}
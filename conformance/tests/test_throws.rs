@@ -0,0 +1,69 @@
+use std::io;
+use std::num::{ParseIntError, TryFromIntError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("failed to read {path}")]
+    Read { path: String, source: io::Error },
+    #[error("invalid number on line {1}")]
+    Parse(#[source] ParseIntError, usize),
+    #[error("number out of range")]
+    Range(#[from] TryFromIntError),
+}
+
+#[derive(Error, Debug)]
+#[error("worker {id} failed")]
+pub struct WorkerError {
+    id: u32,
+    source: LoadError,
+}
+
+#[derive(Error, Debug)]
+#[error("lookup failed")]
+pub struct LookupError<K: std::fmt::Debug> {
+    key: K,
+    source: io::Error,
+}
+
+mod load_error {
+    use super::*;
+
+    thiserror_conformance::throws! {
+        LoadError {
+            throw_read("/etc/count".to_owned())
+                on io::Error = io::Error::from(io::ErrorKind::NotFound)
+                => LoadError::Read { .. },
+            throw_parse(3)
+                on ParseIntError = "x".parse::<i32>().unwrap_err()
+                => LoadError::Parse(_, 3),
+            throw_range()
+                on TryFromIntError = u8::try_from(256).unwrap_err()
+                => LoadError::Range(_),
+        }
+    }
+}
+
+mod worker_error {
+    use super::*;
+
+    thiserror_conformance::throws! {
+        WorkerError {
+            throw_worker(7)
+                on LoadError = LoadError::from(u8::try_from(-1).unwrap_err())
+                => WorkerError { id: 7, .. },
+        }
+    }
+}
+
+mod lookup_error {
+    use super::*;
+
+    thiserror_conformance::throws! {
+        LookupError<&'static str> {
+            throw_lookup("user")
+                on io::Error = io::Error::from(io::ErrorKind::NotFound)
+                => LookupError { key: "user", .. },
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use crate::ast::{Enum, Field, Struct, Variant};
+use crate::attr::Attrs;
+use crate::fields::snake_case;
+use crate::generics::ParamsInScope;
+use crate::prop::{self, type_parameter_of};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{Ident, Member, Type};
+
+// `#[naur(accessors)]`: is_*, as_*, and into_* methods probing for a single
+// variant without a full match.
+pub fn accessor_methods(input: &Enum) -> Option<TokenStream> {
+    input.attrs.accessors?;
+    let ty = &input.ident;
+    let mut methods = TokenStream::new();
+    for variant in &input.variants {
+        let ident = &variant.ident;
+        let name = snake_case(&ident.unraw().to_string());
+        let is_method = format_ident!("is_{}", name);
+        let doc = format!(" Whether this is `{}::{}`.", ty, ident.unraw());
+        methods.extend(quote! {
+            #[doc = #doc]
+            pub fn #is_method(&self) -> bool {
+                #[allow(deprecated)]
+                ::core::matches!(self, #ty::#ident {..})
+            }
+        });
+        if variant.fields.is_empty() {
+            continue;
+        }
+
+        let (members, bindings, ref_ty, owned_ty, value) = variant_fields(variant);
+        let as_method = format_ident!("as_{}", name);
+        let into_method = format_ident!("into_{}", name);
+        let as_doc = format!(
+            " The fields of `{}::{}`, if this is that variant.",
+            ty,
+            ident.unraw(),
+        );
+        let into_doc = format!(
+            " Converts into the fields of `{}::{}`, if this is that variant.",
+            ty,
+            ident.unraw(),
+        );
+        methods.extend(quote! {
+            #[doc = #as_doc]
+            pub fn #as_method(&self) -> ::core::option::Option<#ref_ty> {
+                #[allow(deprecated, unreachable_patterns)]
+                match self {
+                    #ty::#ident { #(#members: #bindings,)* } => ::core::option::Option::Some(#value),
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #[doc = #into_doc]
+            pub fn #into_method(self) -> ::core::option::Option<#owned_ty> {
+                #[allow(deprecated, unreachable_patterns)]
+                match self {
+                    #ty::#ident { #(#members: #bindings,)* } => ::core::option::Option::Some(#value),
+                    _ => ::core::option::Option::None,
+                }
+            }
+        });
+    }
+    Some(methods)
+}
+
+// On a struct, `#[naur(accessors)]` generates into_source and into_parts,
+// which take the error apart for recovery code that wants its pieces back.
+pub fn struct_into_methods(input: &Struct) -> Option<TokenStream> {
+    input.attrs.accessors?;
+    let mut methods = TokenStream::new();
+    if let Some(source) = source_of(&input.attrs, &input.fields) {
+        let member = &source.member;
+        let source_ty = source.ty;
+        methods.extend(quote! {
+            /// Converts into the error's source, dropping the rest of it.
+            pub fn into_source(self) -> #source_ty {
+                self.#member
+            }
+        });
+    }
+    let parts = parts_of(&input.attrs, &input.fields);
+    if !parts.is_empty() {
+        let (members, bindings, parts_ty, value) = parts_fields(&parts);
+        methods.extend(quote! {
+            /// Converts into the error's fields other than its backtrace, in
+            /// declaration order with the source moved last.
+            pub fn into_parts(self) -> #parts_ty {
+                #[allow(deprecated)]
+                let Self { #(#members: #bindings,)* .. } = self;
+                #value
+            }
+        });
+    }
+    Some(methods)
+}
+
+// On an enum, `#[naur(accessors)]` generates into_source, returning the
+// source type shared by every variant with a source or else a boxed error, and
+// into_*_parts for each variant with fields.
+pub fn enum_into_methods(input: &Enum) -> Option<TokenStream> {
+    input.attrs.accessors?;
+    let ty = &input.ident;
+    let mut methods = TokenStream::new();
+    methods.extend(enum_into_source_method(input));
+    for variant in &input.variants {
+        let parts = parts_of(&variant.attrs, &variant.fields);
+        if parts.is_empty() {
+            continue;
+        }
+        let ident = &variant.ident;
+        let name = snake_case(&ident.unraw().to_string());
+        let method = format_ident!("into_{}_parts", name);
+        let doc = format!(
+            " Converts into the fields of `{}::{}` other than its backtrace, with the source moved last, if this is that variant.",
+            ty,
+            ident.unraw(),
+        );
+        let (members, bindings, parts_ty, value) = parts_fields(&parts);
+        methods.extend(quote! {
+            #[doc = #doc]
+            pub fn #method(self) -> ::core::option::Option<#parts_ty> {
+                #[allow(deprecated, unreachable_patterns)]
+                match self {
+                    #ty::#ident { #(#members: #bindings,)* .. } => ::core::option::Option::Some(#value),
+                    _ => ::core::option::Option::None,
+                }
+            }
+        });
+    }
+    Some(methods)
+}
+
+fn enum_into_source_method(input: &Enum) -> Option<TokenStream> {
+    // A variant named Source already has its fields returned by into_source.
+    if input
+        .variants
+        .iter()
+        .any(|variant| snake_case(&variant.ident.unraw().to_string()) == "source")
+    {
+        return None;
+    }
+    let sources: Vec<(&Variant, &Field)> = input
+        .variants
+        .iter()
+        .filter_map(|variant| Some((variant, source_of(&variant.attrs, &variant.fields)?)))
+        .collect();
+    let source_ty = unoptional(sources.first()?.1.ty);
+    let type_name = |ty: &Type| ty.to_token_stream().to_string();
+    let mixed = sources
+        .iter()
+        .any(|(_, field)| type_name(unoptional(field.ty)) != type_name(source_ty));
+    let ty = &input.ident;
+    if mixed {
+        return Some(enum_into_boxed_source_method(input, &sources));
+    }
+    let arms = sources.iter().map(|(variant, field)| {
+        let ident = &variant.ident;
+        let member = &field.member;
+        let value = if field.is_option() {
+            quote!(source)
+        } else {
+            quote!(::core::option::Option::Some(source))
+        };
+        quote!(#ty::#ident { #member: source, .. } => #value,)
+    });
+    Some(quote! {
+        /// Converts into the error's source, if this variant has one,
+        /// dropping the rest of it.
+        pub fn into_source(self) -> ::core::option::Option<#source_ty> {
+            #[allow(deprecated, unreachable_patterns)]
+            match self {
+                #(#arms)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    })
+}
+
+// Variants holding different types of source have them boxed, which requires
+// each to be Send + Sync + 'static like any error boxed for sending across
+// threads. For a type involving the enum's type parameters that is a bound on
+// the method, and otherwise the type must meet it outright.
+fn enum_into_boxed_source_method(input: &Enum, sources: &[(&Variant, &Field)]) -> TokenStream {
+    let ty = &input.ident;
+    let boxed = quote! {
+        ::std::boxed::Box<dyn ::std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static>
+    };
+    let params_in_scope = ParamsInScope::new(input.generics);
+    let mut bounds = Vec::new();
+    for (_, field) in sources {
+        let source_ty = unoptional(field.ty);
+        if params_in_scope.intersects(source_ty) {
+            bounds.push(quote!(#source_ty: ::core::convert::Into<#boxed>));
+        }
+    }
+    let where_clause = (!bounds.is_empty()).then(|| quote!(where #(#bounds,)*));
+    let arms = sources.iter().map(|(variant, field)| {
+        let ident = &variant.ident;
+        let member = &field.member;
+        let value = if field.is_option() {
+            quote!(source.map(::core::convert::Into::into))
+        } else {
+            quote!(::core::option::Option::Some(::core::convert::Into::into(
+                source
+            )))
+        };
+        quote!(#ty::#ident { #member: source, .. } => #value,)
+    });
+    quote! {
+        /// Converts into the error's source, if this variant has one,
+        /// dropping the rest of it. The variants hold different types of
+        /// source, so it is boxed.
+        pub fn into_source(self) -> ::core::option::Option<#boxed> #where_clause {
+            #[allow(deprecated, unreachable_patterns)]
+            match self {
+                #(#arms)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    }
+}
+
+// The field holding the error's source, which for a transparent error is its
+// only field.
+fn source_of<'a, 'b>(attrs: &Attrs, fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    if attrs.transparent.is_some() {
+        prop::transparent_field(fields)
+    } else {
+        prop::source_field(fields)
+    }
+}
+
+// The fields other than a backtrace of its own, with the source moved last.
+fn parts_of<'a, 'b>(attrs: &Attrs, fields: &'a [Field<'b>]) -> Vec<&'a Field<'b>> {
+    let source = source_of(attrs, fields);
+    let backtrace = prop::backtrace_field(fields);
+    let mut parts: Vec<&Field> = fields
+        .iter()
+        .filter(|field| {
+            let is_source = source.map_or(false, |source| source.member == field.member);
+            let is_backtrace =
+                backtrace.map_or(false, |backtrace| backtrace.member == field.member);
+            !is_source && !is_backtrace
+        })
+        .collect();
+    parts.extend(source);
+    parts
+}
+
+fn unoptional(ty: &Type) -> &Type {
+    type_parameter_of(ty, "Option").unwrap_or(ty)
+}
+
+// The members of the parts with a binding for each, and the type and value
+// they are returned as: a single part directly, or several as a tuple.
+fn parts_fields(parts: &[&Field]) -> (Vec<Member>, Vec<Ident>, TokenStream, TokenStream) {
+    let members: Vec<_> = parts.iter().map(|field| field.member.clone()).collect();
+    let bindings: Vec<_> = (0..members.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    let types: Vec<_> = parts.iter().map(|field| field.ty).collect();
+    let (ty, value) = if types.len() == 1 {
+        let part_ty = types[0];
+        let binding = &bindings[0];
+        (quote!(#part_ty), quote!(#binding))
+    } else {
+        (quote!((#(#types),*)), quote!((#(#bindings),*)))
+    };
+    (members, bindings, ty, value)
+}
+
+// With the `test-helpers` feature, expect_* methods which return the fields of
+// a variant like as_* does, or panic showing the error that was found instead.
+pub fn expect_methods(input: &Enum) -> Option<TokenStream> {
+    if !cfg!(feature = "test-helpers") {
+        return None;
+    }
+    let ty = &input.ident;
+    let mut methods = TokenStream::new();
+    for variant in &input.variants {
+        let ident = &variant.ident;
+        let name = snake_case(&ident.unraw().to_string());
+        let expect_method = format_ident!("expect_{}", name);
+        let expected = format!("{}::{}", ty, ident.unraw());
+        let (members, bindings, ref_ty, _owned_ty, value) = variant_fields(variant);
+        let (ret, value) = if variant.fields.is_empty() {
+            (None, quote!(()))
+        } else {
+            (Some(quote!(-> #ref_ty)), value)
+        };
+        let doc = format!(
+            " The fields of `{}`, panicking if this is any other variant.",
+            expected,
+        );
+        methods.extend(quote! {
+            #[doc = #doc]
+            #[track_caller]
+            pub fn #expect_method(&self) #ret
+            where
+                Self: ::core::fmt::Debug,
+            {
+                #[allow(deprecated, unreachable_patterns)]
+                match self {
+                    #ty::#ident { #(#members: #bindings,)* } => #value,
+                    _ => ::core::panic!("expected `{}`, found {:?}", #expected, self),
+                }
+            }
+        });
+    }
+    Some(methods)
+}
+
+// The members of a variant with a binding for each, and the types and value of
+// the fields returned by reference and by value: a single field directly, or
+// several as a tuple.
+fn variant_fields<'a>(
+    variant: &'a Variant,
+) -> (
+    Vec<&'a Member>,
+    Vec<Ident>,
+    TokenStream,
+    TokenStream,
+    TokenStream,
+) {
+    let members: Vec<_> = variant.fields.iter().map(|field| &field.member).collect();
+    let bindings: Vec<_> = (0..members.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    let types: Vec<_> = variant.fields.iter().map(|field| field.ty).collect();
+    let (ref_ty, owned_ty, value) = if types.len() == 1 {
+        let field_ty = types[0];
+        let binding = &bindings[0];
+        (quote!(&#field_ty), quote!(#field_ty), quote!(#binding))
+    } else {
+        (
+            quote!((#(&#types),*)),
+            quote!((#(#types),*)),
+            quote!((#(#bindings),*)),
+        )
+    };
+    (members, bindings, ref_ty, owned_ty, value)
+}
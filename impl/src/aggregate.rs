@@ -0,0 +1,115 @@
+use crate::attr::Aggregate;
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{DeriveInput, Ident};
+
+pub fn aggregate_type(input: &DeriveInput, aggregate: &Aggregate) -> TokenStream {
+    let ty = &input.ident;
+    let vis = &input.vis;
+    let aggregate_ty = format_ident!("{}Aggregate", ty);
+    let container = match &aggregate.container {
+        Some(container) => replace_self(container.to_token_stream(), ty),
+        None => quote!(::std::vec::Vec<#ty>),
+    };
+    let doc = format!(
+        " A collection of [`{}`] errors, generated by `#[naur(aggregate)]`.",
+        ty,
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(::core::fmt::Debug, ::core::default::Default)]
+        #vis struct #aggregate_ty(#container);
+
+        #[allow(unused_qualifications)]
+        impl #aggregate_ty {
+            /// The collected errors, in the order they were added.
+            pub fn errors(&self) -> &[#ty] {
+                &self.0[..]
+            }
+
+            /// The number of collected errors.
+            pub fn len(&self) -> usize {
+                self.errors().len()
+            }
+
+            /// Whether no error has been collected.
+            pub fn is_empty(&self) -> bool {
+                self.errors().is_empty()
+            }
+
+            /// Adds an error to the collection.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the container is full, as extending a fixed-capacity
+            /// container such as `ArrayVec` does.
+            pub fn push(&mut self, error: #ty) {
+                ::core::iter::Extend::extend(&mut self.0, ::core::iter::once(error));
+            }
+
+            /// Consumes the aggregate, returning the underlying container.
+            pub fn into_inner(self) -> #container {
+                self.0
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl ::core::fmt::Display for #aggregate_ty {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                thiserror::__private::display_aggregate(self.errors(), __formatter)
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl std::error::Error for #aggregate_ty {}
+
+        #[allow(unused_qualifications)]
+        impl ::core::convert::From<#ty> for #aggregate_ty {
+            fn from(error: #ty) -> Self {
+                #aggregate_ty(::core::iter::FromIterator::from_iter(::core::iter::once(error)))
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl ::core::iter::FromIterator<#ty> for #aggregate_ty {
+            fn from_iter<__I: ::core::iter::IntoIterator<Item = #ty>>(iter: __I) -> Self {
+                #aggregate_ty(::core::iter::FromIterator::from_iter(iter))
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl ::core::iter::Extend<#ty> for #aggregate_ty {
+            fn extend<__I: ::core::iter::IntoIterator<Item = #ty>>(&mut self, iter: __I) {
+                ::core::iter::Extend::extend(&mut self.0, iter);
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl ::core::iter::IntoIterator for #aggregate_ty {
+            type Item = #ty;
+            type IntoIter = <#container as ::core::iter::IntoIterator>::IntoIter;
+
+            fn into_iter(self) -> Self::IntoIter {
+                ::core::iter::IntoIterator::into_iter(self.0)
+            }
+        }
+    }
+}
+
+// `Self` inside the container type refers to the error type, not to the
+// generated aggregate struct.
+fn replace_self(tokens: TokenStream, ty: &Ident) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => TokenTree::Ident(ty.clone()),
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(group.delimiter(), replace_self(group.stream(), ty));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced)
+            }
+            token => token,
+        })
+        .collect()
+}
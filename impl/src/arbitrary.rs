@@ -0,0 +1,82 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::expand::captured_value;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Error, Ident, Member};
+
+// `#[naur(arbitrary)]`: an Arbitrary impl which builds each field from the
+// fuzzer's input, falling back to a plain source error or a default for field
+// types which cannot be generated. Timestamps and other captured fields are
+// taken at the time of generation.
+pub fn struct_arbitrary_impl(input: &Struct, attr: &Attribute) -> TokenStream {
+    let value = construct(&quote!(Self), &input.fields);
+    arbitrary_impl(&input.ident, attr, value)
+}
+
+pub fn enum_arbitrary_impl(input: &Enum, attr: &Attribute) -> TokenStream {
+    let value = if input.variants.is_empty() {
+        quote! {
+            ::core::result::Result::Err(thiserror::__private::arbitrary::Error::EmptyChoose)
+        }
+    } else {
+        let len = input.variants.len();
+        let arms = input.variants.iter().enumerate().map(|(i, variant)| {
+            let ident = &variant.ident;
+            let value = construct(&quote!(Self::#ident), &variant.fields);
+            quote!(#i => #value,)
+        });
+        quote! {
+            #[allow(deprecated)]
+            match __u.choose_index(#len)? {
+                #(#arms)*
+                _ => ::core::unreachable!(),
+            }
+        }
+    };
+    arbitrary_impl(&input.ident, attr, value)
+}
+
+fn arbitrary_impl(ty: &Ident, attr: &Attribute, value: TokenStream) -> TokenStream {
+    if !cfg!(feature = "arbitrary") {
+        return Error::new_spanned(
+            attr,
+            "#[naur(arbitrary)] requires enabling the \"arbitrary\" feature of thiserror",
+        )
+        .to_compile_error();
+    }
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl<'__a> thiserror::__private::arbitrary::Arbitrary<'__a> for #ty {
+            fn arbitrary(
+                __u: &mut thiserror::__private::arbitrary::Unstructured<'__a>,
+            ) -> thiserror::__private::arbitrary::Result<Self> {
+                #[allow(unused_imports)]
+                use thiserror::__private::arbitrary::{
+                    ArbitraryViaArbitrary as _, ArbitraryViaDefault as _, ArbitraryViaSource as _,
+                };
+                #value
+            }
+        }
+    }
+}
+
+fn construct(path: &TokenStream, fields: &[Field]) -> TokenStream {
+    let members = fields.iter().map(|field| match &field.member {
+        Member::Named(ident) => quote!(#ident),
+        Member::Unnamed(index) => quote!(#index),
+    });
+    let values = fields.iter().map(|field| {
+        if let Some(value) = captured_value(field) {
+            return value;
+        }
+        let ty = field.ty;
+        quote! {
+            (&&&thiserror::__private::arbitrary::ArbitraryWrap::<#ty>(::core::marker::PhantomData))
+                .arbitrary_field(__u)?
+        }
+    });
+    quote! {
+        ::core::result::Result::Ok(#path { #(#members: #values,)* })
+    }
+}
@@ -0,0 +1,45 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::AssertBounds;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::Generics;
+
+pub fn struct_assertion(input: &Struct) -> Option<TokenStream> {
+    let assert = input.attrs.assert.as_ref()?;
+    Some(assertion(assert, input.generics, input.fields.iter()))
+}
+
+pub fn enum_assertion(input: &Enum) -> Option<TokenStream> {
+    let assert = input.attrs.assert.as_ref()?;
+    let fields = input.variants.iter().flat_map(|variant| &variant.fields);
+    Some(assertion(assert, input.generics, fields))
+}
+
+// One check per field, spanned at the field's type, so that an error which
+// stops being Send says which field made it so. A field whose type involves
+// a type parameter satisfies the bounds depending on what the parameter is
+// instantiated with, and is left out.
+fn assertion<'a>(
+    assert: &AssertBounds,
+    generics: &Generics,
+    fields: impl Iterator<Item = &'a Field<'a>>,
+) -> TokenStream {
+    let bounds = &assert.bounds;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let checks = fields.filter(|field| !field.contains_generic).map(|field| {
+        let ty = field.ty;
+        quote_spanned! {ty.span()=>
+            __assert_bounds::<#ty>();
+        }
+    });
+    quote! {
+        #[allow(unused_qualifications, clippy::extra_unused_type_parameters)]
+        const _: () = {
+            fn __assert_bounds<__T: ?::core::marker::Sized + #bounds>() {}
+            fn __assert_fields #impl_generics () #where_clause {
+                #(#checks)*
+            }
+        };
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ast::{Enum, Field, Struct};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Generics, Ident};
+
+// `backtrace()`, which reaches the backtrace of an error without the provide
+// API: its own backtrace field, or else the backtrace of its source if that is
+// an error with a backtrace() of its own.
+pub fn struct_backtrace_method(input: &Struct) -> Option<TokenStream> {
+    if input.attrs.minimal.is_some() {
+        return None;
+    }
+    let backtrace_field = input.backtrace_field()?;
+    let access = |field: &Field| {
+        let member = &field.member;
+        quote!(&self.#member)
+    };
+    let body = backtrace(Some(backtrace_field), input.source_field(), access);
+    Some(backtrace_method(body))
+}
+
+pub fn enum_backtrace_method(input: &Enum) -> Option<TokenStream> {
+    if input.attrs.minimal.is_some()
+        || input
+            .variants
+            .iter()
+            .all(|variant| variant.backtrace_field().is_none())
+    {
+        return None;
+    }
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let backtrace_field = variant.backtrace_field();
+        let source_field = variant.source_field();
+        let mut members = Vec::new();
+        let mut bindings = Vec::new();
+        for field in backtrace_field.iter().chain(&source_field) {
+            if !members.contains(&&field.member) {
+                members.push(&field.member);
+                bindings.push(binding(field, backtrace_field));
+            }
+        }
+        let body = backtrace(backtrace_field, source_field, |field| {
+            let binding = binding(field, backtrace_field);
+            quote!(#binding)
+        });
+        quote!(Self::#ident { #(#members: #bindings,)* .. } => #body,)
+    });
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    Some(backtrace_method(quote! {
+        #[allow(deprecated)]
+        match #void_deref self {
+            #(#arms)*
+        }
+    }))
+}
+
+// Lets the backtrace() of an error be reached through the source field of
+// another.
+pub fn backtrace_impl(generics: &Generics, ty: &Ident, has_method: bool) -> Option<TokenStream> {
+    if !has_method {
+        return None;
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Some(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics thiserror::__private::ThiserrorBacktrace<std::backtrace::Backtrace>
+            for #ty #ty_generics #where_clause
+        {
+            fn thiserror_backtrace(&self) -> ::core::option::Option<&std::backtrace::Backtrace> {
+                Self::backtrace(self)
+            }
+        }
+    })
+}
+
+fn backtrace_method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// The backtrace captured when this error was constructed, or else
+        /// the one carried by its source.
+        pub fn backtrace(&self) -> ::core::option::Option<&std::backtrace::Backtrace> {
+            #body
+        }
+    }
+}
+
+fn backtrace(
+    backtrace_field: Option<&Field>,
+    source_field: Option<&Field>,
+    access: impl Fn(&Field) -> TokenStream,
+) -> TokenStream {
+    let own = backtrace_field
+        .filter(|field| field.holds_backtrace())
+        .map(|field| {
+            let value = access(field);
+            if field.is_option() {
+                quote!(::core::option::Option::as_ref(#value))
+            } else {
+                quote!(::core::option::Option::Some(#value))
+            }
+        });
+    let from_source = source_field.map(|field| {
+        let value = access(field);
+        let source_backtrace = quote! {{
+            use thiserror::__private::{BacktraceViaNone as _, BacktraceViaSource as _};
+            (&&thiserror::__private::BacktraceWrap(source)).thiserror_backtrace()
+        }};
+        if field.is_option() {
+            quote! {
+                ::core::option::Option::and_then(
+                    ::core::option::Option::as_ref(#value),
+                    |source| #source_backtrace,
+                )
+            }
+        } else {
+            quote!({
+                let source = #value;
+                #source_backtrace
+            })
+        }
+    });
+    match (from_source, own) {
+        (Some(from_source), Some(own)) => quote!(::core::option::Option::or(#from_source, #own)),
+        (Some(from_source), None) => from_source,
+        (None, Some(own)) => own,
+        (None, None) => quote!(::core::option::Option::None),
+    }
+}
+
+fn binding(field: &Field, backtrace_field: Option<&Field>) -> Ident {
+    if backtrace_field.map_or(false, |backtrace_field| {
+        backtrace_field.member == field.member
+    }) {
+        format_ident!("__backtrace")
+    } else {
+        format_ident!("__source")
+    }
+}
@@ -0,0 +1,219 @@
+use crate::ast::{Enum, Variant};
+use crate::attr::{Attrs, BoxedPayload};
+use crate::expand::{self, must_use_attr, throws_cfg_attr, throws_doc_attr};
+use crate::{hooks, naming};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, DeriveInput, Error, Field, Generics, Ident};
+
+// `#[error(boxed)]`: a From impl taking the payload itself, so that building
+// the variant does not need a Box::new of its own.
+pub fn boxed_from_impls(input: &Enum) -> TokenStream {
+    let ty = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    input
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            let payload = variant.boxed_payload_type()?;
+            let hooks = hooks::variant_hooks(input, variant);
+            let ident = &variant.ident;
+            let member = &variant.fields[0].member;
+            let construct = hooks::wrap(
+                quote!(#ty::#ident { #member: ::std::boxed::Box::new(payload) }),
+                &hooks,
+            );
+            Some(quote! {
+                #[allow(unused_qualifications)]
+                impl #impl_generics ::core::convert::From<#payload> for #ty #ty_generics #where_clause {
+                    #[allow(deprecated)]
+                    fn from(payload: #payload) -> Self {
+                        #construct
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+// `#[error(boxed { ... })]`: the payload struct holding the listed fields,
+// derived like an error of its own with the variant's message, and unless
+// throw methods are left out, the variant's throw methods, which build the
+// payload and box it through the From impl above.
+pub fn boxed_payloads(input: &Enum, throws: bool) -> TokenStream {
+    input
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            let payload = variant.attrs.boxed_payload.as_ref()?;
+            let ident = variant.boxed_payload_ident()?;
+            let item = payload_struct(input, variant, payload, ident);
+            let throw_methods = if throws {
+                throw_methods(input, variant, payload, ident)
+            } else {
+                None
+            };
+            Some(quote! {
+                #item
+                #throw_methods
+            })
+        })
+        .collect()
+}
+
+fn payload_struct(
+    input: &Enum,
+    variant: &Variant,
+    payload: &BoxedPayload,
+    ident: &Ident,
+) -> TokenStream {
+    let vis = &input.original.vis;
+    let message = payload.message;
+    let fields = &payload.fields;
+    let doc = format!(
+        " The boxed payload of [`{}::{}`], generated by `#[error(boxed {{ ... }})]`.",
+        input.ident, variant.ident,
+    );
+    let mut node: DeriveInput = parse_quote! {
+        #[doc = #doc]
+        #message
+        #vis struct #ident #fields
+    };
+    let impls = expand::expand_without_throws(&node).unwrap_or_else(Error::into_compile_error);
+
+    // The payload is not itself derived, so the attributes which only the
+    // derive understands are removed from it as it is emitted.
+    node.attrs.retain(is_inert);
+    if let Data::Struct(data) = &mut node.data {
+        for field in &mut data.fields {
+            field.attrs.retain(is_inert);
+        }
+    }
+
+    quote! {
+        #[derive(::core::fmt::Debug)]
+        #node
+
+        #impls
+    }
+}
+
+// The variant's throw methods, on a Result whose error is the payload's
+// source, taking the payload's other fields.
+fn throw_methods(
+    input: &Enum,
+    variant: &Variant,
+    payload: &BoxedPayload,
+    ident: &Ident,
+) -> Option<TokenStream> {
+    let fields = &payload.fields.named;
+    let source = fields.iter().find(|field| is_source(field)).or_else(|| {
+        (fields.iter()).find(|field| {
+            field
+                .ident
+                .as_ref()
+                .map_or(false, |ident| ident == "source")
+        })
+    })?;
+    let source_ident = &source.ident;
+    let source_ty = &source.ty;
+    let others: Vec<&Field> = fields
+        .iter()
+        .filter(|field| field.ident != source.ident)
+        .collect();
+    let names: Vec<&Ident> = others
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .collect();
+    let types = others.iter().map(|field| &field.ty);
+    let params = quote!(#(#names: #types),*);
+    let types = others.iter().map(|field| &field.ty);
+    let with_ty = quote!((#(#types),*));
+
+    let ty = &input.ident;
+    let attrs = [&variant.attrs, &input.attrs];
+    let trait_name = format_ident!("{}{}Throws", ty, variant.ident);
+    let method_name = naming::throw_method_name(&input.attrs, ty, Some(&variant.ident));
+    let throw_method = format_ident!("{}", method_name);
+    let with_method = format_ident!("{}_with", method_name);
+    let must_use = must_use_attr(&attrs);
+    let trait_doc = throws_doc_attr(&attrs);
+    let throws_cfg = throws_cfg_attr(&attrs);
+    let throws_vis = (input.attrs.throws_vis.as_ref()).map(|throws| &throws.vis);
+    let construct = quote! {
+        ::core::convert::From::from(#ident {
+            #source_ident: source,
+            #(#names,)*
+        })
+    };
+
+    let with_method_decl = (!names.is_empty()).then(|| {
+        quote! {
+            #must_use
+            fn #with_method<__F: ::core::ops::FnOnce() -> #with_ty>(self, f: __F) -> ::core::result::Result<__RETURN, #ty>;
+        }
+    });
+    let with_method_impl = (!names.is_empty()).then(|| {
+        quote! {
+            fn #with_method<__F: ::core::ops::FnOnce() -> #with_ty>(self, f: __F) -> ::core::result::Result<__RETURN, #ty> {
+                self.map_err(|source| {
+                    let (#(#names),*) = f();
+                    #construct
+                })
+            }
+        }
+    });
+
+    Some(quote! {
+        #throws_cfg
+        #trait_doc
+        #[allow(deprecated)]
+        #throws_vis trait #trait_name<__RETURN> {
+            #must_use
+            fn #throw_method(self, #params) -> ::core::result::Result<__RETURN, #ty>;
+            #with_method_decl
+        }
+
+        #throws_cfg
+        #[allow(deprecated)]
+        impl<__RETURN> #trait_name<__RETURN> for ::core::result::Result<__RETURN, #source_ty> {
+            fn #throw_method(self, #params) -> ::core::result::Result<__RETURN, #ty> {
+                self.map_err(|source| #construct)
+            }
+            #with_method_impl
+        }
+    })
+}
+
+fn is_source(field: &Field) -> bool {
+    (field.attrs.iter()).any(|attr| attr.path().is_ident("source") || attr.path().is_ident("from"))
+}
+
+fn is_inert(attr: &Attribute) -> bool {
+    attr.path().is_ident("doc") || attr.path().is_ident("deprecated")
+}
+
+// With #[naur(boxed_conv)], a method boxing the error as the trait object
+// taken by APIs which accept any error. The From impl into the same box comes
+// from std's blanket impl, so it is not generated here.
+pub fn boxed_conv_method(attrs: &Attrs, generics: &Generics) -> Option<TokenStream> {
+    attrs.boxed_conv?;
+    let where_clause = if generics.type_params().next().is_some() {
+        Some(quote! {
+            where
+                Self: std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static,
+        })
+    } else {
+        None
+    };
+    Some(quote! {
+        /// This error as a `Box<dyn Error + Send + Sync>`.
+        pub fn boxed(
+            self,
+        ) -> ::std::boxed::Box<
+            dyn std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static,
+        > #where_clause {
+            ::std::boxed::Box::new(self)
+        }
+    })
+}
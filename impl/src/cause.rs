@@ -0,0 +1,124 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::expand::{fields_pat, type_is_option};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, Member};
+
+pub fn struct_causes_method(input: &Struct) -> Option<TokenStream> {
+    if !has_causes(&input.fields) {
+        return None;
+    }
+    let ty = &input.ident;
+    let pat = fields_pat(&input.fields);
+    let value = causes(&input.fields);
+    Some(causes_method(&[quote!(#ty #pat => #value,)]))
+}
+
+pub fn enum_causes_method(input: &Enum) -> Option<TokenStream> {
+    if !input
+        .variants
+        .iter()
+        .any(|variant| has_causes(&variant.fields))
+    {
+        return None;
+    }
+    let ty = &input.ident;
+    let arms: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            if has_causes(&variant.fields) {
+                let pat = fields_pat(&variant.fields);
+                let value = causes(&variant.fields);
+                quote!(#ty::#ident #pat => #value,)
+            } else {
+                quote! {
+                    #ty::#ident {..} => ::std::boxed::Box::new(::core::iter::empty()),
+                }
+            }
+        })
+        .collect();
+    Some(causes_method(&arms))
+}
+
+// Where the toolchain supports `Error::provide`, the causes are provided for
+// `ChainFormatter` to list after the sources.
+pub fn struct_provide_causes(input: &Struct, request: &TokenStream) -> Option<TokenStream> {
+    if !has_causes(&input.fields) {
+        return None;
+    }
+    Some(provide_causes(request))
+}
+
+pub fn enum_provide_causes(input: &Enum, request: &TokenStream) -> Option<TokenStream> {
+    if !input
+        .variants
+        .iter()
+        .any(|variant| has_causes(&variant.fields))
+    {
+        return None;
+    }
+    Some(provide_causes(request))
+}
+
+fn provide_causes(request: &TokenStream) -> TokenStream {
+    quote! {
+        thiserror::__private::provide_causes(#request, || self.causes());
+    }
+}
+
+fn has_causes(fields: &[Field]) -> bool {
+    fields.iter().any(|field| field.attrs.cause.is_some())
+}
+
+fn causes_method(arms: &[TokenStream]) -> TokenStream {
+    quote! {
+        /// The errors in the `#[cause]` fields, which led to this one
+        /// alongside its source.
+        pub fn causes(
+            &self,
+        ) -> ::std::boxed::Box<
+            dyn ::core::iter::Iterator<Item = &(dyn std::error::Error + 'static)> + '_,
+        > {
+            use thiserror::__private::AsDynError as _;
+            #[allow(unused_variables, deprecated, clippy::used_underscore_binding)]
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+// The causes of one struct or variant in the order of their fields, leaving
+// out an optional cause which is None.
+fn causes(fields: &[Field]) -> TokenStream {
+    let causes = fields
+        .iter()
+        .filter(|field| field.attrs.cause.is_some())
+        .map(|field| {
+            let binding = binding(field);
+            if type_is_option(field.ty) {
+                quote! {
+                    ::core::option::Option::map(
+                        ::core::option::Option::as_ref(#binding),
+                        |cause| cause.as_dyn_error(),
+                    )
+                }
+            } else {
+                quote!(::core::option::Option::Some(#binding.as_dyn_error()))
+            }
+        });
+    quote! {
+        ::std::boxed::Box::new(::core::iter::Iterator::flatten(
+            ::core::iter::IntoIterator::into_iter([#(#causes),*]),
+        ))
+    }
+}
+
+fn binding(field: &Field) -> Ident {
+    match &field.member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => format_ident!("_{}", index),
+    }
+}
@@ -0,0 +1,85 @@
+use crate::attr::Attrs;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+pub fn chain_methods(attrs: &Attrs, generics: &Generics) -> Option<TokenStream> {
+    attrs.chain?;
+    let bounds = if generics.type_params().next().is_some() {
+        Some(quote!(Self: std::error::Error + 'static,))
+    } else {
+        None
+    };
+    let where_clause = bounds.as_ref().map(|bounds| quote!(where #bounds));
+    Some(quote! {
+        /// Iterates over this error followed by each of its transitive
+        /// sources.
+        pub fn chain(&self) -> thiserror::Chain<'_> #where_clause {
+            thiserror::Chain::new(self)
+        }
+
+        /// The deepest error of the source chain, or this error if it has no
+        /// source.
+        pub fn root_cause(&self) -> &(dyn std::error::Error + 'static) #where_clause {
+            thiserror::__private::root_cause(self)
+        }
+
+        /// The first error of the source chain, not counting this one, which
+        /// is of type `T`.
+        pub fn source_downcast<__T>(&self) -> ::core::option::Option<&__T>
+        where
+            __T: std::error::Error + 'static,
+            #bounds
+        {
+            ::core::iter::Iterator::find_map(
+                &mut ::core::iter::Iterator::skip(thiserror::Chain::new(self), 1),
+                <dyn std::error::Error>::downcast_ref::<__T>,
+            )
+        }
+
+        /// The source of this error, if it is of type `T`.
+        pub fn direct_source_downcast<__T>(&self) -> ::core::option::Option<&__T>
+        where
+            __T: std::error::Error + 'static,
+            #bounds
+        {
+            std::error::Error::source(self)?.downcast_ref::<__T>()
+        }
+
+        /// The number of errors in the source chain, counting this one.
+        pub fn chain_len(&self) -> usize #where_clause {
+            thiserror::__private::chain_len(self)
+        }
+
+        /// This error's message followed by the message of the root cause of
+        /// its source chain, if it has a source.
+        pub fn chain_summary(&self) -> ::std::string::String #where_clause {
+            thiserror::__private::chain_summary(self)
+        }
+    })
+}
+
+// With #[naur(alt_chain)], formatting with `{:#}` appends the source chain to
+// the message. Placed at the top of the Display impl.
+pub fn alt_chain(attrs: &Attrs) -> Option<TokenStream> {
+    attrs.alt_chain?;
+    Some(quote! {
+        if __formatter.alternate() {
+            return thiserror::__private::display_with_chain(self, __formatter);
+        }
+    })
+}
+
+// With #[naur(debug_chain)], the Debug impl prints the message, each source,
+// and the backtrace, as for returning the error from `main`.
+pub fn debug_chain_impl(attrs: &Attrs, ty: &Ident) -> Option<TokenStream> {
+    attrs.debug_chain?;
+    Some(quote! {
+        #[allow(unused_qualifications)]
+        impl ::core::fmt::Debug for #ty {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                thiserror::__private::debug_chain(self, __formatter)
+            }
+        }
+    })
+}
@@ -0,0 +1,106 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::expand::{capture_backtrace, captured_value, deprecated_attrs, store_source};
+use crate::fields::snake_case;
+use crate::hooks::{self, Hooks};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Attribute, Ident, Member};
+
+// `#[naur(constructors)]`: an associated function per variant, or `new` for a
+// struct, which builds the error from its fields without going through a
+// Result.
+pub fn struct_constructor(input: &Struct) -> Option<TokenStream> {
+    input.attrs.constructors?;
+    let ty = &input.ident;
+    let doc = format!(" Constructs a `{}` from its fields.", ty);
+    Some(constructor(
+        &format_ident!("new"),
+        &doc,
+        &input.original.attrs,
+        &quote!(#ty),
+        &input.fields,
+        input.source_field(),
+        input.distinct_backtrace_field(),
+        &hooks::struct_hooks(input),
+    ))
+}
+
+pub fn enum_constructors(input: &Enum) -> Option<TokenStream> {
+    input.attrs.constructors?;
+    let ty = &input.ident;
+    let mut constructors = TokenStream::new();
+    for variant in &input.variants {
+        let ident = &variant.ident;
+        let name = format_ident!("{}", snake_case(&ident.unraw().to_string()));
+        let doc = format!(" Constructs `{}::{}` from its fields.", ty, ident.unraw());
+        constructors.extend(constructor(
+            &name,
+            &doc,
+            &variant.original.attrs,
+            &quote!(#ty::#ident),
+            &variant.fields,
+            variant.source_field(),
+            variant.distinct_backtrace_field(),
+            &hooks::variant_hooks(input, variant),
+        ));
+    }
+    Some(constructors)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn constructor(
+    name: &Ident,
+    doc: &str,
+    attrs: &[Attribute],
+    path: &TokenStream,
+    fields: &[Field],
+    source: Option<&Field>,
+    backtrace: Option<&Field>,
+    hooks: &Hooks,
+) -> TokenStream {
+    // The source comes first, as in the From impl of #[throws(tuple_from)],
+    // then the other fields in declaration order. A backtrace, timestamps, and
+    // #[capture] fields are captured.
+    let ordered = source.into_iter().chain(
+        fields
+            .iter()
+            .filter(|field| source.map_or(true, |source| source.member != field.member)),
+    );
+    let mut params = Vec::new();
+    let mut inits = Vec::new();
+    for field in ordered {
+        let member = &field.member;
+        if backtrace.map_or(false, |backtrace| backtrace.member == *member) {
+            let value = capture_backtrace(field);
+            inits.push(quote!(#member: #value));
+            continue;
+        }
+        if let Some(value) = captured_value(field) {
+            inits.push(quote!(#member: #value));
+            continue;
+        }
+        let param = match member {
+            Member::Named(ident) => ident.clone(),
+            Member::Unnamed(index) => format_ident!("_{}", index),
+        };
+        let ty = field.arc_source_type().unwrap_or(field.ty);
+        params.push(quote!(#param: #ty));
+        if let (Member::Named(_), None) = (member, field.attrs.arc_source) {
+            inits.push(quote!(#param));
+        } else {
+            let value = store_source(field, quote!(#param));
+            inits.push(quote!(#member: #value));
+        }
+    }
+    let deprecated = deprecated_attrs(attrs);
+    let construct = hooks::wrap(quote!(#path { #(#inits,)* }), hooks);
+    quote! {
+        #[doc = #doc]
+        #(#deprecated)*
+        #[allow(deprecated)]
+        pub fn #name(#(#params),*) -> Self {
+            #construct
+        }
+    }
+}
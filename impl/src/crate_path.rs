@@ -0,0 +1,42 @@
+use proc_macro2::{Group, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::Path;
+
+// `#[naur(crate = "...")]`: every path into the runtime crate in the expansion,
+// written as `thiserror::...`, is made to start from the given path instead,
+// for frameworks which re-export the derive under a name of their own.
+pub fn replace(tokens: TokenStream, path: &Path) -> TokenStream {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut replaced = TokenStream::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Ident(ident)
+                if ident == "thiserror"
+                    && starts_path_segment(&tokens[i + 1..])
+                    && !(i >= 2 && is_path_separator(&tokens[i - 2..i])) =>
+            {
+                path.to_tokens(&mut replaced);
+            }
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), replace(group.stream(), path));
+                new.set_span(group.span());
+                replaced.extend([TokenTree::Group(new)]);
+            }
+            token => replaced.extend([token.clone()]),
+        }
+    }
+    replaced
+}
+
+fn starts_path_segment(rest: &[TokenTree]) -> bool {
+    rest.len() >= 2 && is_path_separator(&rest[..2])
+}
+
+fn is_path_separator(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [TokenTree::Punct(first), TokenTree::Punct(second)] => {
+            first.as_char() == ':' && first.spacing() == Spacing::Joint && second.as_char() == ':'
+        }
+        _ => false,
+    }
+}
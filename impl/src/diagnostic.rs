@@ -0,0 +1,294 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::{Attrs, Diagnostic, Display};
+use crate::expand::{fields_pat, use_as_display};
+use crate::generics::InferredBounds;
+use crate::help::help_value;
+use crate::related;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Generics, Ident, Member, Token};
+
+// A struct, or one variant of an enum, as seen by the Diagnostic impl.
+struct Case<'a> {
+    path: TokenStream,
+    attrs: &'a Attrs<'a>,
+    fields: &'a [Field<'a>],
+}
+
+pub fn struct_diagnostic_impl(input: &Struct) -> Option<TokenStream> {
+    let cases = [Case {
+        path: quote!(Self),
+        attrs: &input.attrs,
+        fields: &input.fields,
+    }];
+    diagnostic_impl(&input.ident, input.generics, None, &cases)
+}
+
+pub fn enum_diagnostic_impl(input: &Enum) -> Option<TokenStream> {
+    let ty = &input.ident;
+    let cases: Vec<Case> = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            Case {
+                path: quote!(#ty::#ident),
+                attrs: &variant.attrs,
+                fields: &variant.fields,
+            }
+        })
+        .collect();
+    diagnostic_impl(ty, input.generics, Some(&input.attrs), &cases)
+}
+
+fn diagnostic_impl(
+    ty: &Ident,
+    generics: &Generics,
+    type_attrs: Option<&Attrs>,
+    cases: &[Case],
+) -> Option<TokenStream> {
+    if !cfg!(feature = "miette") {
+        return None;
+    }
+    let is_diagnostic = type_attrs.map_or(false, |attrs| attrs.diagnostic.is_some())
+        || cases.iter().any(|case| {
+            case.attrs.diagnostic.is_some()
+                || case
+                    .fields
+                    .iter()
+                    .any(|field| field.attrs.diagnostic.is_some())
+        });
+    if !is_diagnostic {
+        return None;
+    }
+
+    let mut inferred_bounds = InferredBounds::new();
+    if generics.type_params().next().is_some() {
+        inferred_bounds.insert(<Token![Self]>::default(), quote!(std::error::Error));
+    }
+
+    let code_method = method(
+        cases,
+        quote! {
+            fn code<'__a>(
+                &'__a self,
+            ) -> ::core::option::Option<::std::boxed::Box<dyn ::core::fmt::Display + '__a>>
+        },
+        |case| {
+            let code = diagnostic_option(case.attrs, type_attrs, |d| d.code.as_ref())
+                .or(case.attrs.code.as_ref())
+                .or_else(|| type_attrs.and_then(|attrs| attrs.code.as_ref()))?;
+            Some(quote!(::std::boxed::Box::new(#code)))
+        },
+    );
+
+    let severity_method = method(
+        cases,
+        quote! {
+            fn severity(&self) -> ::core::option::Option<thiserror::__private::miette::Severity>
+        },
+        |case| {
+            let severity = diagnostic_option(case.attrs, type_attrs, |d| d.severity.as_ref())?;
+            Some(quote!(thiserror::__private::miette::Severity::#severity))
+        },
+    );
+
+    let url_method = method(
+        cases,
+        quote! {
+            fn url<'__a>(
+                &'__a self,
+            ) -> ::core::option::Option<::std::boxed::Box<dyn ::core::fmt::Display + '__a>>
+        },
+        |case| {
+            let url = diagnostic_option(case.attrs, type_attrs, |d| d.url.as_ref())?;
+            Some(quote!(::std::boxed::Box::new(#url)))
+        },
+    );
+
+    let help_method = method(
+        cases,
+        quote! {
+            fn help<'__a>(
+                &'__a self,
+            ) -> ::core::option::Option<::std::boxed::Box<dyn ::core::fmt::Display + '__a>>
+        },
+        |case| {
+            let help = help_of(case.attrs)?;
+            for (field, bound) in &help.implied_bounds {
+                let field = &case.fields[*field];
+                if field.contains_generic {
+                    inferred_bounds.insert(field.ty, bound);
+                }
+            }
+            let use_as_display = use_as_display(help.has_bonus_display);
+            let value = help_value(help);
+            Some(quote!({
+                #use_as_display
+                let help: ::std::borrow::Cow<'static, str> = #value;
+                ::std::boxed::Box::new(help)
+            }))
+        },
+    );
+
+    let source_code_method = method(
+        cases,
+        quote! {
+            fn source_code(
+                &self,
+            ) -> ::core::option::Option<&dyn thiserror::__private::miette::SourceCode>
+        },
+        |case| {
+            let field = case.fields.iter().find(|field| {
+                field
+                    .attrs
+                    .diagnostic
+                    .as_ref()
+                    .map_or(false, |diagnostic| diagnostic.source_code)
+            })?;
+            let binding = binding(field);
+            Some(quote!(#binding as &dyn thiserror::__private::miette::SourceCode))
+        },
+    );
+
+    let labels_method = method(
+        cases,
+        quote! {
+            fn labels(
+                &self,
+            ) -> ::core::option::Option<
+                ::std::boxed::Box<
+                    dyn ::core::iter::Iterator<Item = thiserror::__private::miette::LabeledSpan> + '_,
+                >,
+            >
+        },
+        |case| {
+            let labels: Vec<TokenStream> = case
+                .fields
+                .iter()
+                .filter_map(|field| {
+                    let label = field.attrs.diagnostic.as_ref()?.label.as_ref()?;
+                    let label = match label {
+                        Some(label) => quote! {
+                            ::core::option::Option::Some(::std::string::String::from(#label))
+                        },
+                        None => quote!(::core::option::Option::None),
+                    };
+                    let binding = binding(field);
+                    Some(quote! {
+                        thiserror::__private::miette::LabeledSpan::new_with_span(
+                            #label,
+                            ::core::clone::Clone::clone(#binding),
+                        )
+                    })
+                })
+                .collect();
+            if labels.is_empty() {
+                return None;
+            }
+            Some(quote! {
+                ::std::boxed::Box::new(::core::iter::IntoIterator::into_iter([#(#labels),*]))
+            })
+        },
+    );
+
+    let related_method = method(
+        cases,
+        quote! {
+            fn related<'__a>(
+                &'__a self,
+            ) -> ::core::option::Option<
+                ::std::boxed::Box<
+                    dyn ::core::iter::Iterator<Item = &'__a dyn thiserror::__private::miette::Diagnostic>
+                        + '__a,
+                >,
+            >
+        },
+        |case| {
+            let field = case
+                .fields
+                .iter()
+                .find(|field| field.attrs.related.is_some())?;
+            Some(related::diagnostic_related(field))
+        },
+    );
+
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+    Some(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics thiserror::__private::miette::Diagnostic for #ty #ty_generics #where_clause {
+            #code_method
+            #severity_method
+            #url_method
+            #help_method
+            #source_code_method
+            #labels_method
+            #related_method
+        }
+    })
+}
+
+// One match arm per case, with the cases for which `value` returns None falling
+// back to the trait's default of None. The method is left out altogether if
+// every case falls back.
+fn method<'a>(
+    cases: &'a [Case<'a>],
+    signature: TokenStream,
+    mut value: impl FnMut(&'a Case<'a>) -> Option<TokenStream>,
+) -> Option<TokenStream> {
+    let mut any = false;
+    let arms: Vec<TokenStream> = cases
+        .iter()
+        .map(|case| {
+            let path = &case.path;
+            match value(case) {
+                Some(value) => {
+                    any = true;
+                    let pat = fields_pat(case.fields);
+                    quote!(#path #pat => ::core::option::Option::Some(#value),)
+                }
+                None => quote!(#path {..} => ::core::option::Option::None,),
+            }
+        })
+        .collect();
+    if !any {
+        return None;
+    }
+    Some(quote! {
+        #signature {
+            #[allow(unused_variables, deprecated, clippy::used_underscore_binding)]
+            match self {
+                #(#arms)*
+            }
+        }
+    })
+}
+
+pub fn diagnostic_option<'a, T>(
+    attrs: &'a Attrs,
+    type_attrs: Option<&'a Attrs>,
+    option: impl Fn(&'a Diagnostic) -> Option<&'a T>,
+) -> Option<&'a T> {
+    attrs
+        .diagnostic
+        .as_ref()
+        .and_then(&option)
+        .or_else(|| type_attrs?.diagnostic.as_ref().and_then(&option))
+}
+
+// The help of #[diagnostic(help(...))], or else that of #[error(help = ...)].
+pub fn help_of<'a>(attrs: &'a Attrs) -> Option<&'a Display<'a>> {
+    attrs
+        .diagnostic
+        .as_ref()
+        .and_then(|diagnostic| diagnostic.help.as_ref())
+        .or(attrs.help.as_ref())
+}
+
+fn binding(field: &Field) -> Ident {
+    match &field.member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => format_ident!("_{}", index),
+    }
+}
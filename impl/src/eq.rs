@@ -0,0 +1,152 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::{EqStrategy, Equality};
+use crate::generics::{InferredBounds, ParamsInScope};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Generics, Ident, Index, Member};
+
+pub fn struct_eq_impl(input: &Struct) -> Option<TokenStream> {
+    let eq = input.attrs.eq.as_ref()?;
+    let ty = &input.ident;
+    let fields = compared_fields(
+        &input.fields,
+        input.source_field(),
+        input.attrs.transparent.is_some(),
+    );
+    let body = if fields.is_empty() {
+        quote!(true)
+    } else {
+        let comparisons = fields.iter().map(|field| {
+            let member = &field.member;
+            quote!(self.#member == __other.#member)
+        });
+        quote!(#(#comparisons)&&*)
+    };
+    Some(eq_impl(ty, input.generics, eq, &fields, body))
+}
+
+pub fn enum_eq_impl(input: &Enum) -> Option<TokenStream> {
+    let eq = input.attrs.eq.as_ref()?;
+    let ty = &input.ident;
+    let mut all_fields = Vec::new();
+    let body = match eq.strategy {
+        EqStrategy::Kind => quote! {
+            ::core::mem::discriminant(self) == ::core::mem::discriminant(__other)
+        },
+        EqStrategy::Code => quote!(self.code() == __other.code()),
+        EqStrategy::Fields if input.variants.is_empty() => quote!(match *self {}),
+        EqStrategy::Fields => {
+            let arms = input.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let fields = compared_fields(
+                    &variant.fields,
+                    variant.source_field(),
+                    variant.attrs.transparent.is_some(),
+                );
+                let self_bindings = bindings("__self", &fields);
+                let other_bindings = bindings("__other", &fields);
+                let members: Vec<&Member> = fields.iter().map(|field| &field.member).collect();
+                all_fields.extend(fields);
+                quote! {
+                    (
+                        #ty::#ident { #(#members: #self_bindings,)* .. },
+                        #ty::#ident { #(#members: #other_bindings,)* .. },
+                    ) => true #(&& #self_bindings == #other_bindings)*,
+                }
+            });
+            let arms: Vec<TokenStream> = arms.collect();
+            let fallback = if input.variants.len() > 1 {
+                Some(quote!(_ => false,))
+            } else {
+                None
+            };
+            quote! {
+                #[allow(deprecated)]
+                match (self, __other) {
+                    #(#arms)*
+                    #fallback
+                }
+            }
+        }
+    };
+    Some(eq_impl(ty, input.generics, eq, &all_fields, body))
+}
+
+fn eq_impl(
+    ty: &Ident,
+    generics: &Generics,
+    eq: &Equality,
+    fields: &[&Field],
+    body: TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    let params_in_scope = ParamsInScope::new(generics);
+    for field in fields {
+        if params_in_scope.intersects(field.ty) {
+            inferred_bounds.insert(field.ty, quote!(::core::cmp::PartialEq));
+        }
+    }
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+    let eq_trait_impl = match eq.strategy {
+        EqStrategy::Kind => Some(quote! {
+            impl #impl_generics ::core::cmp::Eq for #ty #ty_generics #where_clause {}
+        }),
+        EqStrategy::Code => Some(quote! {
+            impl #impl_generics ::core::cmp::Eq for #ty #ty_generics #where_clause {}
+
+            #[allow(unused_qualifications)]
+            impl #impl_generics ::core::hash::Hash for #ty #ty_generics #where_clause {
+                fn hash<__H: ::core::hash::Hasher>(&self, __state: &mut __H) {
+                    ::core::hash::Hash::hash(&self.code(), __state);
+                }
+            }
+        }),
+        EqStrategy::Fields => None,
+    };
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::cmp::PartialEq for #ty #ty_generics #where_clause {
+            fn eq(&self, __other: &Self) -> bool {
+                #body
+            }
+        }
+        #eq_trait_impl
+    }
+}
+
+// The fields compared by #[naur(eq = "fields")]. Sources, backtraces,
+// timestamps and other captured fields, causes, and related errors are left out
+// because they are rarely comparable, and are not what tells one error apart
+// from another.
+fn compared_fields<'a, 'b>(
+    fields: &'a [Field<'b>],
+    source: Option<&Field>,
+    transparent: bool,
+) -> Vec<&'a Field<'b>> {
+    if transparent {
+        return Vec::new();
+    }
+    let source = source.map(|source| &source.member);
+    fields
+        .iter()
+        .filter(|field| {
+            Some(&field.member) != source
+                && field.attrs.related.is_none()
+                && field.attrs.cause.is_none()
+                && !field.is_captured()
+                && field.attrs.backtrace.is_none()
+                && !field.is_backtrace()
+        })
+        .collect()
+}
+
+fn bindings(prefix: &str, fields: &[&Field]) -> Vec<Ident> {
+    fields
+        .iter()
+        .map(|field| match &field.member {
+            Member::Named(ident) => format_ident!("{}_{}", prefix, ident),
+            Member::Unnamed(Index { index, .. }) => format_ident!("{}_{}", prefix, index),
+        })
+        .collect()
+}
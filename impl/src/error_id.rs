@@ -0,0 +1,95 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::ErrorId;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Error, Type};
+
+// A new identifier for an #[error_id] field, from the function given in
+// #[error_id(generate = ...)] or else by the field's type.
+pub fn generate(field: &Field, error_id: &ErrorId) -> TokenStream {
+    if let Some(generate) = &error_id.generate {
+        return quote!(#generate());
+    }
+    let ty = field.ty;
+    if type_is_uuid(ty) && !cfg!(feature = "uuid") {
+        return Error::new_spanned(
+            error_id.original,
+            "#[error_id] on a Uuid requires enabling the \"uuid\" feature of thiserror, or else #[error_id(generate = path)]",
+        )
+        .to_compile_error();
+    }
+    quote_spanned! {ty.span()=>
+        <#ty as thiserror::__private::capture::Capture<thiserror::__private::capture::ErrorId>>::capture()
+    }
+}
+
+pub fn struct_error_id_method(input: &Struct) -> Option<TokenStream> {
+    let field = input.error_id_field()?;
+    let ty = field.ty;
+    let member = &field.member;
+    Some(quote! {
+        /// The identifier generated for this error when it was constructed,
+        /// for a person to quote when reporting it.
+        pub fn error_id(&self) -> &#ty {
+            &self.#member
+        }
+    })
+}
+
+// Returns the identifier by reference if every variant has one, or else an
+// Option of it.
+pub fn enum_error_id_method(input: &Enum) -> Option<TokenStream> {
+    let ty = input
+        .variants
+        .iter()
+        .find_map(|variant| variant.error_id_field())?
+        .ty;
+    let all = input
+        .variants
+        .iter()
+        .all(|variant| variant.error_id_field().is_some());
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        match variant.error_id_field() {
+            Some(field) => {
+                let member = &field.member;
+                let value = wrap(all, quote!(error_id));
+                quote!(Self::#ident { #member: error_id, .. } => #value,)
+            }
+            None => quote!(Self::#ident { .. } => ::core::option::Option::None,),
+        }
+    });
+    let ret = if all {
+        quote!(&#ty)
+    } else {
+        quote!(::core::option::Option<&#ty>)
+    };
+    Some(quote! {
+        /// The identifier generated for this error when it was constructed,
+        /// for a person to quote when reporting it.
+        pub fn error_id(&self) -> #ret {
+            #[allow(deprecated)]
+            match self {
+                #(#arms)*
+            }
+        }
+    })
+}
+
+fn wrap(all: bool, value: TokenStream) -> TokenStream {
+    if all {
+        value
+    } else {
+        quote!(::core::option::Option::Some(#value))
+    }
+}
+
+fn type_is_uuid(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return false,
+    };
+
+    path.segments.last().unwrap().ident == "Uuid"
+}
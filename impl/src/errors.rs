@@ -0,0 +1,37 @@
+use crate::ast::{Field, Struct};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+// The Display body of a struct without a message of its own, which summarizes
+// the errors held in its #[errors] field.
+pub fn display(field: &Field) -> TokenStream {
+    let member = &field.member;
+    quote! {
+        thiserror::__private::display_errors(&self.#member, __formatter)
+    }
+}
+
+// The body of Error::source, which is the first of the errors.
+pub fn source(field: &Field) -> TokenStream {
+    let member = &field.member;
+    quote! {
+        ::core::option::Option::map(
+            ::core::iter::Iterator::next(
+                &mut ::core::iter::IntoIterator::into_iter(&self.#member),
+            ),
+            |error| error.as_dyn_error(),
+        )
+    }
+}
+
+pub fn struct_errors_method(input: &Struct) -> Option<TokenStream> {
+    let field = input.errors_field()?;
+    let member = &field.member;
+    let ty = field.ty;
+    Some(quote! {
+        /// The errors held in the `#[errors]` field, in order.
+        pub fn errors(&self) -> <&'_ #ty as ::core::iter::IntoIterator>::IntoIter {
+            ::core::iter::IntoIterator::into_iter(&self.#member)
+        }
+    })
+}
@@ -1,30 +1,77 @@
 use crate::ast::{Enum, Field, Input, Struct};
-use crate::attr::Trait;
-use crate::generics::InferredBounds;
+use crate::attr::{Attrs, CaptureKind, Trait};
+use crate::config::Config;
+use crate::generics::{with_explicit_bound, InferredBounds};
 use crate::span::MemberSpan;
+use crate::{
+    accessors, aggregate, arbitrary, assert, backtrace, boxed, cause, chain, constructors,
+    crate_path, diagnostic, eq, error_id, errors, ext, extend, ffi, fields, grpc, help, hooks,
+    http, i18n, io, kind, manifest, message, naming, panic, poison, py, related, retry, sensitive,
+    serialize, size, subset, suggest, templates, up,
+};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use std::collections::BTreeSet as Set;
+use syn::spanned::Spanned;
 use syn::{
-    Data, DeriveInput, GenericArgument, Member, PathArguments, Result, Token, Type, Visibility,
+    Attribute, Data, DeriveInput, GenericArgument, Generics, Ident, Member, PathArguments, Result,
+    Token, Type, Visibility, WhereClause,
 };
 
 pub fn derive(node: &DeriveInput) -> Result<TokenStream> {
+    match Config::load()? {
+        Some(config) => {
+            let mut expanded = expand(&config.apply(node))?;
+            expanded.extend(config.track());
+            Ok(expanded)
+        }
+        None => expand(node),
+    }
+}
+
+fn expand(node: &DeriveInput) -> Result<TokenStream> {
+    let input = Input::from_syn(node)?;
+    input.validate()?;
+    let crate_path = match &input {
+        Input::Struct(input) => &input.attrs.crate_path,
+        Input::Enum(input) => &input.attrs.crate_path,
+    }
+    .as_ref()
+    .map(|crate_path| crate_path.path.clone());
+    let expanded = match input {
+        Input::Struct(input) => impl_struct(input, true),
+        Input::Enum(input) => impl_enum(input, true),
+    };
+    Ok(match crate_path {
+        Some(path) => crate_path::replace(expanded, &path),
+        None => expanded,
+    })
+}
+
+// A type generated by the derive, such as the subset enum of
+// #[naur(subset(...))] or the payload struct of #[error(boxed { ... })],
+// expanded like a derive of its own but without throw traits, whose methods
+// would be ambiguous with those of the enum it was generated for.
+pub fn expand_without_throws(node: &DeriveInput) -> Result<TokenStream> {
     let input = Input::from_syn(node)?;
     input.validate()?;
     Ok(match input {
-        Input::Struct(input) => impl_struct(input),
-        Input::Enum(input) => impl_enum(input),
+        Input::Struct(input) => impl_struct(input, false),
+        Input::Enum(input) => impl_enum(input, false),
     })
 }
 
-fn impl_struct(input: Struct) -> TokenStream {
+fn impl_struct(input: Struct, throws: bool) -> TokenStream {
     let ty = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let mut error_inferred_bounds = InferredBounds::new();
+    // #[error(bound = "...")] stands in for the bounds inferred from the
+    // fields, on the Error, Display, and throws impls.
+    let bound_generics =
+        (input.attrs.bound.as_ref()).map(|bound| with_explicit_bound(input.generics, bound));
 
     let source_body = if let Some(transparent_attr) = &input.attrs.transparent {
-        let only_field = &input.fields[0];
+        let only_field = input.transparent_field().unwrap();
         if only_field.contains_generic {
             error_inferred_bounds.insert(only_field.ty, quote!(std::error::Error));
         }
@@ -35,7 +82,7 @@ fn impl_struct(input: Struct) -> TokenStream {
     } else if let Some(source_field) = input.source_field() {
         let source = &source_field.member;
         if source_field.contains_generic {
-            let ty = unoptional_type(source_field.ty);
+            let ty = source_value_type(source_field);
             error_inferred_bounds.insert(ty, quote!(std::error::Error + 'static));
         }
         let asref = if type_is_option(source_field.ty) {
@@ -43,14 +90,22 @@ fn impl_struct(input: Struct) -> TokenStream {
         } else {
             None
         };
-        let dyn_error = quote_spanned! {source_field.source_span()=>
-            self.#source #asref.as_dyn_error()
+        let dyn_error = if source_field.attrs.arc_source.is_some()
+            || source_field.arc_dyn_source_type().is_some()
+        {
+            quote_spanned! {source_field.source_span()=>
+                ::core::ops::Deref::deref(&self.#source).as_dyn_error()
+            }
+        } else {
+            quote_spanned! {source_field.source_span()=>
+                self.#source #asref.as_dyn_error()
+            }
         };
         Some(quote! {
             ::core::option::Option::Some(#dyn_error)
         })
     } else {
-        None
+        input.errors_field().map(errors::source)
     };
     let source_method = source_body.map(|body| {
         quote! {
@@ -61,9 +116,11 @@ fn impl_struct(input: Struct) -> TokenStream {
         }
     });
 
-    let provide_method = input.backtrace_field().map(|backtrace_field| {
-        let request = quote!(request);
+    let minimal = input.attrs.minimal.is_some();
+    let request = quote!(request);
+    let backtrace_provide = input.backtrace_field().map(|backtrace_field| {
         let backtrace = &backtrace_field.member;
+        let backtrace_ty = provided_backtrace_type(backtrace_field);
         let body = if let Some(source_field) = input.source_field() {
             let source = &source_field.member;
             let source_provide = if type_is_option(source_field.ty) {
@@ -82,12 +139,12 @@ fn impl_struct(input: Struct) -> TokenStream {
             } else if type_is_option(backtrace_field.ty) {
                 Some(quote! {
                     if let ::core::option::Option::Some(backtrace) = &self.#backtrace {
-                        #request.provide_ref::<std::backtrace::Backtrace>(backtrace);
+                        #request.provide_ref::<#backtrace_ty>(backtrace);
                     }
                 })
             } else {
                 Some(quote! {
-                    #request.provide_ref::<std::backtrace::Backtrace>(&self.#backtrace);
+                    #request.provide_ref::<#backtrace_ty>(&self.#backtrace);
                 })
             };
             quote! {
@@ -98,40 +155,88 @@ fn impl_struct(input: Struct) -> TokenStream {
         } else if type_is_option(backtrace_field.ty) {
             quote! {
                 if let ::core::option::Option::Some(backtrace) = &self.#backtrace {
-                    #request.provide_ref::<std::backtrace::Backtrace>(backtrace);
+                    #request.provide_ref::<#backtrace_ty>(backtrace);
                 }
             }
         } else {
             quote! {
-                #request.provide_ref::<std::backtrace::Backtrace>(&self.#backtrace);
+                #request.provide_ref::<#backtrace_ty>(&self.#backtrace);
             }
         };
+        body
+    });
+    let fields_provide = provided_fields(&input.fields, &request).map(|(pat, body)| {
         quote! {
-            fn provide<'_request>(&'_request self, #request: &mut std::error::Request<'_request>) {
-                #body
-            }
+            #[allow(unused_variables, deprecated)]
+            let Self #pat = self;
+            #body
         }
     });
+    let sections_provide = help::struct_provide_sections(&input, &request);
+    let causes_provide = cause::struct_provide_causes(&input, &request);
+    let provide_method = if minimal
+        || (backtrace_provide.is_none()
+            && fields_provide.is_none()
+            && sections_provide.is_none()
+            && causes_provide.is_none())
+    {
+        None
+    } else {
+        Some(quote! {
+            thiserror::__provide! {
+                fn provide<'_request>(&'_request self, #request: &mut std::error::Request<'_request>) {
+                    #sections_provide
+                    #causes_provide
+                    #backtrace_provide
+                    #fields_provide
+                }
+            }
+        })
+    };
 
     let mut display_implied_bounds = Set::new();
     let display_body = if input.attrs.transparent.is_some() {
-        let only_field = &input.fields[0].member;
-        display_implied_bounds.insert((0, Trait::Display));
+        let only_field = input.transparent_field().unwrap();
+        display_implied_bounds.insert((field_index(&input.fields, only_field), Trait::Display));
+        let only_field = &only_field.member;
         Some(quote! {
             ::core::fmt::Display::fmt(&self.#only_field, __formatter)
         })
     } else if let Some(display) = &input.attrs.display {
         display_implied_bounds = display.implied_bounds.clone();
-        let use_as_display = use_as_display(display.has_bonus_display);
+        let alt = input.attrs.alt.as_ref();
+        if let Some(alt) = alt {
+            display_implied_bounds.extend(alt.implied_bounds.iter().copied());
+        }
+        let use_as_display = use_as_display(
+            display.has_bonus_display || alt.map_or(false, |alt| alt.has_bonus_display),
+        );
         let pat = fields_pat(&input.fields);
+        let redact = sensitive::redact(&input.fields);
+        let localize = i18n::localize(input.attrs.i18n.as_ref(), &input.fields);
+        let display = message::alternate(alt, display.to_token_stream());
+        let display = related::display(&input.fields, display);
         Some(quote! {
             #use_as_display
             #[allow(unused_variables, deprecated)]
             let Self #pat = self;
+            #redact
+            #localize
+            #display
+        })
+    } else if let Some(fmt) = &input.attrs.fmt {
+        let pat = fields_pat(&input.fields);
+        let vars = fields_vars(&input.fields);
+        let localize = i18n::localize(input.attrs.i18n.as_ref(), &input.fields);
+        let display = related::display(&input.fields, quote!(#fmt(#(#vars,)* __formatter)));
+        Some(quote! {
+            #[allow(unused_variables, deprecated)]
+            let Self #pat = self;
+            #localize
             #display
         })
     } else {
-        None
+        input.errors_field().map(errors::display)
     };
     let display_impl = display_body.map(|body| {
         let mut display_inferred_bounds = InferredBounds::new();
@@ -141,7 +246,18 @@ fn impl_struct(input: Struct) -> TokenStream {
                 display_inferred_bounds.insert(field.ty, bound);
             }
         }
-        let display_where_clause = display_inferred_bounds.augment_where_clause(input.generics);
+        let display_where_clause = match &bound_generics {
+            Some(bound_generics) => InferredBounds::new().augment_where_clause(bound_generics),
+            None => display_inferred_bounds.augment_where_clause(input.generics),
+        };
+        let alt_chain = chain::alt_chain(&input.attrs);
+        let body = message::single_line(
+            &input.attrs,
+            quote! {
+                #alt_chain
+                #body
+            },
+        );
         quote! {
             #[allow(unused_qualifications)]
             impl #impl_generics ::core::fmt::Display for #ty #ty_generics #display_where_clause {
@@ -153,67 +269,101 @@ fn impl_struct(input: Struct) -> TokenStream {
         }
     });
 
+    let hooks = hooks::struct_hooks(&input);
+    let up_impls = up::struct_up_impls(&input);
     let from_impl = input.from_field().map(|from_field| {
         let backtrace_field = input.distinct_backtrace_field();
-        let from = unoptional_type(from_field.ty);
-        let body = from_initializer(from_field, backtrace_field);
+        let from = source_value_type(from_field);
+        let body = from_initializer(from_field, backtrace_field, &input.fields);
+        let construct = hooks::wrap(quote!(#ty #body), &hooks);
         quote! {
             #[allow(unused_qualifications)]
             impl #impl_generics ::core::convert::From<#from> for #ty #ty_generics #where_clause {
                 #[allow(deprecated)]
                 fn from(source: #from) -> Self {
-                    #ty #body
+                    #construct
                 }
             }
         }
     });
 
-    let variant_traits_impl = if let Some(source) = input.source_field() {
-        let trait_name = format_ident!("{}Throws", input.ident);
-        let method_name = {
-            let mut snake = String::new();
-            for (i, ch) in input.ident.to_string().char_indices() {
-                if i > 0 && ch.is_uppercase() {
-                    snake.push('_');
-                }
-                snake.push(ch.to_ascii_lowercase());
-            }
-            snake = snake.trim_end_matches("_error").to_owned();
-            snake
-        };
-        let throw_method = format_ident!("throw_{}", method_name);
-        let with_method = format_ident!("throw_{}_with", method_name);
+    let poison_from_impl = poison::struct_from_impl(&input);
 
-        let generics = {
-            use proc_macro2::{Ident, Span};
+    let try_from_impl = input.try_from_field().map(|try_from_field| {
+        let backtrace_field = input
+            .backtrace_field()
+            .filter(|field| field.member != try_from_field.member);
+        let construct = |body| hooks::wrap(quote!(#ty #body), &hooks);
+        try_from_impl(
+            input.generics,
+            ty,
+            try_from_field,
+            backtrace_field,
+            &input.fields,
+            construct,
+        )
+    });
 
-            let mut generics = input.generics.clone();
-            generics.params.push(syn::GenericParam::Type(
-                Ident::new("__RETURN", Span::call_site()).into(),
-            ));
-            generics
-        };
+    let from_value_impl = input.from_value_field().map(|from_value_field| {
+        let backtrace_field = input
+            .backtrace_field()
+            .filter(|field| field.member != from_value_field.member);
+        let construct = |body| hooks::wrap(quote!(#ty #body), &hooks);
+        from_value_impl(
+            input.generics,
+            ty,
+            from_value_field,
+            backtrace_field,
+            &input.fields,
+            construct,
+        )
+    });
+
+    let variant_traits_impl = if minimal || !throws {
+        None
+    } else if let Some(source) = input.source_field() {
+        let trait_name = format_ident!("{}Throws", input.ident);
+        let method_name = naming::throw_method_name(&input.attrs, &input.ident, None);
+        let throw_method = format_ident!("{}", method_name);
+        let with_method = format_ident!("{}_with", method_name);
+        let pipe_trait_name = format_ident!("{}Pipe", input.ident);
+        let poll_trait_name = format_ident!("{}PollThrows", input.ident);
+        let pipe_method = format_ident!("pipe_{}", method_name);
+
+        let generics = throws_generics(input.generics);
         let (thiserror_impl_generics, thiserror_ty_generics, _) = generics.split_for_impl();
+        let where_clause = (bound_generics.as_ref())
+            .map_or(where_clause, |generics| generics.where_clause.as_ref());
 
         let is_source = |field: &Field<'_>| {
             if field.attrs.from.is_some() || field.attrs.source.is_some() {
                 return true;
             }
-            match &field.member {
-                Member::Named(ident) if ident == "source" && source.member == field.member => true,
-                _ => false,
-            }
+            matches!(&field.member, Member::Named(ident) if ident == "source" && source.member == field.member)
         };
 
-        let (params, fields, types) = {
+        let (params, fields, types, members, captured) = {
             use syn::{punctuated::Punctuated, token::Comma, Ident};
 
             let mut params = Punctuated::<TokenStream, Comma>::new();
             let mut fields = Punctuated::<Ident, Comma>::new();
             let mut types = Punctuated::<&Type, Comma>::new();
+            let mut members = Vec::new();
+            let mut captured = Vec::new();
 
             for (i, field) in input.fields.iter().filter(|f| !is_source(f)).enumerate() {
                 let field_ty = field.ty;
+                if field.attrs.backtrace_capture.is_some() {
+                    let member = &field.member;
+                    let backtrace = capture_backtrace(field);
+                    captured.push(quote!(#member: #backtrace));
+                    continue;
+                }
+                if let Some(value) = captured_value(field) {
+                    let member = &field.member;
+                    captured.push(quote!(#member: #value));
+                    continue;
+                }
 
                 let field_name = if let Some(field_name) = field.original.ident.as_ref() {
                     field_name.clone()
@@ -226,63 +376,298 @@ fn impl_struct(input: Struct) -> TokenStream {
                 });
                 fields.push(field_name);
                 types.push(field_ty);
+                members.push(&field.member);
             }
 
-            (params, fields, types)
+            (params, fields, types, members, captured)
         };
 
-        let source_ty = source.ty;
+        let context = throw_context(
+            &[&input.attrs],
+            &input.ident.to_string(),
+            format_ident!("{}Context", input.ident),
+            &source.member,
+            &fields,
+            &types,
+        );
+        let has_params = !params.is_empty();
+        let (params, unpack, with_ty, with_unpack) = match &context {
+            Some(context) => {
+                let ThrowContext { ident, pat, .. } = context;
+                (
+                    quote!(ctx: #ident),
+                    Some(quote!(let #ident #pat = ctx;)),
+                    quote!(#ident),
+                    quote!(let #ident #pat = f();),
+                )
+            }
+            None => (
+                quote!(#params),
+                None,
+                quote!((#types)),
+                quote!(let (#fields) = f();),
+            ),
+        };
+        let context_def = context.map(|context| context.def);
+
+        let source_ty = source.source_impl_type();
+        let e = store_source(source, quote!(e));
+        let (throw_impl_generics, throw_error_ty, convert) =
+            throw_error_type(&generics, source, source_ty);
 
         let new_struct = if let Some(source_field) = source.original.ident.as_ref() {
             quote! {
                 #ty {
-                    #source_field : e,
+                    #source_field : #e,
+                    #(#captured,)*
                     #fields
                 }
             }
         } else {
+            let source_member = &source.member;
+            let fields = fields.iter();
             quote! {
-                #ty (e, #fields)
+                #ty {
+                    #source_member : #e,
+                    #(#members : #fields,)*
+                    #(#captured,)*
+                }
             }
         };
+        let new_struct = hooks::wrap(new_struct, &hooks);
+        let constructor = format_ident!("__thiserror_{}", method_name);
+        let constructor_impl = throw_constructor(
+            input.generics,
+            where_clause,
+            ty,
+            &constructor,
+            source_ty,
+            &fields,
+            &types,
+            &new_struct,
+        );
+        let construct = quote!(<#ty #ty_generics>::#constructor(e, #fields));
+        let tuple_from_impl = tuple_from_impl(
+            &[&input.attrs],
+            input.generics,
+            ty,
+            source_ty,
+            &fields,
+            &types,
+            &new_struct,
+        );
+        let throws_cfg = throws_cfg_attr(&[&input.attrs]);
+        let into_error_impl =
+            into_error_impl(input.generics, ty, source_ty, &fields, &types, &new_struct)
+                .map(|into_error_impl| quote!(#throws_cfg #into_error_impl));
+        let deprecated = deprecated_attrs(&input.original.attrs);
+        let must_use = must_use_attr(&[&input.attrs]);
+        let trait_doc = throws_doc_attr(&[&input.attrs]);
+        let throws_vis = (input.attrs.throws_vis.as_ref()).map(|throws| &throws.vis);
 
-        let with_method_decl = (!params.is_empty()).then(|| quote!{
-            fn #with_method<F: FnOnce() -> (#types)> (self, f: F) -> Result<__RETURN, #ty #ty_generics> #where_clause;
+        let with_method_decl = has_params.then(|| quote!{
+            #(#deprecated)*
+            #must_use
+            fn #with_method<F: FnOnce() -> #with_ty> (self, f: F) -> Result<__RETURN, #ty #ty_generics> #where_clause;
         });
-        let with_method_impl = (!params.is_empty()).then(|| quote!{
-            fn #with_method<F: FnOnce() -> (#types)> (self, f: F) -> Result<__RETURN, #ty #ty_generics> #where_clause {
+        let with_method_impl = has_params.then(|| quote!{
+            fn #with_method<F: FnOnce() -> #with_ty> (self, f: F) -> Result<__RETURN, #ty #ty_generics> #where_clause {
                 self.map_err(|e| {
-                    let (#fields) = f();
-                    #new_struct
+                    #convert
+                    #with_unpack
+                    #construct
                 })
             }
         });
 
+        let poll_trait = throws_poll(&[&input.attrs]).then(|| {
+            quote! {
+                #throws_cfg
+                #trait_doc
+                #[allow(deprecated)]
+                #throws_vis trait #poll_trait_name #thiserror_impl_generics {
+                    #(#deprecated)*
+                    #must_use
+                    fn #throw_method (self, #params) -> ::core::task::Poll<Result<__RETURN, #ty #ty_generics>> #where_clause;
+                }
+                #throws_cfg
+                #[allow(deprecated)]
+                impl #throw_impl_generics #poll_trait_name #thiserror_ty_generics for ::core::task::Poll<Result<__RETURN, #throw_error_ty>> #where_clause {
+                    fn #throw_method (self, #params) -> ::core::task::Poll<Result<__RETURN, #ty #ty_generics>> #where_clause {
+                        #unpack
+                        self.map_err(|e| {
+                            #convert
+                            #construct
+                        })
+                    }
+                }
+            }
+        });
+        let pipe_trait = throws_pipe(&[&input.attrs]).then(|| {
+            quote! {
+                #throws_cfg
+                #trait_doc
+                #[allow(deprecated)]
+                #throws_vis trait #pipe_trait_name #thiserror_impl_generics {
+                    #(#deprecated)*
+                    #must_use
+                    fn #pipe_method<__NEXT, __STAGE: FnOnce(__RETURN) -> Result<__NEXT, #source_ty>> (self, stage: __STAGE, #params) -> Result<thiserror::Pipeline<__NEXT>, #ty #ty_generics> #where_clause;
+                }
+                #throws_cfg
+                #[allow(deprecated)]
+                impl #thiserror_impl_generics #pipe_trait_name #thiserror_ty_generics for thiserror::Pipeline<__RETURN> #where_clause {
+                    fn #pipe_method<__NEXT, __STAGE: FnOnce(__RETURN) -> Result<__NEXT, #source_ty>> (self, stage: __STAGE, #params) -> Result<thiserror::Pipeline<__NEXT>, #ty #ty_generics> #where_clause {
+                        #unpack
+                        match stage(self.into_inner()) {
+                            Ok(value) => Ok(thiserror::Pipeline::new(value)),
+                            Err(e) => Err(#construct),
+                        }
+                    }
+                }
+            }
+        });
+
         Some(quote! {
-            trait #trait_name #thiserror_impl_generics {
+            #context_def
+            #throws_cfg
+            #constructor_impl
+            #tuple_from_impl
+            #into_error_impl
+            #throws_cfg
+            #trait_doc
+            #[allow(deprecated)]
+            #throws_vis trait #trait_name #thiserror_impl_generics {
+                #(#deprecated)*
+                #must_use
                 fn #throw_method (self, #params) -> Result<__RETURN, #ty #ty_generics> #where_clause;
                 #with_method_decl
             }
-            impl #thiserror_impl_generics #trait_name #thiserror_ty_generics for Result<__RETURN, #source_ty> #where_clause {
+            #throws_cfg
+            #[allow(deprecated)]
+            impl #throw_impl_generics #trait_name #thiserror_ty_generics for Result<__RETURN, #throw_error_ty> #where_clause {
                 fn #throw_method (self, #params) -> Result<__RETURN, #ty #ty_generics> #where_clause {
+                    #unpack
                     self.map_err(|e| {
-                        #new_struct
+                        #convert
+                        #construct
                     })
                 }
                 #with_method_impl
             }
+            #poll_trait
+            #pipe_trait
         })
     } else {
         None
     };
 
     let error_trait = spanned_error_trait(input.original);
+    if bound_generics.is_some() {
+        error_inferred_bounds = InferredBounds::new();
+    }
     if input.generics.type_params().next().is_some() {
         let self_token = <Token![Self]>::default();
         error_inferred_bounds.insert(self_token, Trait::Debug);
         error_inferred_bounds.insert(self_token, Trait::Display);
     }
-    let error_where_clause = error_inferred_bounds.augment_where_clause(input.generics);
+    let error_where_clause = error_inferred_bounds
+        .augment_where_clause(bound_generics.as_ref().unwrap_or(input.generics));
+
+    let mut methods = http::struct_methods(&input);
+    methods.extend(io::struct_io_kind_method(&input));
+    if display_impl.is_some() && !minimal {
+        methods.extend(message::message_methods(input.generics));
+        methods.extend(message::struct_message_methods(&input));
+    }
+    methods.extend(help::struct_help_methods(&input));
+    if !minimal {
+        methods.extend(hooks::struct_name_methods(&input));
+    }
+    methods.extend(i18n::struct_message_key_method(&input));
+    methods.extend(fields::struct_fields_method(&input));
+    methods.extend(related::struct_related_method(&input));
+    methods.extend(cause::struct_causes_method(&input));
+    methods.extend(errors::struct_errors_method(&input));
+    methods.extend(error_id::struct_error_id_method(&input));
+    methods.extend(accessors::struct_into_methods(&input));
+    let backtrace_method = backtrace::struct_backtrace_method(&input);
+    let backtrace_impl = backtrace::backtrace_impl(input.generics, ty, backtrace_method.is_some());
+    methods.extend(backtrace_method);
+    methods.extend(retry::struct_is_transient_method(&input));
+    methods.extend(constructors::struct_constructor(&input));
+    methods.extend(fields::typed_fields_method(
+        input.attrs.typed_fields.as_ref(),
+    ));
+    methods.extend(chain::chain_methods(&input.attrs, input.generics));
+    methods.extend(boxed::boxed_conv_method(&input.attrs, input.generics));
+    methods.extend(templates::struct_template_const(&input));
+    methods.extend(templates::struct_catalog_const(&input));
+    methods.extend(manifest::struct_manifest_fn(&input));
+    let inherent_impl = inherent_impl(input.generics, ty, methods);
+    let (actix_impl, axum_impl) = if input.has_status() {
+        (
+            Some(http::actix_impl(ty, input.generics, input.has_code())),
+            Some(http::axum_impl(ty, input.generics, input.has_code())),
+        )
+    } else {
+        (None, None)
+    };
+    let grpc_impl = if input.has_grpc() {
+        Some(grpc::struct_status_impl(&input))
+    } else {
+        None
+    };
+    let io_impl = if input.has_io_kind() {
+        Some(io::struct_io_error_impl(&input))
+    } else {
+        None
+    };
+    let py_impl = if input.has_py() {
+        Some(py::struct_py_err_impl(&input))
+    } else {
+        None
+    };
+    let eq_impl = eq::struct_eq_impl(&input);
+    let debug_impl = sensitive::struct_debug_impl(&input)
+        .or_else(|| chain::debug_chain_impl(&input.attrs, &input.ident));
+    let size_assertion = size::struct_size_assertion(&input);
+    let bounds_assertion = assert::struct_assertion(&input);
+    let serialize_impl = input
+        .attrs
+        .serialize
+        .map(|attr| serialize::struct_serialize_impl(&input, attr));
+    let arbitrary_impl = input
+        .attrs
+        .arbitrary
+        .map(|attr| arbitrary::struct_arbitrary_impl(&input, attr));
+    let wire_type = input
+        .attrs
+        .wire
+        .map(|attr| serialize::wire_type(input.original, attr, input.has_code()));
+    let aggregate_type = input
+        .attrs
+        .aggregate
+        .as_ref()
+        .map(|aggregate| aggregate::aggregate_type(input.original, aggregate));
+    let diagnostic_impl = diagnostic::struct_diagnostic_impl(&input);
+    let registration = if minimal {
+        None
+    } else {
+        templates::struct_registration(&input)
+    };
+    let error_ext_impl = if minimal {
+        None
+    } else {
+        Some(ext::struct_error_ext_impl(&input, &error_where_clause))
+    };
+    let typed_fields = fields::struct_typed_fields(&input);
+    let extensions = extend::struct_extensions(&input);
+    let hook_errors = hooks::feature_errors([&input.attrs]);
+    let source_suggestion = if input.attrs.transparent.is_none() {
+        suggest::missing_source(&input.attrs, &input.fields)
+    } else {
+        None
+    };
 
     quote! {
         #[allow(unused_qualifications)]
@@ -290,22 +675,52 @@ fn impl_struct(input: Struct) -> TokenStream {
             #source_method
             #provide_method
         }
+        #error_ext_impl
         #display_impl
         #from_impl
+        #poison_from_impl
+        #up_impls
+        #try_from_impl
+        #from_value_impl
         #variant_traits_impl
+        #inherent_impl
+        #backtrace_impl
+        #actix_impl
+        #axum_impl
+        #grpc_impl
+        #io_impl
+        #py_impl
+        #serialize_impl
+        #arbitrary_impl
+        #eq_impl
+        #debug_impl
+        #size_assertion
+        #bounds_assertion
+        #wire_type
+        #diagnostic_impl
+        #registration
+        #typed_fields
+        #aggregate_type
+        #source_suggestion
+        #extensions
+        #hook_errors
     }
 }
 
-fn impl_enum(input: Enum) -> TokenStream {
+fn impl_enum(input: Enum, throws: bool) -> TokenStream {
     let ty = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let mut error_inferred_bounds = InferredBounds::new();
+    // #[error(bound = "...")] stands in for the bounds inferred from the
+    // fields, on the Error, Display, and throws impls.
+    let bound_generics =
+        (input.attrs.bound.as_ref()).map(|bound| with_explicit_bound(input.generics, bound));
 
     let source_method = if input.has_source() {
         let arms = input.variants.iter().map(|variant| {
             let ident = &variant.ident;
             if let Some(transparent_attr) = &variant.attrs.transparent {
-                let only_field = &variant.fields[0];
+                let only_field = variant.transparent_field().unwrap();
                 if only_field.contains_generic {
                     error_inferred_bounds.insert(only_field.ty, quote!(std::error::Error));
                 }
@@ -314,12 +729,12 @@ fn impl_enum(input: Enum) -> TokenStream {
                     std::error::Error::source(transparent.as_dyn_error())
                 };
                 quote! {
-                    #ty::#ident {#member: transparent} => #source,
+                    #ty::#ident {#member: transparent, ..} => #source,
                 }
             } else if let Some(source_field) = variant.source_field() {
                 let source = &source_field.member;
                 if source_field.contains_generic {
-                    let ty = unoptional_type(source_field.ty);
+                    let ty = source_value_type(source_field);
                     error_inferred_bounds.insert(ty, quote!(std::error::Error + 'static));
                 }
                 let asref = if type_is_option(source_field.ty) {
@@ -328,8 +743,16 @@ fn impl_enum(input: Enum) -> TokenStream {
                     None
                 };
                 let varsource = quote!(source);
-                let dyn_error = quote_spanned! {source_field.source_span()=>
-                    #varsource #asref.as_dyn_error()
+                let dyn_error = if source_field.attrs.arc_source.is_some()
+                    || source_field.arc_dyn_source_type().is_some()
+                {
+                    quote_spanned! {source_field.source_span()=>
+                        ::core::ops::Deref::deref(#varsource).as_dyn_error()
+                    }
+                } else {
+                    quote_spanned! {source_field.source_span()=>
+                        #varsource #asref.as_dyn_error()
+                    }
                 };
                 quote! {
                     #ty::#ident {#source: #varsource, ..} => ::core::option::Option::Some(#dyn_error),
@@ -353,8 +776,9 @@ fn impl_enum(input: Enum) -> TokenStream {
         None
     };
 
-    let provide_method = if input.has_backtrace() {
-        let request = quote!(request);
+    let minimal = input.attrs.minimal.is_some();
+    let request = quote!(request);
+    let backtrace_provide = if input.has_backtrace() {
         let arms = input.variants.iter().map(|variant| {
             let ident = &variant.ident;
             match (variant.backtrace_field(), variant.source_field()) {
@@ -362,6 +786,7 @@ fn impl_enum(input: Enum) -> TokenStream {
                     if backtrace_field.attrs.backtrace.is_none() =>
                 {
                     let backtrace = &backtrace_field.member;
+                    let backtrace_ty = provided_backtrace_type(backtrace_field);
                     let source = &source_field.member;
                     let varsource = quote!(source);
                     let source_provide = if type_is_option(source_field.ty) {
@@ -378,12 +803,12 @@ fn impl_enum(input: Enum) -> TokenStream {
                     let self_provide = if type_is_option(backtrace_field.ty) {
                         quote! {
                             if let ::core::option::Option::Some(backtrace) = backtrace {
-                                #request.provide_ref::<std::backtrace::Backtrace>(backtrace);
+                                #request.provide_ref::<#backtrace_ty>(backtrace);
                             }
                         }
                     } else {
                         quote! {
-                            #request.provide_ref::<std::backtrace::Backtrace>(backtrace);
+                            #request.provide_ref::<#backtrace_ty>(backtrace);
                         }
                     };
                     quote! {
@@ -423,15 +848,16 @@ fn impl_enum(input: Enum) -> TokenStream {
                 }
                 (Some(backtrace_field), _) => {
                     let backtrace = &backtrace_field.member;
+                    let backtrace_ty = provided_backtrace_type(backtrace_field);
                     let body = if type_is_option(backtrace_field.ty) {
                         quote! {
                             if let ::core::option::Option::Some(backtrace) = backtrace {
-                                #request.provide_ref::<std::backtrace::Backtrace>(backtrace);
+                                #request.provide_ref::<#backtrace_ty>(backtrace);
                             }
                         }
                     } else {
                         quote! {
-                            #request.provide_ref::<std::backtrace::Backtrace>(backtrace);
+                            #request.provide_ref::<#backtrace_ty>(backtrace);
                         }
                     };
                     quote! {
@@ -446,24 +872,62 @@ fn impl_enum(input: Enum) -> TokenStream {
             }
         });
         Some(quote! {
-            fn provide<'_request>(&'_request self, #request: &mut std::error::Request<'_request>) {
-                #[allow(deprecated)]
-                match self {
-                    #(#arms)*
-                }
+            #[allow(deprecated)]
+            match self {
+                #(#arms)*
             }
         })
     } else {
         None
     };
+    let provided_arms: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            let ident = &variant.ident;
+            let (pat, body) = provided_fields(&variant.fields, &request)?;
+            Some(quote!(#ty::#ident #pat => { #body }))
+        })
+        .collect();
+    let fields_provide = if provided_arms.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            #[allow(unused_variables, deprecated, unreachable_patterns)]
+            match self {
+                #(#provided_arms)*
+                _ => {}
+            }
+        })
+    };
+    let sections_provide = help::enum_provide_sections(&input, &request);
+    let causes_provide = cause::enum_provide_causes(&input, &request);
+    let provide_method = if minimal
+        || (backtrace_provide.is_none()
+            && fields_provide.is_none()
+            && sections_provide.is_none()
+            && causes_provide.is_none())
+    {
+        None
+    } else {
+        Some(quote! {
+            thiserror::__provide! {
+                fn provide<'_request>(&'_request self, #request: &mut std::error::Request<'_request>) {
+                    #sections_provide
+                    #causes_provide
+                    #backtrace_provide
+                    #fields_provide
+                }
+            }
+        })
+    };
 
     let display_impl = if input.has_display() {
         let mut display_inferred_bounds = InferredBounds::new();
         let has_bonus_display = input.variants.iter().any(|v| {
-            v.attrs
-                .display
-                .as_ref()
-                .map_or(false, |display| display.has_bonus_display)
+            (v.attrs.display.iter())
+                .chain(&v.attrs.alt)
+                .any(|display| display.has_bonus_display)
         });
         let use_as_display = use_as_display(has_bonus_display);
         let void_deref = if input.variants.is_empty() {
@@ -473,17 +937,28 @@ fn impl_enum(input: Enum) -> TokenStream {
         };
         let arms = input.variants.iter().map(|variant| {
             let mut display_implied_bounds = Set::new();
-            let display = match &variant.attrs.display {
-                Some(display) => {
+            let display = match (&variant.attrs.display, &variant.attrs.fmt) {
+                (Some(display), _) => {
                     display_implied_bounds = display.implied_bounds.clone();
-                    display.to_token_stream()
+                    let alt = variant.attrs.alt.as_ref();
+                    if let Some(alt) = alt {
+                        display_implied_bounds.extend(alt.implied_bounds.iter().copied());
+                    }
+                    let display = message::alternate(alt, display.to_token_stream());
+                    related::display(&variant.fields, display)
                 }
-                None => {
-                    let only_field = match &variant.fields[0].member {
+                (None, Some(fmt)) => {
+                    let vars = fields_vars(&variant.fields);
+                    related::display(&variant.fields, quote!(#fmt(#(#vars,)* __formatter)))
+                }
+                (None, None) => {
+                    let field = variant.transparent_field().unwrap();
+                    let only_field = match &field.member {
                         Member::Named(ident) => ident.clone(),
                         Member::Unnamed(index) => format_ident!("_{}", index),
                     };
-                    display_implied_bounds.insert((0, Trait::Display));
+                    display_implied_bounds
+                        .insert((field_index(&variant.fields, field), Trait::Display));
                     quote!(::core::fmt::Display::fmt(#only_field, __formatter))
                 }
             };
@@ -495,21 +970,51 @@ fn impl_enum(input: Enum) -> TokenStream {
             }
             let ident = &variant.ident;
             let pat = fields_pat(&variant.fields);
-            quote! {
-                #ty::#ident #pat => #display
+            let redact = variant
+                .attrs
+                .display
+                .as_ref()
+                .and_then(|_| sensitive::redact(&variant.fields));
+            let localize = i18n::localize(variant.attrs.i18n.as_ref(), &variant.fields);
+            let prefix = (input.attrs.prefix.as_ref())
+                .filter(|_| variant.attrs.no_prefix.is_none())
+                .map(|prefix| quote!(__formatter.write_str(#prefix)?;));
+            match (redact, localize, prefix) {
+                (None, None, None) => quote! {
+                    #ty::#ident #pat => #display
+                },
+                (redact, localize, prefix) => quote! {
+                    #ty::#ident #pat => {
+                        #redact
+                        #prefix
+                        #localize
+                        #display
+                    }
+                },
             }
         });
         let arms = arms.collect::<Vec<_>>();
-        let display_where_clause = display_inferred_bounds.augment_where_clause(input.generics);
+        let display_where_clause = match &bound_generics {
+            Some(bound_generics) => InferredBounds::new().augment_where_clause(bound_generics),
+            None => display_inferred_bounds.augment_where_clause(input.generics),
+        };
+        let alt_chain = chain::alt_chain(&input.attrs);
+        let body = message::single_line(
+            &input.attrs,
+            quote! {
+                #alt_chain
+                #use_as_display
+                #[allow(unused_variables, deprecated, clippy::used_underscore_binding)]
+                match #void_deref self {
+                    #(#arms,)*
+                }
+            },
+        );
         Some(quote! {
             #[allow(unused_qualifications)]
             impl #impl_generics ::core::fmt::Display for #ty #ty_generics #display_where_clause {
                 fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                    #use_as_display
-                    #[allow(unused_variables, deprecated, clippy::used_underscore_binding)]
-                    match #void_deref self {
-                        #(#arms,)*
-                    }
+                    #body
                 }
             }
         })
@@ -520,77 +1025,239 @@ fn impl_enum(input: Enum) -> TokenStream {
     let from_impls = input.variants.iter().filter_map(|variant| {
         let from_field = variant.from_field()?;
         let backtrace_field = variant.distinct_backtrace_field();
+        let hooks = hooks::variant_hooks(&input, variant);
+        let fields = &variant.fields;
         let variant = &variant.ident;
-        let from = unoptional_type(from_field.ty);
-        let body = from_initializer(from_field, backtrace_field);
+        let from = source_value_type(from_field);
+        let body = from_initializer(from_field, backtrace_field, fields);
+        let construct = hooks::wrap(quote!(#ty::#variant #body), &hooks);
         Some(quote! {
             #[allow(unused_qualifications)]
             impl #impl_generics ::core::convert::From<#from> for #ty #ty_generics #where_clause {
                 #[allow(deprecated)]
                 fn from(source: #from) -> Self {
-                    #ty::#variant #body
+                    #construct
                 }
             }
         })
     });
 
+    let from_any_impl = input.variants.iter().find_map(|variant| {
+        let from_field = variant.from_field()?;
+        from_field.attrs.from_any?;
+        let backtrace_field = variant.distinct_backtrace_field();
+        let hooks = hooks::variant_hooks(&input, variant);
+        let vis = &input.original.vis;
+        let marker = format_ident!("{}CatchAll", ty);
+        let fields = &variant.fields;
+        let variant = &variant.ident;
+        let body = from_initializer(from_field, backtrace_field, fields);
+        let construct = hooks::wrap(quote!(#ty::#variant #body), &hooks);
+        // An Arc field takes the error as is, leaving Arc::new to coerce it,
+        // and an `Arc<dyn Error>` field shares it.
+        let convert = if from_field.attrs.arc_source.is_some() {
+            None
+        } else if let Some(object) = from_field.arc_dyn_source_type() {
+            Some(quote!(let source: ::std::sync::Arc<#object> = ::std::sync::Arc::new(source);))
+        } else {
+            Some(quote!(let source = ::core::convert::From::from(source);))
+        };
+        let doc = format!(
+            "Error types converted into `{}::{}` by `From`.\n\nImplement this for each error type that should be caught by `?`. A blanket impl over every error type would overlap with `From<{}> for {}` itself.",
+            ty, variant, ty, ty,
+        );
+        Some(quote! {
+            #[doc = #doc]
+            #vis trait #marker: std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static {}
+
+            #[allow(unused_qualifications)]
+            impl<__E: #marker> ::core::convert::From<__E> for #ty {
+                #[allow(deprecated)]
+                fn from(source: __E) -> Self {
+                    #convert
+                    #construct
+                }
+            }
+        })
+    });
+
+    let up_impls = up::enum_up_impls(&input);
+    let boxed_from_impls = boxed::boxed_from_impls(&input);
+    let boxed_payloads = boxed::boxed_payloads(&input, throws && !minimal);
+
+    let context_trait = input.variants.iter().find_map(|variant| {
+        variant.attrs.context?;
+        let source_field = variant.source_field()?;
+        let source = &source_field.member;
+        let error = if source_field.attrs.arc_source.is_some() {
+            store_source(source_field, quote!(error))
+        } else if source_field.arc_dyn_source_type().is_some() {
+            quote!(::std::sync::Arc::new(error))
+        } else {
+            quote!(::std::boxed::Box::new(error))
+        };
+        let hooks = hooks::variant_hooks(&input, variant);
+        let vis = &input.original.vis;
+        let trait_name = format_ident!("{}Context", ty);
+        let variant = &variant.ident;
+        let construct = hooks::wrap(
+            quote! {
+                #ty::#variant {
+                    #source: #error,
+                    msg: ::core::convert::Into::into(msg),
+                }
+            },
+            &hooks,
+        );
+        let doc = format!(
+            "Attaches a message to any error, converting it into `{}::{}`.",
+            ty, variant,
+        );
+        Some(quote! {
+            #[doc = #doc]
+            #vis trait #trait_name<__T> {
+                /// Wraps the error together with the given message.
+                fn context<__C>(self, msg: __C) -> ::core::result::Result<__T, #ty>
+                where
+                    __C: ::core::convert::Into<::std::string::String>;
+
+                /// Wraps the error together with a message which is only
+                /// computed if there is an error.
+                fn with_context<__C, __F>(self, f: __F) -> ::core::result::Result<__T, #ty>
+                where
+                    __C: ::core::convert::Into<::std::string::String>,
+                    __F: ::core::ops::FnOnce() -> __C;
+            }
+
+            #[allow(unused_qualifications)]
+            impl<__T, __E> #trait_name<__T> for ::core::result::Result<__T, __E>
+            where
+                __E: std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static,
+            {
+                fn context<__C>(self, msg: __C) -> ::core::result::Result<__T, #ty>
+                where
+                    __C: ::core::convert::Into<::std::string::String>,
+                {
+                    #[allow(deprecated)]
+                    self.map_err(|error| #construct)
+                }
+
+                fn with_context<__C, __F>(self, f: __F) -> ::core::result::Result<__T, #ty>
+                where
+                    __C: ::core::convert::Into<::std::string::String>,
+                    __F: ::core::ops::FnOnce() -> __C,
+                {
+                    #[allow(deprecated)]
+                    self.map_err(|error| {
+                        let msg = f();
+                        #construct
+                    })
+                }
+            }
+        })
+    });
+
+    let poison_from_impl = poison::enum_from_impl(&input);
+
+    let try_from_impls = input.variants.iter().filter_map(|variant| {
+        let try_from_field = variant.try_from_field()?;
+        let backtrace_field = variant
+            .backtrace_field()
+            .filter(|field| field.member != try_from_field.member);
+        let hooks = hooks::variant_hooks(&input, variant);
+        let fields = &variant.fields;
+        let variant = &variant.ident;
+        let construct = |body| hooks::wrap(quote!(#ty::#variant #body), &hooks);
+        Some(try_from_impl(
+            input.generics,
+            ty,
+            try_from_field,
+            backtrace_field,
+            fields,
+            construct,
+        ))
+    });
+
+    let from_value_impls = input.variants.iter().filter_map(|variant| {
+        let from_value_field = variant.from_value_field()?;
+        let backtrace_field = variant
+            .backtrace_field()
+            .filter(|field| field.member != from_value_field.member);
+        let hooks = hooks::variant_hooks(&input, variant);
+        let fields = &variant.fields;
+        let variant = &variant.ident;
+        let construct = |body| hooks::wrap(quote!(#ty::#variant #body), &hooks);
+        Some(from_value_impl(
+            input.generics,
+            ty,
+            from_value_field,
+            backtrace_field,
+            fields,
+            construct,
+        ))
+    });
+
     let error_trait = spanned_error_trait(input.original);
+    if bound_generics.is_some() {
+        error_inferred_bounds = InferredBounds::new();
+    }
     if input.generics.type_params().next().is_some() {
         let self_token = <Token![Self]>::default();
         error_inferred_bounds.insert(self_token, Trait::Debug);
         error_inferred_bounds.insert(self_token, Trait::Display);
     }
-    let error_where_clause = error_inferred_bounds.augment_where_clause(input.generics);
+    let error_where_clause = error_inferred_bounds
+        .augment_where_clause(bound_generics.as_ref().unwrap_or(input.generics));
 
+    // IntoError impls by their source and context types, of which those shared
+    // by more than one variant are left out since they would conflict.
+    let mut into_error_impls = Vec::new();
     let variant_traits_impl: Vec<Option<TokenStream>> = {
-        let generics = {
-            use proc_macro2::{Ident, Span};
-
-            let mut generics = input.generics.clone();
-            generics.params.push(syn::GenericParam::Type(
-                Ident::new("__RETURN", Span::call_site()).into(),
-            ));
-            generics
-        };
+        let generics = throws_generics(input.generics);
         let (thiserror_impl_generics, thiserror_ty_generics, _) = generics.split_for_impl();
+        let where_clause = (bound_generics.as_ref())
+            .map_or(where_clause, |generics| generics.where_clause.as_ref());
 
-        input.variants.iter().map(|variant|{
+        input.variants.iter().filter(|_| throws && !minimal).map(|variant|{
             if let Some(source) = variant.source_field() {
                 let variant_ident = &variant.ident;
                 let trait_name = format_ident!("{}{}Throws", input.ident, variant_ident);
-                let method_name = {
-                    let mut snake = String::new();
-                    for (i, ch) in variant_ident.to_string().char_indices() {
-                        if i > 0 && ch.is_uppercase() {
-                            snake.push('_');
-                        }
-                        snake.push(ch.to_ascii_lowercase());
-                    }
-                    snake = snake.trim_end_matches("_error").to_owned();
-                    snake
-                };
-                let throw_method = format_ident!("throw_{}", method_name);
-                let with_method = format_ident!("throw_{}_with", method_name);
+                let method_name = naming::throw_method_name(&input.attrs, &input.ident, Some(variant_ident));
+                let throw_method = format_ident!("{}", method_name);
+                let with_method = format_ident!("{}_with", method_name);
+                let pipe_trait_name = format_ident!("{}{}Pipe", input.ident, variant_ident);
+                let poll_trait_name = format_ident!("{}{}PollThrows", input.ident, variant_ident);
+                let pipe_method = format_ident!("pipe_{}", method_name);
 
                 let is_source = |field: &Field<'_>| {
                     if field.attrs.from.is_some() || field.attrs.source.is_some() {
                         return true;
                     }
-                    match &field.member {
-                        Member::Named(ident) if ident == "source" && source.member == field.member => true,
-                        _ => false,
-                    }
+                    matches!(&field.member, Member::Named(ident) if ident == "source" && source.member == field.member)
                 };
 
-                let (params, fields, types) = {
+                let (params, fields, types, members, captured) = {
                     use syn::{punctuated::Punctuated, token::Comma, Ident};
 
                     let mut params = Punctuated::<TokenStream, Comma>::new();
                     let mut fields = Punctuated::<Ident, Comma>::new();
                     let mut types = Punctuated::<&Type, Comma>::new();
+                    let mut members = Vec::new();
+                    let mut captured = Vec::new();
 
                     for (i, field) in variant.fields.iter().filter(|f| !is_source(f)).enumerate() {
                         let field_ty = field.ty;
+                        if field.attrs.backtrace_capture.is_some() {
+                            let member = &field.member;
+                            let backtrace = capture_backtrace(field);
+                            captured.push(quote!(#member: #backtrace));
+                            continue;
+                        }
+                        if let Some(value) = captured_value(field) {
+                            let member = &field.member;
+                            captured.push(quote!(#member: #value));
+                            continue;
+                        }
 
                         let field_name = if let Some(field_name) = field.original.ident.as_ref() {
                             field_name.clone()
@@ -603,57 +1270,340 @@ fn impl_enum(input: Enum) -> TokenStream {
                         });
                         fields.push(field_name);
                         types.push(field_ty);
+                        members.push(&field.member);
                     }
 
-                    (params, fields, types)
+                    (params, fields, types, members, captured)
                 };
 
-                let source_ty = source.ty;
+                let context = throw_context(
+                    &[&variant.attrs, &input.attrs],
+                    &format!("{}::{}", input.ident, variant_ident),
+                    format_ident!("{}{}Context", input.ident, variant_ident),
+                    &source.member,
+                    &fields,
+                    &types,
+                );
+                let has_params = !params.is_empty();
+                let (params, unpack, with_ty, with_unpack) = match &context {
+                    Some(context) => {
+                        let ThrowContext { ident, pat, .. } = context;
+                        (
+                            quote!(ctx: #ident),
+                            Some(quote!(let #ident #pat = ctx;)),
+                            quote!(#ident),
+                            quote!(let #ident #pat = f();),
+                        )
+                    }
+                    None => (
+                        quote!(#params),
+                        None,
+                        quote!((#types)),
+                        quote!(let (#fields) = f();),
+                    ),
+                };
+                let context_def = context.map(|context| context.def);
+
+                let source_ty = source.source_impl_type();
+                let catch_all = variant.attrs.catch_all.is_some();
+                let e = if catch_all {
+                    quote!(::core::convert::Into::into(e))
+                } else {
+                    store_source(source, quote!(e))
+                };
+                // Every error converts into the box of an #[error(catch_all)]
+                // variant, so its throw method is there on any Result whose
+                // error does.
+                let (throw_impl_generics, throw_error_ty, convert) =
+                    throw_error_type(&generics, source, source_ty);
+                let (throw_impl, poll_impl) = if catch_all {
+                    (
+                        quote!(impl<__RETURN, __E: ::core::convert::Into<#source_ty>> #trait_name<__RETURN> for Result<__RETURN, __E>),
+                        quote!(impl<__RETURN, __E: ::core::convert::Into<#source_ty>> #poll_trait_name<__RETURN> for ::core::task::Poll<Result<__RETURN, __E>>),
+                    )
+                } else {
+                    (
+                        quote!(impl #throw_impl_generics #trait_name #thiserror_ty_generics for Result<__RETURN, #throw_error_ty> #where_clause),
+                        quote!(impl #throw_impl_generics #poll_trait_name #thiserror_ty_generics for ::core::task::Poll<Result<__RETURN, #throw_error_ty>> #where_clause),
+                    )
+                };
 
                 let new_struct = if let Some(source_field) = source.original.ident.as_ref() {
                     quote! {
                         #ty :: #variant_ident {
-                            #source_field : e,
+                            #source_field : #e,
+                            #(#captured,)*
                             #fields
                         }
                     }
                 } else {
+                    let source_member = &source.member;
+                    let fields = fields.iter();
                     quote! {
-                        #ty :: #variant_ident (e, #fields)
+                        #ty :: #variant_ident {
+                            #source_member : #e,
+                            #(#members : #fields,)*
+                            #(#captured,)*
+                        }
                     }
                 };
+                let new_struct = hooks::wrap(new_struct, &hooks::variant_hooks(&input, variant));
+                let constructor = format_ident!("__thiserror_{}", method_name);
+                let constructor_impl = throw_constructor(
+                    input.generics,
+                    where_clause,
+                    ty,
+                    &constructor,
+                    source_ty,
+                    &fields,
+                    &types,
+                    &new_struct,
+                );
+                let e = if catch_all {
+                    quote!(::core::convert::Into::into(e))
+                } else {
+                    quote!(e)
+                };
+                let construct = quote!(<#ty #ty_generics>::#constructor(#e, #fields));
+                let tuple_from_impl = tuple_from_impl(
+                    &[&variant.attrs, &input.attrs],
+                    input.generics,
+                    ty,
+                    source_ty,
+                    &fields,
+                    &types,
+                    &new_struct,
+                );
+                let throws_cfg = throws_cfg_attr(&[&variant.attrs, &input.attrs]);
+                if let Some(into_error_impl) =
+                    into_error_impl(input.generics, ty, source_ty, &fields, &types, &new_struct)
+                {
+                    let key = quote!(#source_ty, #types).to_string();
+                    into_error_impls.push((key, quote!(#throws_cfg #into_error_impl)));
+                }
+                let deprecated = deprecated_attrs(&variant.original.attrs);
+                let must_use = must_use_attr(&[&variant.attrs, &input.attrs]);
+                let trait_doc = throws_doc_attr(&[&variant.attrs, &input.attrs]);
+                let throws_vis = (input.attrs.throws_vis.as_ref()).map(|throws| &throws.vis);
 
-                let with_method_decl = (!params.is_empty()).then(|| quote!{
-                    fn #with_method<F: FnOnce() -> (#types)> (self, f: F) -> Result<__RETURN, #ty #ty_generics> #where_clause;
+                let with_method_decl = has_params.then(|| quote!{
+                    #(#deprecated)*
+                    #must_use
+                    fn #with_method<F: FnOnce() -> #with_ty> (self, f: F) -> Result<__RETURN, #ty #ty_generics> #where_clause;
                 });
-                let with_method_impl = (!params.is_empty()).then(|| quote!{
-                    fn #with_method<F: FnOnce() -> (#types)> (self, f: F) -> Result<__RETURN, #ty #ty_generics> #where_clause {
+                let with_method_impl = has_params.then(|| quote!{
+                    fn #with_method<F: FnOnce() -> #with_ty> (self, f: F) -> Result<__RETURN, #ty #ty_generics> #where_clause {
                         self.map_err(|e| {
-                            let (#fields) = f();
-                            #new_struct
+                            #convert
+                            #with_unpack
+                            #construct
                         })
                     }
                 });
 
+                let poll_trait = throws_poll(&[&variant.attrs, &input.attrs]).then(|| {
+                    quote! {
+                        #throws_cfg
+                        #trait_doc
+                        #[allow(deprecated)]
+                        #throws_vis trait #poll_trait_name #thiserror_impl_generics {
+                            #(#deprecated)*
+                            #must_use
+                            fn #throw_method (self, #params) -> ::core::task::Poll<Result<__RETURN, #ty #ty_generics>> #where_clause;
+                        }
+                        #throws_cfg
+                        #[allow(deprecated)]
+                        #poll_impl {
+                            fn #throw_method (self, #params) -> ::core::task::Poll<Result<__RETURN, #ty #ty_generics>> #where_clause {
+                                #unpack
+                                self.map_err(|e| {
+                                    #convert
+                                    #construct
+                                })
+                            }
+                        }
+                    }
+                });
+                let pipe_trait = throws_pipe(&[&variant.attrs, &input.attrs]).then(|| {
+                    quote! {
+                        #throws_cfg
+                        #trait_doc
+                        #[allow(deprecated)]
+                        #throws_vis trait #pipe_trait_name #thiserror_impl_generics {
+                            #(#deprecated)*
+                            #must_use
+                            fn #pipe_method<__NEXT, __STAGE: FnOnce(__RETURN) -> Result<__NEXT, #source_ty>> (self, stage: __STAGE, #params) -> Result<thiserror::Pipeline<__NEXT>, #ty #ty_generics> #where_clause;
+                        }
+                        #throws_cfg
+                        #[allow(deprecated)]
+                        impl #thiserror_impl_generics #pipe_trait_name #thiserror_ty_generics for thiserror::Pipeline<__RETURN> #where_clause {
+                            fn #pipe_method<__NEXT, __STAGE: FnOnce(__RETURN) -> Result<__NEXT, #source_ty>> (self, stage: __STAGE, #params) -> Result<thiserror::Pipeline<__NEXT>, #ty #ty_generics> #where_clause {
+                                #unpack
+                                match stage(self.into_inner()) {
+                                    Ok(value) => Ok(thiserror::Pipeline::new(value)),
+                                    Err(e) => Err(#construct),
+                                }
+                            }
+                        }
+                    }
+                });
+
                 Some(quote! {
-                    trait #trait_name #thiserror_impl_generics {
+                    #context_def
+                    #throws_cfg
+                    #constructor_impl
+                    #tuple_from_impl
+                    #throws_cfg
+                    #trait_doc
+                    #[allow(deprecated)]
+                    #throws_vis trait #trait_name #thiserror_impl_generics {
+                        #(#deprecated)*
+                        #must_use
                         fn #throw_method (self, #params) -> Result<__RETURN, #ty #ty_generics> #where_clause;
                         #with_method_decl
                     }
-                    impl #thiserror_impl_generics #trait_name #thiserror_ty_generics for Result<__RETURN, #source_ty> #where_clause {
+                    #throws_cfg
+                    #[allow(deprecated)]
+                    #throw_impl {
                         fn #throw_method (self, #params) -> Result<__RETURN, #ty #ty_generics> #where_clause {
+                            #unpack
                             self.map_err(|e| {
-                                #new_struct
+                                #convert
+                                #construct
                             })
                         }
                         #with_method_impl
                     }
+                    #poll_trait
+                    #pipe_trait
                 })
             } else {
                 None
             }
         }).collect()
     };
+    let into_error_impls: Vec<&TokenStream> = into_error_impls
+        .iter()
+        .filter(|(key, _)| {
+            into_error_impls
+                .iter()
+                .filter(|(other, _)| other == key)
+                .count()
+                == 1
+        })
+        .map(|(_, into_error_impl)| into_error_impl)
+        .collect();
+
+    let mut methods = http::enum_methods(&input);
+    methods.extend(io::enum_io_kind_method(&input));
+    if display_impl.is_some() && !minimal {
+        methods.extend(message::message_methods(input.generics));
+        methods.extend(message::enum_message_methods(&input));
+    }
+    methods.extend(help::enum_help_methods(&input));
+    if !minimal {
+        methods.extend(hooks::enum_name_methods(&input));
+    }
+    methods.extend(i18n::enum_message_key_method(&input));
+    methods.extend(fields::enum_fields_method(&input));
+    methods.extend(related::enum_related_method(&input));
+    methods.extend(cause::enum_causes_method(&input));
+    methods.extend(error_id::enum_error_id_method(&input));
+    let backtrace_method = backtrace::enum_backtrace_method(&input);
+    let backtrace_impl = backtrace::backtrace_impl(input.generics, ty, backtrace_method.is_some());
+    methods.extend(backtrace_method);
+    methods.extend(panic::catch_unwind_method(&input));
+    methods.extend(retry::enum_is_transient_method(&input));
+    methods.extend(kind::kind_method(&input));
+    methods.extend(kind::discriminant_method(&input));
+    methods.extend(ffi::ffi_methods(&input));
+    methods.extend(accessors::accessor_methods(&input));
+    methods.extend(accessors::enum_into_methods(&input));
+    methods.extend(accessors::expect_methods(&input));
+    methods.extend(constructors::enum_constructors(&input));
+    methods.extend(fields::typed_fields_method(
+        input.attrs.typed_fields.as_ref(),
+    ));
+    methods.extend(chain::chain_methods(&input.attrs, input.generics));
+    methods.extend(boxed::boxed_conv_method(&input.attrs, input.generics));
+    if !minimal {
+        methods.extend(templates::enum_messages_const(&input));
+    }
+    methods.extend(templates::enum_templates_const(&input));
+    methods.extend(templates::enum_catalog_const(&input));
+    methods.extend(manifest::enum_manifest_fn(&input));
+    let inherent_impl = inherent_impl(input.generics, ty, methods);
+    let (actix_impl, axum_impl) = if input.has_status() {
+        (
+            Some(http::actix_impl(ty, input.generics, input.has_code())),
+            Some(http::axum_impl(ty, input.generics, input.has_code())),
+        )
+    } else {
+        (None, None)
+    };
+    let grpc_impl = if input.has_grpc() {
+        Some(grpc::enum_status_impl(&input))
+    } else {
+        None
+    };
+    let io_impl = if input.has_io_kind() {
+        Some(io::enum_io_error_impl(&input))
+    } else {
+        None
+    };
+    let py_impl = if input.has_py() {
+        Some(py::enum_py_err_impl(&input))
+    } else {
+        None
+    };
+    let serialize_impl = input
+        .attrs
+        .serialize
+        .map(|attr| serialize::enum_serialize_impl(&input, attr));
+    let arbitrary_impl = input
+        .attrs
+        .arbitrary
+        .map(|attr| arbitrary::enum_arbitrary_impl(&input, attr));
+    let kind_enum = kind::kind_enum(&input);
+    let code_enum = ffi::code_enum(&input);
+    let eq_impl = eq::enum_eq_impl(&input);
+    let debug_impl = sensitive::enum_debug_impl(&input)
+        .or_else(|| chain::debug_chain_impl(&input.attrs, &input.ident));
+    let size_assertion = size::enum_size_assertion(&input);
+    let bounds_assertion = assert::enum_assertion(&input);
+    let wire_type = input
+        .attrs
+        .wire
+        .map(|attr| serialize::wire_type(input.original, attr, input.has_code()));
+    let aggregate_type = input
+        .attrs
+        .aggregate
+        .as_ref()
+        .map(|aggregate| aggregate::aggregate_type(input.original, aggregate));
+    let subset_enums = subset::subset_enums(&input);
+    let diagnostic_impl = diagnostic::enum_diagnostic_impl(&input);
+    let registration = if minimal {
+        None
+    } else {
+        templates::enum_registration(&input)
+    };
+    let error_ext_impl = if minimal {
+        None
+    } else {
+        Some(ext::enum_error_ext_impl(&input, &error_where_clause))
+    };
+    let typed_fields = fields::enum_typed_fields(&input);
+    let extensions = extend::enum_extensions(&input);
+    let hook_errors = hooks::feature_errors(
+        Some(&input.attrs)
+            .into_iter()
+            .chain(input.variants.iter().map(|variant| &variant.attrs)),
+    );
+    let source_suggestions = input
+        .variants
+        .iter()
+        .filter(|variant| variant.attrs.transparent.is_none())
+        .filter_map(|variant| suggest::missing_source(&variant.attrs, &variant.fields));
 
     quote! {
         #[allow(unused_qualifications)]
@@ -661,13 +1611,80 @@ fn impl_enum(input: Enum) -> TokenStream {
             #source_method
             #provide_method
         }
+        #error_ext_impl
         #display_impl
         #(#from_impls)*
+        #poison_from_impl
+        #boxed_from_impls
+        #boxed_payloads
+        #from_any_impl
+        #context_trait
+        #up_impls
+        #(#try_from_impls)*
+        #(#from_value_impls)*
         #(#variant_traits_impl)*
+        #(#into_error_impls)*
+        #inherent_impl
+        #backtrace_impl
+        #actix_impl
+        #axum_impl
+        #grpc_impl
+        #io_impl
+        #py_impl
+        #serialize_impl
+        #arbitrary_impl
+        #wire_type
+        #kind_enum
+        #code_enum
+        #eq_impl
+        #debug_impl
+        #size_assertion
+        #bounds_assertion
+        #diagnostic_impl
+        #registration
+        #typed_fields
+        #subset_enums
+        #aggregate_type
+        #(#source_suggestions)*
+        #extensions
+        #hook_errors
     }
 }
 
-fn fields_pat(fields: &[Field]) -> TokenStream {
+fn inherent_impl(generics: &Generics, ty: &Ident, methods: TokenStream) -> Option<TokenStream> {
+    if methods.is_empty() {
+        return None;
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Some(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #ty #ty_generics #where_clause {
+            #methods
+        }
+    })
+}
+
+// The locals bound by `fields_pat`, in declaration order.
+fn fields_vars(fields: &[Field]) -> Vec<Ident> {
+    fields
+        .iter()
+        .map(|field| match &field.member {
+            Member::Named(ident) => ident.clone(),
+            Member::Unnamed(index) => format_ident!("_{}", index),
+        })
+        .collect()
+}
+
+// The position of the field among its struct's or variant's, as recorded by
+// implied bounds.
+fn field_index(fields: &[Field], field: &Field) -> usize {
+    fields
+        .iter()
+        .position(|other| other.member == field.member)
+        .unwrap()
+}
+
+pub fn fields_pat(fields: &[Field]) -> TokenStream {
     let mut members = fields.iter().map(|field| &field.member).peekable();
     match members.peek() {
         Some(Member::Named(_)) => quote!({ #(#members),* }),
@@ -682,7 +1699,7 @@ fn fields_pat(fields: &[Field]) -> TokenStream {
     }
 }
 
-fn use_as_display(needs_as_display: bool) -> Option<TokenStream> {
+pub fn use_as_display(needs_as_display: bool) -> Option<TokenStream> {
     if needs_as_display {
         Some(quote! {
             use thiserror::__private::AsDisplay as _;
@@ -692,32 +1709,438 @@ fn use_as_display(needs_as_display: bool) -> Option<TokenStream> {
     }
 }
 
-fn from_initializer(from_field: &Field, backtrace_field: Option<&Field>) -> TokenStream {
+// The #[deprecated] attributes of a type or variant, repeated on the throw
+// methods which construct it so that their callers are warned too.
+pub fn deprecated_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("deprecated"))
+        .collect()
+}
+
+// `#[must_use]` for the throw methods under #[throws(must_use)], taken from the
+// first of the given attributes which sets it, so a variant may override its
+// enum.
+pub fn must_use_attr(attrs: &[&Attrs]) -> Option<TokenStream> {
+    attrs.iter().find_map(|attrs| attrs.must_use)?;
+    Some(quote! {
+        #[must_use]
+        #[allow(clippy::double_must_use)]
+    })
+}
+
+// The documentation of the throw traits under #[throws(doc = "...")] or
+// #[throws(doc(hidden))], chosen the same way as `must_use_attr`.
+pub fn throws_doc_attr(attrs: &[&Attrs]) -> Option<TokenStream> {
+    let doc = attrs.iter().find_map(|attrs| attrs.throws_doc.as_ref())?;
+    Some(match &doc.text {
+        Some(text) => quote!(#[doc = #text]),
+        None => quote!(#[doc(hidden)]),
+    })
+}
+
+// Under #[throws(feature = "...")], chosen the same way as `must_use_attr`,
+// the cfg which every item generated for the throw methods is compiled under.
+pub fn throws_cfg_attr(attrs: &[&Attrs]) -> Option<TokenStream> {
+    let feature = attrs
+        .iter()
+        .find_map(|attrs| attrs.throws_feature.as_ref())?;
+    let name = &feature.name;
+    Some(quote!(#[cfg(feature = #name)]))
+}
+
+// Under #[throws(poll)], chosen the same way as `must_use_attr`, whether the
+// throw method also gets a sibling trait on `Poll<Result<T, E>>`.
+fn throws_poll(attrs: &[&Attrs]) -> bool {
+    attrs.iter().any(|attrs| attrs.throws_poll.is_some())
+}
+
+// Under #[throws(pipe)], chosen the same way as `must_use_attr`, whether the
+// throw method also gets a `pipe_*` counterpart on `thiserror::Pipeline`.
+fn throws_pipe(attrs: &[&Attrs]) -> bool {
+    attrs.iter().any(|attrs| attrs.throws_pipe.is_some())
+}
+
+// The constructor which throw methods call to build the error from its source
+// and the other fields. Kept out of line and free of `__RETURN`, so that it is
+// compiled once however many success types the throw methods are used with.
+#[allow(clippy::too_many_arguments)]
+fn throw_constructor(
+    generics: &Generics,
+    where_clause: Option<&WhereClause>,
+    ty: &Ident,
+    constructor: &Ident,
+    source_ty: &Type,
+    fields: &syn::punctuated::Punctuated<Ident, Token![,]>,
+    types: &syn::punctuated::Punctuated<&Type, Token![,]>,
+    construct: &TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let fields = fields.iter();
+    let types = types.iter();
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #ty #ty_generics #where_clause {
+            #[doc(hidden)]
+            #[inline(never)]
+            #[allow(deprecated, clippy::too_many_arguments)]
+            fn #constructor(e: #source_ty, #(#fields: #types),*) -> Self {
+                #construct
+            }
+        }
+    }
+}
+
+// Under #[throws(tuple_from)], chosen the same way as `must_use_attr`, a From
+// impl which constructs the error the way its throw methods do, from a tuple
+// of the source followed by the other fields.
+fn tuple_from_impl(
+    attrs: &[&Attrs],
+    generics: &Generics,
+    ty: &Ident,
+    source_ty: &Type,
+    fields: &syn::punctuated::Punctuated<Ident, Token![,]>,
+    types: &syn::punctuated::Punctuated<&Type, Token![,]>,
+    construct: &TokenStream,
+) -> Option<TokenStream> {
+    attrs.iter().find_map(|attrs| attrs.tuple_from)?;
+    if fields.is_empty() {
+        return None;
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let cfg = throws_cfg_attr(attrs);
+    Some(quote! {
+        #cfg
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::convert::From<(#source_ty, #types)> for #ty #ty_generics #where_clause {
+            #[allow(deprecated)]
+            fn from((e, #fields): (#source_ty, #types)) -> Self {
+                #construct
+            }
+        }
+    })
+}
+
+// The thiserror::IntoError impl for the source type, which constructs the
+// error the way its throw methods do, taking the other fields as the context:
+// `()` if there are none, the field itself if there is one, or else a tuple.
+// Left out for generic errors, whose source may be a type parameter.
+fn into_error_impl(
+    generics: &Generics,
+    ty: &Ident,
+    source_ty: &Type,
+    fields: &syn::punctuated::Punctuated<Ident, Token![,]>,
+    types: &syn::punctuated::Punctuated<&Type, Token![,]>,
+    construct: &TokenStream,
+) -> Option<TokenStream> {
+    if !generics.params.is_empty() {
+        return None;
+    }
+    let (pat, context_ty) = if fields.len() == 1 {
+        (quote!(#fields), quote!(#types))
+    } else {
+        (quote!((#fields)), quote!((#types)))
+    };
+    Some(quote! {
+        #[allow(unused_qualifications)]
+        impl thiserror::IntoError<#ty, #context_ty> for #source_ty {
+            #[allow(deprecated)]
+            fn into_error_with(self, #pat: #context_ty) -> #ty {
+                let e = self;
+                #construct
+            }
+        }
+    })
+}
+
+// Under #[throws(context_struct)], chosen the same way as `must_use_attr`, a
+// struct of the fields other than the source which the throw methods take in
+// place of one parameter per field, along with the pattern which unpacks it
+// back into those parameters.
+struct ThrowContext {
+    ident: Ident,
+    def: TokenStream,
+    pat: TokenStream,
+}
+
+fn throw_context(
+    attrs: &[&Attrs],
+    name: &str,
+    ident: Ident,
+    source: &Member,
+    fields: &syn::punctuated::Punctuated<Ident, Token![,]>,
+    types: &syn::punctuated::Punctuated<&Type, Token![,]>,
+) -> Option<ThrowContext> {
+    attrs.iter().find_map(|attrs| attrs.context_struct)?;
+    if fields.is_empty() {
+        return None;
+    }
+    let names: Vec<&Ident> = fields.iter().collect();
+    let tys: Vec<&Type> = types.iter().copied().collect();
+    let (body, pat) = match source {
+        Member::Named(_) => (quote!({ #(pub #names: #tys,)* }), quote!({ #(#names),* })),
+        Member::Unnamed(_) => (quote!((#(pub #tys),*);), quote!((#(#names),*))),
+    };
+    let (tuple_ty, tuple_pat) = if names.len() == 1 {
+        (quote!(#(#tys)*), quote!(#(#names)*))
+    } else {
+        (quote!((#(#tys),*)), quote!((#(#names),*)))
+    };
+    let doc = format!(
+        "The fields of `{}` other than its source, taken by its throw methods.",
+        name,
+    );
+    let cfg = throws_cfg_attr(attrs);
+    let vis = (attrs.iter())
+        .find_map(|attrs| attrs.throws_vis.as_ref())
+        .map(|throws| &throws.vis);
+    let def = quote! {
+        #cfg
+        #[doc = #doc]
+        #vis struct #ident #body
+
+        #cfg
+        impl ::core::convert::From<#tuple_ty> for #ident {
+            fn from(#tuple_pat: #tuple_ty) -> Self {
+                #ident #pat
+            }
+        }
+    };
+    Some(ThrowContext { ident, def, pat })
+}
+
+pub fn from_initializer(
+    from_field: &Field,
+    backtrace_field: Option<&Field>,
+    fields: &[Field],
+) -> TokenStream {
     let from_member = &from_field.member;
+    let source = store_source(from_field, quote!(source));
     let some_source = if type_is_option(from_field.ty) {
-        quote!(::core::option::Option::Some(source))
+        quote!(::core::option::Option::Some(#source))
     } else {
-        quote!(source)
+        source
     };
     let backtrace = backtrace_field.map(|backtrace_field| {
         let backtrace_member = &backtrace_field.member;
-        if type_is_option(backtrace_field.ty) {
-            quote! {
-                #backtrace_member: ::core::option::Option::Some(std::backtrace::Backtrace::capture()),
-            }
-        } else {
-            quote! {
-                #backtrace_member: ::core::convert::From::from(std::backtrace::Backtrace::capture()),
-            }
-        }
+        let backtrace = capture_backtrace(backtrace_field);
+        quote!(#backtrace_member: #backtrace,)
+    });
+    let captured = fields.iter().filter_map(|field| {
+        let member = &field.member;
+        let value = captured_value(field)?;
+        Some(quote!(#member: #value,))
     });
     quote!({
         #from_member: #some_source,
         #backtrace
+        #(#captured)*
     })
 }
 
-fn type_is_option(ty: &Type) -> bool {
+// The value of a field which is filled in when the error is constructed rather
+// than passed in: the current time for #[timestamp], the current thread or
+// process for #[capture(...)], the thread's context for #[context_frames], a new identifier for #[error_id], PhantomData
+// for a marker field, or the type's default under #[throws(default)].
+pub fn captured_value(field: &Field) -> Option<TokenStream> {
+    if field.attrs.throws_default.is_some() {
+        let ty = field.ty;
+        return Some(quote_spanned!(ty.span()=> <#ty as ::core::default::Default>::default()));
+    }
+    if let Some(error_id) = &field.attrs.error_id {
+        return Some(error_id::generate(field, error_id));
+    }
+    let kind = if field.attrs.timestamp.is_some() {
+        quote!(Timestamp)
+    } else if field.attrs.context_frames.is_some() {
+        quote!(Context)
+    } else if let Some(capture) = &field.attrs.capture {
+        match capture.kind {
+            CaptureKind::ThreadName => quote!(ThreadName),
+            CaptureKind::ThreadId => quote!(ThreadId),
+            CaptureKind::Pid => quote!(Pid),
+        }
+    } else if field.is_phantom() {
+        return Some(quote!(::core::marker::PhantomData));
+    } else {
+        return None;
+    };
+    let ty = field.ty;
+    Some(quote_spanned! {ty.span()=>
+        <#ty as thiserror::__private::capture::Capture<thiserror::__private::capture::#kind>>::capture()
+    })
+}
+
+// A backtrace for the field, captured by the function given in
+// #[backtrace(capture = ...)] or else by std.
+pub fn capture_backtrace(backtrace_field: &Field) -> TokenStream {
+    let capture = match &backtrace_field.attrs.backtrace_capture {
+        Some(capture) => quote!(#capture()),
+        None => quote!(std::backtrace::Backtrace::capture()),
+    };
+    if type_is_option(backtrace_field.ty) {
+        quote!(::core::option::Option::Some(#capture))
+    } else {
+        quote!(::core::convert::From::from(#capture))
+    }
+}
+
+// The type a backtrace field is provided as: std's Backtrace, unless it is
+// captured by a function of its own, in which case the field's type.
+fn provided_backtrace_type(backtrace_field: &Field) -> TokenStream {
+    if backtrace_field.attrs.backtrace_capture.is_none() {
+        return quote!(std::backtrace::Backtrace);
+    }
+    let ty = type_parameter_of_option(backtrace_field.ty).unwrap_or(backtrace_field.ty);
+    quote!(#ty)
+}
+
+// Like the From impl of a #[from] field, except that the value is handed back
+// as the conversion error if the validation function rejects it.
+fn try_from_impl(
+    generics: &Generics,
+    ty: &Ident,
+    try_from_field: &Field,
+    backtrace_field: Option<&Field>,
+    fields: &[Field],
+    construct: impl FnOnce(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let from = source_value_type(try_from_field);
+    let validate = &try_from_field.attrs.try_from.as_ref().unwrap().validate;
+    let construct = construct(from_initializer(try_from_field, backtrace_field, fields));
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::convert::TryFrom<#from> for #ty #ty_generics #where_clause {
+            type Error = #from;
+
+            #[allow(deprecated)]
+            fn try_from(source: #from) -> ::core::result::Result<Self, #from> {
+                if !#validate(&source) {
+                    return ::core::result::Result::Err(source);
+                }
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+    }
+}
+
+// The From impl of a #[from_value] field, which holds a plain value rather than
+// an error source.
+fn from_value_impl(
+    generics: &Generics,
+    ty: &Ident,
+    from_value_field: &Field,
+    backtrace_field: Option<&Field>,
+    fields: &[Field],
+    construct: impl FnOnce(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let from = unoptional_type(from_value_field.ty);
+    let construct = construct(from_initializer(from_value_field, backtrace_field, fields));
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::convert::From<#from> for #ty #ty_generics #where_clause {
+            #[allow(deprecated)]
+            fn from(source: #from) -> Self {
+                #construct
+            }
+        }
+    }
+}
+
+// The type which From impls and throw methods accept for the source field:
+// the one given by #[source(ty = ...)], or else the field's own type, unwrapped
+// from an Option or an #[error(arc_source)] Arc.
+fn source_value_type(field: &Field) -> TokenStream {
+    if let Some(ty) = &field.attrs.source_ty {
+        return quote!(#ty);
+    }
+    match field.arc_source_type() {
+        Some(ty) => quote!(#ty),
+        None => unoptional_type(field.ty),
+    }
+}
+
+// The generics of a throws trait: those of the error type with `__RETURN`, the
+// success type of the Result.
+fn throws_generics(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    generics.params.push(syn::parse_quote!(__RETURN));
+    generics
+}
+
+// The impl generics and error type of the Result which a throw method is
+// implemented on, along with the conversion of that error into a value of
+// `source_value_type`. An `Arc<dyn Error>` source takes any error which boxes
+// into the trait object, and arcs the box.
+fn throw_error_type(
+    generics: &Generics,
+    source: &Field,
+    source_ty: &Type,
+) -> (TokenStream, TokenStream, Option<TokenStream>) {
+    match source.arc_dyn_source_type() {
+        Some(object) => {
+            let mut generics = generics.clone();
+            generics.params.push(syn::parse_quote! {
+                __E: ::core::convert::Into<::std::boxed::Box<#object>>
+            });
+            let (impl_generics, _, _) = generics.split_for_impl();
+            let convert = quote! {
+                let e = ::std::sync::Arc::<#object>::from(
+                    ::core::convert::Into::<::std::boxed::Box<#object>>::into(e),
+                );
+            };
+            (quote!(#impl_generics), quote!(__E), Some(convert))
+        }
+        None => {
+            let (impl_generics, _, _) = generics.split_for_impl();
+            (quote!(#impl_generics), quote!(#source_ty), None)
+        }
+    }
+}
+
+// Converts a value of `source_value_type` into the field's type.
+pub fn store_source(field: &Field, value: TokenStream) -> TokenStream {
+    if field.attrs.arc_source.is_some() {
+        quote!(::std::sync::Arc::new(#value))
+    } else {
+        value
+    }
+}
+
+// Provides each #[provide] field by its type, or for an Option the type it
+// holds when it is Some, and the #[context_frames] field for a Report to list.
+// Returns the fields pattern to bind them with.
+fn provided_fields(fields: &[Field], request: &TokenStream) -> Option<(TokenStream, TokenStream)> {
+    let provides: Vec<TokenStream> = fields
+        .iter()
+        .filter(|field| field.attrs.provide.is_some() || field.attrs.context_frames.is_some())
+        .map(|field| {
+            let binding = match &field.member {
+                Member::Named(ident) => ident.clone(),
+                Member::Unnamed(index) => format_ident!("_{}", index),
+            };
+            match type_parameter_of_option(field.ty) {
+                Some(ty) => quote! {
+                    if let ::core::option::Option::Some(value) = #binding {
+                        #request.provide_ref::<#ty>(value);
+                    }
+                },
+                None => {
+                    let ty = field.ty;
+                    quote!(#request.provide_ref::<#ty>(#binding);)
+                }
+            }
+        })
+        .collect();
+    if provides.is_empty() {
+        return None;
+    }
+    Some((fields_pat(fields), quote!(#(#provides)*)))
+}
+
+pub fn type_is_option(ty: &Type) -> bool {
     type_parameter_of_option(ty).is_some()
 }
 
@@ -0,0 +1,163 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Attrs;
+use crate::diagnostic::diagnostic_option;
+use crate::help::implied_bounds;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, LitStr, WhereClause};
+
+pub fn struct_error_ext_impl(input: &Struct, where_clause: &WhereClause) -> TokenStream {
+    let code = code_of(&input.attrs, None).map(|code| quote!(::core::option::Option::Some(#code)));
+    let severity = severity_of(&input.attrs, None)
+        .map(|severity| quote!(::core::option::Option::Some(#severity)));
+    let has_help = input
+        .attrs
+        .help
+        .as_ref()
+        .map_or(false, |help| implied_bounds(help, &input.fields).is_empty());
+    let methods = Methods {
+        code,
+        severity,
+        has_help,
+        has_retry: input.attrs.retry.is_some(),
+    };
+    error_ext_impl(input.generics, where_clause, &input.ident, methods)
+}
+
+pub fn enum_error_ext_impl(input: &Enum, where_clause: &WhereClause) -> TokenStream {
+    let ty = &input.ident;
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    let per_variant = |value: &dyn Fn(&Attrs) -> Option<TokenStream>| {
+        if input
+            .variants
+            .iter()
+            .all(|variant| value(&variant.attrs).is_none())
+        {
+            return None;
+        }
+        let arms = input.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let value = match value(&variant.attrs) {
+                Some(value) => quote!(::core::option::Option::Some(#value)),
+                None => quote!(::core::option::Option::None),
+            };
+            quote!(#ty::#ident {..} => #value,)
+        });
+        Some(quote! {
+            #[allow(deprecated)]
+            match #void_deref self {
+                #(#arms)*
+            }
+        })
+    };
+    let code = per_variant(&|attrs| code_of(attrs, Some(&input.attrs)).map(|code| quote!(#code)));
+    let severity = per_variant(&|attrs| {
+        severity_of(attrs, Some(&input.attrs)).map(|severity| quote!(#severity))
+    });
+    let mut has_help = false;
+    for variant in &input.variants {
+        if let Some(help) = &variant.attrs.help {
+            if !implied_bounds(help, &variant.fields).is_empty() {
+                has_help = false;
+                break;
+            }
+            has_help = true;
+        }
+    }
+    let has_retry = input.attrs.retry.is_some()
+        || input
+            .variants
+            .iter()
+            .any(|variant| variant.attrs.retry.is_some());
+    let methods = Methods {
+        code,
+        severity,
+        has_help,
+        has_retry,
+    };
+    error_ext_impl(input.generics, where_clause, ty, methods)
+}
+
+// The bodies of the ErrorExt methods which the error overrides; the others
+// keep their defaults.
+struct Methods {
+    code: Option<TokenStream>,
+    severity: Option<TokenStream>,
+    // Whether the error has a help() accessor which needs no bounds beyond
+    // those of its Error impl.
+    has_help: bool,
+    has_retry: bool,
+}
+
+fn error_ext_impl(
+    generics: &Generics,
+    where_clause: &WhereClause,
+    ty: &Ident,
+    methods: Methods,
+) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let code_method = methods.code.map(|code| {
+        quote! {
+            fn code(&self) -> ::core::option::Option<&'static str> {
+                #code
+            }
+        }
+    });
+    let severity_method = methods.severity.map(|severity| {
+        quote! {
+            fn severity(&self) -> ::core::option::Option<&'static str> {
+                #severity
+            }
+        }
+    });
+    let help_method = methods.has_help.then(|| {
+        quote! {
+            fn help(&self) -> ::core::option::Option<::std::borrow::Cow<'static, str>> {
+                Self::help(self)
+            }
+        }
+    });
+    let is_transient_method = methods.has_retry.then(|| {
+        quote! {
+            fn is_transient(&self) -> bool {
+                Self::is_transient(self)
+            }
+        }
+    });
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics thiserror::ErrorExt for #ty #ty_generics #where_clause {
+            #code_method
+            #severity_method
+            #help_method
+
+            fn kind_name(&self) -> ::core::option::Option<&'static str> {
+                ::core::option::Option::Some(Self::variant_name(self))
+            }
+
+            #is_transient_method
+        }
+    }
+}
+
+// The code of #[error(code = ...)], or else that of #[diagnostic(code(...))],
+// as in the catalog.
+fn code_of<'a>(attrs: &'a Attrs, type_attrs: Option<&'a Attrs>) -> Option<&'a LitStr> {
+    attrs
+        .code
+        .as_ref()
+        .or_else(|| type_attrs?.code.as_ref())
+        .or_else(|| diagnostic_option(attrs, type_attrs, |d| d.code.as_ref()))
+}
+
+fn severity_of(attrs: &Attrs, type_attrs: Option<&Attrs>) -> Option<LitStr> {
+    let severity = diagnostic_option(attrs, type_attrs, |d| d.severity.as_ref())?;
+    Some(LitStr::new(
+        &severity.to_string().to_lowercase(),
+        severity.span(),
+    ))
+}
@@ -0,0 +1,132 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::{Attrs, Display};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Generics, Ident, LitInt, LitStr};
+
+pub fn struct_extensions(input: &Struct) -> TokenStream {
+    if input.attrs.extensions.is_empty() {
+        return TokenStream::new();
+    }
+    let ty = &input.ident;
+    let variant = variant_model(
+        ty,
+        quote!(#ty),
+        &input.attrs,
+        None,
+        &input.fields,
+        input.source_field(),
+    );
+    let model = model(quote!(struct), ty, input.generics, &[variant]);
+    invoke(&input.attrs, &model)
+}
+
+pub fn enum_extensions(input: &Enum) -> TokenStream {
+    if input.attrs.extensions.is_empty() {
+        return TokenStream::new();
+    }
+    let ty = &input.ident;
+    let variants: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            variant_model(
+                ident,
+                quote!(#ty::#ident),
+                &variant.attrs,
+                Some(&input.attrs),
+                &variant.fields,
+                variant.source_field(),
+            )
+        })
+        .collect();
+    let model = model(quote!(enum), ty, input.generics, &variants);
+    invoke(&input.attrs, &model)
+}
+
+fn invoke(attrs: &Attrs, model: &TokenStream) -> TokenStream {
+    let paths = attrs.extensions.iter().map(|extension| &extension.path);
+    quote! {
+        #(
+            #paths! { #model }
+        )*
+    }
+}
+
+fn model(
+    kind: TokenStream,
+    ty: &Ident,
+    generics: &Generics,
+    variants: &[TokenStream],
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        kind = #kind,
+        ident = #ty,
+        impl_generics = [#impl_generics],
+        ty_generics = [#ty_generics],
+        where_clause = [#where_clause],
+        variants = [#(#variants),*],
+    }
+}
+
+// The attributes of a variant fall back to those of the enum, as in the
+// generated accessors.
+fn variant_model(
+    ident: &Ident,
+    path: TokenStream,
+    attrs: &Attrs,
+    type_attrs: Option<&Attrs>,
+    fields: &[Field],
+    source_field: Option<&Field>,
+) -> TokenStream {
+    let message = option(attrs.display.as_ref().map(Display::template_tokens));
+    let code = option(
+        attrs
+            .code
+            .as_ref()
+            .or_else(|| type_attrs.and_then(|attrs| attrs.code.as_ref()))
+            .map(LitStr::to_token_stream),
+    );
+    let status = option(
+        attrs
+            .status
+            .as_ref()
+            .or_else(|| type_attrs.and_then(|attrs| attrs.status.as_ref()))
+            .map(LitInt::to_token_stream),
+    );
+    let fields = fields.iter().map(|field| {
+        let member = &field.member;
+        let ty = field.ty;
+        let source = source_field.map_or(false, |source| source.member == field.member);
+        let from = field.attrs.from.is_some();
+        let backtrace = field.attrs.backtrace.is_some() || field.is_backtrace();
+        quote! {
+            {
+                member = #member,
+                ty = [#ty],
+                source = #source,
+                from = #from,
+                backtrace = #backtrace,
+            }
+        }
+    });
+    quote! {
+        {
+            ident = #ident,
+            path = [#path],
+            message = #message,
+            code = #code,
+            status = #status,
+            fields = [#(#fields),*],
+        }
+    }
+}
+
+fn option(value: Option<TokenStream>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(#value)),
+        None => quote!(::core::option::Option::None),
+    }
+}
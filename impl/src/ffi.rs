@@ -0,0 +1,73 @@
+use crate::ast::Enum;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+// `#[naur(ffi)]`: a `#[repr(C)]` enum of codes mirroring the variants, which
+// start at 1 so that 0 is left for success.
+pub fn code_enum(input: &Enum) -> Option<TokenStream> {
+    input.attrs.ffi?;
+    let ty = &input.ident;
+    let vis = &input.original.vis;
+    let code = format_ident!("{}Code", ty);
+    let variants = input.variants.iter().map(|variant| &variant.ident);
+    let values = (1..=input.variants.len()).map(|i| Literal::i32_unsuffixed(i as i32));
+    let doc = format!("The C error codes of the variants of `{}`.", ty);
+    Some(quote! {
+        #[doc = #doc]
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+        )]
+        #[repr(C)]
+        #vis enum #code {
+            #(
+                #[allow(missing_docs)]
+                #variants = #values,
+            )*
+        }
+    })
+}
+
+pub fn ffi_methods(input: &Enum) -> Option<TokenStream> {
+    input.attrs.ffi?;
+    let ty = &input.ident;
+    let code = format_ident!("{}Code", ty);
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        quote!(#ty::#ident {..} => #code::#ident,)
+    });
+    let where_clause = if input.generics.type_params().next().is_some() {
+        Some(quote!(where Self: ::core::fmt::Display))
+    } else {
+        None
+    };
+    Some(quote! {
+        /// The C error code of the current variant.
+        pub fn to_ffi_code(&self) -> #code {
+            #[allow(deprecated)]
+            match self {
+                #(#arms)*
+            }
+        }
+
+        /// Writes the Display message into a C buffer of `len` bytes,
+        /// truncated to fit and terminated by a NUL, and returns the length
+        /// of the whole message in bytes, not counting the NUL. A return value
+        /// of `len` or more means the message was truncated.
+        ///
+        /// # Safety
+        ///
+        /// `buf` must be null or valid for writes of `len` bytes.
+        pub unsafe fn message_into(
+            &self,
+            buf: *mut ::std::os::raw::c_char,
+            len: usize,
+        ) -> usize #where_clause {
+            thiserror::__private::message_into(self, buf, len)
+        }
+    })
+}
@@ -0,0 +1,285 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::TypedFields;
+use crate::sensitive;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Ident, LitStr, Member, Type};
+
+pub fn struct_fields_method(input: &Struct) -> Option<TokenStream> {
+    input.attrs.fields?;
+    let ty = &input.ident;
+    let entries = Entries::new(&input.fields, input.source_field());
+    let len = entries.values.len();
+    let pat = &entries.pat;
+    let values = &entries.values;
+    let arm = quote! {
+        #ty #pat => [#(#values),*],
+    };
+    Some(fields_method(len, &[arm], &entries.bounds))
+}
+
+pub fn enum_fields_method(input: &Enum) -> Option<TokenStream> {
+    input.attrs.fields?;
+    let ty = &input.ident;
+    let entries: Vec<Entries> = input
+        .variants
+        .iter()
+        .map(|variant| Entries::new(&variant.fields, variant.source_field()))
+        .collect();
+    let len = entries
+        .iter()
+        .map(|entries| entries.values.len())
+        .max()
+        .unwrap_or(0);
+    let arms: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .zip(&entries)
+        .map(|(variant, entries)| {
+            let ident = &variant.ident;
+            let pat = &entries.pat;
+            let values = &entries.values;
+            let padding = (values.len()..len).map(|_| quote!(::core::option::Option::None));
+            quote! {
+                #ty::#ident #pat => [#(#values,)* #(#padding),*],
+            }
+        })
+        .collect();
+    let bounds: Vec<TokenStream> = entries
+        .into_iter()
+        .flat_map(|entries| entries.bounds)
+        .collect();
+    Some(fields_method(len, &arms, &bounds))
+}
+
+fn fields_method(len: usize, arms: &[TokenStream], bounds: &[TokenStream]) -> TokenStream {
+    let where_clause = if bounds.is_empty() {
+        None
+    } else {
+        Some(quote!(where #(#bounds,)*))
+    };
+    let void_deref = if arms.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+
+    quote! {
+        /// The name and value of each field of the error, other than its
+        /// source and backtrace, for structured logging.
+        pub fn fields(
+            &self,
+        ) -> impl ::core::iter::Iterator<Item = (&'static str, thiserror::FieldValue<'_>)>
+        #where_clause
+        {
+            #[allow(unused_imports)]
+            use thiserror::__private::{FieldValueViaDebug as _, FieldValueViaDisplay as _};
+            #[allow(deprecated)]
+            let fields: [::core::option::Option<(&'static str, thiserror::FieldValue<'_>)>; #len] =
+                match #void_deref self {
+                    #(#arms)*
+                };
+            ::core::iter::Iterator::flatten(::core::iter::IntoIterator::into_iter(fields))
+        }
+    }
+}
+
+struct Entries {
+    pat: TokenStream,
+    values: Vec<TokenStream>,
+    bounds: Vec<TokenStream>,
+}
+
+impl Entries {
+    fn new(fields: &[Field], source_field: Option<&Field>) -> Self {
+        let mut members = Vec::new();
+        let mut bindings = Vec::new();
+        let mut values = Vec::new();
+        let mut bounds = Vec::new();
+        for field in fields {
+            let is_source = source_field.map_or(false, |source| source.member == field.member);
+            if is_source || field.attrs.backtrace.is_some() || field.is_backtrace() {
+                continue;
+            }
+            let binding = match &field.member {
+                Member::Named(ident) => format_ident!("__field_{}", ident),
+                Member::Unnamed(index) => format_ident!("__field_{}", index),
+            };
+            let name = match &field.member {
+                Member::Named(ident) => LitStr::new(&ident.unraw().to_string(), ident.span()),
+                Member::Unnamed(index) => LitStr::new(&index.index.to_string(), index.span),
+            };
+            if let Some(redacted) = sensitive::redacted_value(field) {
+                values.push(quote!(::core::option::Option::Some((#name, #redacted))));
+                continue;
+            }
+            // Autoref specialization cannot see through generic parameters,
+            // so fields mentioning one are always rendered with Debug.
+            let value = if field.contains_generic {
+                let ty = field.ty;
+                bounds.push(quote!(#ty: ::core::fmt::Debug));
+                quote!(thiserror::FieldValue::Debug(#binding))
+            } else {
+                quote!((&&thiserror::__private::FieldValueWrap(#binding)).field_value())
+            };
+            values.push(quote!(::core::option::Option::Some((#name, #value))));
+            members.push(&field.member);
+            bindings.push(binding);
+        }
+        let pat = quote!({ #(#members: #bindings,)* .. });
+        Entries {
+            pat,
+            values,
+            bounds,
+        }
+    }
+}
+
+pub fn typed_fields_method(typed_fields: Option<&TypedFields>) -> Option<TokenStream> {
+    typed_fields?;
+    Some(quote! {
+        /// The value of the field identified by the marker type `F`, if the
+        /// error carries one.
+        pub fn get<__F>(&self) -> ::core::option::Option<&__F::Value>
+        where
+            __F: thiserror::ErrorField<Self>,
+        {
+            __F::get(self)
+        }
+    })
+}
+
+pub fn struct_typed_fields(input: &Struct) -> Option<TokenStream> {
+    let typed_fields = input.attrs.typed_fields.as_ref()?;
+    let ty = &input.ident;
+    let markers = input
+        .fields
+        .iter()
+        .filter_map(|field| {
+            let ident = match &field.member {
+                Member::Named(ident) => ident,
+                Member::Unnamed(_) => return None,
+            };
+            let arms = vec![quote!(#ty { #ident, .. } => ::core::option::Option::Some(#ident),)];
+            Some((ident, field.ty, arms))
+        })
+        .collect();
+    Some(typed_fields_items(input.original, typed_fields, markers))
+}
+
+pub fn enum_typed_fields(input: &Enum) -> Option<TokenStream> {
+    let typed_fields = input.attrs.typed_fields.as_ref()?;
+    let mut markers: Vec<(&Ident, &Type, Vec<TokenStream>)> = Vec::new();
+    for variant in &input.variants {
+        for field in &variant.fields {
+            let ident = match &field.member {
+                Member::Named(ident) => ident,
+                Member::Unnamed(_) => continue,
+            };
+            if !markers.iter().any(|(marker, _, _)| *marker == ident) {
+                markers.push((ident, field.ty, Vec::new()));
+            }
+        }
+    }
+    let ty = &input.ident;
+    for (ident, _, arms) in &mut markers {
+        for variant in &input.variants {
+            let variant_ident = &variant.ident;
+            let has_field = variant
+                .fields
+                .iter()
+                .any(|field| matches!(&field.member, Member::Named(member) if member == *ident));
+            arms.push(if has_field {
+                quote!(#ty::#variant_ident { #ident, .. } => ::core::option::Option::Some(#ident),)
+            } else {
+                quote!(#ty::#variant_ident {..} => ::core::option::Option::None,)
+            });
+        }
+    }
+    Some(typed_fields_items(input.original, typed_fields, markers))
+}
+
+// A module of zero-sized marker types, one per field name, each implementing
+// ErrorField to look its field up in whichever variant carries it.
+fn typed_fields_items(
+    input: &DeriveInput,
+    typed_fields: &TypedFields,
+    markers: Vec<(&Ident, &Type, Vec<TokenStream>)>,
+) -> TokenStream {
+    let ty = &input.ident;
+    let vis = &input.vis;
+    let module = match &typed_fields.module {
+        Some(module) => module.clone(),
+        None => format_ident!("{}_fields", snake_case(&ty.to_string())),
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let structs = markers.iter().map(|(ident, _, _)| {
+        let marker = format_ident!("{}", upper_camel_case(&ident.unraw().to_string()));
+        let doc = format!(" Marker for the `{}` field of `{}`.", ident.unraw(), ty);
+        quote! {
+            #[doc = #doc]
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::marker::Copy)]
+            pub struct #marker;
+        }
+    });
+    let impls = markers.iter().map(|(ident, field_ty, arms)| {
+        let marker = format_ident!("{}", upper_camel_case(&ident.unraw().to_string()));
+        quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics thiserror::ErrorField<#ty #ty_generics> for #module::#marker #where_clause {
+                type Value = #field_ty;
+
+                fn get(error: &#ty #ty_generics) -> ::core::option::Option<&Self::Value> {
+                    #[allow(deprecated)]
+                    match error {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+    });
+    let doc = format!(
+        " Field marker types of `{}`, generated by `#[naur(typed_fields)]`.",
+        ty,
+    );
+    quote! {
+        #[doc = #doc]
+        #vis mod #module {
+            #(#structs)*
+        }
+
+        #(#impls)*
+    }
+}
+
+pub fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, ch) in name.char_indices() {
+        if ch.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+    snake
+}
+
+fn upper_camel_case(name: &str) -> String {
+    let mut camel = String::new();
+    let mut upper = true;
+    for ch in name.chars() {
+        if ch == '_' {
+            upper = true;
+        } else if upper {
+            camel.extend(ch.to_uppercase());
+            upper = false;
+        } else {
+            camel.push(ch);
+        }
+    }
+    camel
+}
@@ -0,0 +1,66 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Trait;
+use crate::generics::InferredBounds;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+pub fn struct_status_impl(input: &Struct) -> TokenStream {
+    let code = match &input.attrs.grpc {
+        Some(code) => quote!(thiserror::__private::tonic::Code::#code),
+        None => quote!(thiserror::__private::tonic::Code::Internal),
+    };
+    let code = quote! {
+        let code = #code;
+    };
+    status_impl(&input.ident, input.generics, &code)
+}
+
+pub fn enum_status_impl(input: &Enum) -> TokenStream {
+    let ty = &input.ident;
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let code = match variant.attrs.grpc.as_ref().or(input.attrs.grpc.as_ref()) {
+            Some(code) => quote!(thiserror::__private::tonic::Code::#code),
+            None => quote!(thiserror::__private::tonic::Code::Internal),
+        };
+        quote! {
+            #ty::#ident {..} => #code,
+        }
+    });
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    let code = quote! {
+        #[allow(deprecated)]
+        let code = match #void_deref &error {
+            #(#arms)*
+        };
+    };
+    status_impl(ty, input.generics, &code)
+}
+
+fn status_impl(ty: &Ident, generics: &Generics, code: &TokenStream) -> TokenStream {
+    if !cfg!(feature = "tonic") {
+        return TokenStream::new();
+    }
+
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    if generics.type_params().next().is_some() {
+        inferred_bounds.insert(quote!(#ty #ty_generics), Trait::Display);
+    }
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::convert::From<#ty #ty_generics> for thiserror::__private::tonic::Status #where_clause {
+            fn from(error: #ty #ty_generics) -> Self {
+                #code
+                thiserror::__private::tonic::Status::new(code, error.to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::{Attrs, Display};
+use crate::expand::{fields_pat, use_as_display};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::Ident;
+
+pub fn struct_help_methods(input: &Struct) -> TokenStream {
+    let mut methods = TokenStream::new();
+    for hint in HINTS {
+        methods.extend(struct_hint_method(input, hint));
+    }
+    methods
+}
+
+pub fn enum_help_methods(input: &Enum) -> TokenStream {
+    HINTS
+        .iter()
+        .filter_map(|hint| enum_hint_method(input, hint))
+        .collect()
+}
+
+// The attributes of #[error(...)] holding a hint for the person reading the
+// error, each of which gets an accessor of the same name.
+struct Hint {
+    name: &'static str,
+    doc: &'static str,
+    get: for<'a, 'b> fn(&'a Attrs<'b>) -> Option<&'a Display<'b>>,
+}
+
+const HINTS: &[Hint] = &[
+    Hint {
+        name: "help",
+        doc: "The hint declared by `#[error(help = ...)]`",
+        get: |attrs| attrs.help.as_ref(),
+    },
+    Hint {
+        name: "suggestion",
+        doc: "The suggestion declared by `#[error(suggestion = ...)]`",
+        get: |attrs| attrs.suggestion.as_ref(),
+    },
+    Hint {
+        name: "url",
+        doc: "The link declared by `#[error(url = ...)]`",
+        get: |attrs| attrs.url.as_ref(),
+    },
+];
+
+fn struct_hint_method(input: &Struct, hint: &Hint) -> Option<TokenStream> {
+    let display = (hint.get)(&input.attrs)?;
+    let method = Ident::new(hint.name, Span::call_site());
+    let doc = format!(" {}.", hint.doc);
+    let pat = fields_pat(&input.fields);
+    let use_as_display = use_as_display(display.has_bonus_display);
+    let value = help_value(display);
+    let where_clause = where_clause(&implied_bounds(display, &input.fields));
+    Some(quote! {
+        #[doc = #doc]
+        pub fn #method(&self) -> ::core::option::Option<::std::borrow::Cow<'static, str>> #where_clause {
+            #use_as_display
+            #[allow(unused_variables, deprecated)]
+            let Self #pat = self;
+            ::core::option::Option::Some(#value)
+        }
+    })
+}
+
+fn enum_hint_method(input: &Enum, hint: &Hint) -> Option<TokenStream> {
+    if input
+        .variants
+        .iter()
+        .all(|variant| (hint.get)(&variant.attrs).is_none())
+    {
+        return None;
+    }
+    let ty = &input.ident;
+    let method = Ident::new(hint.name, Span::call_site());
+    let doc = format!(" {} on the current\n variant, if any.", hint.doc);
+    let mut has_bonus_display = false;
+    let mut bounds = Vec::new();
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        match (hint.get)(&variant.attrs) {
+            Some(display) => {
+                has_bonus_display |= display.has_bonus_display;
+                bounds.extend(implied_bounds(display, &variant.fields));
+                let pat = fields_pat(&variant.fields);
+                let value = help_value(display);
+                quote! {
+                    #ty::#ident #pat => ::core::option::Option::Some(#value),
+                }
+            }
+            None => quote! {
+                #ty::#ident {..} => ::core::option::Option::None,
+            },
+        }
+    });
+    let arms: Vec<TokenStream> = arms.collect();
+    let use_as_display = use_as_display(has_bonus_display);
+    let where_clause = where_clause(&bounds);
+    Some(quote! {
+        #[doc = #doc]
+        pub fn #method(&self) -> ::core::option::Option<::std::borrow::Cow<'static, str>> #where_clause {
+            #use_as_display
+            #[allow(unused_variables, deprecated, clippy::used_underscore_binding)]
+            match self {
+                #(#arms)*
+            }
+        }
+    })
+}
+
+// Where the toolchain supports `Error::provide`, the suggestion and link are
+// provided for `Report` to print. Left out where the accessors need bounds
+// which the Error impl does not have.
+pub fn struct_provide_sections(input: &Struct, request: &TokenStream) -> Option<TokenStream> {
+    provide_sections(&[(&input.attrs, &input.fields)], request)
+}
+
+pub fn enum_provide_sections(input: &Enum, request: &TokenStream) -> Option<TokenStream> {
+    let variants: Vec<_> = input
+        .variants
+        .iter()
+        .map(|variant| (&variant.attrs, variant.fields.as_slice()))
+        .collect();
+    provide_sections(&variants, request)
+}
+
+fn provide_sections(variants: &[(&Attrs, &[Field])], request: &TokenStream) -> Option<TokenStream> {
+    let mut has_suggestion = false;
+    let mut has_url = false;
+    for (attrs, fields) in variants {
+        let sections = [
+            (&attrs.suggestion, &mut has_suggestion),
+            (&attrs.url, &mut has_url),
+        ];
+        for (section, has_section) in sections {
+            if let Some(section) = section {
+                if !implied_bounds(section, fields).is_empty() {
+                    return None;
+                }
+                *has_section = true;
+            }
+        }
+    }
+    if !has_suggestion && !has_url {
+        return None;
+    }
+    let accessor = |has_section: bool, method: TokenStream| {
+        if has_section {
+            quote!(self.#method())
+        } else {
+            quote!(::core::option::Option::None)
+        }
+    };
+    let suggestion = accessor(has_suggestion, quote!(suggestion));
+    let url = accessor(has_url, quote!(url));
+    Some(quote! {
+        thiserror::__private::provide_sections(#request, #suggestion, #url);
+    })
+}
+
+// A hint without interpolation is borrowed rather than formatted.
+pub fn help_value(help: &Display) -> TokenStream {
+    let template = help.template.value();
+    if help.args.is_empty() && !template.contains('{') && !template.contains('}') {
+        let template = &help.template;
+        return quote!(::std::borrow::Cow::Borrowed(#template));
+    }
+    let fmt = &help.fmt;
+    let args = &help.args;
+    quote!(::std::borrow::Cow::Owned(::std::format!(#fmt #args)))
+}
+
+pub fn implied_bounds(help: &Display, fields: &[Field]) -> Vec<TokenStream> {
+    help.implied_bounds
+        .iter()
+        .filter_map(|(field, bound)| {
+            let field = &fields[*field];
+            if field.contains_generic {
+                let ty = field.ty;
+                Some(quote!(#ty: #bound))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn where_clause(bounds: &[TokenStream]) -> Option<TokenStream> {
+    if bounds.is_empty() {
+        None
+    } else {
+        Some(quote!(where #(#bounds,)*))
+    }
+}
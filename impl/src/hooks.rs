@@ -0,0 +1,341 @@
+use crate::ast::{Enum, Field, Struct, Variant};
+use crate::attr::{Attrs, Hook};
+use crate::sensitive;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{Error, Ident, LitStr, Member};
+
+// The error being constructed, as seen by the hooks.
+struct Construction<'a> {
+    path: TokenStream,
+    name: &'a Ident,
+    code: Option<&'a LitStr>,
+    fields: &'a [Field<'a>],
+    source: Option<&'a Field<'a>>,
+    // The Display impl of a generic error may carry bounds that the From
+    // impls and throw methods do not, so its message cannot be rendered.
+    generic: bool,
+}
+
+// What runs around the construction of the error in From impls and throw
+// methods.
+pub struct Hooks {
+    statements: Option<TokenStream>,
+    // `#[naur(cold)]`: construct the error out of line.
+    cold: bool,
+}
+
+pub fn struct_hooks(input: &Struct) -> Hooks {
+    let ty = &input.ident;
+    let construction = Construction {
+        path: quote!(#ty),
+        name: ty,
+        code: input.attrs.code.as_ref(),
+        fields: &input.fields,
+        source: input.source_field(),
+        generic: input.generics.type_params().next().is_some(),
+    };
+    Hooks {
+        statements: hooks(&input.attrs, None, &construction),
+        cold: input.attrs.cold.is_some(),
+    }
+}
+
+pub fn variant_hooks(input: &Enum, variant: &Variant) -> Hooks {
+    let ty = &input.ident;
+    let ident = &variant.ident;
+    let construction = Construction {
+        path: quote!(#ty::#ident),
+        name: ident,
+        code: variant.attrs.code.as_ref().or(input.attrs.code.as_ref()),
+        fields: &variant.fields,
+        source: variant.source_field(),
+        generic: input.generics.type_params().next().is_some(),
+    };
+    Hooks {
+        statements: hooks(&input.attrs, Some(&variant.attrs), &construction),
+        cold: input.attrs.cold.is_some(),
+    }
+}
+
+pub fn struct_name_methods(input: &Struct) -> TokenStream {
+    let name = LitStr::new(&input.ident.unraw().to_string(), input.ident.span());
+    quote! {
+        /// The name of the error type, exactly as declared.
+        pub fn variant_name(&self) -> &'static str {
+            #name
+        }
+
+        /// A low-cardinality label for metrics and traces: the name of the
+        /// error type, never any of its fields.
+        pub fn metric_label(&self) -> &'static str {
+            self.variant_name()
+        }
+    }
+}
+
+pub fn enum_name_methods(input: &Enum) -> TokenStream {
+    let ty = &input.ident;
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = LitStr::new(&ident.unraw().to_string(), ident.span());
+        quote! {
+            #ty::#ident {..} => #name,
+        }
+    });
+    quote! {
+        /// The name of the current variant, exactly as declared.
+        pub fn variant_name(&self) -> &'static str {
+            #[allow(deprecated)]
+            match #void_deref self {
+                #(#arms)*
+            }
+        }
+
+        /// A low-cardinality label for metrics and traces: the name of the
+        /// current variant, never any of its fields.
+        pub fn metric_label(&self) -> &'static str {
+            self.variant_name()
+        }
+    }
+}
+
+// Runs the hooks on the freshly constructed error before handing it out.
+pub fn wrap(construct: TokenStream, hooks: &Hooks) -> TokenStream {
+    let construct = match &hooks.statements {
+        Some(statements) => quote! {
+            {
+                let __error = #construct;
+                #statements
+                __error
+            }
+        },
+        None => construct,
+    };
+    if hooks.cold {
+        quote!(thiserror::__private::cold(move || #construct))
+    } else {
+        construct
+    }
+}
+
+// Hooks whose integration is not compiled in are reported once per attribute
+// rather than at every construction site.
+pub fn feature_errors<'a>(attrs: impl IntoIterator<Item = &'a Attrs<'a>>) -> TokenStream {
+    let mut errors = TokenStream::new();
+    for attrs in attrs {
+        if let Some(trace) = &attrs.trace {
+            if !cfg!(feature = "tracing") {
+                errors.extend(feature_error(trace.original, "#[throws(trace)]", "tracing"));
+            }
+        }
+        if let Some(log) = &attrs.log {
+            if !cfg!(feature = "log") {
+                errors.extend(feature_error(log.original, "#[throws(log)]", "log"));
+            }
+        }
+        if let Some(metric) = &attrs.metric {
+            if !cfg!(feature = "metrics") {
+                errors.extend(feature_error(metric, "#[error(metric = ...)]", "metrics"));
+            }
+        }
+        if let Some(i18n) = &attrs.i18n {
+            if !cfg!(feature = "fluent") {
+                errors.extend(feature_error(i18n, "#[error(i18n = ...)]", "fluent"));
+            }
+        }
+    }
+    errors
+}
+
+fn feature_error(tokens: impl ToTokens, attr: &str, feature: &str) -> TokenStream {
+    let msg = format!(
+        "{} requires enabling the \"{}\" feature of thiserror",
+        attr, feature,
+    );
+    Error::new_spanned(tokens, msg).to_compile_error()
+}
+
+fn hooks(
+    type_attrs: &Attrs,
+    variant_attrs: Option<&Attrs>,
+    construction: &Construction,
+) -> Option<TokenStream> {
+    let mut hooks = TokenStream::new();
+    if let Some(on_create) = &type_attrs.on_create {
+        let path = &on_create.path;
+        hooks.extend(quote!(#path(&__error);));
+    }
+    if cfg!(feature = "tracing") {
+        let trace = variant_attrs.and_then(|attrs| attrs.trace.as_ref());
+        if let Some(trace) = trace.or(type_attrs.trace.as_ref()) {
+            hooks.extend(trace_event(trace, construction));
+        }
+    }
+    if cfg!(feature = "log") {
+        let log = variant_attrs.and_then(|attrs| attrs.log.as_ref());
+        if let Some(log) = log.or(type_attrs.log.as_ref()) {
+            hooks.extend(log_record(log, construction));
+        }
+    }
+    if cfg!(feature = "otel") {
+        hooks.extend(record_exception(construction));
+    }
+    if cfg!(feature = "metrics") {
+        let metric = variant_attrs.and_then(|attrs| attrs.metric.as_ref());
+        if let Some(metric) = metric.or(type_attrs.metric.as_ref()) {
+            hooks.extend(increment_counter(metric));
+        }
+    }
+    if hooks.is_empty() {
+        None
+    } else {
+        Some(hooks)
+    }
+}
+
+fn trace_event(trace: &Hook, construction: &Construction) -> TokenStream {
+    let level = format_ident!("{}", trace.level.to_string().to_uppercase());
+    let code = construction.code.map(|code| quote!("code" = #code,));
+    let path = &construction.path;
+    let context = Context::new(construction);
+    let pat = &context.pat;
+    let keys = &context.keys;
+    let values = &context.values;
+    let message = if construction.generic {
+        None
+    } else {
+        Some(quote!("{}", __error))
+    };
+
+    quote! {
+        #[allow(irrefutable_let_patterns, unused_variables, deprecated)]
+        if let #path #pat = &__error {
+            #[allow(unused_imports)]
+            use thiserror::__private::{FieldValueViaDebug as _, FieldValueViaDisplay as _};
+            thiserror::__private::tracing::event!(
+                thiserror::__private::tracing::Level::#level,
+                "variant" = __error.metric_label(),
+                #code
+                #(#keys = %#values,)*
+                #message
+            );
+        }
+    }
+}
+
+fn log_record(log: &Hook, construction: &Construction) -> TokenStream {
+    let level = {
+        let mut level = log.level.to_string();
+        level[..1].make_ascii_uppercase();
+        format_ident!("{}", level)
+    };
+    let level = quote!(thiserror::__private::log::Level::#level);
+
+    if construction.generic {
+        let name = LitStr::new(&construction.name.to_string(), construction.name.span());
+        return quote! {
+            thiserror::__private::log::log!(#level, "{}", #name);
+        };
+    }
+
+    quote! {
+        match std::error::Error::source(&__error) {
+            ::core::option::Option::Some(source) => {
+                thiserror::__private::log::log!(#level, "{}: {}", __error, source);
+            }
+            ::core::option::Option::None => {
+                thiserror::__private::log::log!(#level, "{}", __error);
+            }
+        }
+    }
+}
+
+fn record_exception(construction: &Construction) -> TokenStream {
+    let path = &construction.path;
+    let error_type = LitStr::new(&path.to_string().replace(' ', ""), construction.name.span());
+    let code = match construction.code {
+        Some(code) => quote!(::core::option::Option::Some(#code)),
+        None => quote!(::core::option::Option::None),
+    };
+    let message = if construction.generic {
+        quote!(::core::option::Option::None)
+    } else {
+        quote!(::core::option::Option::Some(
+            &__error as &dyn ::core::fmt::Display
+        ))
+    };
+    quote! {
+        thiserror::__private::record_exception(
+            ::core::concat!(::core::module_path!(), "::", #error_type),
+            #code,
+            #message,
+        );
+    }
+}
+
+fn increment_counter(metric: &LitStr) -> TokenStream {
+    quote! {
+        thiserror::__private::metrics::counter!(#metric, "variant" => __error.metric_label())
+            .increment(1);
+    }
+}
+
+// Named bindings for the context fields of the error: every field other than
+// its source and backtrace. Fields of a generic type are left out for the same
+// reason as the message of a generic error.
+struct Context {
+    pat: TokenStream,
+    keys: Vec<LitStr>,
+    values: Vec<TokenStream>,
+}
+
+impl Context {
+    fn new(construction: &Construction) -> Self {
+        let mut members = Vec::new();
+        let mut bindings = Vec::new();
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+        for field in construction.fields {
+            let is_source = construction
+                .source
+                .map_or(false, |source| source.member == field.member);
+            if is_source
+                || field.attrs.backtrace.is_some()
+                || field.is_backtrace()
+                || field.contains_generic
+            {
+                continue;
+            }
+            let (binding, key) = match &field.member {
+                Member::Named(ident) => (
+                    format_ident!("__field_{}", ident),
+                    LitStr::new(&ident.unraw().to_string(), ident.span()),
+                ),
+                Member::Unnamed(index) => (
+                    format_ident!("__field_{}", index),
+                    LitStr::new(&index.index.to_string(), index.span),
+                ),
+            };
+            keys.push(key);
+            if let Some(redacted) = sensitive::redacted_value(field) {
+                values.push(redacted);
+                continue;
+            }
+            values.push(quote!((&&thiserror::__private::FieldValueWrap(#binding)).field_value()));
+            members.push(&field.member);
+            bindings.push(binding);
+        }
+        Context {
+            pat: quote!({ #(#members: #bindings,)* .. }),
+            keys,
+            values,
+        }
+    }
+}
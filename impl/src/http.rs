@@ -0,0 +1,197 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Trait;
+use crate::generics::InferredBounds;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, Token};
+
+pub fn struct_methods(input: &Struct) -> TokenStream {
+    let status_code_method = input.attrs.status.as_ref().map(|status| {
+        quote! {
+            /// The HTTP status code declared by `#[error(status = ...)]`.
+            pub fn status_code(&self) -> u16 {
+                #status
+            }
+        }
+    });
+
+    let code_method = input.attrs.code.as_ref().map(|code| {
+        quote! {
+            /// The error code declared by `#[error(code = ...)]`.
+            pub fn code(&self) -> ::core::option::Option<&'static str> {
+                ::core::option::Option::Some(#code)
+            }
+        }
+    });
+
+    let problem_method = problem_method(
+        input.generics,
+        input.attrs.status.is_some(),
+        input.attrs.code.is_some(),
+    );
+
+    quote! {
+        #status_code_method
+        #code_method
+        #problem_method
+    }
+}
+
+pub fn enum_methods(input: &Enum) -> TokenStream {
+    let ty = &input.ident;
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+
+    let status_code_method = if input.has_status() {
+        let arms = input.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let status = match variant
+                .attrs
+                .status
+                .as_ref()
+                .or(input.attrs.status.as_ref())
+            {
+                Some(status) => quote!(#status),
+                None => quote!(500),
+            };
+            quote! {
+                #ty::#ident {..} => #status,
+            }
+        });
+        Some(quote! {
+            /// The HTTP status code declared by `#[error(status = ...)]` on the
+            /// current variant, or 500 if the variant does not declare one.
+            pub fn status_code(&self) -> u16 {
+                #[allow(deprecated)]
+                match #void_deref self {
+                    #(#arms)*
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let code_method = if input.has_code() {
+        let arms = input.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let code = match variant.attrs.code.as_ref().or(input.attrs.code.as_ref()) {
+                Some(code) => quote!(::core::option::Option::Some(#code)),
+                None => quote!(::core::option::Option::None),
+            };
+            quote! {
+                #ty::#ident {..} => #code,
+            }
+        });
+        Some(quote! {
+            /// The error code declared by `#[error(code = ...)]` on the current
+            /// variant, if any.
+            pub fn code(&self) -> ::core::option::Option<&'static str> {
+                #[allow(deprecated)]
+                match #void_deref self {
+                    #(#arms)*
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let problem_method = problem_method(input.generics, input.has_status(), input.has_code());
+
+    quote! {
+        #status_code_method
+        #code_method
+        #problem_method
+    }
+}
+
+fn problem_method(generics: &Generics, has_status: bool, has_code: bool) -> Option<TokenStream> {
+    if !has_status && !has_code {
+        return None;
+    }
+    let status = if has_status {
+        quote!(::core::option::Option::Some(self.status_code()))
+    } else {
+        quote!(::core::option::Option::None)
+    };
+    let code = if has_code {
+        quote!(self.code())
+    } else {
+        quote!(::core::option::Option::None)
+    };
+    let where_clause = if generics.type_params().next().is_some() {
+        Some(quote!(where Self: ::core::fmt::Display))
+    } else {
+        None
+    };
+    Some(quote! {
+        /// An RFC 7807 problem document for this error, using the error code
+        /// as the problem type and the Display message as its detail.
+        pub fn to_problem(&self) -> thiserror::Problem #where_clause {
+            thiserror::__private::problem(#code, #status, self)
+        }
+    })
+}
+
+pub fn axum_impl(ty: &Ident, generics: &Generics, has_code: bool) -> TokenStream {
+    if !cfg!(feature = "axum") {
+        return TokenStream::new();
+    }
+
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    if generics.type_params().next().is_some() {
+        inferred_bounds.insert(<Token![Self]>::default(), Trait::Display);
+    }
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+    let code = if has_code {
+        quote!(self.code())
+    } else {
+        quote!(::core::option::Option::None)
+    };
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics thiserror::__private::axum::IntoResponse for #ty #ty_generics #where_clause {
+            fn into_response(self) -> thiserror::__private::axum::Response {
+                thiserror::__private::axum::into_response(self.status_code(), #code, &self)
+            }
+        }
+    }
+}
+
+pub fn actix_impl(ty: &Ident, generics: &Generics, has_code: bool) -> TokenStream {
+    if !cfg!(feature = "actix") {
+        return TokenStream::new();
+    }
+
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    if generics.type_params().next().is_some() {
+        inferred_bounds.insert(<Token![Self]>::default(), Trait::Debug);
+        inferred_bounds.insert(<Token![Self]>::default(), Trait::Display);
+    }
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+    let code = if has_code {
+        quote!(self.code())
+    } else {
+        quote!(::core::option::Option::None)
+    };
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics thiserror::__private::actix::ResponseError for #ty #ty_generics #where_clause {
+            fn status_code(&self) -> thiserror::__private::actix::StatusCode {
+                thiserror::__private::actix::status_code(#ty::status_code(self))
+            }
+
+            fn error_response(&self) -> thiserror::__private::actix::HttpResponse {
+                thiserror::__private::actix::error_response(#ty::status_code(self), #code, self)
+            }
+        }
+    }
+}
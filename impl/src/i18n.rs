@@ -0,0 +1,89 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::sensitive;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{LitStr, Member};
+
+// Looks the message up in the registered Fluent bundle, returning early from
+// the Display impl if found. Expects the fields to be bound by `fields_pat`.
+pub fn localize(key: Option<&LitStr>, fields: &[Field]) -> Option<TokenStream> {
+    if !cfg!(feature = "fluent") {
+        return None;
+    }
+    let key = key?;
+    let mut names = Vec::new();
+    let mut values = Vec::new();
+    for field in fields {
+        // Same reasoning as for the fields() method: autoref specialization
+        // cannot see through generic parameters.
+        if field.attrs.backtrace.is_some() || field.is_backtrace() || field.contains_generic {
+            continue;
+        }
+        let (name, binding) = match &field.member {
+            Member::Named(ident) => (
+                LitStr::new(&ident.unraw().to_string(), ident.span()),
+                ident.clone(),
+            ),
+            Member::Unnamed(index) => (
+                LitStr::new(&index.index.to_string(), index.span),
+                format_ident!("_{}", index),
+            ),
+        };
+        names.push(name);
+        values.push(sensitive::redacted_value(field).unwrap_or_else(
+            || quote!((&&thiserror::__private::FieldValueWrap(#binding)).field_value()),
+        ));
+    }
+    Some(quote! {
+        {
+            #[allow(unused_imports)]
+            use thiserror::__private::{FieldValueViaDebug as _, FieldValueViaDisplay as _};
+            if let ::core::option::Option::Some(__message) =
+                thiserror::__private::localize(#key, &[#((#names, #values)),*])
+            {
+                return __formatter.write_str(&__message);
+            }
+        }
+    })
+}
+
+pub fn struct_message_key_method(input: &Struct) -> Option<TokenStream> {
+    if !cfg!(feature = "fluent") {
+        return None;
+    }
+    let key = input.attrs.i18n.as_ref()?;
+    Some(quote! {
+        /// The Fluent message key declared by `#[error(i18n = ...)]`.
+        pub fn message_key(&self) -> ::core::option::Option<&'static str> {
+            ::core::option::Option::Some(#key)
+        }
+    })
+}
+
+pub fn enum_message_key_method(input: &Enum) -> Option<TokenStream> {
+    if !cfg!(feature = "fluent") || input.variants.iter().all(|v| v.attrs.i18n.is_none()) {
+        return None;
+    }
+    let ty = &input.ident;
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let key = match &variant.attrs.i18n {
+            Some(key) => quote!(::core::option::Option::Some(#key)),
+            None => quote!(::core::option::Option::None),
+        };
+        quote! {
+            #ty::#ident {..} => #key,
+        }
+    });
+    Some(quote! {
+        /// The Fluent message key declared by `#[error(i18n = ...)]` on the
+        /// current variant, if any.
+        pub fn message_key(&self) -> ::core::option::Option<&'static str> {
+            #[allow(deprecated)]
+            match self {
+                #(#arms)*
+            }
+        }
+    })
+}
@@ -0,0 +1,79 @@
+use crate::ast::{Enum, Struct};
+use crate::generics::InferredBounds;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+// The conversion takes its kind from io_kind(), falling back to `Other` for
+// variants without one, so that the two never disagree.
+pub fn struct_io_error_impl(input: &Struct) -> TokenStream {
+    io_error_impl(&input.ident, input.generics)
+}
+
+pub fn enum_io_error_impl(input: &Enum) -> TokenStream {
+    io_error_impl(&input.ident, input.generics)
+}
+
+pub fn struct_io_kind_method(input: &Struct) -> Option<TokenStream> {
+    let kind = input.attrs.io_kind.as_ref()?;
+    Some(quote! {
+        /// The `std::io::ErrorKind` declared by `#[error(io_kind = ...)]`.
+        pub fn io_kind(&self) -> ::core::option::Option<std::io::ErrorKind> {
+            ::core::option::Option::Some(std::io::ErrorKind::#kind)
+        }
+    })
+}
+
+pub fn enum_io_kind_method(input: &Enum) -> Option<TokenStream> {
+    if !input.has_io_kind() {
+        return None;
+    }
+    let ty = &input.ident;
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let kind = match (variant.attrs.io_kind.as_ref()).or(input.attrs.io_kind.as_ref()) {
+            Some(kind) => quote!(::core::option::Option::Some(std::io::ErrorKind::#kind)),
+            None => quote!(::core::option::Option::None),
+        };
+        quote! {
+            #ty::#ident {..} => #kind,
+        }
+    });
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    Some(quote! {
+        /// The `std::io::ErrorKind` declared by `#[error(io_kind = ...)]` on
+        /// the current variant or on the enum, if any.
+        pub fn io_kind(&self) -> ::core::option::Option<std::io::ErrorKind> {
+            #[allow(deprecated)]
+            match #void_deref self {
+                #(#arms)*
+            }
+        }
+    })
+}
+
+fn io_error_impl(ty: &Ident, generics: &Generics) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    if generics.type_params().next().is_some() {
+        inferred_bounds.insert(
+            quote!(#ty #ty_generics),
+            quote!(std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static),
+        );
+    }
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::convert::From<#ty #ty_generics> for std::io::Error #where_clause {
+            fn from(error: #ty #ty_generics) -> Self {
+                let kind = error.io_kind().unwrap_or(std::io::ErrorKind::Other);
+                std::io::Error::new(kind, error)
+            }
+        }
+    }
+}
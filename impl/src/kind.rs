@@ -0,0 +1,246 @@
+use crate::ast::{Enum, Variant};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{Ident, LitStr};
+
+// `#[naur(kind)]`: a fieldless enum mirroring the variants, for matching on
+// which variant an error is without destructuring its payload.
+pub fn kind_enum(input: &Enum) -> Option<TokenStream> {
+    input.attrs.kind?;
+    let ty = &input.ident;
+    let vis = &input.original.vis;
+    let kind = format_ident!("{}Kind", ty);
+    let variants: Vec<_> = input
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect();
+    let names: Vec<LitStr> = variants
+        .iter()
+        .map(|ident| LitStr::new(&ident.to_string(), ident.span()))
+        .collect();
+    let doc = format!("The variants of `{}`, without their fields.", ty);
+    let repr = repr(input);
+    let repr_attr = repr.as_ref().map(|repr| quote!(#[repr(#repr)]));
+    let discriminants = input.variants.iter().map(|variant| {
+        let (eq, discriminant) = variant.original.discriminant.as_ref()?;
+        Some(quote!(#eq #discriminant))
+    });
+    let try_from_impl = repr.map(|repr| {
+        quote! {
+            impl ::core::convert::TryFrom<#repr> for #kind {
+                type Error = #repr;
+
+                fn try_from(value: #repr) -> ::core::result::Result<Self, #repr> {
+                    #(
+                        if value == #kind::#variants as #repr {
+                            return ::core::result::Result::Ok(#kind::#variants);
+                        }
+                    )*
+                    ::core::result::Result::Err(value)
+                }
+            }
+        }
+    });
+    let from_kind_impl = from_kind_impl(input, &kind);
+    let codes = input.variants.iter().map(|variant| {
+        let code = variant.attrs.code.as_ref()?;
+        Some(quote!(| #code))
+    });
+    let code_values = input.variants.iter().map(|variant| {
+        match variant.attrs.code.as_ref().or(input.attrs.code.as_ref()) {
+            Some(code) => quote!(::core::option::Option::Some(#code)),
+            None => quote!(::core::option::Option::None),
+        }
+    });
+    Some(quote! {
+        #[doc = #doc]
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+        )]
+        #repr_attr
+        #vis enum #kind {
+            #(
+                #[allow(missing_docs)]
+                #variants #discriminants,
+            )*
+        }
+
+        impl #kind {
+            /// Every kind, in the order of the variants.
+            pub const ALL: &'static [#kind] = &[#(#kind::#variants),*];
+
+            /// Iterates over every kind, in the order of the variants.
+            pub fn iter() -> ::core::iter::Copied<::core::slice::Iter<'static, #kind>> {
+                Self::ALL.iter().copied()
+            }
+
+            /// The name of the variant, exactly as declared.
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(#kind::#variants => #names,)*
+                }
+            }
+
+            /// The error code declared by `#[error(code = ...)]` on the
+            /// variant or on the enum, if any.
+            pub fn code(self) -> ::core::option::Option<&'static str> {
+                match self {
+                    #(#kind::#variants => #code_values,)*
+                }
+            }
+        }
+
+        #try_from_impl
+        #from_kind_impl
+
+        impl ::core::fmt::Display for #kind {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                __formatter.write_str(self.name())
+            }
+        }
+
+        impl ::core::str::FromStr for #kind {
+            type Err = thiserror::ParseKindError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, thiserror::ParseKindError> {
+                #[allow(unreachable_patterns)]
+                match s {
+                    #(#names #codes => ::core::result::Result::Ok(#kind::#variants),)*
+                    _ => ::core::result::Result::Err(thiserror::ParseKindError::new(s)),
+                }
+            }
+        }
+    })
+}
+
+// The error of a kind, for the kinds whose variants have no fields: From if
+// every variant is fieldless, or else TryFrom, giving back any other kind.
+fn from_kind_impl(input: &Enum, kind: &Ident) -> TokenStream {
+    let ty = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let fieldless = |variant: &&Variant| variant.fields.is_empty();
+    if input.variants.iter().all(|variant| fieldless(&variant)) {
+        let variants = input.variants.iter().map(|variant| &variant.ident);
+        return quote! {
+            #[allow(deprecated)]
+            impl #impl_generics ::core::convert::From<#kind> for #ty #ty_generics #where_clause {
+                fn from(kind: #kind) -> Self {
+                    match kind {
+                        #(#kind::#variants => #ty::#variants {},)*
+                    }
+                }
+            }
+        };
+    }
+    let variants = input
+        .variants
+        .iter()
+        .filter(fieldless)
+        .map(|variant| &variant.ident);
+    quote! {
+        #[allow(deprecated)]
+        impl #impl_generics ::core::convert::TryFrom<#kind> for #ty #ty_generics #where_clause {
+            type Error = #kind;
+
+            fn try_from(kind: #kind) -> ::core::result::Result<Self, #kind> {
+                match kind {
+                    #(#kind::#variants => ::core::result::Result::Ok(#ty::#variants {}),)*
+                    _ => ::core::result::Result::Err(kind),
+                }
+            }
+        }
+    }
+}
+
+pub fn kind_method(input: &Enum) -> Option<TokenStream> {
+    input.attrs.kind?;
+    let ty = &input.ident;
+    let kind = format_ident!("{}Kind", ty);
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        quote!(#ty::#ident {..} => #kind::#ident,)
+    });
+    Some(quote! {
+        /// Which variant this error is, without its fields.
+        pub fn kind(&self) -> #kind {
+            #[allow(deprecated)]
+            match #void_deref self {
+                #(#arms)*
+            }
+        }
+    })
+}
+
+// On an enum with a primitive representation, such as `#[repr(u16)]`, the
+// numeric code of each variant: its discriminant as written, or one more than
+// the previous variant's.
+pub fn discriminant_method(input: &Enum) -> Option<TokenStream> {
+    let repr = repr(input)?;
+    let ty = &input.ident;
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    let mut base = None;
+    let mut offset = 0;
+    let arms = input.variants.iter().enumerate().map(|(i, variant)| {
+        if let Some((_, discriminant)) = &variant.original.discriminant {
+            base = Some(discriminant);
+            offset = 0;
+        } else if i > 0 {
+            offset += 1;
+        }
+        let ident = &variant.ident;
+        let value = match base {
+            Some(base) if offset == 0 => quote!(#base),
+            Some(base) => {
+                let offset = Literal::u128_unsuffixed(offset);
+                quote!((#base) + #offset)
+            }
+            None => Literal::u128_unsuffixed(offset).into_token_stream(),
+        };
+        quote!(#ty::#ident {..} => #value,)
+    });
+    Some(quote! {
+        /// The numeric code of the current variant, its discriminant under
+        /// the enum's `#[repr(...)]`.
+        pub fn discriminant(&self) -> #repr {
+            #[allow(deprecated)]
+            match #void_deref self {
+                #(#arms)*
+            }
+        }
+    })
+}
+
+fn repr(input: &Enum) -> Option<Ident> {
+    const PRIMITIVES: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    let mut repr = None;
+    for attr in &input.original.attrs {
+        if attr.path().is_ident("repr") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if let Some(ident) = meta.path.get_ident() {
+                    if PRIMITIVES.contains(&ident.to_string().as_str()) {
+                        repr = Some(ident.clone());
+                    }
+                }
+                Ok(())
+            });
+        }
+    }
+    repr
+}
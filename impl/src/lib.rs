@@ -1,5 +1,5 @@
 #![allow(
-    clippy::blocks_in_if_conditions,
+    clippy::blocks_in_conditions,
     clippy::cast_lossless,
     clippy::cast_possible_truncation,
     clippy::manual_find,
@@ -18,22 +18,107 @@
 
 extern crate proc_macro;
 
-mod ast;
-mod attr;
+mod accessors;
+mod aggregate;
+mod arbitrary;
+mod assert;
+mod backtrace;
+mod boxed;
+mod cause;
+mod chain;
+mod constructors;
+mod crate_path;
+mod diagnostic;
+mod eq;
+mod error_id;
+mod errors;
 mod expand;
-mod fmt;
-mod generics;
-mod prop;
-mod span;
-mod valid;
+mod ext;
+mod extend;
+mod ffi;
+mod fields;
+mod grpc;
+mod help;
+mod hooks;
+mod http;
+mod i18n;
+mod io;
+mod kind;
+mod manifest;
+mod message;
+mod naming;
+mod panic;
+mod poison;
+mod py;
+mod related;
+mod retry;
+mod sensitive;
+mod serialize;
+mod set;
+mod size;
+mod subset;
+mod suggest;
+mod templates;
 
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
+use thiserror_model::{ast, attr, config, generics, prop, span, up};
 
-#[proc_macro_derive(Error, attributes(backtrace, error, from, source))]
+#[proc_macro_derive(
+    Error,
+    attributes(
+        backtrace,
+        capture,
+        cause,
+        context_frames,
+        diagnostic,
+        error,
+        error_id,
+        errors,
+        from,
+        from_value,
+        naur,
+        provide,
+        related,
+        sensitive,
+        source,
+        throws,
+        timestamp
+    )
+)]
 pub fn derive_error(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     expand::derive(&input)
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
+
+#[proc_macro_derive(
+    ErrorSet,
+    attributes(
+        backtrace,
+        capture,
+        cause,
+        context_frames,
+        diagnostic,
+        error,
+        error_id,
+        errors,
+        from,
+        from_value,
+        naur,
+        provide,
+        related,
+        sensitive,
+        set,
+        source,
+        throws,
+        timestamp
+    )
+)]
+pub fn derive_error_set(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    set::derive(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
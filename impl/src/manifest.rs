@@ -0,0 +1,125 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::{Attrs, Display};
+use crate::diagnostic;
+use crate::prop;
+use crate::templates::{code_of, option};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{Ident, LitInt, LitStr, Member, Type};
+
+pub fn struct_manifest_fn(input: &Struct) -> Option<TokenStream> {
+    input.attrs.manifest?;
+    let status = input.attrs.status.as_ref().map(LitInt::to_token_stream);
+    let variant = variant_manifest(&input.ident, &input.attrs, None, status, &input.fields);
+    Some(manifest_fn(&input.ident, false, &[variant]))
+}
+
+pub fn enum_manifest_fn(input: &Enum) -> Option<TokenStream> {
+    input.attrs.manifest?;
+    let has_status = input.has_status();
+    let variants: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let status = variant
+                .attrs
+                .status
+                .as_ref()
+                .or(input.attrs.status.as_ref())
+                .map(LitInt::to_token_stream)
+                .or_else(|| has_status.then(|| quote!(500)));
+            variant_manifest(
+                &variant.ident,
+                &variant.attrs,
+                Some(&input.attrs),
+                status,
+                &variant.fields,
+            )
+        })
+        .collect();
+    Some(manifest_fn(&input.ident, true, &variants))
+}
+
+fn manifest_fn(ident: &Ident, is_enum: bool, variants: &[TokenStream]) -> TokenStream {
+    let name = LitStr::new(&ident.unraw().to_string(), ident.span());
+    quote! {
+        /// A structured description of this type: its variants with their
+        /// fields, message templates, codes, and HTTP statuses, for build
+        /// tooling to write out as documentation or SDK definitions.
+        pub fn manifest() -> thiserror::TypeManifest {
+            const MANIFEST: thiserror::TypeManifest = thiserror::TypeManifest {
+                name: #name,
+                is_enum: #is_enum,
+                variants: &[#(#variants),*],
+            };
+            MANIFEST
+        }
+    }
+}
+
+fn variant_manifest(
+    ident: &Ident,
+    attrs: &Attrs,
+    type_attrs: Option<&Attrs>,
+    status: Option<TokenStream>,
+    fields: &[Field],
+) -> TokenStream {
+    let name = LitStr::new(&ident.unraw().to_string(), ident.span());
+    let code = option(code_of(attrs, type_attrs).map(LitStr::to_token_stream));
+    let status = option(status);
+    let template = option(attrs.display.as_ref().map(Display::template_tokens));
+    let help = option(diagnostic::help_of(attrs).map(|help| help.template.to_token_stream()));
+    let source = prop::source_field(fields).map(|field| &field.member);
+    let backtrace = prop::backtrace_field(fields).map(|field| &field.member);
+    let fields = fields.iter().map(|field| {
+        let name = match &field.member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        };
+        let ty = type_name(field.ty);
+        let is_source = source == Some(&field.member);
+        let is_backtrace = backtrace == Some(&field.member);
+        quote! {
+            thiserror::FieldManifest {
+                name: #name,
+                ty: #ty,
+                source: #is_source,
+                backtrace: #is_backtrace,
+            }
+        }
+    });
+    quote! {
+        thiserror::VariantManifest {
+            name: #name,
+            code: #code,
+            status: #status,
+            template: #template,
+            help: #help,
+            fields: &[#(#fields),*],
+        }
+    }
+}
+
+// The type as written, without the spaces which the token stream puts between
+// every pair of tokens.
+pub fn type_name(ty: &Type) -> String {
+    let mut name = ty.to_token_stream().to_string();
+    for (spaced, tight) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" ;", ";"),
+        ("& ", "&"),
+        ("[ ", "["),
+        (" ]", "]"),
+        ("( ", "("),
+        (" )", ")"),
+    ] {
+        name = name.replace(spaced, tight);
+    }
+    name
+}
@@ -0,0 +1,157 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::{Attrs, Display};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, LitStr};
+
+pub fn message_methods(generics: &Generics) -> TokenStream {
+    let where_clause = where_clause(generics);
+    quote! {
+        /// Renders the Display message into a caller-provided buffer, without
+        /// allocating.
+        pub fn write_message(
+            &self,
+            out: &mut impl ::core::fmt::Write,
+        ) -> ::core::fmt::Result #where_clause {
+            ::core::fmt::write(out, ::core::format_args!("{}", self))
+        }
+
+        /// The length in bytes of the Display message, for sizing a buffer
+        /// before calling `write_message`.
+        pub fn message_len_hint(&self) -> usize #where_clause {
+            thiserror::__private::message_len(self)
+        }
+    }
+}
+
+pub fn struct_message_methods(input: &Struct) -> TokenStream {
+    let message = static_message(&input.attrs, &input.attrs, &input.fields);
+    let value = message_value(message.as_ref());
+    let static_value = static_message_value(message.as_ref());
+    let where_clause = where_clause(input.generics);
+    quote! {
+        /// The Display message, borrowed without allocating when the message
+        /// interpolates nothing.
+        pub fn message(&self) -> ::std::borrow::Cow<'static, str> #where_clause {
+            #value
+        }
+
+        /// The Display message if it interpolates nothing, and so is the same
+        /// for every instance.
+        pub fn static_message(&self) -> ::core::option::Option<&'static str> {
+            #static_value
+        }
+    }
+}
+
+pub fn enum_message_methods(input: &Enum) -> TokenStream {
+    let ty = &input.ident;
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    let mut arms = Vec::new();
+    let mut static_arms = Vec::new();
+    for variant in &input.variants {
+        let ident = &variant.ident;
+        let message = static_message(&input.attrs, &variant.attrs, &variant.fields);
+        let value = message_value(message.as_ref());
+        let static_value = static_message_value(message.as_ref());
+        arms.push(quote!(#ty::#ident {..} => #value,));
+        static_arms.push(quote!(#ty::#ident {..} => #static_value,));
+    }
+    let where_clause = where_clause(input.generics);
+    quote! {
+        /// The Display message of the current variant, borrowed without
+        /// allocating when the variant's message interpolates nothing.
+        pub fn message(&self) -> ::std::borrow::Cow<'static, str> #where_clause {
+            #[allow(deprecated)]
+            match #void_deref self {
+                #(#arms)*
+            }
+        }
+
+        /// The Display message of the current variant if it interpolates
+        /// nothing, and so is the same for every instance of the variant.
+        pub fn static_message(&self) -> ::core::option::Option<&'static str> {
+            #[allow(deprecated)]
+            match #void_deref self {
+                #(#static_arms)*
+            }
+        }
+    }
+}
+
+// The message which is the same for every instance, after the enum's prefix,
+// if nothing decided at runtime goes into it.
+fn static_message(container: &Attrs, attrs: &Attrs, fields: &[Field]) -> Option<LitStr> {
+    let display: &Display = attrs.display.as_ref()?;
+    if attrs.i18n.is_some() || fields.iter().any(|field| field.attrs.related.is_some()) {
+        return None;
+    }
+    let mut message = display.static_message()?;
+    if let Some(prefix) = container
+        .prefix
+        .as_ref()
+        .filter(|_| attrs.no_prefix.is_none())
+    {
+        message.insert_str(0, &prefix.value());
+    }
+    if container.single_line.is_some() && message.contains(['\n', '\r']) {
+        return None;
+    }
+    Some(LitStr::new(&message, display.fmt.span()))
+}
+
+fn message_value(message: Option<&LitStr>) -> TokenStream {
+    match message {
+        Some(message) => quote!(::std::borrow::Cow::Borrowed(#message)),
+        None => quote!(::std::borrow::Cow::Owned(
+            ::std::string::ToString::to_string(self)
+        )),
+    }
+}
+
+fn static_message_value(message: Option<&LitStr>) -> TokenStream {
+    match message {
+        Some(message) => quote!(::core::option::Option::Some(#message)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+fn where_clause(generics: &Generics) -> Option<TokenStream> {
+    if generics.type_params().next().is_some() {
+        Some(quote!(where Self: ::core::fmt::Display))
+    } else {
+        None
+    }
+}
+
+// With #[naur(single_line)], the body of the Display impl writes through an
+// adapter which collapses the line breaks of interpolated fields and sources.
+pub fn single_line(attrs: &Attrs, body: TokenStream) -> TokenStream {
+    if attrs.single_line.is_none() {
+        return body;
+    }
+    quote! {
+        thiserror::__private::single_line(__formatter, |__formatter| {
+            #body
+        })
+    }
+}
+
+// With #[error(alt = "...")], formatting with `{:#}` writes the alternate
+// message instead of the terse one.
+pub fn alternate(alt: Option<&Display>, display: TokenStream) -> TokenStream {
+    match alt {
+        Some(alt) => quote! {
+            if __formatter.alternate() {
+                #alt
+            } else {
+                #display
+            }
+        },
+        None => display,
+    }
+}
@@ -0,0 +1,54 @@
+use crate::attr::Attrs;
+use syn::ext::IdentExt;
+use syn::Ident;
+
+// The name of the throw method for a struct or for a variant of an enum, such
+// as `throw_read`, after which the `_with` and `pipe_` methods are named too.
+// By default the prefix is `throw_`, each capital starts a word, and a
+// trailing `_error` is trimmed, which #[naur(throws_prefix = "...")] and
+// #[naur(throws_naming(...))] adjust.
+pub fn throw_method_name(attrs: &Attrs, ty: &Ident, variant: Option<&Ident>) -> String {
+    let prefix = match &attrs.throws_prefix {
+        Some(prefix) => prefix.prefix.value(),
+        None => "throw_".to_owned(),
+    };
+    prefix + &snake_name(attrs, ty, variant)
+}
+
+fn snake_name(attrs: &Attrs, ty: &Ident, variant: Option<&Ident>) -> String {
+    let naming = attrs.throws_naming.as_ref();
+    let keep_suffix = naming.map_or(false, |naming| naming.keep_suffix);
+    let acronyms = naming.map_or(false, |naming| naming.acronyms);
+    let full = naming.map_or(false, |naming| naming.full);
+
+    let word = |ident: &Ident| {
+        let snake = snake_case(&ident.unraw().to_string(), acronyms);
+        match snake.strip_suffix("_error") {
+            Some(trimmed) if !keep_suffix => trimmed.to_owned(),
+            _ => snake,
+        }
+    };
+    match variant {
+        Some(variant) if full => format!("{}_{}", word(ty), word(variant)),
+        Some(variant) => word(variant),
+        None => word(ty),
+    }
+}
+
+fn snake_case(name: &str, acronyms: bool) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, &ch) in chars.iter().enumerate() {
+        if i > 0 && ch.is_uppercase() {
+            let prev = chars[i - 1];
+            let starts_word = !acronyms
+                || !prev.is_uppercase()
+                || chars.get(i + 1).map_or(false, |next| next.is_lowercase());
+            if starts_word && prev != '_' {
+                snake.push('_');
+            }
+        }
+        snake.extend(ch.to_lowercase());
+    }
+    snake
+}
@@ -0,0 +1,56 @@
+use crate::ast::Enum;
+use crate::hooks;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+// `MyError::catch_unwind(f)` for an enum with an #[error(panic)] variant,
+// which runs the closure and turns a panic in it into that variant.
+pub fn catch_unwind_method(input: &Enum) -> Option<TokenStream> {
+    let variant = input
+        .variants
+        .iter()
+        .find(|variant| variant.attrs.panic.is_some())?;
+    let (payload, location) = variant.panic_fields()?;
+    let ident = &variant.ident;
+
+    let payload_member = &payload.member;
+    let payload_ty = payload.ty;
+    let payload_value = quote_spanned! {payload_ty.span()=>
+        <#payload_ty as thiserror::__private::PanicPayload>::from_payload(__panic.payload)
+    };
+    let location = location.map(|location| {
+        let member = &location.member;
+        let ty = location.ty;
+        let value = quote_spanned! {ty.span()=>
+            <#ty as thiserror::__private::PanicLocation>::from_location(__panic.location)
+        };
+        quote!(#member: #value,)
+    });
+    let construct = hooks::wrap(
+        quote! {
+            Self::#ident {
+                #payload_member: #payload_value,
+                #location
+            }
+        },
+        &hooks::variant_hooks(input, variant),
+    );
+    let doc = format!(
+        " Runs the closure, converting a panic in it into `{}::{}`.",
+        input.ident, ident,
+    );
+    Some(quote! {
+        #[doc = #doc]
+        #[allow(deprecated)]
+        pub fn catch_unwind<__T, __F>(f: __F) -> ::core::result::Result<__T, Self>
+        where
+            __F: ::core::ops::FnOnce() -> __T + ::std::panic::UnwindSafe,
+        {
+            match thiserror::__private::catch_unwind(f) {
+                ::core::result::Result::Ok(value) => ::core::result::Result::Ok(value),
+                ::core::result::Result::Err(__panic) => ::core::result::Result::Err(#construct),
+            }
+        }
+    })
+}
@@ -0,0 +1,57 @@
+use crate::ast::{Enum, Struct};
+use crate::expand::from_initializer;
+use crate::hooks;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{GenericParam, Generics, Ident};
+
+// `From<PoisonError<G>>` for every guard type `G`, into the struct or variant
+// whose source is marked #[source(poison)], which keeps a thiserror::Poisoned
+// in place of the PoisonError and its guard.
+pub fn struct_from_impl(input: &Struct) -> Option<TokenStream> {
+    let field = input
+        .fields
+        .iter()
+        .find(|field| field.attrs.poison.is_some())?;
+    let ty = &input.ident;
+    let body = from_initializer(field, input.distinct_backtrace_field(), &input.fields);
+    let construct = hooks::wrap(quote!(#ty #body), &hooks::struct_hooks(input));
+    Some(from_impl(input.generics, ty, construct))
+}
+
+pub fn enum_from_impl(input: &Enum) -> Option<TokenStream> {
+    let (variant, field) = input.variants.iter().find_map(|variant| {
+        let field = variant
+            .fields
+            .iter()
+            .find(|field| field.attrs.poison.is_some())?;
+        Some((variant, field))
+    })?;
+    let ty = &input.ident;
+    let ident = &variant.ident;
+    let body = from_initializer(field, variant.distinct_backtrace_field(), &variant.fields);
+    let construct = hooks::wrap(
+        quote!(#ty::#ident #body),
+        &hooks::variant_hooks(input, variant),
+    );
+    Some(from_impl(input.generics, ty, construct))
+}
+
+fn from_impl(generics: &Generics, ty: &Ident, construct: TokenStream) -> TokenStream {
+    let mut impl_generics = generics.clone();
+    impl_generics.params.push(GenericParam::Type(
+        Ident::new("__G", Span::call_site()).into(),
+    ));
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::convert::From<::std::sync::PoisonError<__G>> for #ty #ty_generics #where_clause {
+            #[allow(deprecated)]
+            fn from(source: ::std::sync::PoisonError<__G>) -> Self {
+                let source = ::core::convert::From::from(thiserror::Poisoned::new(&source));
+                #construct
+            }
+        }
+    }
+}
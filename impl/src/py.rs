@@ -0,0 +1,67 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Trait;
+use crate::generics::InferredBounds;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, Path};
+
+pub fn struct_py_err_impl(input: &Struct) -> TokenStream {
+    let exception = exception(input.attrs.py.as_ref());
+    let new_err = quote! {
+        #exception::new_err(message)
+    };
+    py_err_impl(&input.ident, input.generics, &new_err)
+}
+
+pub fn enum_py_err_impl(input: &Enum) -> TokenStream {
+    let ty = &input.ident;
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let exception = exception(variant.attrs.py.as_ref().or(input.attrs.py.as_ref()));
+        quote! {
+            #ty::#ident {..} => #exception::new_err(message),
+        }
+    });
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    let new_err = quote! {
+        #[allow(deprecated)]
+        match #void_deref &error {
+            #(#arms)*
+        }
+    };
+    py_err_impl(ty, input.generics, &new_err)
+}
+
+fn exception(path: Option<&Path>) -> TokenStream {
+    match path {
+        Some(path) => quote!(#path),
+        None => quote!(thiserror::__private::pyo3::exceptions::PyRuntimeError),
+    }
+}
+
+fn py_err_impl(ty: &Ident, generics: &Generics, new_err: &TokenStream) -> TokenStream {
+    if !cfg!(feature = "pyo3") {
+        return TokenStream::new();
+    }
+
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    if generics.type_params().next().is_some() {
+        inferred_bounds.insert(quote!(#ty #ty_generics), Trait::Display);
+    }
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::convert::From<#ty #ty_generics> for thiserror::__private::pyo3::PyErr #where_clause {
+            fn from(error: #ty #ty_generics) -> Self {
+                let message = ::std::string::ToString::to_string(&error);
+                #new_err
+            }
+        }
+    }
+}
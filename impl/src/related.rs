@@ -0,0 +1,110 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::expand::fields_pat;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, Member};
+
+// Appends the number of related errors, if there are any, to a message written
+// by `display`. Placed where the Display impl has bound the fields.
+pub fn display(fields: &[Field], display: TokenStream) -> TokenStream {
+    let field = match fields.iter().find(|field| field.attrs.related.is_some()) {
+        Some(field) => field,
+        None => return display,
+    };
+    let binding = binding(field);
+    quote! {{
+        #display?;
+        thiserror::__private::display_related_count(
+            __formatter,
+            ::core::iter::Iterator::count(::core::iter::IntoIterator::into_iter(#binding)),
+        )
+    }}
+}
+
+pub fn struct_related_method(input: &Struct) -> Option<TokenStream> {
+    let field = input.related_field()?;
+    let ty = &input.ident;
+    let pat = fields_pat(&input.fields);
+    let value = related_errors(field);
+    Some(related_method(&[quote!(#ty #pat => #value,)]))
+}
+
+pub fn enum_related_method(input: &Enum) -> Option<TokenStream> {
+    if input
+        .variants
+        .iter()
+        .all(|variant| variant.related_field().is_none())
+    {
+        return None;
+    }
+    let ty = &input.ident;
+    let arms: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            match variant.related_field() {
+                Some(field) => {
+                    let pat = fields_pat(&variant.fields);
+                    let value = related_errors(field);
+                    quote!(#ty::#ident #pat => #value,)
+                }
+                None => quote! {
+                    #ty::#ident {..} => ::std::boxed::Box::new(::core::iter::empty()),
+                },
+            }
+        })
+        .collect();
+    Some(related_method(&arms))
+}
+
+fn related_method(arms: &[TokenStream]) -> TokenStream {
+    quote! {
+        /// The errors collected in the `#[related]` field, which are reported
+        /// alongside this one rather than as its source.
+        pub fn related(
+            &self,
+        ) -> ::std::boxed::Box<
+            dyn ::core::iter::Iterator<Item = &(dyn std::error::Error + 'static)> + '_,
+        > {
+            use thiserror::__private::AsDynError as _;
+            #[allow(unused_variables, deprecated, clippy::used_underscore_binding)]
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+fn related_errors(field: &Field) -> TokenStream {
+    let binding = binding(field);
+    quote! {
+        ::std::boxed::Box::new(
+            ::core::iter::Iterator::map(
+                ::core::iter::IntoIterator::into_iter(#binding),
+                |error| error.as_dyn_error(),
+            ),
+        )
+    }
+}
+
+// The related errors as forwarded to miette, for types which implement
+// Diagnostic.
+pub fn diagnostic_related(field: &Field) -> TokenStream {
+    let binding = binding(field);
+    quote! {
+        ::std::boxed::Box::new(
+            ::core::iter::Iterator::map(
+                ::core::iter::IntoIterator::into_iter(#binding),
+                |diagnostic| diagnostic as &dyn thiserror::__private::miette::Diagnostic,
+            ),
+        )
+    }
+}
+
+fn binding(field: &Field) -> Ident {
+    match &field.member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => format_ident!("_{}", index),
+    }
+}
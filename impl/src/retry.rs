@@ -0,0 +1,85 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::{Attrs, RetryClass};
+use crate::prop;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub fn struct_is_transient_method(input: &Struct) -> Option<TokenStream> {
+    let retry = input.attrs.retry.as_ref()?;
+    let value = match retry.class {
+        RetryClass::Transient => quote!(true),
+        RetryClass::Permanent => quote!(false),
+        RetryClass::Source => {
+            let member = &asked_field(&input.attrs, &input.fields).member;
+            quote! {
+                let Self { #member: source, .. } = self;
+                source.is_transient()
+            }
+        }
+    };
+    Some(is_transient_method(value))
+}
+
+pub fn enum_is_transient_method(input: &Enum) -> Option<TokenStream> {
+    if input.attrs.retry.is_none()
+        && input
+            .variants
+            .iter()
+            .all(|variant| variant.attrs.retry.is_none())
+    {
+        return None;
+    }
+    let ty = &input.ident;
+    let default = input
+        .attrs
+        .retry
+        .as_ref()
+        .map_or(RetryClass::Permanent, |retry| retry.class);
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let class = variant
+            .attrs
+            .retry
+            .as_ref()
+            .map_or(default, |retry| retry.class);
+        match class {
+            RetryClass::Transient => quote!(#ty::#ident {..} => true,),
+            RetryClass::Permanent => quote!(#ty::#ident {..} => false,),
+            RetryClass::Source => {
+                let member = &asked_field(&variant.attrs, &variant.fields).member;
+                quote!(#ty::#ident { #member: source, .. } => source.is_transient(),)
+            }
+        }
+    });
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    Some(is_transient_method(quote! {
+        #[allow(deprecated)]
+        match #void_deref self {
+            #(#arms)*
+        }
+    }))
+}
+
+fn is_transient_method(value: TokenStream) -> TokenStream {
+    quote! {
+        /// Whether the error is worth retrying, as declared by
+        /// `#[error(transient)]` or `#[error(permanent)]`.
+        pub fn is_transient(&self) -> bool {
+            #value
+        }
+    }
+}
+
+// The field whose `is_transient()` decides under #[error(transient = source)]:
+// the field a transparent error delegates to, or else the source.
+fn asked_field<'a, 'b>(attrs: &Attrs, fields: &'a [Field<'b>]) -> &'a Field<'b> {
+    if attrs.transparent.is_some() {
+        prop::transparent_field(fields).unwrap()
+    } else {
+        prop::source_field(fields).unwrap()
+    }
+}
@@ -0,0 +1,230 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::Attrs;
+use crate::expand::fields_pat;
+use crate::generics::{InferredBounds, ParamsInScope};
+use crate::{manifest, prop};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Generics, Ident, LitStr, Member, Type};
+
+const DEFAULT_TEXT: &str = "[REDACTED]";
+
+// Rebinds each #[sensitive] field to its replacement text, so that nothing
+// written after it in the Display impl, whether the message's placeholders,
+// its arguments, or a localized message, can reach the value. Placed where
+// the fields have been bound.
+pub fn redact(fields: &[Field]) -> Option<TokenStream> {
+    let redactions: Vec<TokenStream> = fields
+        .iter()
+        .filter_map(|field| {
+            let binding = binding(field);
+            let redacted = redacted(field)?;
+            Some(quote! {
+                #[allow(unused_variables)]
+                let #binding = &#redacted;
+            })
+        })
+        .collect();
+    if redactions.is_empty() {
+        None
+    } else {
+        Some(quote!(#(#redactions)*))
+    }
+}
+
+pub fn struct_debug_impl(input: &Struct) -> Option<TokenStream> {
+    let stable = debug_style(&input.attrs)?;
+    let pat = fields_pat(&input.fields);
+    let debug = debug_fields(&input.ident, &input.fields, stable);
+    let body = quote! {
+        #[allow(unused_variables, deprecated)]
+        let Self #pat = self;
+        #debug
+    };
+    let bounds = debug_bounds(&input.fields, stable);
+    Some(debug_impl(&input.ident, input.generics, bounds, body))
+}
+
+pub fn enum_debug_impl(input: &Enum) -> Option<TokenStream> {
+    let stable = debug_style(&input.attrs)?;
+    let ty = &input.ident;
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let pat = fields_pat(&variant.fields);
+        let debug = debug_fields(ident, &variant.fields, stable);
+        quote!(#ty::#ident #pat => #debug,)
+    });
+    let body = if input.variants.is_empty() {
+        quote!(match *self {})
+    } else {
+        quote! {
+            #[allow(unused_variables, deprecated)]
+            match self {
+                #(#arms)*
+            }
+        }
+    };
+    let bounds = input
+        .variants
+        .iter()
+        .flat_map(|variant| debug_bounds(&variant.fields, stable))
+        .collect();
+    Some(debug_impl(ty, input.generics, bounds, body))
+}
+
+// Whether the Debug impl is the one of #[naur(stable_debug)] rather than of
+// #[naur(redacted_debug)], if either is requested.
+fn debug_style(attrs: &Attrs) -> Option<bool> {
+    if attrs.stable_debug.is_some() {
+        Some(true)
+    } else {
+        attrs.redacted_debug.map(|_| false)
+    }
+}
+
+fn debug_impl(
+    ty: &Ident,
+    generics: &Generics,
+    bounds: Vec<(&Type, TokenStream)>,
+    body: TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    let params_in_scope = ParamsInScope::new(generics);
+    for (ty, bound) in bounds {
+        if params_in_scope.intersects(ty) {
+            inferred_bounds.insert(ty, bound);
+        }
+    }
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::fmt::Debug for #ty #ty_generics #where_clause {
+            #[allow(clippy::used_underscore_binding)]
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                #body
+            }
+        }
+    }
+}
+
+// The bound each printed field needs: Debug, or for an error shown by its
+// message in a stable Debug impl, Error.
+fn debug_bounds<'a>(fields: &'a [Field], stable: bool) -> Vec<(&'a Type, TokenStream)> {
+    debug_order(fields, stable)
+        .into_iter()
+        .filter(|field| field.attrs.sensitive.is_none())
+        .map(|field| {
+            if stable && is_error(fields, field) {
+                let ty = prop::type_parameter_of(field.ty, "Option").unwrap_or(field.ty);
+                (ty, quote!(std::error::Error + 'static))
+            } else {
+                (field.ty, quote!(::core::fmt::Debug))
+            }
+        })
+        .collect()
+}
+
+// The same output as #[derive(Debug)], with each #[sensitive] field shown as
+// its replacement text. A stable Debug impl also sorts named fields by name,
+// leaves out backtraces, and shows the source and causes by their type and
+// message alone.
+fn debug_fields(ident: &Ident, fields: &[Field], stable: bool) -> TokenStream {
+    let name = ident.unraw().to_string();
+    let printed = debug_order(fields, stable);
+    let values = printed.iter().map(|field| {
+        if let Some(redacted) = redacted(field) {
+            return quote!(&#redacted);
+        }
+        let binding = binding(field);
+        if !(stable && is_error(fields, field)) {
+            return quote!(#binding);
+        }
+        match prop::type_parameter_of(field.ty, "Option") {
+            Some(ty) => {
+                let ty = manifest::type_name(ty);
+                quote! {
+                    &::core::option::Option::map(
+                        ::core::option::Option::as_ref(#binding),
+                        |error| thiserror::__private::StableSource(#ty, error.as_dyn_error()),
+                    )
+                }
+            }
+            None => {
+                let ty = manifest::type_name(field.ty);
+                quote!(&thiserror::__private::StableSource(#ty, #binding.as_dyn_error()))
+            }
+        }
+    });
+    let debug = match fields.first().map(|field| &field.member) {
+        None => quote!(__formatter.write_str(#name)),
+        Some(Member::Named(_)) => {
+            let names = printed.iter().map(|field| match &field.member {
+                Member::Named(ident) => ident.unraw().to_string(),
+                Member::Unnamed(_) => unreachable!(),
+            });
+            quote! {
+                __formatter.debug_struct(#name)
+                    #(.field(#names, #values))*
+                    .finish()
+            }
+        }
+        Some(Member::Unnamed(_)) => quote! {
+            __formatter.debug_tuple(#name)
+                #(.field(#values))*
+                .finish()
+        },
+    };
+    if stable {
+        quote! {
+            {
+                #[allow(unused_imports)]
+                use thiserror::__private::AsDynError as _;
+                #debug
+            }
+        }
+    } else {
+        debug
+    }
+}
+
+fn debug_order<'a, 'b>(fields: &'a [Field<'b>], stable: bool) -> Vec<&'a Field<'b>> {
+    let mut printed: Vec<&Field> = fields.iter().collect();
+    if stable {
+        printed.retain(|field| field.attrs.backtrace.is_none() && !field.is_backtrace());
+        printed.sort_by_key(|field| match &field.member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(_) => String::new(),
+        });
+    }
+    printed
+}
+
+fn is_error(fields: &[Field], field: &Field) -> bool {
+    field.attrs.cause.is_some()
+        || prop::source_field(fields).map_or(false, |source| source.member == field.member)
+}
+
+// The FieldValue standing in for a #[sensitive] field wherever the generated
+// code hands out field values, such as fields() and the tracing hook.
+pub fn redacted_value(field: &Field) -> Option<TokenStream> {
+    let redacted = redacted(field)?;
+    Some(quote!(thiserror::FieldValue::Display(&#redacted)))
+}
+
+fn redacted(field: &Field) -> Option<TokenStream> {
+    let sensitive = field.attrs.sensitive.as_ref()?;
+    let text = match &sensitive.text {
+        Some(text) => text.clone(),
+        None => LitStr::new(DEFAULT_TEXT, sensitive.original.pound_token.span),
+    };
+    Some(quote!(thiserror::__private::Redacted(#text)))
+}
+
+fn binding(field: &Field) -> Ident {
+    match &field.member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => format_ident!("_{}", index),
+    }
+}
@@ -0,0 +1,243 @@
+use crate::ast::{Enum, Struct};
+use crate::generics::InferredBounds;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, DeriveInput, Error, Generics, Ident, LitStr};
+
+pub fn struct_serialize_impl(input: &Struct, attr: &Attribute) -> TokenStream {
+    let name = LitStr::new(&input.ident.to_string(), input.ident.span());
+    let variant = quote! {
+        let variant = #name;
+    };
+    let code = input.attrs.code.as_ref();
+    let schema_variants = vec![schema_variant(&name, code)];
+    let serialize_impl = serialize_impl(
+        &input.ident,
+        input.generics,
+        attr,
+        &variant,
+        input.has_code(),
+    );
+    let schema_impl = schema_impl(&input.ident, input.generics, &schema_variants);
+    quote! {
+        #serialize_impl
+        #schema_impl
+    }
+}
+
+pub fn enum_serialize_impl(input: &Enum, attr: &Attribute) -> TokenStream {
+    let ty = &input.ident;
+    let arms = input.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = LitStr::new(&ident.to_string(), ident.span());
+        quote! {
+            #ty::#ident {..} => #name,
+        }
+    });
+    let void_deref = if input.variants.is_empty() {
+        Some(quote!(*))
+    } else {
+        None
+    };
+    let variant = quote! {
+        #[allow(deprecated)]
+        let variant = match #void_deref self {
+            #(#arms)*
+        };
+    };
+    let schema_variants: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let name = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+            let code = variant.attrs.code.as_ref().or(input.attrs.code.as_ref());
+            schema_variant(&name, code)
+        })
+        .collect();
+    let serialize_impl = serialize_impl(ty, input.generics, attr, &variant, input.has_code());
+    let schema_impl = schema_impl(ty, input.generics, &schema_variants);
+    quote! {
+        #serialize_impl
+        #schema_impl
+    }
+}
+
+fn serialize_impl(
+    ty: &Ident,
+    generics: &Generics,
+    attr: &Attribute,
+    variant: &TokenStream,
+    has_code: bool,
+) -> TokenStream {
+    if !cfg!(feature = "serde") {
+        return Error::new_spanned(
+            attr,
+            "#[naur(serialize)] requires enabling the \"serde\" feature of thiserror",
+        )
+        .to_compile_error();
+    }
+
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    if generics.type_params().next().is_some() {
+        inferred_bounds.insert(quote!(Self), quote!(std::error::Error));
+    }
+    let where_clause = inferred_bounds.augment_where_clause(generics);
+    let name = LitStr::new(&ty.to_string(), ty.span());
+    let code = if has_code {
+        quote!(self.code())
+    } else {
+        quote!(::core::option::Option::None)
+    };
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics thiserror::__private::serde::Serialize for #ty #ty_generics #where_clause {
+            fn serialize<__S>(&self, __serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: thiserror::__private::serde::Serializer,
+            {
+                #variant
+                thiserror::__private::serde::serialize_error(__serializer, #name, variant, #code, self)
+            }
+        }
+    }
+}
+
+// A plain copy of the error's serialized form, for holding on to an error whose
+// sources cannot be serialized, or for receiving one from another service.
+pub fn wire_type(input: &DeriveInput, attr: &Attribute, has_code: bool) -> TokenStream {
+    if !cfg!(feature = "serde") {
+        return Error::new_spanned(
+            attr,
+            "#[naur(wire)] requires enabling the \"serde\" feature of thiserror",
+        )
+        .to_compile_error();
+    }
+
+    let ty = &input.ident;
+    let vis = &input.vis;
+    let wire_ty = format_ident!("{}Wire", ty);
+    let name = LitStr::new(&wire_ty.to_string(), wire_ty.span());
+    let mut from_generics = input.generics.clone();
+    from_generics.params.insert(0, parse_quote!('__a));
+    let (impl_generics, _, _) = from_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut inferred_bounds = InferredBounds::new();
+    if input.generics.type_params().next().is_some() {
+        inferred_bounds.insert(quote!(#ty #ty_generics), quote!(std::error::Error));
+    }
+    let where_clause = inferred_bounds.augment_where_clause(&input.generics);
+    let code = if has_code {
+        quote!(error.code().map(::std::borrow::ToOwned::to_owned))
+    } else {
+        quote!(::core::option::Option::None)
+    };
+    let doc = format!(
+        " A serializable copy of a [`{}`], generated by `#[naur(wire)]`.",
+        ty,
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+        #vis struct #wire_ty {
+            /// The name of the variant, or of the struct.
+            pub variant: ::std::string::String,
+            /// The Display message of the error.
+            pub message: ::std::string::String,
+            /// The error code declared by `#[error(code = ...)]`, if any.
+            pub code: ::core::option::Option<::std::string::String>,
+            /// The messages of the error's source chain, outermost first.
+            pub sources: ::std::vec::Vec<::std::string::String>,
+        }
+
+        #[allow(unused_qualifications)]
+        impl ::core::fmt::Display for #wire_ty {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                __formatter.write_str(&self.message)
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl std::error::Error for #wire_ty {}
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::core::convert::From<&'__a #ty #ty_generics> for #wire_ty #where_clause {
+            fn from(error: &'__a #ty #ty_generics) -> Self {
+                #wire_ty {
+                    variant: ::std::borrow::ToOwned::to_owned(error.metric_label()),
+                    message: ::std::string::ToString::to_string(error),
+                    code: #code,
+                    sources: thiserror::__private::serde::source_messages(error),
+                }
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl thiserror::__private::serde::Serialize for #wire_ty {
+            fn serialize<__S>(&self, __serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: thiserror::__private::serde::Serializer,
+            {
+                thiserror::__private::serde::serialize_wire(
+                    __serializer,
+                    #name,
+                    &self.variant,
+                    &self.message,
+                    self.code.as_deref(),
+                    &self.sources,
+                )
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl<'__de> thiserror::__private::serde::Deserialize<'__de> for #wire_ty {
+            fn deserialize<__D>(__deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+            where
+                __D: thiserror::__private::serde::Deserializer<'__de>,
+            {
+                let wire = thiserror::__private::serde::deserialize_wire(__deserializer)?;
+                ::core::result::Result::Ok(#wire_ty {
+                    variant: wire.variant,
+                    message: wire.message,
+                    code: wire.code,
+                    sources: wire.sources,
+                })
+            }
+        }
+    }
+}
+
+fn schema_variant(name: &LitStr, code: Option<&LitStr>) -> TokenStream {
+    match code {
+        Some(code) => quote!((#name, ::core::option::Option::Some(#code))),
+        None => quote!((#name, ::core::option::Option::None)),
+    }
+}
+
+// The schema describes the representation written by the Serialize impl above,
+// so it is generated from the same variant names and codes.
+fn schema_impl(ty: &Ident, generics: &Generics, variants: &[TokenStream]) -> TokenStream {
+    if !cfg!(feature = "schemars") {
+        return TokenStream::new();
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = LitStr::new(&ty.to_string(), ty.span());
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics thiserror::__private::schemars::JsonSchema for #ty #ty_generics #where_clause {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#name)
+            }
+
+            fn json_schema(
+                _generator: &mut thiserror::__private::schemars::SchemaGenerator,
+            ) -> thiserror::__private::schemars::Schema {
+                thiserror::__private::schemars::error_schema(&[#(#variants),*])
+            }
+        }
+    }
+}
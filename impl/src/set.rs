@@ -0,0 +1,57 @@
+use crate::expand;
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{parse_quote_spanned, Data, DeriveInput, Error, Fields, Result};
+
+// `#[derive(ErrorSet)]` is `#[derive(Error)]` with every `#[set]` variant
+// rewritten into a transparent variant converted from its member error.
+pub fn derive(node: &DeriveInput) -> Result<TokenStream> {
+    let mut node = node.clone();
+    let data = match &mut node.data {
+        Data::Enum(data) => data,
+        Data::Struct(_) | Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &node.ident,
+                "ErrorSet can only be derived for enums",
+            ));
+        }
+    };
+    for variant in &mut data.variants {
+        let set = match variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("set"))
+        {
+            Some(set) => set,
+            None => continue,
+        };
+        set.meta.require_path_only()?;
+        let span = set.span();
+        if variant
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("error"))
+        {
+            return Err(Error::new_spanned(
+                set,
+                "a #[set] variant displays its member error; remove the #[error(...)] attribute",
+            ));
+        }
+        let field = match &mut variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &mut fields.unnamed[0],
+            _ => {
+                return Err(Error::new_spanned(
+                    set,
+                    "#[set] requires the variant to have exactly one unnamed field, the member error",
+                ));
+            }
+        };
+        if !field.attrs.iter().any(|attr| attr.path().is_ident("from")) {
+            field.attrs.push(parse_quote_spanned!(span=> #[from]));
+        }
+        variant
+            .attrs
+            .push(parse_quote_spanned!(span=> #[error(transparent)]));
+    }
+    expand::derive(&node)
+}
@@ -0,0 +1,66 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::MaxSize;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Ident, LitStr};
+
+pub fn struct_size_assertion(input: &Struct) -> Option<TokenStream> {
+    let max_size = input.attrs.max_size.as_ref()?;
+    Some(size_assertion(&input.ident, max_size, Vec::new()))
+}
+
+// Each variant's fields are checked before the enum as a whole, so that a build
+// broken by one large variant says which variant it is.
+pub fn enum_size_assertion(input: &Enum) -> Option<TokenStream> {
+    let max_size = input.attrs.max_size.as_ref()?;
+    let ty = &input.ident;
+    let bytes = &max_size.bytes;
+    let variant_checks = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let fields = fields_tuple(&variant.fields);
+            let message = LitStr::new(
+                &format!(
+                    "the fields of `{}::{}` take more than the {} bytes allowed by #[naur(max_size = {})]; consider moving them behind #[error(boxed)]",
+                    ty, variant.ident, bytes, bytes,
+                ),
+                bytes.span(),
+            );
+            quote_spanned! {bytes.span()=>
+                if ::core::mem::size_of::<#fields>() > #bytes {
+                    ::core::panic!(#message);
+                }
+            }
+        })
+        .collect();
+    Some(size_assertion(ty, max_size, variant_checks))
+}
+
+fn size_assertion(ty: &Ident, max_size: &MaxSize, variant_checks: Vec<TokenStream>) -> TokenStream {
+    let bytes = &max_size.bytes;
+    let message = LitStr::new(
+        &format!(
+            "`{}` takes more than the {} bytes allowed by #[naur(max_size = {})]",
+            ty, bytes, bytes,
+        ),
+        bytes.span(),
+    );
+    let check = quote_spanned! {bytes.span()=>
+        if ::core::mem::size_of::<#ty>() > #bytes {
+            ::core::panic!(#message);
+        }
+    };
+    quote! {
+        #[allow(unused_qualifications)]
+        const _: () = {
+            #(#variant_checks)*
+            #check
+        };
+    }
+}
+
+fn fields_tuple(fields: &[Field]) -> TokenStream {
+    let types = fields.iter().map(|field| field.ty);
+    quote!((#(#types,)*))
+}
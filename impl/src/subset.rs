@@ -0,0 +1,136 @@
+use crate::ast::{Enum, Variant};
+use crate::attr::Subset;
+use crate::expand;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, DeriveInput, Error};
+
+pub fn subset_enums(input: &Enum) -> TokenStream {
+    let enums = input
+        .attrs
+        .subsets
+        .iter()
+        .map(|subset| subset_enum(input, subset));
+    quote! {
+        #(#enums)*
+    }
+}
+
+fn subset_enum(input: &Enum, subset: &Subset) -> TokenStream {
+    let ty = &input.ident;
+    let subset_ty = &subset.ident;
+    let vis = &input.original.vis;
+    let type_attrs = input
+        .original
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("error"));
+
+    let listed: Vec<&Variant> = subset
+        .variants
+        .iter()
+        .filter_map(|ident| input.variants.iter().find(|v| v.ident == *ident))
+        .collect();
+
+    let variants = listed.iter().map(|variant| subset_variant(variant));
+    let internal = if listed.iter().any(|variant| variant.ident == "Internal") {
+        None
+    } else {
+        Some(quote! {
+            #[error("internal error")]
+            Internal,
+        })
+    };
+
+    let arms = listed.iter().map(|variant| {
+        let ident = &variant.ident;
+        let members = variant.fields.iter().map(|field| &field.member);
+        let bindings: Vec<_> = (0..variant.fields.len())
+            .map(|i| format_ident!("__field{}", i))
+            .collect();
+        let pat = quote!({ #(#members: #bindings),* });
+        let members = variant.fields.iter().map(|field| &field.member);
+        quote! {
+            #ty::#ident #pat => #subset_ty::#ident { #(#members: #bindings),* },
+        }
+    });
+
+    let doc = format!(
+        " The public subset of [`{}`], generated by `#[naur(subset(...))]`.",
+        ty,
+    );
+
+    let node: DeriveInput = parse_quote! {
+        #[doc = #doc]
+        #(#type_attrs)*
+        #vis enum #subset_ty {
+            #(#variants)*
+            #internal
+        }
+    };
+    let impls = expand::expand_without_throws(&node).unwrap_or_else(Error::into_compile_error);
+    let item = strip_helper_attrs(node);
+
+    quote! {
+        #[derive(::core::fmt::Debug)]
+        #item
+
+        #impls
+
+        #[allow(unused_qualifications)]
+        impl ::core::convert::From<#ty> for #subset_ty {
+            #[allow(deprecated)]
+            fn from(error: #ty) -> Self {
+                match error {
+                    #(#arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => #subset_ty::Internal,
+                }
+            }
+        }
+    }
+}
+
+// The subset is not itself derived, so the attributes which only the derive
+// understands are removed from the enum as it is emitted.
+fn strip_helper_attrs(mut node: DeriveInput) -> DeriveInput {
+    node.attrs.retain(is_inert);
+    if let Data::Enum(data) = &mut node.data {
+        for variant in &mut data.variants {
+            variant.attrs.retain(is_inert);
+            for field in &mut variant.fields {
+                field.attrs.retain(is_inert);
+            }
+        }
+    }
+    node
+}
+
+fn is_inert(attr: &Attribute) -> bool {
+    attr.path().is_ident("doc") || attr.path().is_ident("deprecated")
+}
+
+// The variant is copied as declared, except that #[from] becomes #[source] so
+// the subset does not grow From impls of its own.
+fn subset_variant(variant: &Variant) -> TokenStream {
+    let mut original = variant.original.clone();
+    original.attrs.retain(keep_attr);
+    original.discriminant = None;
+    let transparent = variant.attrs.transparent.is_some();
+    for (field, ast) in original.fields.iter_mut().zip(&variant.fields) {
+        field.attrs.retain(keep_attr);
+        if ast.attrs.from.is_some() && ast.attrs.source.is_none() && !transparent {
+            field.attrs.push(parse_quote!(#[source]));
+        }
+    }
+    quote!(#original,)
+}
+
+fn keep_attr(attr: &Attribute) -> bool {
+    let path = attr.path();
+    path.is_ident("error")
+        || path.is_ident("source")
+        || path.is_ident("backtrace")
+        || path.is_ident("doc")
+        || path.is_ident("deprecated")
+}
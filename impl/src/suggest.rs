@@ -0,0 +1,108 @@
+use crate::ast::Field;
+use crate::attr::{Attrs, Display};
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Member, Type};
+
+// Error types from std which are recognizable from their path alone.
+const STD_ERRORS: &[&str] = &[
+    "AddrParseError",
+    "FromUtf8Error",
+    "FromUtf16Error",
+    "IntoStringError",
+    "JoinPathsError",
+    "NulError",
+    "ParseBoolError",
+    "ParseCharError",
+    "ParseFloatError",
+    "ParseIntError",
+    "RecvError",
+    "RecvTimeoutError",
+    "StripPrefixError",
+    "SystemTimeError",
+    "TryFromIntError",
+    "TryRecvError",
+    "Utf8Error",
+    "VarError",
+];
+
+// A lone field of an error type, without #[from] or #[source], is almost
+// always meant to be the source of the error. Proc macros cannot emit warnings
+// on stable, so the help note is carried by a deprecated item used at the
+// field's span. A message which interpolates the field shows the error on
+// purpose, and #[error(no_source)] says so explicitly.
+pub fn missing_source(attrs: &Attrs, fields: &[Field]) -> Option<TokenStream> {
+    let field = match fields {
+        [field] => field,
+        _ => return None,
+    };
+    let is_named_source = matches!(&field.member, Member::Named(ident) if ident == "source");
+    if !field.original.attrs.is_empty()
+        || is_named_source
+        || attrs.no_source.is_some()
+        || !is_std_error(field.ty)
+    {
+        return None;
+    }
+    if let Some(display) = &attrs.display {
+        if interpolates(display, &field.member) {
+            return None;
+        }
+    }
+
+    let span = field.original.span();
+    let usage = quote_spanned!(span=> let _ = consider_from_or_source;);
+    Some(quote! {
+        const _: () = {
+            #[deprecated(
+                note = "this field is an error but is not marked as the source; add #[from] or #[source] so that it is reported by Error::source(), or #[error(no_source)] if it is not"
+            )]
+            #[allow(non_camel_case_types)]
+            struct consider_from_or_source;
+            #usage
+        };
+    })
+}
+
+// Whether the message refers to the field, as in `{0}`, `{0:?}` or
+// `{source.kind}`.
+fn interpolates(display: &Display, member: &Member) -> bool {
+    let member = match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    };
+    let template = display.template.value();
+    let mut rest = template.as_str();
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let end = rest.find(['}', ':', '.']).unwrap_or(rest.len());
+        if rest[..end].trim() == member {
+            return true;
+        }
+    }
+    false
+}
+
+fn is_std_error(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(ty) if ty.qself.is_none() => &ty.path,
+        _ => return false,
+    };
+    let mut segments = path.segments.iter().rev();
+    let last = match segments.next() {
+        Some(last) if last.arguments.is_none() => &last.ident,
+        _ => return false,
+    };
+    if last == "Error" {
+        return match segments.next() {
+            Some(module) => module.ident == "io" || module.ident == "fmt",
+            None => false,
+        };
+    }
+    STD_ERRORS.iter().any(|name| last == name)
+}
@@ -0,0 +1,157 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::{Attrs, Display};
+use crate::diagnostic;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Ident, LitStr};
+
+pub fn struct_template_const(input: &Struct) -> Option<TokenStream> {
+    input.attrs.templates?;
+    let template = input.attrs.display.as_ref()?.template_tokens();
+    Some(quote! {
+        /// The format string of this error's `#[error("...")]` attribute, as
+        /// written.
+        pub const MESSAGE_TEMPLATE: &'static str = #template;
+    })
+}
+
+// The messages which are the same for every instance of their variant,
+// rendered ahead of time.
+pub fn enum_messages_const(input: &Enum) -> Option<TokenStream> {
+    let entries: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            let display = variant.attrs.display.as_ref()?;
+            if variant.attrs.i18n.is_some() || variant.related_field().is_some() {
+                return None;
+            }
+            let message = LitStr::new(&display.static_message()?, display.fmt.span());
+            let name = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+            Some(quote!((#name, #message)))
+        })
+        .collect();
+    if entries.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        /// The name of each variant whose message has no fields in it, with
+        /// that message. Variants which interpolate fields are left out.
+        pub const MESSAGES: &'static [(&'static str, &'static str)] = &[#(#entries),*];
+    })
+}
+
+pub fn enum_templates_const(input: &Enum) -> Option<TokenStream> {
+    input.attrs.templates?;
+    let entries = input.variants.iter().filter_map(|variant| {
+        let display = variant.attrs.display.as_ref()?;
+        let name = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+        let template = display.template_tokens();
+        Some(quote!((#name, #template)))
+    });
+    Some(quote! {
+        /// The name of each variant with the format string of its
+        /// `#[error("...")]` attribute, as written. Transparent variants have
+        /// no message of their own and are left out.
+        pub const MESSAGE_TEMPLATES: &'static [(&'static str, &'static str)] = &[#(#entries),*];
+    })
+}
+
+pub fn struct_catalog_const(input: &Struct) -> Option<TokenStream> {
+    input.attrs.catalog?;
+    let descriptor = descriptor(&input.ident, &input.attrs, None);
+    Some(catalog_const(&[descriptor]))
+}
+
+pub fn enum_catalog_const(input: &Enum) -> Option<TokenStream> {
+    input.attrs.catalog?;
+    let descriptors: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .map(|variant| descriptor(&variant.ident, &variant.attrs, Some(&input.attrs)))
+        .collect();
+    Some(catalog_const(&descriptors))
+}
+
+// With the `registry` feature, the descriptors of the error are added to the
+// list of every error in the binary. Generic errors are left out, having no
+// single set of descriptors.
+pub fn struct_registration(input: &Struct) -> Option<TokenStream> {
+    if !cfg!(feature = "registry") || !input.generics.params.is_empty() {
+        return None;
+    }
+    let descriptor = descriptor(&input.ident, &input.attrs, None);
+    Some(registration(&[descriptor]))
+}
+
+pub fn enum_registration(input: &Enum) -> Option<TokenStream> {
+    if !cfg!(feature = "registry") || !input.generics.params.is_empty() {
+        return None;
+    }
+    let descriptors: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .map(|variant| descriptor(&variant.ident, &variant.attrs, Some(&input.attrs)))
+        .collect();
+    Some(registration(&descriptors))
+}
+
+fn registration(descriptors: &[TokenStream]) -> TokenStream {
+    quote! {
+        const _: () = {
+            #[thiserror::__private::registry::linkme::distributed_slice(
+                thiserror::__private::registry::REGISTRY
+            )]
+            #[linkme(crate = thiserror::__private::registry::linkme)]
+            static DESCRIPTORS: &'static [thiserror::ErrorDescriptor] = &[#(#descriptors),*];
+        };
+    }
+}
+
+fn catalog_const(descriptors: &[TokenStream]) -> TokenStream {
+    quote! {
+        /// A description of every error this type can be: its name, code,
+        /// message template, severity, and help, for dumping a catalog of
+        /// errors.
+        pub const CATALOG: &'static [thiserror::ErrorDescriptor] = &[#(#descriptors),*];
+    }
+}
+
+fn descriptor(ident: &Ident, attrs: &Attrs, type_attrs: Option<&Attrs>) -> TokenStream {
+    let name = LitStr::new(&ident.to_string(), ident.span());
+    let code = code_of(attrs, type_attrs);
+    let template = attrs.display.as_ref().map(Display::template_tokens);
+    let severity = diagnostic::diagnostic_option(attrs, type_attrs, |d| d.severity.as_ref())
+        .map(|severity| LitStr::new(&severity.to_string().to_lowercase(), severity.span()));
+    let help = diagnostic::help_of(attrs).map(|help| help.template.to_token_stream());
+    let code = option(code.map(LitStr::to_token_stream));
+    let template = option(template);
+    let severity = option(severity.as_ref().map(LitStr::to_token_stream));
+    let help = option(help);
+    quote! {
+        thiserror::ErrorDescriptor {
+            name: #name,
+            code: #code,
+            template: #template,
+            severity: #severity,
+            help: #help,
+        }
+    }
+}
+
+// The code of a struct or variant, from `#[error(code = ...)]` or else from
+// `#[diagnostic(code(...))]`.
+pub fn code_of<'a>(attrs: &'a Attrs, type_attrs: Option<&'a Attrs>) -> Option<&'a LitStr> {
+    attrs
+        .code
+        .as_ref()
+        .or_else(|| type_attrs?.code.as_ref())
+        .or_else(|| diagnostic::diagnostic_option(attrs, type_attrs, |d| d.code.as_ref()))
+}
+
+pub fn option(value: Option<TokenStream>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(#value)),
+        None => quote!(::core::option::Option::None),
+    }
+}
@@ -0,0 +1,114 @@
+//! The error model behind `#[derive(Error)]`, exposed for external tooling.
+//!
+//! This crate exposes the very same parsing and validation model that the
+//! `thiserror-impl` procedural macro uses, shared through the
+//! `thiserror-model` crate, so documentation generators, linters, and code
+//! generators for other languages see exactly the data the derive sees: the
+//! struct or enum shape, each variant's `#[error("...")]` message template,
+//! its source, `#[from]` and backtrace fields, and any declared HTTP status
+//! or error code.
+//!
+//! ```
+//! use syn::parse_quote;
+//!
+//! let input: syn::DeriveInput = parse_quote! {
+//!     pub enum DataStoreError {
+//!         #[error("data store disconnected")]
+//!         Disconnect(#[from] std::io::Error),
+//!         #[error("the data for key `{0}` is not available")]
+//!         #[error(status = 404, code = "MISSING_KEY")]
+//!         Redaction(String),
+//!     }
+//! };
+//!
+//! let model = thiserror_internals::parse(&input).unwrap();
+//! let data = match model {
+//!     thiserror_internals::ErrorModel::Enum(data) => data,
+//!     thiserror_internals::ErrorModel::Struct(_) => unreachable!(),
+//! };
+//!
+//! let disconnect = &data.variants[0];
+//! assert_eq!(disconnect.ident, "Disconnect");
+//! assert!(disconnect.from_field().is_some());
+//!
+//! let redaction = &data.variants[1];
+//! assert_eq!(redaction.attrs.code.as_ref().unwrap().value(), "MISSING_KEY");
+//! assert_eq!(redaction.attrs.status.as_ref().unwrap().base10_digits(), "404");
+//! ```
+//!
+//! Message templates are stored with the derive's shorthand already expanded,
+//! i.e. `"{var}"` is rewritten into a positional format string plus arguments
+//! exactly as it will be passed to `write!`. The format string as written is
+//! kept alongside, in `Display::template`.
+//!
+//! `#[diagnostic(...)]` attributes are only parsed, into `Attrs::diagnostic`,
+//! with the `miette` feature of this crate enabled, mirroring the `miette`
+//! feature of thiserror.
+//!
+//! Tools run outside of the derive do not see the workspace-wide defaults of
+//! a `naur.toml` file unless they apply them, as the derive does:
+//!
+//! ```
+//! use syn::parse_quote;
+//! use thiserror_internals::config::Config;
+//!
+//! let config = Config::parse("[naur]\nfields = true\n\n[throws]\ntrace = \"warn\"\n").unwrap();
+//!
+//! let input: syn::DeriveInput = parse_quote! {
+//!     #[throws(trace = "info")]
+//!     #[error("request timed out after {secs}s")]
+//!     pub struct Timeout {
+//!         secs: u64,
+//!     }
+//! };
+//! let input = config.apply(&input);
+//!
+//! let model = thiserror_internals::parse(&input).unwrap();
+//! let data = match model {
+//!     thiserror_internals::ErrorModel::Struct(data) => data,
+//!     thiserror_internals::ErrorModel::Enum(_) => unreachable!(),
+//! };
+//! assert!(data.attrs.fields.is_some());
+//! assert_eq!(data.attrs.trace.as_ref().unwrap().level, "info");
+//! ```
+//!
+//! The [`typescript`] module builds on this model to emit TypeScript
+//! declarations of a crate's errors from a build script.
+
+#![doc(html_root_url = "https://docs.rs/thiserror-internals/1.0.50")]
+#![allow(
+    clippy::blocks_in_conditions,
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::manual_find,
+    clippy::manual_let_else,
+    clippy::manual_map,
+    clippy::map_unwrap_or,
+    clippy::missing_errors_doc,
+    clippy::module_name_repetitions,
+    clippy::must_use_candidate,
+    clippy::needless_pass_by_value,
+    clippy::option_if_let_else,
+    clippy::range_plus_one,
+    clippy::single_match_else,
+    clippy::struct_field_names,
+    clippy::too_many_lines,
+    clippy::wrong_self_convention
+)]
+
+pub mod typescript;
+
+pub use thiserror_model::{ast, attr, config};
+
+use syn::{DeriveInput, Result};
+
+/// The parsed representation of a `#[derive(Error)]` input.
+pub type ErrorModel<'a> = ast::Input<'a>;
+
+/// Parses and validates a `#[derive(Error)]` input the same way the derive
+/// macro does, reporting the same errors the macro would.
+pub fn parse(input: &DeriveInput) -> Result<ErrorModel<'_>> {
+    let model = ErrorModel::from_syn(input)?;
+    model.validate()?;
+    Ok(model)
+}
@@ -0,0 +1,310 @@
+//! TypeScript declarations for the errors derived in a crate.
+//!
+//! Every `#[derive(Error)]` struct or enum with `#[naur(serialize)]` becomes a
+//! discriminated union matching the shape that option serializes, keyed on
+//! `variant`. Types which do not serialize have no shape on the wire, and are
+//! left out. Types declaring `#[error(code = "...")]` additionally get a
+//! constant object mapping each variant to its code, so frontend clients can
+//! match on codes without hardcoding string literals.
+//!
+//! This is meant to be called from a build script:
+//!
+//! ```no_run
+//! // build.rs
+//! use std::{env, fs, path::Path};
+//!
+//! fn main() {
+//!     println!("cargo:rerun-if-changed=src");
+//!     let declarations = thiserror_internals::typescript::generate_file("src/lib.rs").unwrap();
+//!     let out = Path::new(&env::var("OUT_DIR").unwrap()).join("errors.ts");
+//!     fs::write(out, declarations).unwrap();
+//! }
+//! ```
+
+use crate::ast::Input;
+use crate::config::Config;
+use proc_macro2::Span;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, DeriveInput, Error, Expr, Item, Lit, Meta, Result, Token};
+
+/// Generates TypeScript declarations for every `#[derive(Error)]` type with
+/// `#[naur(serialize)]` in the given Rust source, including those in inline
+/// modules. Modules declared as `mod name;` live in files of their own, which
+/// only [`generate_file`] can find.
+///
+/// ```
+/// let source = r#"
+///     #[derive(Error, Debug)]
+///     #[naur(serialize)]
+///     pub enum DataStoreError {
+///         #[error("data store disconnected")]
+///         Disconnect(#[from] std::io::Error),
+///         #[error("the data for key `{0}` is not available")]
+///         #[error(code = "MISSING_KEY")]
+///         Redaction(String),
+///     }
+/// "#;
+///
+/// let declarations = thiserror_internals::typescript::generate(source).unwrap();
+/// assert_eq!(
+///     declarations,
+///     r#"export type DataStoreError =
+///   | { variant: "Disconnect"; message: string; code: null; sources: string[] }
+///   | { variant: "Redaction"; message: string; code: "MISSING_KEY"; sources: string[] };
+///
+/// export const DataStoreErrorCode = {
+///   Redaction: "MISSING_KEY",
+/// } as const;
+/// "#,
+/// );
+/// ```
+pub fn generate(source: &str) -> Result<String> {
+    let file = syn::parse_file(source)?;
+    let mut inputs = Vec::new();
+    collect_derived(file.items, None, &mut inputs)?;
+    declarations(&inputs)
+}
+
+/// Generates TypeScript declarations like [`generate`] for the Rust source
+/// file at the given path and every module it declares, such as `src/lib.rs`
+/// and the whole crate below it.
+///
+/// Modules declared as `mod name;` are read from `name.rs` or `name/mod.rs`,
+/// or from the file given by `#[path = "..."]`, the same as rustc does. The
+/// defaults of the `naur.toml` closest to the file are applied first, so that
+/// a workspace-wide `serialize = true` is seen as the derive sees it.
+pub fn generate_file(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let mut inputs = Vec::new();
+    collect_file(path, &mut inputs)?;
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    if let Some(config) = Config::load_from(dir)? {
+        for input in &mut inputs {
+            *input = config.apply(input);
+        }
+    }
+    declarations(&inputs)
+}
+
+fn declarations(inputs: &[DeriveInput]) -> Result<String> {
+    let mut out = String::new();
+    for input in inputs {
+        let model = crate::parse(input)?;
+        if !serializes(&model) {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        write_declarations(&mut out, &model);
+    }
+    Ok(out)
+}
+
+fn serializes(model: &Input) -> bool {
+    match model {
+        Input::Struct(input) => input.attrs.serialize.is_some(),
+        Input::Enum(input) => input.attrs.serialize.is_some(),
+    }
+}
+
+fn collect_file(path: &Path, inputs: &mut Vec<DeriveInput>) -> Result<()> {
+    let source = fs::read_to_string(path).map_err(|err| {
+        Error::new(
+            Span::call_site(),
+            format!("failed to read {}: {}", path.display(), err),
+        )
+    })?;
+    let file = syn::parse_file(&source)
+        .map_err(|err| Error::new(err.span(), format!("{}: {}", path.display(), err)))?;
+    collect_derived(file.items, Some(&module_dir(path)), inputs)
+}
+
+// The directory holding the files of the modules declared in the given file:
+// its own directory for a crate root or a mod.rs, and otherwise a directory
+// named after the file.
+fn module_dir(path: &Path) -> PathBuf {
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    match path.file_stem().and_then(|stem| stem.to_str()) {
+        Some("lib" | "main" | "mod") | None => dir.to_owned(),
+        Some(stem) => dir.join(stem),
+    }
+}
+
+// Collects the derived types among the items, descending into inline modules
+// and, given the directory of the module's files, into those declared with
+// `mod name;`.
+fn collect_derived(
+    items: Vec<Item>,
+    dir: Option<&Path>,
+    inputs: &mut Vec<DeriveInput>,
+) -> Result<()> {
+    for item in items {
+        let input = match item {
+            Item::Struct(item) => DeriveInput::from(item),
+            Item::Enum(item) => DeriveInput::from(item),
+            Item::Mod(item) => {
+                let path = path_attr(&item.attrs)?;
+                match (item.content, dir) {
+                    (Some((_, items)), dir) => {
+                        let dir = dir.map(|dir| match &path {
+                            Some(path) => dir.join(path),
+                            None => dir.join(item.ident.to_string()),
+                        });
+                        collect_derived(items, dir.as_deref(), inputs)?;
+                    }
+                    (None, Some(dir)) => {
+                        let file = match path {
+                            Some(path) => dir.join(path),
+                            None => module_file(dir, &item.ident)?,
+                        };
+                        collect_file(&file, inputs)?;
+                    }
+                    (None, None) => {}
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if derives_error(&input)? {
+            inputs.push(input);
+        }
+    }
+    Ok(())
+}
+
+fn module_file(dir: &Path, ident: &syn::Ident) -> Result<PathBuf> {
+    let name = ident.to_string();
+    let name = name.strip_prefix("r#").unwrap_or(&name);
+    let file = dir.join(format!("{}.rs", name));
+    if file.is_file() {
+        return Ok(file);
+    }
+    let mod_file = dir.join(name).join("mod.rs");
+    if mod_file.is_file() {
+        return Ok(mod_file);
+    }
+    Err(Error::new(
+        ident.span(),
+        format!(
+            "file not found for module `{}`, expected {} or {}",
+            name,
+            file.display(),
+            mod_file.display(),
+        ),
+    ))
+}
+
+// The file named by `#[path = "..."]` on a module, if any.
+fn path_attr(attrs: &[Attribute]) -> Result<Option<String>> {
+    for attr in attrs {
+        if !attr.path().is_ident("path") {
+            continue;
+        }
+        if let Meta::NameValue(meta) = &attr.meta {
+            if let Expr::Lit(expr) = &meta.value {
+                if let Lit::Str(lit) = &expr.lit {
+                    return Ok(Some(lit.value()));
+                }
+            }
+        }
+        return Err(Error::new_spanned(attr, "expected #[path = \"...\"]"));
+    }
+    Ok(None)
+}
+
+// Whether the type derives thiserror's Error, written as `Error` or
+// `thiserror::Error`, rather than some other crate's derive of that name.
+fn derives_error(input: &DeriveInput) -> Result<bool> {
+    for attr in &input.attrs {
+        if !attr.path().is_ident("derive") {
+            continue;
+        }
+        let paths = attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)?;
+        for path in paths {
+            let segments: Vec<String> = (path.segments.iter())
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            match segments.as_slice() {
+                [error] if error == "Error" && path.leading_colon.is_none() => return Ok(true),
+                [krate, error] if krate == "thiserror" && error == "Error" => return Ok(true),
+                _ => {}
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn write_declarations(out: &mut String, model: &Input) {
+    let (ty, cases) = match model {
+        Input::Struct(input) => {
+            let code = input.attrs.code.as_ref().map(syn::LitStr::value);
+            (&input.ident, vec![(input.ident.to_string(), code)])
+        }
+        Input::Enum(input) => {
+            let cases = input
+                .variants
+                .iter()
+                .map(|variant| {
+                    let code = variant.attrs.code.as_ref().or(input.attrs.code.as_ref());
+                    (variant.ident.to_string(), code.map(syn::LitStr::value))
+                })
+                .collect();
+            (&input.ident, cases)
+        }
+    };
+
+    if cases.is_empty() {
+        let _ = writeln!(out, "export type {} = never;", ty);
+        return;
+    }
+
+    let _ = writeln!(out, "export type {} =", ty);
+    for (i, (variant, code)) in cases.iter().enumerate() {
+        let code = match code {
+            Some(code) => string_literal(code),
+            None => "null".to_owned(),
+        };
+        let terminator = if i + 1 == cases.len() { ";" } else { "" };
+        let _ = writeln!(
+            out,
+            "  | {{ variant: {}; message: string; code: {}; sources: string[] }}{}",
+            string_literal(variant),
+            code,
+            terminator,
+        );
+    }
+
+    if cases.iter().any(|(_, code)| code.is_some()) {
+        let _ = writeln!(out, "\nexport const {}Code = {{", ty);
+        for (variant, code) in &cases {
+            if let Some(code) = code {
+                let _ = writeln!(out, "  {}: {},", variant, string_literal(code));
+            }
+        }
+        let _ = writeln!(out, "}} as const;");
+    }
+}
+
+fn string_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(literal, "\\u{{{:x}}}", ch as u32);
+            }
+            ch => literal.push(ch),
+        }
+    }
+    literal.push('"');
+    literal
+}
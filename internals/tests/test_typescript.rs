@@ -0,0 +1,118 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use thiserror_internals::typescript;
+
+// A scratch crate with the given files under `src/`, at the root of its own
+// workspace so that no naur.toml further up is picked up.
+fn scratch(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = env::temp_dir().join(format!("naur-typescript-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("Cargo.toml"), "[workspace]\n").unwrap();
+    for (path, source) in files {
+        let path = dir.join("src").join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, source).unwrap();
+    }
+    dir
+}
+
+fn leaf(name: &str) -> String {
+    format!(
+        "#[derive(Error, Debug)]\n#[naur(serialize)]\n#[error(\"leaf\")]\npub struct {}Error;\n",
+        name,
+    )
+}
+
+fn types(declarations: &str) -> Vec<&str> {
+    declarations
+        .lines()
+        .filter_map(|line| line.strip_prefix("export type "))
+        .collect()
+}
+
+#[test]
+fn test_module_files() {
+    let dir = scratch(
+        "modules",
+        &[
+            (
+                "lib.rs",
+                "mod a;\nmod b;\nmod inline { mod c; }\n#[path = \"other.rs\"]\nmod d;\n",
+            ),
+            ("a.rs", &leaf("A")),
+            ("b/mod.rs", &format!("mod nested;\n{}", leaf("B"))),
+            ("b/nested.rs", &leaf("Nested")),
+            ("inline/c.rs", &leaf("C")),
+            ("other.rs", &leaf("D")),
+        ],
+    );
+
+    let declarations = typescript::generate_file(dir.join("src/lib.rs")).unwrap();
+    assert_eq!(
+        types(&declarations),
+        [
+            "AError =",
+            "NestedError =",
+            "BError =",
+            "CError =",
+            "DError =",
+        ],
+    );
+}
+
+#[test]
+fn test_missing_module_file() {
+    let dir = scratch("missing", &[("lib.rs", "mod gone;\n")]);
+
+    let error = typescript::generate_file(dir.join("src/lib.rs")).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("file not found for module `gone`"));
+}
+
+#[test]
+fn test_serialize_required() {
+    let source = r#"
+        #[derive(Error, Debug)]
+        #[error("shown")]
+        #[naur(serialize)]
+        pub struct Shown;
+
+        #[derive(Error, Debug)]
+        #[error("hidden")]
+        pub struct Hidden;
+    "#;
+
+    let declarations = typescript::generate(source).unwrap();
+    assert_eq!(types(&declarations), ["Shown ="]);
+}
+
+#[test]
+fn test_serialize_from_config() {
+    let source = leaf("Leaf").replace("#[naur(serialize)]\n", "");
+    let dir = scratch("config", &[("lib.rs", &source)]);
+    fs::write(dir.join("naur.toml"), "[naur]\nserialize = true\n").unwrap();
+
+    let declarations = typescript::generate_file(dir.join("src/lib.rs")).unwrap();
+    assert_eq!(types(&declarations), ["LeafError ="]);
+}
+
+#[test]
+fn test_other_error_derive() {
+    let source = r#"
+        #[derive(other::Error, Debug)]
+        #[error("other")]
+        #[naur(serialize)]
+        pub struct Other;
+
+        #[derive(thiserror::Error, Debug)]
+        #[error("qualified")]
+        #[naur(serialize)]
+        pub struct Qualified;
+    "#;
+
+    let declarations = typescript::generate(source).unwrap();
+    assert_eq!(types(&declarations), ["Qualified ="]);
+}
@@ -0,0 +1,349 @@
+use crate::attr::{self, Attrs, Display, Transparent};
+use crate::generics::ParamsInScope;
+use crate::prop;
+use proc_macro2::Span;
+use syn::{
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, Generics, Ident, Index,
+    Member, Result, Type,
+};
+
+pub enum Input<'a> {
+    Struct(Struct<'a>),
+    Enum(Enum<'a>),
+}
+
+pub struct Struct<'a> {
+    pub original: &'a DeriveInput,
+    pub attrs: Attrs<'a>,
+    pub ident: Ident,
+    pub generics: &'a Generics,
+    pub fields: Vec<Field<'a>>,
+}
+
+pub struct Enum<'a> {
+    pub original: &'a DeriveInput,
+    pub attrs: Attrs<'a>,
+    pub ident: Ident,
+    pub generics: &'a Generics,
+    pub variants: Vec<Variant<'a>>,
+}
+
+pub struct Variant<'a> {
+    pub original: &'a syn::Variant,
+    pub attrs: Attrs<'a>,
+    pub ident: Ident,
+    pub fields: Vec<Field<'a>>,
+}
+
+pub struct Field<'a> {
+    pub original: &'a syn::Field,
+    pub attrs: Attrs<'a>,
+    pub member: Member,
+    pub ty: &'a Type,
+    pub contains_generic: bool,
+}
+
+impl<'a> Input<'a> {
+    pub fn from_syn(node: &'a DeriveInput) -> Result<Self> {
+        match &node.data {
+            Data::Struct(data) => Struct::from_syn(node, data).map(Input::Struct),
+            Data::Enum(data) => Enum::from_syn(node, data).map(Input::Enum),
+            Data::Union(_) => Err(Error::new_spanned(
+                node,
+                "union as errors are not supported",
+            )),
+        }
+    }
+}
+
+impl<'a> Struct<'a> {
+    fn from_syn(node: &'a DeriveInput, data: &'a DataStruct) -> Result<Self> {
+        let mut attrs = attr::get(&node.attrs)?;
+        let scope = ParamsInScope::new(&node.generics);
+        let span = attrs.span().unwrap_or_else(Span::call_site);
+        let mut fields = Field::multiple_from_syn(&data.fields, &scope, span)?;
+        inherit_arc_source(attrs.arc_source, &mut fields);
+        inherit_backtrace_capture(&attrs, &mut fields);
+        if let Some(display) = &mut attrs.display {
+            display.expand_shorthand(&fields)?;
+        }
+        let hints = [
+            &mut attrs.alt,
+            &mut attrs.help,
+            &mut attrs.suggestion,
+            &mut attrs.url,
+        ];
+        for hint in hints.into_iter().flatten() {
+            hint.expand_shorthand(&fields)?;
+        }
+        if let Some(help) = attrs.diagnostic.as_mut().and_then(|d| d.help.as_mut()) {
+            help.expand_shorthand(&fields)?;
+        }
+        Ok(Struct {
+            original: node,
+            attrs,
+            ident: node.ident.clone(),
+            generics: &node.generics,
+            fields,
+        })
+    }
+}
+
+impl<'a> Enum<'a> {
+    fn from_syn(node: &'a DeriveInput, data: &'a DataEnum) -> Result<Self> {
+        let attrs = attr::get(&node.attrs)?;
+        let scope = ParamsInScope::new(&node.generics);
+        let span = attrs.span().unwrap_or_else(Span::call_site);
+        let variants = data
+            .variants
+            .iter()
+            .map(|node| {
+                let mut variant = Variant::from_syn(node, &scope, span)?;
+                if let Some(same_as) = &variant.attrs.same_as {
+                    if variant.attrs.display.is_some()
+                        || variant.attrs.transparent.is_some()
+                        || variant.attrs.fmt.is_some()
+                    {
+                        return Err(Error::new_spanned(
+                            same_as,
+                            "cannot have both #[error(same_as = ...)] and a message of its own",
+                        ));
+                    }
+                    variant.attrs.display = Some(shared_display(data, same_as)?);
+                }
+                if let Some(payload) = &mut variant.attrs.boxed_payload {
+                    // The message is that of the generated payload, which
+                    // the variant displays through the box.
+                    payload.message =
+                        (variant.attrs.display.take()).map(|display| display.original);
+                }
+                if variant.attrs.boxed.is_some() && variant.attrs.transparent.is_none() {
+                    // Display and source go through the box to the payload.
+                    variant.attrs.transparent = variant.attrs.boxed;
+                }
+                if let Some(catch_all) = variant.attrs.catch_all {
+                    // The box is the source and is converted from by `From`,
+                    // and without a message of its own the variant displays
+                    // the error inside.
+                    if let [field] = variant.fields.as_mut_slice() {
+                        if field.attrs.from.is_none() && field.attrs.source.is_none() {
+                            field.attrs.from = Some(catch_all.original);
+                        }
+                    }
+                    if variant.attrs.display.is_none() && variant.attrs.fmt.is_none() {
+                        variant.attrs.transparent = Some(catch_all);
+                    }
+                }
+                if let Some(auto_transparent) = attrs.auto_transparent {
+                    // A thin wrapper around its source displays the source's
+                    // message, as if marked #[error(transparent)].
+                    if variant.attrs.display.is_none()
+                        && variant.attrs.fmt.is_none()
+                        && variant.attrs.transparent.is_none()
+                        && variant.fields.len() == 1
+                        && variant.source_field().is_some()
+                    {
+                        variant.attrs.transparent = Some(Transparent {
+                            original: auto_transparent,
+                            span: variant.ident.span(),
+                        });
+                    }
+                }
+                if variant.attrs.fmt.is_none()
+                    && variant.attrs.boxed.is_none()
+                    && variant.attrs.catch_all.is_none()
+                {
+                    if let display @ None = &mut variant.attrs.display {
+                        *display = attrs.display.clone();
+                    }
+                    if variant.attrs.display.is_none() && variant.attrs.transparent.is_none() {
+                        variant.attrs.display = (attrs.default_display.as_ref())
+                            .map(|default| default.for_variant(&variant.ident));
+                    }
+                }
+                if variant.attrs.arc_source.is_none() && variant.source_field().is_some() {
+                    variant.attrs.arc_source = attrs.arc_source;
+                }
+                inherit_arc_source(variant.attrs.arc_source, &mut variant.fields);
+                inherit_backtrace_capture(&attrs, &mut variant.fields);
+                let hints = [
+                    &mut variant.attrs.alt,
+                    &mut variant.attrs.help,
+                    &mut variant.attrs.suggestion,
+                    &mut variant.attrs.url,
+                ];
+                for hint in hints.into_iter().flatten() {
+                    hint.expand_shorthand(&variant.fields)?;
+                }
+                let diagnostic = variant.attrs.diagnostic.as_mut();
+                if let Some(help) = diagnostic.and_then(|d| d.help.as_mut()) {
+                    help.expand_shorthand(&variant.fields)?;
+                }
+                if let Some(display) = &mut variant.attrs.display {
+                    display.expand_shorthand(&variant.fields).map_err(|error| {
+                        match &variant.attrs.same_as {
+                            Some(same_as) => fields_mismatch(same_as, error),
+                            None => error,
+                        }
+                    })?;
+                } else if variant.attrs.transparent.is_none() && variant.attrs.fmt.is_none() {
+                    variant.attrs.transparent = attrs.transparent;
+                }
+                Ok(variant)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Enum {
+            original: node,
+            attrs,
+            ident: node.ident.clone(),
+            generics: &node.generics,
+            variants,
+        })
+    }
+}
+
+// The message of the variant named by #[error(same_as = ...)], not yet expanded
+// against the fields of either variant.
+fn shared_display<'a>(data: &'a DataEnum, same_as: &Ident) -> Result<Display<'a>> {
+    let target = data
+        .variants
+        .iter()
+        .find(|variant| variant.ident == *same_as)
+        .ok_or_else(|| Error::new_spanned(same_as, format!("no variant named `{}`", same_as)))?;
+    let attrs = attr::get(&target.attrs)?;
+    if attrs.same_as.is_some() {
+        return Err(Error::new_spanned(
+            same_as,
+            format!(
+                "`{}` shares the message of another variant; refer to that variant instead",
+                same_as,
+            ),
+        ));
+    }
+    attrs.display.ok_or_else(|| {
+        Error::new_spanned(
+            same_as,
+            format!("`{}` has no #[error(\"...\")] message to share", same_as),
+        )
+    })
+}
+
+fn fields_mismatch(same_as: &Ident, error: Error) -> Error {
+    let mut mismatch = Error::new_spanned(
+        same_as,
+        format!(
+            "the fields of this variant do not line up with the message of `{}`",
+            same_as,
+        ),
+    );
+    mismatch.combine(error);
+    mismatch
+}
+
+impl<'a> Variant<'a> {
+    fn from_syn(node: &'a syn::Variant, scope: &ParamsInScope<'a>, span: Span) -> Result<Self> {
+        let attrs = attr::get(&node.attrs)?;
+        let span = attrs.span().unwrap_or(span);
+        Ok(Variant {
+            original: node,
+            attrs,
+            ident: node.ident.clone(),
+            fields: Field::multiple_from_syn(&node.fields, scope, span)?,
+        })
+    }
+}
+
+impl<'a> Field<'a> {
+    fn multiple_from_syn(
+        fields: &'a Fields,
+        scope: &ParamsInScope<'a>,
+        span: Span,
+    ) -> Result<Vec<Self>> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| Field::from_syn(i, field, scope, span))
+            .collect()
+    }
+
+    fn from_syn(
+        i: usize,
+        node: &'a syn::Field,
+        scope: &ParamsInScope<'a>,
+        span: Span,
+    ) -> Result<Self> {
+        let mut attrs = attr::get(&node.attrs)?;
+        // #[from(skip)] marks the source field without deriving From for it.
+        attrs.source = attrs.source.or(attrs.from_skip);
+        Ok(Field {
+            original: node,
+            attrs,
+            member: node.ident.clone().map(Member::Named).unwrap_or_else(|| {
+                Member::Unnamed(Index {
+                    index: i as u32,
+                    span,
+                })
+            }),
+            ty: &node.ty,
+            contains_generic: scope.intersects(&node.ty),
+        })
+    }
+}
+
+// A type- or variant-level #[error(arc_source)] applies to the source field,
+// unless it is an `Arc<dyn Error>` which is shared as it is.
+// #[naur(backtrace_capture = ...)] applies to the backtrace field of each
+// struct or variant unless it names a capture function of its own. A backtrace
+// on the source field is the source's own, so is never captured.
+fn inherit_backtrace_capture(attrs: &Attrs, fields: &mut [Field]) {
+    let capture = match &attrs.default_backtrace_capture {
+        Some(capture) => &capture.path,
+        None => return,
+    };
+    let backtrace = match prop::backtrace_field(fields) {
+        Some(backtrace) => backtrace.member.clone(),
+        None => return,
+    };
+    let source = prop::source_field(fields).map(|source| source.member.clone());
+    if source.as_ref() == Some(&backtrace) {
+        return;
+    }
+    for field in fields {
+        if field.member == backtrace {
+            field
+                .attrs
+                .backtrace_capture
+                .get_or_insert_with(|| capture.clone());
+        }
+    }
+}
+
+fn inherit_arc_source<'a>(arc_source: Option<&'a Attribute>, fields: &mut [Field<'a>]) {
+    let arc_source = match arc_source {
+        Some(arc_source) => arc_source,
+        None => return,
+    };
+    let source = match prop::source_field(fields) {
+        Some(source) => source.member.clone(),
+        None => return,
+    };
+    for field in fields {
+        if field.member == source && field.arc_dyn_source_type().is_none() {
+            field.attrs.arc_source.get_or_insert(arc_source);
+        }
+    }
+}
+
+impl Attrs<'_> {
+    pub fn span(&self) -> Option<Span> {
+        if let Some(display) = &self.display {
+            Some(display.fmt.span())
+        } else if let Some(transparent) = &self.transparent {
+            Some(transparent.span)
+        } else if let Some(fmt) = &self.fmt {
+            Some(fmt.segments.last().unwrap().ident.span())
+        } else {
+            None
+        }
+    }
+}
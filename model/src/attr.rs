@@ -0,0 +1,1774 @@
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use std::collections::BTreeSet as Set;
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::{
+    braced, bracketed, parenthesized, token, Attribute, Error, Expr, ExprLit, FieldsNamed, Ident,
+    Index, Lit, LitInt, LitStr, Meta, MetaList, Path, Result, Token, Type, TypeParamBound,
+    Visibility, WherePredicate,
+};
+
+pub struct Attrs<'a> {
+    pub display: Option<Display<'a>>,
+    pub fmt: Option<Path>,
+    pub same_as: Option<Ident>,
+    pub alt: Option<Display<'a>>,
+    pub help: Option<Display<'a>>,
+    pub suggestion: Option<Display<'a>>,
+    pub url: Option<Display<'a>>,
+    pub default_display: Option<Display<'a>>,
+    pub source: Option<&'a Attribute>,
+    pub poison: Option<&'a Attribute>,
+    pub source_ty: Option<Type>,
+    pub backtrace: Option<&'a Attribute>,
+    pub backtrace_capture: Option<Path>,
+    pub default_backtrace_capture: Option<DefaultBacktraceCapture<'a>>,
+    pub cause: Option<&'a Attribute>,
+    pub related: Option<&'a Attribute>,
+    pub errors: Option<&'a Attribute>,
+    pub provide: Option<&'a Attribute>,
+    pub sensitive: Option<Sensitive<'a>>,
+    pub timestamp: Option<&'a Attribute>,
+    pub context_frames: Option<&'a Attribute>,
+    pub capture: Option<Capture<'a>>,
+    pub error_id: Option<ErrorId<'a>>,
+    pub from: Option<&'a Attribute>,
+    pub from_skip: Option<&'a Attribute>,
+    pub from_value: Option<&'a Attribute>,
+    pub transparent: Option<Transparent<'a>>,
+    pub context: Option<&'a Attribute>,
+    pub arc_source: Option<&'a Attribute>,
+    pub boxed: Option<Transparent<'a>>,
+    pub boxed_payload: Option<BoxedPayload<'a>>,
+    pub catch_all: Option<Transparent<'a>>,
+    pub panic: Option<&'a Attribute>,
+    pub ups: Vec<Up<'a>>,
+    pub status: Option<LitInt>,
+    pub code: Option<LitStr>,
+    pub grpc: Option<Ident>,
+    pub io_kind: Option<Ident>,
+    pub py: Option<Path>,
+    pub retry: Option<Retry<'a>>,
+    pub metric: Option<LitStr>,
+    pub i18n: Option<LitStr>,
+    pub bound: Option<Bound<'a>>,
+    pub prefix: Option<LitStr>,
+    pub no_prefix: Option<&'a Attribute>,
+    pub no_source: Option<&'a Attribute>,
+    pub serialize: Option<&'a Attribute>,
+    pub subsets: Vec<Subset<'a>>,
+    pub aggregate: Option<Aggregate<'a>>,
+    pub fields: Option<&'a Attribute>,
+    pub typed_fields: Option<TypedFields<'a>>,
+    pub chain: Option<&'a Attribute>,
+    pub alt_chain: Option<&'a Attribute>,
+    pub single_line: Option<&'a Attribute>,
+    pub debug_chain: Option<&'a Attribute>,
+    pub boxed_conv: Option<&'a Attribute>,
+    pub throws_naming: Option<ThrowsNaming<'a>>,
+    pub throws_prefix: Option<ThrowsPrefix<'a>>,
+    pub templates: Option<&'a Attribute>,
+    pub catalog: Option<&'a Attribute>,
+    pub manifest: Option<&'a Attribute>,
+    pub crate_path: Option<CratePath<'a>>,
+    pub wire: Option<&'a Attribute>,
+    pub kind: Option<&'a Attribute>,
+    pub ffi: Option<&'a Attribute>,
+    pub accessors: Option<&'a Attribute>,
+    pub arbitrary: Option<&'a Attribute>,
+    pub constructors: Option<&'a Attribute>,
+    pub eq: Option<Equality<'a>>,
+    pub max_size: Option<MaxSize<'a>>,
+    pub assert: Option<AssertBounds<'a>>,
+    pub code_range: Option<CodeRange<'a>>,
+    pub cold: Option<&'a Attribute>,
+    pub auto_transparent: Option<&'a Attribute>,
+    pub redacted_debug: Option<&'a Attribute>,
+    pub stable_debug: Option<&'a Attribute>,
+    pub minimal: Option<&'a Attribute>,
+    pub no_defaults: Option<&'a Attribute>,
+    pub extensions: Vec<Extension<'a>>,
+    pub diagnostic: Option<Diagnostic<'a>>,
+    pub trace: Option<Hook<'a>>,
+    pub log: Option<Hook<'a>>,
+    pub must_use: Option<&'a Attribute>,
+    pub throws_pipe: Option<&'a Attribute>,
+    pub throws_poll: Option<&'a Attribute>,
+    pub context_struct: Option<&'a Attribute>,
+    pub tuple_from: Option<&'a Attribute>,
+    pub throws_doc: Option<ThrowsDoc<'a>>,
+    pub throws_feature: Option<ThrowsFeature<'a>>,
+    pub on_create: Option<OnCreate<'a>>,
+    pub throws_vis: Option<ThrowsVis<'a>>,
+    pub phantom: Option<&'a Attribute>,
+    pub throws_default: Option<&'a Attribute>,
+    pub try_from: Option<Validate<'a>>,
+    pub from_any: Option<&'a Attribute>,
+}
+
+#[derive(Clone)]
+pub struct Display<'a> {
+    pub original: &'a Attribute,
+    pub fmt: LitStr,
+    // The format string as written, before shorthand expansion rewrites `fmt`.
+    pub template: LitStr,
+    // `#[error(const = path)]`: the template is the constant's, interpolated
+    // at runtime, and `fmt` only writes it out.
+    pub constant: Option<Path>,
+    pub args: TokenStream,
+    pub has_bonus_display: bool,
+    pub implied_bounds: Set<(usize, Trait)>,
+}
+
+#[derive(Copy, Clone)]
+pub struct Transparent<'a> {
+    pub original: &'a Attribute,
+    pub span: Span,
+}
+
+// `#[sensitive]` or `#[sensitive = "..."]`, a field whose value is replaced by
+// the given text in the message and the Debug representation.
+pub struct Sensitive<'a> {
+    pub original: &'a Attribute,
+    pub text: Option<LitStr>,
+}
+
+// `#[capture(...)]`, a field filled in with the current thread or process
+// whenever the error is constructed by a generated From impl, throw method, or
+// constructor.
+pub struct Capture<'a> {
+    pub original: &'a Attribute,
+    pub kind: CaptureKind,
+}
+
+#[derive(Copy, Clone)]
+pub enum CaptureKind {
+    ThreadName,
+    ThreadId,
+    Pid,
+}
+
+// `#[error_id]` or `#[error_id(generate = path)]`, a field filled in with a
+// unique identifier for each error constructed.
+pub struct ErrorId<'a> {
+    pub original: &'a Attribute,
+    pub generate: Option<Path>,
+}
+
+// A `#[throws(...)]` option run whenever the error is constructed by a
+// generated From impl or throw method.
+pub struct Hook<'a> {
+    pub original: &'a Attribute,
+    pub level: Ident,
+}
+
+// `#[error(transient)]`, `#[error(permanent)]`, or `#[error(transient = source)]`,
+// how `is_transient()` classifies the error.
+pub struct Retry<'a> {
+    pub original: &'a Attribute,
+    pub class: RetryClass,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum RetryClass {
+    Transient,
+    Permanent,
+    // Asks the source, which has an `is_transient()` of its own.
+    Source,
+}
+
+// `#[throws(doc = "...")]` or `#[throws(doc(hidden))]`, the documentation of
+// the generated throw traits.
+pub struct ThrowsDoc<'a> {
+    pub original: &'a Attribute,
+    pub text: Option<LitStr>,
+}
+
+// `#[throws(feature = "...")]`, the cargo feature of the crate deriving the
+// error which the throw traits and their impls are compiled under.
+pub struct ThrowsFeature<'a> {
+    pub original: &'a Attribute,
+    pub name: LitStr,
+}
+
+// `#[throws(on_create = path::to::hook)]`, a function called with `&Self` by
+// every generated constructor right after building the error.
+pub struct OnCreate<'a> {
+    pub original: &'a Attribute,
+    pub path: Path,
+}
+
+// `#[error(boxed { ... })]`: the fields of the payload struct which the derive
+// generates for the variant, and the variant's message, which becomes the
+// payload's.
+pub struct BoxedPayload<'a> {
+    pub fields: FieldsNamed,
+    pub message: Option<&'a Attribute>,
+}
+
+// `#[throws(vis = "pub(crate)")]`, the visibility of the generated throw
+// traits, which are otherwise private to the module of the error.
+pub struct ThrowsVis<'a> {
+    pub original: &'a Attribute,
+    pub vis: Visibility,
+}
+
+// `#[naur(backtrace_capture = path)]`, the function which captures every
+// backtrace field without a `#[backtrace(capture = ...)]` of its own.
+pub struct DefaultBacktraceCapture<'a> {
+    pub original: &'a Attribute,
+    pub path: Path,
+}
+
+// The where predicates of `#[error(bound = "...")]`, replacing inferred ones.
+pub struct Bound<'a> {
+    pub original: &'a Attribute,
+    pub predicates: Punctuated<WherePredicate, Token![,]>,
+}
+
+// A field converted into the error through TryFrom, accepted only if the
+// validation function returns true.
+pub struct Validate<'a> {
+    pub original: &'a Attribute,
+    pub validate: Path,
+}
+
+// A user macro re-invoked by `#[naur(extend_with = path)]` with the parsed
+// error model.
+pub struct Extension<'a> {
+    pub original: &'a Attribute,
+    pub path: Path,
+}
+
+// Options of `#[diagnostic(...)]`, which make the derive implement
+// `miette::Diagnostic`.
+pub struct Diagnostic<'a> {
+    pub original: &'a Attribute,
+    pub code: Option<LitStr>,
+    pub help: Option<Display<'a>>,
+    pub severity: Option<Ident>,
+    pub url: Option<LitStr>,
+    pub label: Option<Option<LitStr>>,
+    pub source_code: bool,
+}
+
+// `#[error(up(Parent::Variant))]`, a From impl converting the error into a
+// variant of a parent error type.
+pub struct Up<'a> {
+    pub original: &'a Attribute,
+    pub variant: Path,
+}
+
+// `#[naur(crate = "...")]`, the path through which the generated code refers
+// to the runtime crate, for frameworks which re-export it under another name.
+pub struct CratePath<'a> {
+    pub original: &'a Attribute,
+    pub path: Path,
+}
+
+// `#[naur(eq = "...")]`, a PartialEq impl which compares errors without
+// requiring their sources to be comparable.
+pub struct Equality<'a> {
+    pub original: &'a Attribute,
+    pub strategy: EqStrategy,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum EqStrategy {
+    // Only which variant the errors are.
+    Kind,
+    // The variant and its fields other than the source, backtrace, and
+    // related errors.
+    Fields,
+    // Only the code of the variant, which is hashed too.
+    Code,
+}
+
+// `#[naur(max_size = N)]`, a compile-time bound on the size of the error.
+pub struct MaxSize<'a> {
+    pub original: &'a Attribute,
+    pub bytes: LitInt,
+}
+
+// `#[naur(assert = "Send + Sync + 'static")]`, bounds which every field, and
+// so the error itself, is checked against at compile time.
+pub struct AssertBounds<'a> {
+    pub original: &'a Attribute,
+    pub bounds: Punctuated<TypeParamBound, Token![+]>,
+}
+
+// `#[naur(code_range = 4000..5000)]`, the numbers which the error codes of the
+// type are allowed to use.
+pub struct CodeRange<'a> {
+    pub original: &'a Attribute,
+    pub start: u64,
+    pub end: u64,
+    pub inclusive: bool,
+}
+
+impl CodeRange<'_> {
+    pub fn contains(&self, n: u64) -> bool {
+        self.start <= n && (n < self.end || self.inclusive && n == self.end)
+    }
+
+    pub fn render(&self) -> String {
+        let dots = if self.inclusive { "..=" } else { ".." };
+        format!("{}{}{}", self.start, dots, self.end)
+    }
+}
+
+// `#[naur(throws_naming(...))]`, how the throw methods are named after the
+// struct or variant.
+pub struct ThrowsNaming<'a> {
+    pub original: &'a Attribute,
+    // Keep a trailing `Error` rather than trimming it.
+    pub keep_suffix: bool,
+    // Treat a run of capitals as one word, so `IOError` is `io`, not `i_o`.
+    pub acronyms: bool,
+    // Prefix the name of an enum variant with that of the enum.
+    pub full: bool,
+}
+
+// `#[naur(throws_prefix = "...")]`, which replaces the `throw_` at the start of
+// the throw methods' names.
+pub struct ThrowsPrefix<'a> {
+    pub original: &'a Attribute,
+    pub prefix: LitStr,
+}
+
+// `#[naur(typed_fields)]`, optionally naming the module of marker types.
+pub struct TypedFields<'a> {
+    pub original: &'a Attribute,
+    pub module: Option<Ident>,
+}
+
+pub struct Subset<'a> {
+    pub original: &'a Attribute,
+    pub ident: Ident,
+    pub variants: Vec<Ident>,
+}
+
+pub struct Aggregate<'a> {
+    pub original: &'a Attribute,
+    pub container: Option<Type>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub enum Trait {
+    Debug,
+    Display,
+    Octal,
+    LowerHex,
+    UpperHex,
+    Pointer,
+    Binary,
+    LowerExp,
+    UpperExp,
+}
+
+pub fn get(input: &[Attribute]) -> Result<Attrs<'_>> {
+    let mut attrs = Attrs {
+        display: None,
+        fmt: None,
+        same_as: None,
+        alt: None,
+        help: None,
+        suggestion: None,
+        url: None,
+        default_display: None,
+        source: None,
+        poison: None,
+        source_ty: None,
+        backtrace: None,
+        backtrace_capture: None,
+        default_backtrace_capture: None,
+        cause: None,
+        related: None,
+        errors: None,
+        provide: None,
+        sensitive: None,
+        timestamp: None,
+        context_frames: None,
+        capture: None,
+        error_id: None,
+        from: None,
+        from_skip: None,
+        from_value: None,
+        transparent: None,
+        context: None,
+        arc_source: None,
+        boxed: None,
+        boxed_payload: None,
+        catch_all: None,
+        panic: None,
+        ups: Vec::new(),
+        status: None,
+        code: None,
+        grpc: None,
+        io_kind: None,
+        py: None,
+        retry: None,
+        metric: None,
+        i18n: None,
+        bound: None,
+        prefix: None,
+        no_prefix: None,
+        no_source: None,
+        serialize: None,
+        subsets: Vec::new(),
+        aggregate: None,
+        fields: None,
+        typed_fields: None,
+        chain: None,
+        alt_chain: None,
+        single_line: None,
+        debug_chain: None,
+        boxed_conv: None,
+        throws_naming: None,
+        throws_prefix: None,
+        templates: None,
+        catalog: None,
+        manifest: None,
+        crate_path: None,
+        wire: None,
+        kind: None,
+        ffi: None,
+        accessors: None,
+        arbitrary: None,
+        constructors: None,
+        eq: None,
+        max_size: None,
+        assert: None,
+        code_range: None,
+        cold: None,
+        auto_transparent: None,
+        redacted_debug: None,
+        stable_debug: None,
+        minimal: None,
+        no_defaults: None,
+        extensions: Vec::new(),
+        diagnostic: None,
+        trace: None,
+        log: None,
+        must_use: None,
+        throws_pipe: None,
+        throws_poll: None,
+        context_struct: None,
+        tuple_from: None,
+        throws_doc: None,
+        throws_feature: None,
+        on_create: None,
+        throws_vis: None,
+        phantom: None,
+        throws_default: None,
+        try_from: None,
+        from_any: None,
+    };
+
+    for attr in input {
+        if attr.path().is_ident("error") {
+            parse_error_attribute(&mut attrs, attr, input)?;
+        } else if attr.path().is_ident("source") {
+            if attrs.source.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[source] attribute"));
+            }
+            if let Meta::List(_) = &attr.meta {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("poison") {
+                        attrs.poison = Some(attr);
+                        Ok(())
+                    } else if meta.path.is_ident("ty") {
+                        attrs.source_ty = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected #[source(poison)] or #[source(ty = ...)]"))
+                    }
+                })?;
+            } else {
+                attr.meta.require_path_only()?;
+            }
+            attrs.source = Some(attr);
+        } else if attr.path().is_ident("backtrace") {
+            if attrs.backtrace.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[backtrace] attribute"));
+            }
+            if let Meta::List(_) = attr.meta {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("capture") {
+                        attrs.backtrace_capture = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected #[backtrace(capture = path)]"))
+                    }
+                })?;
+            } else {
+                attr.meta.require_path_only()?;
+            }
+            attrs.backtrace = Some(attr);
+        } else if attr.path().is_ident("cause") {
+            attr.meta.require_path_only()?;
+            if attrs.cause.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[cause] attribute"));
+            }
+            attrs.cause = Some(attr);
+        } else if attr.path().is_ident("related") {
+            attr.meta.require_path_only()?;
+            if attrs.related.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[related] attribute"));
+            }
+            attrs.related = Some(attr);
+        } else if attr.path().is_ident("errors") {
+            attr.meta.require_path_only()?;
+            if attrs.errors.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[errors] attribute"));
+            }
+            attrs.errors = Some(attr);
+        } else if attr.path().is_ident("provide") {
+            attr.meta.require_path_only()?;
+            if attrs.provide.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[provide] attribute"));
+            }
+            attrs.provide = Some(attr);
+        } else if attr.path().is_ident("sensitive") {
+            if attrs.sensitive.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[sensitive] attribute"));
+            }
+            let text =
+                match &attr.meta {
+                    Meta::Path(_) => None,
+                    Meta::NameValue(meta) => match &meta.value {
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(text),
+                            ..
+                        }) => Some(text.clone()),
+                        value => return Err(Error::new_spanned(
+                            value,
+                            "expected #[sensitive = \"...\"], the text shown in place of the value",
+                        )),
+                    },
+                    Meta::List(meta) => {
+                        return Err(Error::new_spanned(
+                            meta,
+                            "expected #[sensitive] or #[sensitive = \"...\"]",
+                        ))
+                    }
+                };
+            attrs.sensitive = Some(Sensitive {
+                original: attr,
+                text,
+            });
+        } else if attr.path().is_ident("timestamp") {
+            attr.meta.require_path_only()?;
+            if attrs.timestamp.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[timestamp] attribute"));
+            }
+            attrs.timestamp = Some(attr);
+        } else if attr.path().is_ident("context_frames") {
+            attr.meta.require_path_only()?;
+            if attrs.context_frames.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[context_frames] attribute",
+                ));
+            }
+            attrs.context_frames = Some(attr);
+        } else if attr.path().is_ident("capture") {
+            if attrs.capture.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[capture] attribute"));
+            }
+            let ident: Ident = attr.parse_args()?;
+            let kind = match ident.to_string().as_str() {
+                "thread_name" => CaptureKind::ThreadName,
+                "thread_id" => CaptureKind::ThreadId,
+                "pid" => CaptureKind::Pid,
+                _ => return Err(Error::new_spanned(
+                    ident,
+                    "expected #[capture(thread_name)], #[capture(thread_id)], or #[capture(pid)]",
+                )),
+            };
+            attrs.capture = Some(Capture {
+                original: attr,
+                kind,
+            });
+        } else if attr.path().is_ident("error_id") {
+            if attrs.error_id.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[error_id] attribute"));
+            }
+            let mut generate = None;
+            if let Meta::List(_) = attr.meta {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("generate") {
+                        generate = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected #[error_id(generate = path)]"))
+                    }
+                })?;
+            } else {
+                attr.meta.require_path_only()?;
+            }
+            attrs.error_id = Some(ErrorId {
+                original: attr,
+                generate,
+            });
+        } else if attr.path().is_ident("from") {
+            match &attr.meta {
+                Meta::Path(_) => {}
+                Meta::List(list) if is_skip(list) => {
+                    if attrs.from_skip.is_some() {
+                        return Err(Error::new_spanned(
+                            attr,
+                            "duplicate #[from(skip)] attribute",
+                        ));
+                    }
+                    attrs.from_skip = Some(attr);
+                    continue;
+                }
+                Meta::List(_) | Meta::NameValue(_) => {
+                    // Assume this is meant for derive_more crate or something.
+                    continue;
+                }
+            }
+            if attrs.from.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[from] attribute"));
+            }
+            attrs.from = Some(attr);
+        } else if attr.path().is_ident("from_value") {
+            attr.meta.require_path_only()?;
+            if attrs.from_value.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[from_value] attribute",
+                ));
+            }
+            attrs.from_value = Some(attr);
+        } else if attr.path().is_ident("naur") {
+            parse_naur_attribute(&mut attrs, attr)?;
+        } else if attr.path().is_ident("throws") {
+            parse_throws_attribute(&mut attrs, attr)?;
+        } else if attr.path().is_ident("diagnostic") && cfg!(feature = "miette") {
+            // Without the miette feature, the attribute is left to miette's
+            // own derive.
+            parse_diagnostic_attribute(&mut attrs, attr)?;
+        }
+    }
+
+    Ok(attrs)
+}
+
+// `#[from(skip)]`, whereas any other list is left alone, as likely meant for
+// derive_more or the like.
+fn is_skip(list: &MetaList) -> bool {
+    matches!(list.parse_args::<Ident>(), Ok(ident) if ident == "skip")
+}
+
+fn parse_error_attribute<'a>(
+    attrs: &mut Attrs<'a>,
+    attr: &'a Attribute,
+    input: &'a [Attribute],
+) -> Result<()> {
+    syn::custom_keyword!(transparent);
+    syn::custom_keyword!(context);
+    syn::custom_keyword!(arc_source);
+    syn::custom_keyword!(boxed);
+    syn::custom_keyword!(catch_all);
+    syn::custom_keyword!(panic);
+    syn::custom_keyword!(no_prefix);
+    syn::custom_keyword!(no_source);
+    syn::custom_keyword!(doc);
+    syn::custom_keyword!(up);
+    syn::custom_keyword!(transient);
+    syn::custom_keyword!(permanent);
+
+    let siblings = input;
+    attr.parse_args_with(|input: ParseStream| {
+        if let Some(kw) = input.parse::<Option<transparent>>()? {
+            if attrs.transparent.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[error(transparent)] attribute",
+                ));
+            }
+            attrs.transparent = Some(Transparent {
+                original: attr,
+                span: kw.span,
+            });
+            return Ok(());
+        }
+
+        if input.parse::<Option<context>>()?.is_some() {
+            if attrs.context.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[error(context)] attribute",
+                ));
+            }
+            attrs.context = Some(attr);
+            return Ok(());
+        }
+
+        if input.parse::<Option<arc_source>>()?.is_some() {
+            if attrs.arc_source.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[error(arc_source)] attribute",
+                ));
+            }
+            attrs.arc_source = Some(attr);
+            return Ok(());
+        }
+
+        if let Some(kw) = input.parse::<Option<boxed>>()? {
+            if attrs.boxed.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[error(boxed)] attribute",
+                ));
+            }
+            attrs.boxed = Some(Transparent {
+                original: attr,
+                span: kw.span,
+            });
+            if input.peek(token::Brace) {
+                attrs.boxed_payload = Some(BoxedPayload {
+                    fields: input.parse()?,
+                    message: None,
+                });
+            }
+            return Ok(());
+        }
+
+        if let Some(kw) = input.parse::<Option<catch_all>>()? {
+            if attrs.catch_all.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[error(catch_all)] attribute",
+                ));
+            }
+            attrs.catch_all = Some(Transparent {
+                original: attr,
+                span: kw.span,
+            });
+            return Ok(());
+        }
+
+        if input.parse::<Option<panic>>()?.is_some() {
+            if attrs.panic.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[error(panic)] attribute",
+                ));
+            }
+            attrs.panic = Some(attr);
+            return Ok(());
+        }
+
+        if input.parse::<Option<no_prefix>>()?.is_some() {
+            if attrs.no_prefix.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[error(no_prefix)] attribute",
+                ));
+            }
+            attrs.no_prefix = Some(attr);
+            return Ok(());
+        }
+
+        if input.parse::<Option<no_source>>()?.is_some() {
+            if attrs.no_source.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[error(no_source)] attribute",
+                ));
+            }
+            attrs.no_source = Some(attr);
+            return Ok(());
+        }
+
+        if input.peek(up) && input.peek2(token::Paren) {
+            input.parse::<up>()?;
+            let content;
+            parenthesized!(content in input);
+            let variant: Path = content.parse()?;
+            if variant.segments.len() < 2 {
+                return Err(Error::new_spanned(
+                    variant,
+                    "expected a path to a variant of the parent error, as in #[error(up(AppError::Database))]",
+                ));
+            }
+            attrs.ups.push(Up {
+                original: attr,
+                variant,
+            });
+            return Ok(());
+        }
+
+        if input.peek(Ident::peek_any) && input.peek2(Token![=]) {
+            return parse_error_options(attrs, attr, input);
+        }
+
+        let class = if input.parse::<Option<transient>>()?.is_some() {
+            Some(RetryClass::Transient)
+        } else if input.parse::<Option<permanent>>()?.is_some() {
+            Some(RetryClass::Permanent)
+        } else {
+            None
+        };
+        if let Some(class) = class {
+            return set_retry(attrs, attr, class);
+        }
+
+        let (fmt, args) = if let Some(kw) = input.parse::<Option<doc>>()? {
+            (first_doc_line(siblings, kw.span)?, TokenStream::new())
+        } else {
+            let fmt: LitStr = input.parse()?;
+            (fmt, parse_token_expr(input, false)?)
+        };
+        let display = Display {
+            original: attr,
+            template: fmt.clone(),
+            constant: None,
+            fmt,
+            args,
+            has_bonus_display: false,
+            implied_bounds: Set::new(),
+        };
+        if attrs.display.is_some() {
+            return Err(Error::new_spanned(
+                attr,
+                "only one #[error(...)] attribute is allowed",
+            ));
+        }
+        attrs.display = Some(display);
+        Ok(())
+    })
+}
+
+// The first non-blank line of the doc comment, for use as the format string
+// of #[error(doc)].
+fn first_doc_line(attrs: &[Attribute], span: Span) -> Result<LitStr> {
+    for attr in attrs {
+        if !attr.path().is_ident("doc") {
+            continue;
+        }
+        let lit = match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                }) => lit,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let value = lit.value();
+        if let Some(line) = value.lines().map(str::trim).find(|line| !line.is_empty()) {
+            return Ok(LitStr::new(line, lit.span()));
+        }
+    }
+    Err(Error::new(
+        span,
+        "#[error(doc)] requires a doc comment to use as the message",
+    ))
+}
+
+fn parse_naur_attribute<'a>(attrs: &mut Attrs<'a>, attr: &'a Attribute) -> Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("serialize") {
+            if attrs.serialize.is_some() {
+                return Err(meta.error("duplicate #[naur(serialize)] attribute"));
+            }
+            attrs.serialize = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("fields") {
+            if attrs.fields.is_some() {
+                return Err(meta.error("duplicate #[naur(fields)] attribute"));
+            }
+            attrs.fields = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("typed_fields") {
+            if attrs.typed_fields.is_some() {
+                return Err(meta.error("duplicate #[naur(typed_fields)] attribute"));
+            }
+            let module = if meta.input.peek(Token![=]) {
+                Some(meta.value()?.parse()?)
+            } else {
+                None
+            };
+            attrs.typed_fields = Some(TypedFields {
+                original: attr,
+                module,
+            });
+            Ok(())
+        } else if meta.path.is_ident("chain") {
+            if attrs.chain.is_some() {
+                return Err(meta.error("duplicate #[naur(chain)] attribute"));
+            }
+            attrs.chain = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("alt_chain") {
+            if attrs.alt_chain.is_some() {
+                return Err(meta.error("duplicate #[naur(alt_chain)] attribute"));
+            }
+            attrs.alt_chain = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("single_line") {
+            if attrs.single_line.is_some() {
+                return Err(meta.error("duplicate #[naur(single_line)] attribute"));
+            }
+            attrs.single_line = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("templates") {
+            if attrs.templates.is_some() {
+                return Err(meta.error("duplicate #[naur(templates)] attribute"));
+            }
+            attrs.templates = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("catalog") {
+            if attrs.catalog.is_some() {
+                return Err(meta.error("duplicate #[naur(catalog)] attribute"));
+            }
+            attrs.catalog = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("manifest") {
+            if attrs.manifest.is_some() {
+                return Err(meta.error("duplicate #[naur(manifest)] attribute"));
+            }
+            attrs.manifest = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("crate") {
+            if attrs.crate_path.is_some() {
+                return Err(meta.error("duplicate #[naur(crate = ...)] attribute"));
+            }
+            let value: LitStr = meta.value()?.parse()?;
+            attrs.crate_path = Some(CratePath {
+                original: attr,
+                path: value.parse()?,
+            });
+            Ok(())
+        } else if meta.path.is_ident("wire") {
+            if attrs.wire.is_some() {
+                return Err(meta.error("duplicate #[naur(wire)] attribute"));
+            }
+            attrs.wire = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("kind") {
+            if attrs.kind.is_some() {
+                return Err(meta.error("duplicate #[naur(kind)] attribute"));
+            }
+            attrs.kind = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("ffi") {
+            if attrs.ffi.is_some() {
+                return Err(meta.error("duplicate #[naur(ffi)] attribute"));
+            }
+            attrs.ffi = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("accessors") {
+            if attrs.accessors.is_some() {
+                return Err(meta.error("duplicate #[naur(accessors)] attribute"));
+            }
+            attrs.accessors = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("arbitrary") {
+            if attrs.arbitrary.is_some() {
+                return Err(meta.error("duplicate #[naur(arbitrary)] attribute"));
+            }
+            attrs.arbitrary = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("constructors") {
+            if attrs.constructors.is_some() {
+                return Err(meta.error("duplicate #[naur(constructors)] attribute"));
+            }
+            attrs.constructors = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("eq") {
+            if attrs.eq.is_some() {
+                return Err(meta.error("duplicate #[naur(eq = ...)] attribute"));
+            }
+            let value: LitStr = meta.value()?.parse()?;
+            let strategy = match value.value().as_str() {
+                "kind" => EqStrategy::Kind,
+                "fields" => EqStrategy::Fields,
+                "code" => EqStrategy::Code,
+                _ => {
+                    return Err(Error::new_spanned(
+                        value,
+                        "expected #[naur(eq = \"kind\")], #[naur(eq = \"fields\")], or #[naur(eq = \"code\")]",
+                    ))
+                }
+            };
+            attrs.eq = Some(Equality {
+                original: attr,
+                strategy,
+            });
+            Ok(())
+        } else if meta.path.is_ident("max_size") {
+            if attrs.max_size.is_some() {
+                return Err(meta.error("duplicate #[naur(max_size = ...)] attribute"));
+            }
+            let bytes: LitInt = meta.value()?.parse()?;
+            bytes.base10_parse::<usize>()?;
+            attrs.max_size = Some(MaxSize {
+                original: attr,
+                bytes,
+            });
+            Ok(())
+        } else if meta.path.is_ident("assert") {
+            if attrs.assert.is_some() {
+                return Err(meta.error("duplicate #[naur(assert = ...)] attribute"));
+            }
+            let value: LitStr = meta.value()?.parse()?;
+            attrs.assert = Some(AssertBounds {
+                original: attr,
+                bounds: value.parse_with(Punctuated::parse_separated_nonempty)?,
+            });
+            Ok(())
+        } else if meta.path.is_ident("code_range") {
+            if attrs.code_range.is_some() {
+                return Err(meta.error("duplicate #[naur(code_range = ...)] attribute"));
+            }
+            let input = meta.value()?;
+            let start: LitInt = input.parse()?;
+            let inclusive = if input.peek(Token![..=]) {
+                input.parse::<Token![..=]>()?;
+                true
+            } else {
+                input.parse::<Token![..]>()?;
+                false
+            };
+            let end: LitInt = input.parse()?;
+            attrs.code_range = Some(CodeRange {
+                original: attr,
+                start: start.base10_parse()?,
+                end: end.base10_parse()?,
+                inclusive,
+            });
+            Ok(())
+        } else if meta.path.is_ident("cold") {
+            if attrs.cold.is_some() {
+                return Err(meta.error("duplicate #[naur(cold)] attribute"));
+            }
+            attrs.cold = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("auto_transparent") {
+            if attrs.auto_transparent.is_some() {
+                return Err(meta.error("duplicate #[naur(auto_transparent)] attribute"));
+            }
+            attrs.auto_transparent = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("debug_chain") {
+            if attrs.debug_chain.is_some() {
+                return Err(meta.error("duplicate #[naur(debug_chain)] attribute"));
+            }
+            attrs.debug_chain = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("boxed_conv") {
+            if attrs.boxed_conv.is_some() {
+                return Err(meta.error("duplicate #[naur(boxed_conv)] attribute"));
+            }
+            attrs.boxed_conv = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("throws_naming") {
+            if attrs.throws_naming.is_some() {
+                return Err(meta.error("duplicate #[naur(throws_naming(...))] attribute"));
+            }
+            let mut naming = ThrowsNaming {
+                original: attr,
+                keep_suffix: false,
+                acronyms: false,
+                full: false,
+            };
+            meta.parse_nested_meta(|meta| {
+                let option = if meta.path.is_ident("keep_suffix") {
+                    &mut naming.keep_suffix
+                } else if meta.path.is_ident("acronyms") {
+                    &mut naming.acronyms
+                } else if meta.path.is_ident("full") {
+                    &mut naming.full
+                } else {
+                    return Err(meta.error(
+                        "unrecognized #[naur(throws_naming(...))] option, expected keep_suffix, acronyms, or full",
+                    ));
+                };
+                if *option {
+                    return Err(meta.error("duplicate #[naur(throws_naming(...))] option"));
+                }
+                *option = true;
+                Ok(())
+            })?;
+            attrs.throws_naming = Some(naming);
+            Ok(())
+        } else if meta.path.is_ident("throws_prefix") {
+            if attrs.throws_prefix.is_some() {
+                return Err(meta.error("duplicate #[naur(throws_prefix = ...)] attribute"));
+            }
+            let prefix: LitStr = meta.value()?.parse()?;
+            let value = prefix.value();
+            let valid = value.chars().all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+                && !value.starts_with(|ch: char| ch.is_ascii_digit());
+            if !valid {
+                return Err(Error::new_spanned(
+                    prefix,
+                    "expected the start of a method name, such as \"or_\"",
+                ));
+            }
+            attrs.throws_prefix = Some(ThrowsPrefix {
+                original: attr,
+                prefix,
+            });
+            Ok(())
+        } else if meta.path.is_ident("backtrace_capture") {
+            if attrs.default_backtrace_capture.is_some() {
+                return Err(meta.error("duplicate #[naur(backtrace_capture = ...)] attribute"));
+            }
+            attrs.default_backtrace_capture = Some(DefaultBacktraceCapture {
+                original: attr,
+                path: meta.value()?.parse()?,
+            });
+            Ok(())
+        } else if meta.path.is_ident("redacted_debug") {
+            if attrs.redacted_debug.is_some() {
+                return Err(meta.error("duplicate #[naur(redacted_debug)] attribute"));
+            }
+            attrs.redacted_debug = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("stable_debug") {
+            if attrs.stable_debug.is_some() {
+                return Err(meta.error("duplicate #[naur(stable_debug)] attribute"));
+            }
+            attrs.stable_debug = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("minimal") {
+            if attrs.minimal.is_some() {
+                return Err(meta.error("duplicate #[naur(minimal)] attribute"));
+            }
+            attrs.minimal = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("no_defaults") {
+            if attrs.no_defaults.is_some() {
+                return Err(meta.error("duplicate #[naur(no_defaults)] attribute"));
+            }
+            attrs.no_defaults = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("subset") {
+            let content;
+            parenthesized!(content in meta.input);
+            let ident: Ident = content.parse()?;
+            content.parse::<Token![:]>()?;
+            let variants = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+            attrs.subsets.push(Subset {
+                original: attr,
+                ident,
+                variants: variants.into_iter().collect(),
+            });
+            Ok(())
+        } else if meta.path.is_ident("aggregate") {
+            if attrs.aggregate.is_some() {
+                return Err(meta.error("duplicate #[naur(aggregate)] attribute"));
+            }
+            let mut container = None;
+            if meta.input.peek(token::Paren) {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("container") {
+                        container = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("unrecognized #[naur(aggregate(...))] option"))
+                    }
+                })?;
+            }
+            attrs.aggregate = Some(Aggregate {
+                original: attr,
+                container,
+            });
+            Ok(())
+        } else if meta.path.is_ident("extend_with") {
+            let path: Path = meta.value()?.parse()?;
+            attrs.extensions.push(Extension {
+                original: attr,
+                path,
+            });
+            Ok(())
+        } else if meta.path.is_ident("from_any") {
+            if attrs.from_any.is_some() {
+                return Err(meta.error("duplicate #[naur(from_any)] attribute"));
+            }
+            attrs.from_any = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("try_from") {
+            if attrs.try_from.is_some() {
+                return Err(meta.error("duplicate #[naur(try_from)] attribute"));
+            }
+            let mut validate = None;
+            if meta.input.peek(token::Paren) {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("validate") {
+                        validate = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("unrecognized #[naur(try_from(...))] option"))
+                    }
+                })?;
+            }
+            let validate = validate.ok_or_else(|| {
+                meta.error(
+                    "expected a validation function: #[naur(try_from(validate = path::to_fn))]",
+                )
+            })?;
+            attrs.try_from = Some(Validate {
+                original: attr,
+                validate,
+            });
+            Ok(())
+        } else {
+            Err(meta.error("unrecognized #[naur(...)] option"))
+        }
+    })
+}
+
+fn parse_diagnostic_attribute<'a>(attrs: &mut Attrs<'a>, attr: &'a Attribute) -> Result<()> {
+    if attrs.diagnostic.is_some() {
+        return Err(Error::new_spanned(
+            attr,
+            "duplicate #[diagnostic] attribute",
+        ));
+    }
+    let mut diagnostic = Diagnostic {
+        original: attr,
+        code: None,
+        help: None,
+        severity: None,
+        url: None,
+        label: None,
+        source_code: false,
+    };
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("code") {
+            let content;
+            parenthesized!(content in meta.input);
+            let code: Path = content.parse()?;
+            let repr = code.to_token_stream().to_string().replace(' ', "");
+            diagnostic.code = Some(LitStr::new(&repr, code.segments[0].ident.span()));
+            Ok(())
+        } else if meta.path.is_ident("help") {
+            let content;
+            parenthesized!(content in meta.input);
+            let help: LitStr = content.parse()?;
+            diagnostic.help = Some(Display {
+                original: attr,
+                template: help.clone(),
+                constant: None,
+                fmt: help,
+                args: TokenStream::new(),
+                has_bonus_display: false,
+                implied_bounds: Set::new(),
+            });
+            Ok(())
+        } else if meta.path.is_ident("severity") {
+            let content;
+            parenthesized!(content in meta.input);
+            let severity: Ident = content.parse()?;
+            if severity != "Error" && severity != "Warning" && severity != "Advice" {
+                return Err(Error::new_spanned(
+                    severity,
+                    "expected one of Error, Warning, Advice",
+                ));
+            }
+            diagnostic.severity = Some(severity);
+            Ok(())
+        } else if meta.path.is_ident("url") {
+            let content;
+            parenthesized!(content in meta.input);
+            diagnostic.url = Some(content.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("label") {
+            diagnostic.label = Some(if meta.input.peek(Token![=]) {
+                Some(meta.value()?.parse()?)
+            } else {
+                None
+            });
+            Ok(())
+        } else if meta.path.is_ident("source_code") {
+            diagnostic.source_code = true;
+            Ok(())
+        } else {
+            Err(meta.error("unrecognized #[diagnostic(...)] option"))
+        }
+    })?;
+    attrs.diagnostic = Some(diagnostic);
+    Ok(())
+}
+
+fn parse_throws_attribute<'a>(attrs: &mut Attrs<'a>, attr: &'a Attribute) -> Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("trace") {
+            if attrs.trace.is_some() {
+                return Err(meta.error("duplicate #[throws(trace)] attribute"));
+            }
+            let level = parse_level(&meta, "error")?;
+            attrs.trace = Some(Hook {
+                original: attr,
+                level,
+            });
+            Ok(())
+        } else if meta.path.is_ident("log") {
+            if attrs.log.is_some() {
+                return Err(meta.error("duplicate #[throws(log)] attribute"));
+            }
+            let level = parse_level(&meta, "error")?;
+            attrs.log = Some(Hook {
+                original: attr,
+                level,
+            });
+            Ok(())
+        } else if meta.path.is_ident("must_use") {
+            if attrs.must_use.is_some() {
+                return Err(meta.error("duplicate #[throws(must_use)] attribute"));
+            }
+            attrs.must_use = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("pipe") {
+            if attrs.throws_pipe.is_some() {
+                return Err(meta.error("duplicate #[throws(pipe)] attribute"));
+            }
+            attrs.throws_pipe = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("poll") {
+            if attrs.throws_poll.is_some() {
+                return Err(meta.error("duplicate #[throws(poll)] attribute"));
+            }
+            attrs.throws_poll = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("context_struct") {
+            if attrs.context_struct.is_some() {
+                return Err(meta.error("duplicate #[throws(context_struct)] attribute"));
+            }
+            attrs.context_struct = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("tuple_from") {
+            if attrs.tuple_from.is_some() {
+                return Err(meta.error("duplicate #[throws(tuple_from)] attribute"));
+            }
+            attrs.tuple_from = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("phantom") {
+            if attrs.phantom.is_some() {
+                return Err(meta.error("duplicate #[throws(phantom)] attribute"));
+            }
+            attrs.phantom = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("default") {
+            if attrs.throws_default.is_some() {
+                return Err(meta.error("duplicate #[throws(default)] attribute"));
+            }
+            attrs.throws_default = Some(attr);
+            Ok(())
+        } else if meta.path.is_ident("doc") {
+            if attrs.throws_doc.is_some() {
+                return Err(meta.error("duplicate #[throws(doc ...)] attribute"));
+            }
+            let text = if meta.input.peek(Token![=]) {
+                Some(meta.value()?.parse()?)
+            } else {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("hidden") {
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected #[throws(doc(hidden))]"))
+                    }
+                })?;
+                None
+            };
+            attrs.throws_doc = Some(ThrowsDoc {
+                original: attr,
+                text,
+            });
+            Ok(())
+        } else if meta.path.is_ident("feature") {
+            if attrs.throws_feature.is_some() {
+                return Err(meta.error("duplicate #[throws(feature = ...)] attribute"));
+            }
+            attrs.throws_feature = Some(ThrowsFeature {
+                original: attr,
+                name: meta.value()?.parse()?,
+            });
+            Ok(())
+        } else if meta.path.is_ident("on_create") {
+            if attrs.on_create.is_some() {
+                return Err(meta.error("duplicate #[throws(on_create = ...)] attribute"));
+            }
+            attrs.on_create = Some(OnCreate {
+                original: attr,
+                path: meta.value()?.parse()?,
+            });
+            Ok(())
+        } else if meta.path.is_ident("vis") {
+            if attrs.throws_vis.is_some() {
+                return Err(meta.error("duplicate #[throws(vis = ...)] attribute"));
+            }
+            let vis: LitStr = meta.value()?.parse()?;
+            attrs.throws_vis = Some(ThrowsVis {
+                original: attr,
+                vis: vis.parse()?,
+            });
+            Ok(())
+        } else {
+            Err(meta.error("unrecognized #[throws(...)] option"))
+        }
+    })
+}
+
+// Parses the optional `= "level"` of a logging hook into the level's name.
+fn parse_level(meta: &ParseNestedMeta, default: &str) -> Result<Ident> {
+    if !meta.input.peek(Token![=]) {
+        return Ok(Ident::new(default, meta.path.get_ident().unwrap().span()));
+    }
+    let level: LitStr = meta.value()?.parse()?;
+    match level.value().as_str() {
+        "error" | "warn" | "info" | "debug" | "trace" => {
+            Ok(Ident::new(&level.value(), level.span()))
+        }
+        _ => Err(Error::new_spanned(
+            level,
+            "expected one of \"error\", \"warn\", \"info\", \"debug\", \"trace\"",
+        )),
+    }
+}
+
+fn set_retry<'a>(attrs: &mut Attrs<'a>, attr: &'a Attribute, class: RetryClass) -> Result<()> {
+    if attrs.retry.is_some() {
+        return Err(Error::new_spanned(
+            attr,
+            "only one of #[error(transient)] and #[error(permanent)] is allowed",
+        ));
+    }
+    attrs.retry = Some(Retry {
+        original: attr,
+        class,
+    });
+    Ok(())
+}
+
+fn parse_error_options<'a>(
+    attrs: &mut Attrs<'a>,
+    attr: &'a Attribute,
+    input: ParseStream,
+) -> Result<()> {
+    loop {
+        let key = input.call(Ident::parse_any)?;
+        input.parse::<Token![=]>()?;
+        if key == "fmt" {
+            let fmt: Path = input.parse()?;
+            if attrs.fmt.is_some() {
+                return Err(Error::new_spanned(key, "duplicate #[error(fmt)] attribute"));
+            }
+            attrs.fmt = Some(fmt);
+        } else if key == "const" {
+            let constant: Path = input.parse()?;
+            if attrs.display.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "only one #[error(...)] attribute is allowed",
+                ));
+            }
+            let fmt = LitStr::new("{}", key.span());
+            attrs.display = Some(Display {
+                original: attr,
+                template: fmt.clone(),
+                constant: Some(constant),
+                fmt,
+                args: TokenStream::new(),
+                has_bonus_display: false,
+                implied_bounds: Set::new(),
+            });
+        } else if key == "same_as" {
+            let same_as: Ident = input.parse()?;
+            if attrs.same_as.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(same_as)] attribute",
+                ));
+            }
+            attrs.same_as = Some(same_as);
+        } else if key == "alt" {
+            let alt: LitStr = input.parse()?;
+            if attrs.alt.is_some() {
+                return Err(Error::new_spanned(key, "duplicate #[error(alt)] attribute"));
+            }
+            attrs.alt = Some(Display {
+                original: attr,
+                template: alt.clone(),
+                constant: None,
+                fmt: alt,
+                args: TokenStream::new(),
+                has_bonus_display: false,
+                implied_bounds: Set::new(),
+            });
+        } else if key == "help" {
+            let help: LitStr = input.parse()?;
+            if attrs.help.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(help)] attribute",
+                ));
+            }
+            attrs.help = Some(Display {
+                original: attr,
+                template: help.clone(),
+                constant: None,
+                fmt: help,
+                args: TokenStream::new(),
+                has_bonus_display: false,
+                implied_bounds: Set::new(),
+            });
+        } else if key == "suggestion" || key == "url" {
+            let template: LitStr = input.parse()?;
+            let section = if key == "suggestion" {
+                &mut attrs.suggestion
+            } else {
+                &mut attrs.url
+            };
+            if section.is_some() {
+                return Err(Error::new_spanned(
+                    &key,
+                    format!("duplicate #[error({})] attribute", key),
+                ));
+            }
+            *section = Some(Display {
+                original: attr,
+                template: template.clone(),
+                constant: None,
+                fmt: template,
+                args: TokenStream::new(),
+                has_bonus_display: false,
+                implied_bounds: Set::new(),
+            });
+        } else if key == "default" {
+            let fmt: LitStr = input.parse()?;
+            if attrs.default_display.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(default)] attribute",
+                ));
+            }
+            attrs.default_display = Some(Display {
+                original: attr,
+                template: fmt.clone(),
+                constant: None,
+                fmt,
+                args: TokenStream::new(),
+                has_bonus_display: false,
+                implied_bounds: Set::new(),
+            });
+        } else if key == "status" {
+            let status: LitInt = input.parse()?;
+            match status.base10_parse::<u16>() {
+                Ok(100..=999) => {}
+                _ => {
+                    return Err(Error::new_spanned(
+                        status,
+                        "expected an HTTP status code between 100 and 999",
+                    ));
+                }
+            }
+            if attrs.status.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(status)] attribute",
+                ));
+            }
+            attrs.status = Some(LitInt::new(status.base10_digits(), status.span()));
+        } else if key == "code" {
+            let code: LitStr = input.parse()?;
+            if attrs.code.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(code)] attribute",
+                ));
+            }
+            attrs.code = Some(code);
+        } else if key == "grpc" {
+            let grpc: Ident = input.parse()?;
+            if attrs.grpc.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(grpc)] attribute",
+                ));
+            }
+            attrs.grpc = Some(grpc);
+        } else if key == "io_kind" {
+            let io_kind: Ident = input.parse()?;
+            if attrs.io_kind.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(io_kind)] attribute",
+                ));
+            }
+            attrs.io_kind = Some(io_kind);
+        } else if key == "py" {
+            let py: Path = input.parse()?;
+            if attrs.py.is_some() {
+                return Err(Error::new_spanned(key, "duplicate #[error(py)] attribute"));
+            }
+            attrs.py = Some(py);
+        } else if key == "transient" {
+            let value: Ident = input.parse()?;
+            if value != "source" {
+                return Err(Error::new_spanned(
+                    value,
+                    "expected `source`, as in #[error(transient = source)]",
+                ));
+            }
+            set_retry(attrs, attr, RetryClass::Source)?;
+        } else if key == "metric" {
+            let metric: LitStr = input.parse()?;
+            if attrs.metric.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(metric)] attribute",
+                ));
+            }
+            attrs.metric = Some(metric);
+        } else if key == "i18n" {
+            let i18n: LitStr = input.parse()?;
+            if attrs.i18n.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(i18n)] attribute",
+                ));
+            }
+            attrs.i18n = Some(i18n);
+        } else if key == "prefix" {
+            let prefix: LitStr = input.parse()?;
+            if attrs.prefix.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(prefix)] attribute",
+                ));
+            }
+            attrs.prefix = Some(prefix);
+        } else if key == "bound" {
+            let bound: LitStr = input.parse()?;
+            if attrs.bound.is_some() {
+                return Err(Error::new_spanned(
+                    key,
+                    "duplicate #[error(bound)] attribute",
+                ));
+            }
+            attrs.bound = Some(Bound {
+                original: attr,
+                predicates: bound.parse_with(Punctuated::parse_terminated)?,
+            });
+        } else {
+            return Err(Error::new_spanned(
+                &key,
+                format!("unrecognized #[error({} = ...)] attribute", key),
+            ));
+        }
+        if input.is_empty() {
+            return Ok(());
+        }
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            return Ok(());
+        }
+    }
+}
+
+fn parse_token_expr(input: ParseStream, mut begin_expr: bool) -> Result<TokenStream> {
+    let mut tokens = Vec::new();
+    while !input.is_empty() {
+        if begin_expr && input.peek(Token![.]) {
+            if input.peek2(Ident) {
+                input.parse::<Token![.]>()?;
+                begin_expr = false;
+                continue;
+            }
+            if input.peek2(LitInt) {
+                input.parse::<Token![.]>()?;
+                let int: Index = input.parse()?;
+                let ident = format_ident!("_{}", int.index, span = int.span);
+                tokens.push(TokenTree::Ident(ident));
+                begin_expr = false;
+                continue;
+            }
+        }
+
+        begin_expr = input.peek(Token![break])
+            || input.peek(Token![continue])
+            || input.peek(Token![if])
+            || input.peek(Token![in])
+            || input.peek(Token![match])
+            || input.peek(Token![mut])
+            || input.peek(Token![return])
+            || input.peek(Token![while])
+            || input.peek(Token![+])
+            || input.peek(Token![&])
+            || input.peek(Token![!])
+            || input.peek(Token![^])
+            || input.peek(Token![,])
+            || input.peek(Token![/])
+            || input.peek(Token![=])
+            || input.peek(Token![>])
+            || input.peek(Token![<])
+            || input.peek(Token![|])
+            || input.peek(Token![%])
+            || input.peek(Token![;])
+            || input.peek(Token![*])
+            || input.peek(Token![-]);
+
+        let token: TokenTree = if input.peek(token::Paren) {
+            let content;
+            let delimiter = parenthesized!(content in input);
+            let nested = parse_token_expr(&content, true)?;
+            let mut group = Group::new(Delimiter::Parenthesis, nested);
+            group.set_span(delimiter.span.join());
+            TokenTree::Group(group)
+        } else if input.peek(token::Brace) {
+            let content;
+            let delimiter = braced!(content in input);
+            let nested = parse_token_expr(&content, true)?;
+            let mut group = Group::new(Delimiter::Brace, nested);
+            group.set_span(delimiter.span.join());
+            TokenTree::Group(group)
+        } else if input.peek(token::Bracket) {
+            let content;
+            let delimiter = bracketed!(content in input);
+            let nested = parse_token_expr(&content, true)?;
+            let mut group = Group::new(Delimiter::Bracket, nested);
+            group.set_span(delimiter.span.join());
+            TokenTree::Group(group)
+        } else {
+            input.parse()?
+        };
+        tokens.push(token);
+    }
+    Ok(TokenStream::from_iter(tokens))
+}
+
+impl ToTokens for Display<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if let Some(message) = self.static_message() {
+            let message = LitStr::new(&message, self.fmt.span());
+            tokens.extend(quote! {
+                __formatter.write_str(#message)
+            });
+            return;
+        }
+        let fmt = &self.fmt;
+        let args = &self.args;
+        tokens.extend(quote! {
+            ::core::write!(__formatter, #fmt #args)
+        });
+    }
+}
+
+impl ToTokens for Trait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let trait_name = format_ident!("{}", format!("{:?}", self));
+        tokens.extend(quote!(::core::fmt::#trait_name));
+    }
+}
@@ -0,0 +1,296 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{parse_quote, Attribute, DeriveInput, Error, Ident, LitStr, Meta, Result};
+
+// Options of #[naur(...)] which may be given a workspace-wide default.
+const NAUR_OPTIONS: &[(&str, Value)] = &[
+    ("serialize", Value::Flag),
+    ("fields", Value::Flag),
+    ("typed_fields", Value::Flag),
+    ("chain", Value::Flag),
+    ("alt_chain", Value::Flag),
+    ("single_line", Value::Flag),
+    ("boxed_conv", Value::Flag),
+    ("templates", Value::Flag),
+    ("catalog", Value::Flag),
+    ("manifest", Value::Flag),
+    ("wire", Value::Flag),
+    ("cold", Value::Flag),
+    ("throws_prefix", Value::String),
+    ("backtrace_capture", Value::Path),
+];
+
+// Options of #[throws(...)] which may be given a workspace-wide default.
+const THROWS_OPTIONS: &[(&str, Value)] = &[
+    ("trace", Value::FlagOrString),
+    ("log", Value::FlagOrString),
+    ("must_use", Value::Flag),
+    ("pipe", Value::Flag),
+    ("poll", Value::Flag),
+    ("vis", Value::String),
+];
+
+/// Defaults for the container attributes of every `#[derive(Error)]` in a
+/// workspace, read from a `naur.toml` file.
+///
+/// ```toml
+/// [naur]
+/// fields = true
+/// chain = true
+/// throws_prefix = "or_"
+/// backtrace_capture = "my_crate::trace::capture"
+///
+/// [throws]
+/// trace = "warn"
+/// log = true
+/// vis = "pub(crate)"
+/// ```
+///
+/// Each entry stands for the container option of the same name: `true` adds
+/// the option as if written on the type and `false` leaves it out. A string
+/// is passed as the value of an option which takes one, such as the level of
+/// `trace`, the visibility of the throw traits, or the path of the backtrace
+/// capture function.
+/// An option written on the type itself takes precedence over its default,
+/// and `#[naur(no_defaults)]` opts a type out of all of them, as does
+/// `#[naur(minimal)]`.
+pub struct Config {
+    /// The file the configuration was read from, if any.
+    pub path: Option<PathBuf>,
+    naur: Vec<Attribute>,
+    throws: Vec<Attribute>,
+}
+
+impl Config {
+    /// Finds the `naur.toml` closest to the crate being compiled, looking in
+    /// `CARGO_MANIFEST_DIR` and then each of its ancestors, so that one file
+    /// at the root of a workspace covers every member.
+    pub fn load() -> Result<Option<Config>> {
+        match env::var_os("CARGO_MANIFEST_DIR") {
+            Some(manifest_dir) => Config::load_from(Path::new(&manifest_dir)),
+            None => Ok(None),
+        }
+    }
+
+    /// Finds the `naur.toml` closest to the given crate directory. The search
+    /// stops at the root of the workspace, the first directory whose
+    /// `Cargo.toml` has a `[workspace]` table, so that a file outside of the
+    /// workspace never applies to it.
+    pub fn load_from(manifest_dir: &Path) -> Result<Option<Config>> {
+        for dir in manifest_dir.ancestors() {
+            let path = dir.join("naur.toml");
+            if !path.is_file() {
+                if is_workspace_root(dir) {
+                    break;
+                }
+                continue;
+            }
+            let text = fs::read_to_string(&path).map_err(|err| {
+                Error::new(
+                    Span::call_site(),
+                    format!("failed to read {}: {}", path.display(), err),
+                )
+            })?;
+            let mut config = Config::parse(&text)
+                .map_err(|err| Error::new(err.span(), format!("{}: {}", path.display(), err)))?;
+            config.path = Some(path);
+            return Ok(Some(config));
+        }
+        Ok(None)
+    }
+
+    /// Parses the contents of a `naur.toml` file.
+    pub fn parse(text: &str) -> Result<Config> {
+        let mut config = Config {
+            path: None,
+            naur: Vec::new(),
+            throws: Vec::new(),
+        };
+        let mut section = None;
+        for (i, line) in text.lines().enumerate() {
+            let error =
+                |msg: &str| Error::new(Span::call_site(), format!("line {}: {}", i + 1, msg));
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                section = match name.trim() {
+                    "naur" => Some(Section::Naur),
+                    "throws" => Some(Section::Throws),
+                    _ => return Err(error("expected a [naur] or [throws] table")),
+                };
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return Err(error("expected `option = value`")),
+            };
+            let section = match section {
+                Some(section) => section,
+                None => return Err(error("expected a [naur] or [throws] table")),
+            };
+            let (attr, options) = match section {
+                Section::Naur => ("naur", NAUR_OPTIONS),
+                Section::Throws => ("throws", THROWS_OPTIONS),
+            };
+            let kind = match options.iter().find(|(option, _)| *option == key) {
+                Some((_, kind)) => *kind,
+                None => {
+                    return Err(error(&format!(
+                        "unrecognized #[{}(...)] option `{}`",
+                        attr, key
+                    )));
+                }
+            };
+            let key = Ident::new(key, Span::call_site());
+            let option = match (value, kind) {
+                ("true", Value::Flag | Value::FlagOrString) => quote!(#key),
+                ("false", _) => continue,
+                (_, Value::Flag) => return Err(error("expected true or false")),
+                (_, Value::FlagOrString | Value::String) => match parse_string(value) {
+                    Some(value) => {
+                        let value = LitStr::new(&value, Span::call_site());
+                        quote!(#key = #value)
+                    }
+                    None if kind == Value::String => {
+                        return Err(error("expected false or a string"));
+                    }
+                    None => return Err(error("expected true, false, or a string")),
+                },
+                (_, Value::Path) => match parse_string(value) {
+                    Some(value) => match syn::parse_str::<syn::Path>(&value) {
+                        Ok(path) => quote!(#key = #path),
+                        Err(_) => return Err(error("expected the path of a function")),
+                    },
+                    None => return Err(error("expected false or a string")),
+                },
+            };
+            match section {
+                Section::Naur => config.naur.push(parse_quote!(#[naur(#option)])),
+                Section::Throws => config.throws.push(parse_quote!(#[throws(#option)])),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Adds the defaults which the input does not already set, or does not
+    /// opt out of, to its attributes.
+    pub fn apply(&self, input: &DeriveInput) -> DeriveInput {
+        let mut input = input.clone();
+        let given = given_options(&input.attrs);
+        if given
+            .iter()
+            .any(|(_, option)| option == "no_defaults" || option == "minimal")
+        {
+            return input;
+        }
+        let defaults = self.naur.iter().chain(&self.throws);
+        let missing: Vec<Attribute> = defaults
+            .filter(|default| {
+                let (attr, option) = &given_options(std::slice::from_ref(*default))[0];
+                !given.contains(&(attr.clone(), option.clone()))
+            })
+            .cloned()
+            .collect();
+        input.attrs.splice(0..0, missing);
+        input
+    }
+
+    /// Makes the crate recompile when the configuration file changes.
+    pub fn track(&self) -> TokenStream {
+        match &self.path {
+            Some(path) => {
+                let path = path.to_string_lossy();
+                quote! {
+                    const _: &str = ::core::include_str!(#path);
+                }
+            }
+            None => TokenStream::new(),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Section {
+    Naur,
+    Throws,
+}
+
+// The values an option accepts besides `false`.
+#[derive(Copy, Clone, PartialEq)]
+enum Value {
+    // `true`.
+    Flag,
+    // `true` or a string, such as the level of a logging hook.
+    FlagOrString,
+    // A string, such as a method name prefix or a visibility.
+    String,
+    // A string holding the path of a function.
+    Path,
+}
+
+// Whether the directory holds the Cargo.toml of a workspace, above which no
+// naur.toml is looked for.
+fn is_workspace_root(dir: &Path) -> bool {
+    match fs::read_to_string(dir.join("Cargo.toml")) {
+        Ok(manifest) => manifest
+            .lines()
+            .any(|line| strip_comment(line).trim() == "[workspace]"),
+        Err(_) => false,
+    }
+}
+
+// The options named at the top level of each #[naur(...)] and #[throws(...)]
+// attribute, as (attribute, option) pairs.
+fn given_options(attrs: &[Attribute]) -> Vec<(String, String)> {
+    let mut given = Vec::new();
+    for attr in attrs {
+        let list = match &attr.meta {
+            Meta::List(list) if attr.path().is_ident("naur") || attr.path().is_ident("throws") => {
+                list
+            }
+            _ => continue,
+        };
+        let name = attr.path().get_ident().unwrap().to_string();
+        let mut expect_option = true;
+        for token in list.tokens.clone() {
+            match token {
+                TokenTree::Ident(ident) if expect_option => {
+                    given.push((name.clone(), ident.to_string()));
+                    expect_option = false;
+                }
+                TokenTree::Punct(punct) if punct.as_char() == ',' => expect_option = true,
+                _ => expect_option = false,
+            }
+        }
+    }
+    given
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+// A basic TOML string without escapes, which is all the options need.
+fn parse_string(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    if value.contains(['"', '\\']) {
+        return None;
+    }
+    Some(value.to_owned())
+}
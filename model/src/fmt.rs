@@ -0,0 +1,676 @@
+use crate::ast::Field;
+use crate::attr::{Display, Trait};
+use proc_macro2::{Group, Spacing, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote_spanned, ToTokens};
+use std::cmp;
+use std::collections::{BTreeSet as Set, HashMap as Map};
+use syn::ext::IdentExt;
+use syn::parse::{ParseStream, Parser};
+use syn::spanned::Spanned;
+use syn::{Error, Ident, Index, LitStr, Member, Result, Token};
+
+impl Display<'_> {
+    // Transform `"error {var}"` to `"error {}", var`.
+    pub fn expand_shorthand(&mut self, fields: &[Field]) -> Result<()> {
+        if self.constant.is_some() {
+            self.expand_constant(fields);
+            return Ok(());
+        }
+        let raw_args = self.args.clone();
+        let mut named_args = explicit_named_args.parse2(raw_args).unwrap();
+        let positional_args = explicit_positional_args(&self.args);
+        let mut member_index = Map::new();
+        for (i, field) in fields.iter().enumerate() {
+            member_index.insert(&field.member, i);
+        }
+
+        let span = self.fmt.span();
+        let fmt = self.fmt.value();
+        let mut read = fmt.as_str();
+        let mut out = String::new();
+        let mut args = self.args.clone();
+        let mut has_bonus_display = false;
+        let mut implied_bounds = Set::new();
+        let mut expressions = 0usize;
+
+        let mut has_trailing_comma = false;
+        if let Some(TokenTree::Punct(punct)) = args.clone().into_iter().last() {
+            if punct.as_char() == ',' {
+                has_trailing_comma = true;
+            }
+        }
+
+        while let Some(brace) = read.find('{') {
+            out += &read[..brace + 1];
+            read = &read[brace + 1..];
+            if read.starts_with('{') {
+                out.push('{');
+                read = &read[1..];
+                continue;
+            }
+            let next = match read.chars().next() {
+                Some(next) => next,
+                None => return Ok(()),
+            };
+            let member = match next {
+                '0'..='9' => {
+                    let start = fmt.len() - read.len();
+                    let int = take_int(&mut read);
+                    let member = match int.parse::<u32>() {
+                        Ok(index) => Member::Unnamed(Index { index, span }),
+                        Err(_) => return Ok(()),
+                    };
+                    if !member_index.contains_key(&member) {
+                        if int
+                            .parse::<usize>()
+                            .map_or(false, |index| index >= positional_args)
+                        {
+                            let span = placeholder_span(&self.fmt, start, start + int.len());
+                            return Err(unknown_member(&int, fields, span));
+                        }
+                        out += &int;
+                        continue;
+                    }
+                    member
+                }
+                'a'..='z' | 'A'..='Z' | '_' => {
+                    let start = fmt.len() - read.len();
+                    let mut ident = take_ident(&mut read);
+                    if !named_args.contains(&ident) {
+                        // `{type}` refers to a field declared as `r#type`.
+                        ident = raw_field(fields, &ident).unwrap_or(ident);
+                        // A lowercase name which is not a field is a typo
+                        // rather than a constant in scope, unless it begins a
+                        // path, call, or macro such as `{helper::describe()}`.
+                        let name = ident.unraw().to_string();
+                        if name.starts_with(|ch: char| ch.is_ascii_lowercase())
+                            && name != "self"
+                            && !member_index.contains_key(&Member::Named(ident.clone()))
+                            && !read.starts_with(['(', '!'])
+                            && !read.starts_with("::")
+                        {
+                            let end = fmt.len() - read.len();
+                            let span = placeholder_span(&self.fmt, start, end);
+                            return Err(unknown_member(&name, fields, span));
+                        }
+                    }
+                    ident.set_span(span);
+                    Member::Named(ident)
+                }
+                ':' => {
+                    let counts = expand_counts(&mut read, &mut out, &member_index, span);
+                    push_counts(
+                        counts,
+                        &mut args,
+                        &mut named_args,
+                        &mut has_trailing_comma,
+                        span,
+                    );
+                    continue;
+                }
+                _ => continue,
+            };
+            let local = match &member {
+                Member::Unnamed(index) => format_ident!("_{}", index),
+                Member::Named(ident) => ident.clone(),
+            };
+            let after_member = read;
+            // `{config.path}` reaches into the fields of a member.
+            let mut path = Vec::new();
+            while read.starts_with('.') {
+                let segment = match read[1..].chars().next() {
+                    Some('0'..='9') => {
+                        read = &read[1..];
+                        let int = take_int(&mut read);
+                        match int.parse::<u32>() {
+                            Ok(index) => Member::Unnamed(Index { index, span }),
+                            Err(_) => return Ok(()),
+                        }
+                    }
+                    Some('a'..='z' | 'A'..='Z' | '_') => {
+                        read = &read[1..];
+                        let mut ident = take_ident(&mut read);
+                        ident.set_span(span);
+                        Member::Named(ident)
+                    }
+                    _ => break,
+                };
+                path.push(segment);
+            }
+            // Anything else before the end of the placeholder or its format
+            // spec makes it an expression, such as `{path.display()}` or
+            // `{len + 1}`, evaluated with the member bound to its local.
+            if !read.starts_with('}') && (!read.starts_with(':') || read.starts_with("::")) {
+                let end = match expression_end(after_member) {
+                    Some(end) => end,
+                    None => return Ok(()),
+                };
+                let expr = format!("{}{}", local, &after_member[..end]);
+                let expr = match expr.parse::<TokenStream>() {
+                    Ok(expr) => respan(expr, span),
+                    Err(_) => return Ok(()),
+                };
+                read = &after_member[end..];
+                let mut formatvar = format_ident!("expr__{}", expressions);
+                expressions += 1;
+                while member_index.contains_key(&Member::Named(formatvar.clone()))
+                    || named_args.contains(&formatvar)
+                {
+                    formatvar = format_ident!("{}_", formatvar);
+                }
+                out += &formatvar.to_string();
+                let counts = expand_counts(&mut read, &mut out, &member_index, span);
+                push_counts(
+                    counts,
+                    &mut args,
+                    &mut named_args,
+                    &mut has_trailing_comma,
+                    span,
+                );
+                named_args.insert(formatvar.clone());
+                if !has_trailing_comma {
+                    args.extend(quote_spanned!(span=> ,));
+                }
+                args.extend(quote_spanned!(span=> #formatvar = (#expr)));
+                has_trailing_comma = false;
+                continue;
+            }
+            let sensitive = member_index
+                .get(&member)
+                .map_or(false, |&field| fields[field].attrs.sensitive.is_some());
+            if let (Some(&field), true, false) =
+                (member_index.get(&member), path.is_empty(), sensitive)
+            {
+                let end_spec = match read.find('}') {
+                    Some(end_spec) => end_spec,
+                    None => return Ok(()),
+                };
+                let bound = match read[..end_spec].chars().next_back() {
+                    Some('?') => Trait::Debug,
+                    Some('o') => Trait::Octal,
+                    Some('x') => Trait::LowerHex,
+                    Some('X') => Trait::UpperHex,
+                    Some('p') => Trait::Pointer,
+                    Some('b') => Trait::Binary,
+                    Some('e') => Trait::LowerExp,
+                    Some('E') => Trait::UpperExp,
+                    Some(_) | None => Trait::Display,
+                };
+                implied_bounds.insert((field, bound));
+            }
+            let mut formatvar = local.clone();
+            if !path.is_empty() {
+                formatvar = format_ident!("nested__{}", formatvar);
+            }
+            for segment in &path {
+                formatvar = match segment {
+                    Member::Unnamed(index) => format_ident!("{}__{}", formatvar, index),
+                    Member::Named(ident) => format_ident!("{}__{}", formatvar, ident),
+                };
+            }
+            if !path.is_empty() {
+                while member_index.contains_key(&Member::Named(formatvar.clone())) {
+                    formatvar = format_ident!("{}_", formatvar);
+                }
+            }
+            if formatvar.to_string().starts_with("r#") {
+                formatvar = format_ident!("r_{}", formatvar);
+            }
+            if formatvar == "self" {
+                // `{self:?}` formats the whole error, passed under a name which
+                // a format argument can have.
+                formatvar = format_ident!("self_");
+                while member_index.contains_key(&Member::Named(formatvar.clone())) {
+                    formatvar = format_ident!("{}_", formatvar);
+                }
+            }
+            if formatvar.to_string().starts_with('_') {
+                // Work around leading underscore being rejected by 1.40 and
+                // older compilers. https://github.com/rust-lang/rust/pull/66847
+                formatvar = format_ident!("field_{}", formatvar);
+            }
+            // Formatted with Display, whether or not there is a spec such as a
+            // width, so that a Path can be shown through `.display()`.
+            let displayed = match read.find('}') {
+                Some(end_spec) => !matches!(
+                    read[..end_spec].chars().next_back(),
+                    Some('?' | 'o' | 'x' | 'X' | 'p' | 'b' | 'e' | 'E'),
+                ),
+                None => false,
+            };
+            out += &formatvar.to_string();
+            let counts = expand_counts(&mut read, &mut out, &member_index, span);
+            push_counts(
+                counts,
+                &mut args,
+                &mut named_args,
+                &mut has_trailing_comma,
+                span,
+            );
+            if !named_args.insert(formatvar.clone()) {
+                // Already specified in the format argument list.
+                continue;
+            }
+            if !has_trailing_comma {
+                args.extend(quote_spanned!(span=> ,));
+            }
+            if path.is_empty() {
+                args.extend(quote_spanned!(span=> #formatvar = #local));
+            } else {
+                args.extend(quote_spanned!(span=> #formatvar = (&#local #(.#path)*)));
+            }
+            if displayed && member_index.contains_key(&member) {
+                has_bonus_display = true;
+                args.extend(quote_spanned!(span=> .as_display()));
+            }
+            has_trailing_comma = false;
+        }
+
+        out += read;
+        self.fmt = LitStr::new(&out, self.fmt.span());
+        self.args = args;
+        self.has_bonus_display = has_bonus_display;
+        self.implied_bounds = implied_bounds;
+        Ok(())
+    }
+
+    // The template as written: the string literal, or the constant named by
+    // `#[error(const = path)]`.
+    pub fn template_tokens(&self) -> TokenStream {
+        match &self.constant {
+            Some(path) => path.to_token_stream(),
+            None => self.template.to_token_stream(),
+        }
+    }
+
+    // The message of a variant without one of its own, from the enum's
+    // `#[error(default = "...")]`, in which `{variant}` stands for the name of
+    // the variant.
+    pub fn for_variant(&self, variant: &Ident) -> Self {
+        let name = variant.unraw().to_string();
+        let template = self.template.value();
+        let mut read = template.as_str();
+        let mut out = String::new();
+        while let Some(brace) = read.find('{') {
+            out += &read[..brace];
+            read = &read[brace..];
+            if let Some(rest) = read.strip_prefix("{{") {
+                out += "{{";
+                read = rest;
+            } else if let Some(rest) = read.strip_prefix("{variant}") {
+                out += &name;
+                read = rest;
+            } else {
+                out.push('{');
+                read = &read[1..];
+            }
+        }
+        out += read;
+        let fmt = LitStr::new(&out, self.template.span());
+        Display {
+            original: self.original,
+            template: fmt.clone(),
+            constant: None,
+            fmt,
+            args: TokenStream::new(),
+            has_bonus_display: false,
+            implied_bounds: Set::new(),
+        }
+    }
+
+    // `#[error(const = path)]` writes out the constant's template with its
+    // placeholders filled in from the fields by name, as i18n does.
+    fn expand_constant(&mut self, fields: &[Field]) {
+        let path = self.constant.as_ref().unwrap();
+        let span = path.span();
+        let mut names = Vec::new();
+        let mut bindings = Vec::new();
+        for field in fields {
+            // Autoref specialization cannot see through generic parameters.
+            if field.attrs.backtrace.is_some() || field.is_backtrace() || field.contains_generic {
+                continue;
+            }
+            let (name, binding) = match &field.member {
+                Member::Named(ident) => (
+                    LitStr::new(&ident.unraw().to_string(), ident.span()),
+                    ident.clone(),
+                ),
+                Member::Unnamed(index) => (
+                    LitStr::new(&index.index.to_string(), index.span),
+                    format_ident!("_{}", index),
+                ),
+            };
+            names.push(name);
+            bindings.push(binding);
+        }
+        self.args = quote_spanned! {span=>
+            , {
+                #[allow(unused_imports)]
+                use thiserror::__private::{FieldValueViaDebug as _, FieldValueViaDisplay as _};
+                thiserror::__private::ConstMessage::new(
+                    #path,
+                    [#((#names, (&&thiserror::__private::FieldValueWrap(#bindings)).field_value())),*],
+                )
+            }
+        };
+    }
+
+    // Whether the message formats `{self}` with Display, which as the Display
+    // impl being defined would recurse forever.
+    pub fn displays_self(&self) -> bool {
+        let template = self.template.value();
+        let mut read = template.as_str();
+        while let Some(brace) = read.find('{') {
+            read = &read[brace + 1..];
+            if let Some(rest) = read.strip_prefix('{') {
+                read = rest;
+                continue;
+            }
+            let end = match read.find('}') {
+                Some(end) => end,
+                None => return false,
+            };
+            let (name, spec) = match read[..end].split_once(':') {
+                Some((name, spec)) => (name, spec),
+                None => (&read[..end], ""),
+            };
+            let displayed = !matches!(
+                spec.chars().next_back(),
+                Some('?' | 'o' | 'x' | 'X' | 'p' | 'b' | 'e' | 'E'),
+            );
+            if name.trim() == "self" && displayed {
+                return true;
+            }
+            read = &read[end..];
+        }
+        false
+    }
+
+    // The message itself, if the format string interpolates nothing and so
+    // can be written without the formatting machinery.
+    pub fn static_message(&self) -> Option<String> {
+        if !self.args.is_empty() {
+            return None;
+        }
+        let fmt = self.fmt.value();
+        let mut message = String::new();
+        let mut chars = fmt.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' | '}' if chars.next() == Some(ch) => message.push(ch),
+                '{' | '}' => return None,
+                _ => message.push(ch),
+            }
+        }
+        Some(message)
+    }
+}
+
+// Copies the format spec at the start of `read` to `out`, rewriting each width
+// or precision which names a field, such as `{value:>width$}` or
+// `{ratio:.1$}`, to an argument of its own. A count has to be a usize, so the
+// field is passed as it is rather than through the display wrapper it gets as
+// a placeholder. Returns the new arguments with the locals they stand for.
+fn expand_counts(
+    read: &mut &str,
+    out: &mut String,
+    member_index: &Map<&Member, usize>,
+    span: Span,
+) -> Vec<(Ident, Ident)> {
+    let mut counts = Vec::new();
+    if !read.starts_with(':') {
+        return counts;
+    }
+    let end_spec = match read.find('}') {
+        Some(end_spec) => end_spec,
+        None => return counts,
+    };
+    let mut pieces = read[..end_spec].split('$').peekable();
+    while let Some(piece) = pieces.next() {
+        if pieces.peek().is_none() {
+            *out += piece;
+            break;
+        }
+        let start = piece
+            .trim_end_matches(|ch: char| ch.is_ascii_alphanumeric() || ch == '_')
+            .len();
+        let (before, name) = piece.split_at(start);
+        *out += before;
+        let member = match name.chars().next() {
+            Some('0'..='9') => name
+                .parse::<u32>()
+                .ok()
+                .map(|index| Member::Unnamed(Index { index, span })),
+            Some('a'..='z' | 'A'..='Z' | '_') => {
+                member_index.keys().find_map(|member| match member {
+                    Member::Named(ident) if ident.unraw() == name => Some((*member).clone()),
+                    _ => None,
+                })
+            }
+            _ => None,
+        };
+        match member.filter(|member| member_index.contains_key(member)) {
+            Some(member) => {
+                let local = match &member {
+                    Member::Unnamed(index) => format_ident!("_{}", index),
+                    Member::Named(ident) => ident.clone(),
+                };
+                let countvar = format_ident!("count__{}", local);
+                *out += &countvar.to_string();
+                counts.push((countvar, local));
+            }
+            None => *out += name,
+        }
+        out.push('$');
+    }
+    *read = &read[end_spec..];
+    counts
+}
+
+fn raw_field(fields: &[Field], ident: &Ident) -> Option<Ident> {
+    fields.iter().find_map(|field| match &field.member {
+        Member::Named(member) if member != ident && member.unraw() == ident.unraw() => {
+            Some(member.clone())
+        }
+        _ => None,
+    })
+}
+
+// The error for a placeholder naming a field the error does not have, spanned
+// to the placeholder where the toolchain can point inside a string literal.
+fn unknown_member(name: &str, fields: &[Field], span: Span) -> Error {
+    let available: Vec<String> = fields
+        .iter()
+        .map(|field| match &field.member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        })
+        .collect();
+    let mut msg = format!("no field `{}` on this error", name);
+    // Positional fields are listed below; only a name can be misspelled.
+    let suggestion = if name.starts_with(|ch: char| ch.is_ascii_digit()) {
+        None
+    } else {
+        available
+            .iter()
+            .map(|field| (edit_distance(name, field), field))
+            .filter(|&(distance, field)| distance <= cmp::max(name.len(), field.len()) / 3 + 1)
+            .min_by_key(|&(distance, _)| distance)
+    };
+    if let Some((_, field)) = suggestion {
+        msg += &format!("; did you mean `{}`?", field);
+    }
+    if available.is_empty() {
+        msg += "\nthis error has no fields";
+    } else {
+        let list: Vec<String> = available
+            .iter()
+            .map(|field| format!("`{}`", field))
+            .collect();
+        msg += &format!("\navailable fields: {}", list.join(", "));
+    }
+    Error::new(span, msg)
+}
+
+// The span of `fmt[start..end]` within the string literal, if it is written
+// without escapes so that offsets in its value are offsets in its source.
+fn placeholder_span(lit: &LitStr, start: usize, end: usize) -> Span {
+    let token = lit.token();
+    if token.to_string() != format!("\"{}\"", lit.value()) {
+        return lit.span();
+    }
+    token
+        .subspan(start + 1..end + 1)
+        .unwrap_or_else(|| lit.span())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = cmp::min(substitution, cmp::min(row[j], row[j + 1]) + 1);
+        }
+    }
+    row[b.len()]
+}
+
+fn push_counts(
+    counts: Vec<(Ident, Ident)>,
+    args: &mut TokenStream,
+    named_args: &mut Set<Ident>,
+    has_trailing_comma: &mut bool,
+    span: Span,
+) {
+    for (countvar, local) in counts {
+        if named_args.insert(countvar.clone()) {
+            if !*has_trailing_comma {
+                args.extend(quote_spanned!(span=> ,));
+            }
+            args.extend(quote_spanned!(span=> #countvar = #local));
+            *has_trailing_comma = false;
+        }
+    }
+}
+
+fn explicit_named_args(input: ParseStream) -> Result<Set<Ident>> {
+    let mut named_args = Set::new();
+
+    while !input.is_empty() {
+        if input.peek(Token![,]) && input.peek2(Ident::peek_any) && input.peek3(Token![=]) {
+            input.parse::<Token![,]>()?;
+            let ident = input.call(Ident::parse_any)?;
+            input.parse::<Token![=]>()?;
+            named_args.insert(ident);
+        } else {
+            input.parse::<TokenTree>()?;
+        }
+    }
+
+    Ok(named_args)
+}
+
+// The number of arguments after the format string which are not `name = ...`,
+// and so can be referred to as `{0}`, `{1}`, and so on.
+fn explicit_positional_args(args: &TokenStream) -> usize {
+    let mut segments = vec![Vec::new()];
+    for token in args.clone() {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => segments.push(Vec::new()),
+            _ => segments.last_mut().unwrap().push(token),
+        }
+    }
+    segments
+        .iter()
+        .filter(|segment| match segment.as_slice() {
+            [] => false,
+            [TokenTree::Ident(_), TokenTree::Punct(eq), ..] => {
+                eq.as_char() != '=' || eq.spacing() != Spacing::Alone
+            }
+            _ => true,
+        })
+        .count()
+}
+
+// The length of the expression at the start of a placeholder, which extends to
+// the closing brace or to the colon introducing a format spec, whichever comes
+// first outside of any delimiters, string literal, or path separator.
+fn expression_end(read: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut chars = read.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        if in_string {
+            match ch {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.checked_sub(1)?,
+            ':' if chars.peek().map(|&(_, next)| next) == Some(':') => {
+                chars.next();
+            }
+            ':' | '}' if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &mut token {
+                let stream = respan(group.stream(), span);
+                *group = Group::new(group.delimiter(), stream);
+            }
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
+fn take_int(read: &mut &str) -> String {
+    let mut int = String::new();
+    for (i, ch) in read.char_indices() {
+        match ch {
+            '0'..='9' => int.push(ch),
+            _ => {
+                *read = &read[i..];
+                break;
+            }
+        }
+    }
+    int
+}
+
+fn take_ident(read: &mut &str) -> Ident {
+    let mut ident = String::new();
+    let raw = read.starts_with("r#");
+    if raw {
+        ident.push_str("r#");
+        *read = &read[2..];
+    }
+    for (i, ch) in read.char_indices() {
+        match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => ident.push(ch),
+            _ => {
+                *read = &read[i..];
+                break;
+            }
+        }
+    }
+    Ident::parse_any.parse_str(&ident).unwrap()
+}
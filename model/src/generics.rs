@@ -1,3 +1,4 @@
+use crate::attr::Bound;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use std::collections::btree_map::Entry;
@@ -81,3 +82,14 @@ impl InferredBounds {
         generics.where_clause.unwrap()
     }
 }
+
+// The generics with the predicates of #[error(bound = "...")] added to their
+// where clause, for the impls whose inferred bounds they replace.
+pub fn with_explicit_bound(generics: &Generics, bound: &Bound) -> Generics {
+    let mut generics = generics.clone();
+    let where_clause = generics.make_where_clause();
+    where_clause
+        .predicates
+        .extend(bound.predicates.iter().cloned());
+    generics
+}
@@ -0,0 +1,44 @@
+//! The error model which `#[derive(Error)]` parses its input into and
+//! validates, shared by the `thiserror-impl` procedural macro and the
+//! `thiserror-internals` crate for external tooling.
+//!
+//! This is an implementation detail of thiserror, with no stable API of its
+//! own. Tools should go through `thiserror-internals` instead.
+
+#![doc(html_root_url = "https://docs.rs/thiserror-model/1.0.50")]
+#![allow(
+    clippy::blocks_in_conditions,
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::manual_find,
+    clippy::manual_let_else,
+    clippy::manual_map,
+    clippy::map_unwrap_or,
+    clippy::missing_errors_doc,
+    clippy::module_name_repetitions,
+    clippy::must_use_candidate,
+    clippy::needless_pass_by_value,
+    clippy::new_without_default,
+    clippy::option_if_let_else,
+    clippy::range_plus_one,
+    clippy::single_match_else,
+    clippy::struct_field_names,
+    clippy::too_many_lines,
+    clippy::wrong_self_convention
+)]
+
+pub mod ast;
+pub mod attr;
+pub mod config;
+#[doc(hidden)]
+pub mod fmt;
+#[doc(hidden)]
+pub mod generics;
+#[doc(hidden)]
+pub mod prop;
+#[doc(hidden)]
+pub mod span;
+#[doc(hidden)]
+pub mod up;
+#[doc(hidden)]
+pub mod valid;
@@ -0,0 +1,440 @@
+use crate::ast::{Enum, Field, Struct, Variant};
+use crate::span::MemberSpan;
+use proc_macro2::Span;
+use syn::{GenericArgument, Ident, Member, PathArguments, Type};
+
+impl Struct<'_> {
+    pub fn from_field(&self) -> Option<&Field<'_>> {
+        from_field(&self.fields)
+    }
+
+    pub fn try_from_field(&self) -> Option<&Field<'_>> {
+        try_from_field(&self.fields)
+    }
+
+    pub fn from_value_field(&self) -> Option<&Field<'_>> {
+        from_value_field(&self.fields)
+    }
+
+    pub fn source_field(&self) -> Option<&Field<'_>> {
+        source_field(&self.fields)
+    }
+
+    pub fn transparent_field(&self) -> Option<&Field<'_>> {
+        transparent_field(&self.fields)
+    }
+
+    pub fn backtrace_field(&self) -> Option<&Field<'_>> {
+        backtrace_field(&self.fields)
+    }
+
+    pub fn related_field(&self) -> Option<&Field<'_>> {
+        related_field(&self.fields)
+    }
+
+    pub fn errors_field(&self) -> Option<&Field<'_>> {
+        self.fields
+            .iter()
+            .find(|field| field.attrs.errors.is_some())
+    }
+
+    pub fn error_id_field(&self) -> Option<&Field<'_>> {
+        error_id_field(&self.fields)
+    }
+
+    pub fn distinct_backtrace_field(&self) -> Option<&Field<'_>> {
+        let backtrace_field = self.backtrace_field()?;
+        distinct_backtrace_field(backtrace_field, self.from_field())
+    }
+
+    pub fn has_status(&self) -> bool {
+        self.attrs.status.is_some()
+    }
+
+    pub fn has_code(&self) -> bool {
+        self.attrs.code.is_some()
+    }
+
+    pub fn has_grpc(&self) -> bool {
+        self.attrs.grpc.is_some()
+    }
+
+    pub fn has_io_kind(&self) -> bool {
+        self.attrs.io_kind.is_some()
+    }
+
+    pub fn has_py(&self) -> bool {
+        self.attrs.py.is_some()
+    }
+}
+
+impl Enum<'_> {
+    pub fn has_source(&self) -> bool {
+        self.variants
+            .iter()
+            .any(|variant| variant.source_field().is_some() || variant.attrs.transparent.is_some())
+    }
+
+    pub fn has_backtrace(&self) -> bool {
+        self.variants
+            .iter()
+            .any(|variant| variant.backtrace_field().is_some())
+    }
+
+    pub fn has_display(&self) -> bool {
+        self.attrs.display.is_some()
+            || self.attrs.transparent.is_some()
+            || self.attrs.auto_transparent.is_some()
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.display.is_some() || variant.attrs.fmt.is_some())
+            || self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.transparent.is_some())
+    }
+
+    pub fn has_status(&self) -> bool {
+        self.attrs.status.is_some()
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.status.is_some())
+    }
+
+    pub fn has_code(&self) -> bool {
+        self.attrs.code.is_some()
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.code.is_some())
+    }
+
+    pub fn has_grpc(&self) -> bool {
+        self.attrs.grpc.is_some()
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.grpc.is_some())
+    }
+
+    pub fn has_io_kind(&self) -> bool {
+        self.attrs.io_kind.is_some()
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.io_kind.is_some())
+    }
+
+    pub fn has_py(&self) -> bool {
+        self.attrs.py.is_some()
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.py.is_some())
+    }
+}
+
+impl Variant<'_> {
+    pub fn from_field(&self) -> Option<&Field<'_>> {
+        from_field(&self.fields)
+    }
+
+    pub fn try_from_field(&self) -> Option<&Field<'_>> {
+        try_from_field(&self.fields)
+    }
+
+    pub fn from_value_field(&self) -> Option<&Field<'_>> {
+        from_value_field(&self.fields)
+    }
+
+    pub fn source_field(&self) -> Option<&Field<'_>> {
+        source_field(&self.fields)
+    }
+
+    pub fn transparent_field(&self) -> Option<&Field<'_>> {
+        transparent_field(&self.fields)
+    }
+
+    pub fn backtrace_field(&self) -> Option<&Field<'_>> {
+        backtrace_field(&self.fields)
+    }
+
+    pub fn related_field(&self) -> Option<&Field<'_>> {
+        related_field(&self.fields)
+    }
+
+    pub fn error_id_field(&self) -> Option<&Field<'_>> {
+        error_id_field(&self.fields)
+    }
+
+    pub fn distinct_backtrace_field(&self) -> Option<&Field<'_>> {
+        let backtrace_field = self.backtrace_field()?;
+        distinct_backtrace_field(backtrace_field, self.from_field())
+    }
+
+    // The fields of an #[error(panic)] variant which receive the panic's
+    // payload and, if there is one, its location: the first and second field
+    // of a tuple variant, or else those named `payload` or `message`, and
+    // `location`.
+    pub fn panic_fields(&self) -> Option<(&Field<'_>, Option<&Field<'_>>)> {
+        let is_named = |field: &Field, names: &[&str]| match &field.member {
+            Member::Named(ident) => names.iter().any(|name| ident == name),
+            Member::Unnamed(_) => true,
+        };
+        let is_payload = |field: &&Field| is_named(field, &["payload", "message"]);
+        let is_location = |field: &&Field| is_named(field, &["location"]);
+        match self.fields.as_slice() {
+            [payload] if is_payload(&payload) => Some((payload, None)),
+            [first, second] if is_payload(&first) && is_location(&second) => {
+                Some((first, Some(second)))
+            }
+            [first, second] if is_location(&first) && is_payload(&second) => {
+                Some((second, Some(first)))
+            }
+            _ => None,
+        }
+    }
+
+    // The payload held in the Box of an #[error(boxed)] variant.
+    pub fn boxed_payload_type(&self) -> Option<&Type> {
+        self.attrs.boxed?;
+        match self.fields.as_slice() {
+            [field] => type_parameter_of(field.ty, "Box"),
+            _ => None,
+        }
+    }
+
+    // The name of the payload struct which the derive generates for an
+    // #[error(boxed { ... })] variant, as written in the Box.
+    pub fn boxed_payload_ident(&self) -> Option<&Ident> {
+        self.attrs.boxed_payload.as_ref()?;
+        match self.boxed_payload_type()? {
+            Type::Path(ty) if ty.qself.is_none() => ty.path.get_ident(),
+            _ => None,
+        }
+    }
+
+    // The error held in the Box of an #[error(catch_all)] variant, typically
+    // `dyn Error + Send + Sync`.
+    pub fn catch_all_type(&self) -> Option<&Type> {
+        self.attrs.catch_all?;
+        match self.fields.as_slice() {
+            [field] => type_parameter_of(field.ty, "Box"),
+            _ => None,
+        }
+    }
+}
+
+impl Field<'_> {
+    pub fn is_backtrace(&self) -> bool {
+        type_is_backtrace(self.ty)
+    }
+
+    // Whether the field holds a std Backtrace, possibly in an Option, rather
+    // than the source it was forwarded from or a type of its own given by
+    // #[backtrace(capture = ...)].
+    pub fn holds_backtrace(&self) -> bool {
+        type_is_backtrace(type_parameter_of(self.ty, "Option").unwrap_or(self.ty))
+    }
+
+    pub fn is_option(&self) -> bool {
+        type_parameter_of(self.ty, "Option").is_some()
+    }
+
+    // Whether the field is filled in when the error is constructed, by
+    // #[timestamp], #[capture(...)], #[context_frames], or #[error_id], or as
+    // a PhantomData, rather than passed in.
+    pub fn is_captured(&self) -> bool {
+        self.attrs.timestamp.is_some()
+            || self.attrs.context_frames.is_some()
+            || self.attrs.capture.is_some()
+            || self.attrs.error_id.is_some()
+            || self.is_phantom()
+    }
+
+    // A marker field, declared as `PhantomData<T>` or marked
+    // #[throws(phantom)], which holds nothing worth passing in.
+    pub fn is_phantom(&self) -> bool {
+        self.attrs.phantom.is_some() || type_is_phantom_data(self.ty)
+    }
+
+    // The type held in the Arc of an #[error(arc_source)] field, which is what
+    // From impls and throw methods accept.
+    pub fn arc_source_type(&self) -> Option<&Type> {
+        self.attrs.arc_source?;
+        type_parameter_of(self.ty, "Arc")
+    }
+
+    // The type which the From impls and throw traits of a source field are
+    // implemented for: the one given by #[source(ty = ...)], else the type in
+    // the Arc of an #[error(arc_source)] field, else the field's own type.
+    pub fn source_impl_type(&self) -> &Type {
+        (self.attrs.source_ty.as_ref())
+            .or_else(|| self.arc_source_type())
+            .unwrap_or(self.ty)
+    }
+
+    // The trait object held in a source field declared as `Arc<dyn Error>`,
+    // which is already shared, and which From impls and throw methods fill in
+    // by arcing any error boxed into it.
+    pub fn arc_dyn_source_type(&self) -> Option<&Type> {
+        match type_parameter_of(self.ty, "Arc")? {
+            ty @ Type::TraitObject(_) => Some(ty),
+            _ => None,
+        }
+    }
+
+    pub fn source_span(&self) -> Span {
+        if let Some(source_attr) = &self.attrs.source {
+            source_attr.path().get_ident().unwrap().span()
+        } else if let Some(from_attr) = &self.attrs.from {
+            from_attr.path().get_ident().unwrap().span()
+        } else {
+            self.member.member_span()
+        }
+    }
+}
+
+fn from_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    for field in fields {
+        if field.attrs.from.is_some() {
+            return Some(field);
+        }
+    }
+    None
+}
+
+fn try_from_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    for field in fields {
+        if field.attrs.try_from.is_some() {
+            return Some(field);
+        }
+    }
+    None
+}
+
+fn from_value_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    for field in fields {
+        if field.attrs.from_value.is_some() {
+            return Some(field);
+        }
+    }
+    None
+}
+
+pub fn source_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    for field in fields {
+        if field.attrs.from.is_some() || field.attrs.source.is_some() {
+            return Some(field);
+        }
+    }
+    for field in fields {
+        match &field.member {
+            Member::Named(ident) if ident == "source" => return Some(field),
+            _ => {}
+        }
+    }
+    None
+}
+
+// The field which a transparent error delegates to: its only field, or else
+// the source among several, the others being context carried alongside.
+pub fn transparent_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    match fields {
+        [field] => Some(field),
+        _ => source_field(fields),
+    }
+}
+
+fn related_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    for field in fields {
+        if field.attrs.related.is_some() {
+            return Some(field);
+        }
+    }
+    None
+}
+
+fn error_id_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    for field in fields {
+        if field.attrs.error_id.is_some() {
+            return Some(field);
+        }
+    }
+    None
+}
+
+pub fn backtrace_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    for field in fields {
+        if field.attrs.backtrace.is_some() {
+            return Some(field);
+        }
+    }
+    for field in fields {
+        if field.is_backtrace() {
+            return Some(field);
+        }
+    }
+    None
+}
+
+// The #[backtrace] field, if it is not the same as the #[from] field.
+fn distinct_backtrace_field<'a, 'b>(
+    backtrace_field: &'a Field<'b>,
+    from_field: Option<&Field>,
+) -> Option<&'a Field<'b>> {
+    if from_field.map_or(false, |from_field| {
+        from_field.member == backtrace_field.member
+    }) {
+        None
+    } else {
+        Some(backtrace_field)
+    }
+}
+
+fn type_is_backtrace(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return false,
+    };
+
+    let last = path.segments.last().unwrap();
+    last.ident == "Backtrace" && last.arguments.is_empty()
+}
+
+fn type_is_phantom_data(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return false,
+    };
+
+    path.segments.last().unwrap().ident == "PhantomData"
+}
+
+pub fn type_parameter_of<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return None,
+    };
+
+    let last = path.segments.last().unwrap();
+    if last.ident != wrapper {
+        return None;
+    }
+
+    let bracketed = match &last.arguments {
+        PathArguments::AngleBracketed(bracketed) => bracketed,
+        _ => return None,
+    };
+
+    if bracketed.args.len() != 1 {
+        return None;
+    }
+
+    match &bracketed.args[0] {
+        GenericArgument::Type(arg) => Some(arg),
+        _ => None,
+    }
+}
@@ -0,0 +1,105 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Up;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Member, Path};
+
+pub fn struct_up_impls(input: &Struct) -> TokenStream {
+    let ty = &input.ident;
+    input
+        .attrs
+        .ups
+        .iter()
+        .map(|up| {
+            let parent = parent(&up.variant);
+            let variant = &up.variant;
+            quote! {
+                #[allow(unused_qualifications)]
+                impl ::core::convert::From<#ty> for #parent {
+                    #[allow(deprecated)]
+                    fn from(error: #ty) -> Self {
+                        #variant(error)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+// One From impl per parent type, converting each variant into the parent
+// variant it names itself, or else into the one named on top of the enum.
+pub fn enum_up_impls(input: &Enum) -> TokenStream {
+    let ty = &input.ident;
+    let mut impls = TokenStream::new();
+    for parent in parents(input) {
+        let type_up = find(&input.attrs.ups, &parent);
+        let arms = input.variants.iter().filter_map(|variant| {
+            let up = find(&variant.attrs.ups, &parent)?;
+            let ident = &variant.ident;
+            let pat = match &variant.fields[0].member {
+                Member::Named(member) => quote!({ #member: __field }),
+                Member::Unnamed(_) => quote!((__field)),
+            };
+            let target = &up.variant;
+            Some(quote! {
+                #ty::#ident #pat => #target(__field),
+            })
+        });
+        let fallback = type_up.map(|up| {
+            let target = &up.variant;
+            quote! {
+                #[allow(unreachable_patterns)]
+                error => #target(error),
+            }
+        });
+        impls.extend(quote! {
+            #[allow(unused_qualifications)]
+            impl ::core::convert::From<#ty> for #parent {
+                #[allow(deprecated)]
+                fn from(error: #ty) -> Self {
+                    match error {
+                        #(#arms)*
+                        #fallback
+                    }
+                }
+            }
+        });
+    }
+    impls
+}
+
+// The parent types named by the enum and its variants, in order of first
+// mention.
+pub fn parents(input: &Enum) -> Vec<Path> {
+    let mut parents: Vec<Path> = Vec::new();
+    let ups = input
+        .attrs
+        .ups
+        .iter()
+        .chain(input.variants.iter().flat_map(|variant| &variant.attrs.ups));
+    for up in ups {
+        let parent = parent(&up.variant);
+        if !parents.iter().any(|seen| same_path(seen, &parent)) {
+            parents.push(parent);
+        }
+    }
+    parents
+}
+
+// The #[error(up(...))] of the given attributes into the given parent type.
+pub fn find<'a, 'b>(ups: &'a [Up<'b>], parent: &Path) -> Option<&'a Up<'b>> {
+    ups.iter()
+        .find(|up| same_path(&self::parent(&up.variant), parent))
+}
+
+// The path of the parent type: the variant's path without the variant.
+pub fn parent(variant: &Path) -> Path {
+    let mut parent = variant.clone();
+    parent.segments.pop();
+    parent.segments.pop_punct();
+    parent
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    a.to_token_stream().to_string() == b.to_token_stream().to_string()
+}
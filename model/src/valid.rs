@@ -0,0 +1,1897 @@
+use crate::ast::{Enum, Field, Input, Struct, Variant};
+use crate::attr::{Attrs, EqStrategy, RetryClass, Up};
+use crate::{prop, up};
+use quote::ToTokens;
+use std::collections::{BTreeMap as Map, BTreeSet as Set};
+use syn::{
+    Attribute, Error, GenericArgument, Generics, LitStr, Member, PathArguments, Result, Type,
+};
+
+const TRANSPARENT_FIELDS: &str =
+    "#[error(transparent)] requires exactly one field, or else a source field to delegate to among several";
+
+impl Input<'_> {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Input::Struct(input) => input.validate(),
+            Input::Enum(input) => input.validate(),
+        }
+    }
+}
+
+impl Struct<'_> {
+    fn validate(&self) -> Result<()> {
+        check_non_field_attrs(&self.attrs)?;
+        if let Some(subset) = self.attrs.subsets.first() {
+            return Err(Error::new_spanned(
+                subset.original,
+                "#[naur(subset(...))] is only supported on enums",
+            ));
+        }
+        if let Some(same_as) = &self.attrs.same_as {
+            return Err(Error::new_spanned(
+                same_as,
+                "#[error(same_as = ...)] is only supported on enum variants",
+            ));
+        }
+        if let Some(kind) = self.attrs.kind {
+            return Err(Error::new_spanned(
+                kind,
+                "#[naur(kind)] is only supported on enums",
+            ));
+        }
+        if let Some(ffi) = self.attrs.ffi {
+            return Err(Error::new_spanned(
+                ffi,
+                "#[naur(ffi)] is only supported on enums",
+            ));
+        }
+        if let Some(auto_transparent) = self.attrs.auto_transparent {
+            return Err(Error::new_spanned(
+                auto_transparent,
+                "#[naur(auto_transparent)] is only supported on enums",
+            ));
+        }
+        if let Some(eq) = &self.attrs.eq {
+            let strategy = match eq.strategy {
+                EqStrategy::Kind => Some("kind"),
+                EqStrategy::Code => Some("code"),
+                EqStrategy::Fields => None,
+            };
+            if let Some(strategy) = strategy {
+                return Err(Error::new_spanned(
+                    eq.original,
+                    format!("#[naur(eq = \"{}\")] is only supported on enums", strategy),
+                ));
+            }
+        }
+        check_aggregate(&self.attrs, self.generics)?;
+        check_alt_chain(&self.attrs, self.generics)?;
+        check_alt(&self.attrs, self.attrs.alt_chain)?;
+        check_debug_chain(&self.attrs, self.generics)?;
+        check_context_struct(&self.attrs, self.generics)?;
+        check_max_size(&self.attrs, self.generics)?;
+        check_codes(&self.attrs, &[])?;
+        check_arbitrary(&self.attrs, self.generics)?;
+        check_minimal(&self.attrs, None)?;
+        check_minimal_provide(&self.attrs, &self.fields)?;
+        check_retry_source(&self.attrs, &self.fields)?;
+        if let (Some(no_source), Some(_)) = (self.attrs.no_source, self.source_field()) {
+            return Err(Error::new_spanned(
+                no_source,
+                "cannot have both #[error(no_source)] and a source field",
+            ));
+        }
+        if let Some(context) = self.attrs.context {
+            return Err(Error::new_spanned(
+                context,
+                "#[error(context)] is only supported on enum variants",
+            ));
+        }
+        if let Some(boxed) = &self.attrs.boxed {
+            return Err(Error::new_spanned(
+                boxed.original,
+                "#[error(boxed)] is only supported on enum variants",
+            ));
+        }
+        if let Some(catch_all) = &self.attrs.catch_all {
+            return Err(Error::new_spanned(
+                catch_all.original,
+                "#[error(catch_all)] is only supported on enum variants",
+            ));
+        }
+        if let Some(panic) = self.attrs.panic {
+            return Err(Error::new_spanned(
+                panic,
+                "#[error(panic)] is only supported on enum variants",
+            ));
+        }
+        if let Some(prefix) = &self.attrs.prefix {
+            return Err(Error::new_spanned(
+                prefix,
+                "#[error(prefix = ...)] is only supported on enums; write the prefix into the struct's message",
+            ));
+        }
+        if let Some(default) = &self.attrs.default_display {
+            return Err(Error::new_spanned(
+                &default.template,
+                "#[error(default = ...)] is only supported on enums",
+            ));
+        }
+        if let Some(no_prefix) = self.attrs.no_prefix {
+            return Err(Error::new_spanned(
+                no_prefix,
+                "#[error(no_prefix)] is only supported on enum variants",
+            ));
+        }
+        check_ups(&self.attrs.ups, self.generics)?;
+        if let Some(transparent) = self.attrs.transparent {
+            if let Some(templates) = self.attrs.templates {
+                return Err(Error::new_spanned(
+                    templates,
+                    "#[naur(templates)] is not supported on transparent errors, which have no message template of their own",
+                ));
+            }
+            if self.transparent_field().is_none() {
+                return Err(Error::new_spanned(transparent.original, TRANSPARENT_FIELDS));
+            }
+            if let [field] = self.fields.as_slice() {
+                if let Some(source) = field.attrs.source {
+                    return Err(Error::new_spanned(
+                        source,
+                        "transparent error struct can't contain #[source]",
+                    ));
+                }
+            }
+            if let Some(sensitive) = self.fields.iter().find_map(|f| f.attrs.sensitive.as_ref()) {
+                return Err(Error::new_spanned(
+                    sensitive.original,
+                    "#[sensitive] is not supported on transparent errors, whose message is the field's own",
+                ));
+            }
+        }
+        check_field_attrs(&self.fields)?;
+        check_arc_source(&self.attrs, &self.fields)?;
+        check_diagnostic_fields(&self.fields)?;
+        if let Some(errors) = self.errors_field().and_then(|field| field.attrs.errors) {
+            if self.attrs.transparent.is_some() || self.source_field().is_some() {
+                return Err(Error::new_spanned(
+                    errors,
+                    "#[errors] is not supported on an error with a source of its own; Error::source() returns the first of the errors",
+                ));
+            }
+        }
+        for field in &self.fields {
+            field.validate()?;
+            if let Some(from_any) = field.attrs.from_any {
+                return Err(Error::new_spanned(
+                    from_any,
+                    "#[naur(from_any)] is only supported on enum variants",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Enum<'_> {
+    fn validate(&self) -> Result<()> {
+        check_non_field_attrs(&self.attrs)?;
+        check_aggregate(&self.attrs, self.generics)?;
+        check_alt_chain(&self.attrs, self.generics)?;
+        check_debug_chain(&self.attrs, self.generics)?;
+        check_context_struct(&self.attrs, self.generics)?;
+        for variant in &self.variants {
+            check_context_struct(&variant.attrs, self.generics)?;
+        }
+        check_max_size(&self.attrs, self.generics)?;
+        check_arbitrary(&self.attrs, self.generics)?;
+        check_eq_code(self)?;
+        check_codes(&self.attrs, &self.variants)?;
+        check_error_id_types(self)?;
+        check_prefix(self)?;
+        check_default_display(self)?;
+        for variant in &self.variants {
+            check_alt(&variant.attrs, self.attrs.alt_chain)?;
+        }
+        if let Some(ffi) = self.attrs.ffi {
+            if self.variants.is_empty() {
+                return Err(Error::new_spanned(
+                    ffi,
+                    "#[naur(ffi)] requires at least one variant to give a code",
+                ));
+            }
+        }
+        check_minimal(
+            &self.attrs,
+            self.variants.iter().map(|variant| &variant.attrs),
+        )?;
+        for variant in &self.variants {
+            check_minimal_provide(&self.attrs, &variant.fields)?;
+        }
+        if let Some(fmt) = &self.attrs.fmt {
+            return Err(Error::new_spanned(
+                fmt,
+                "not expected here; the #[error(fmt = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(i18n) = &self.attrs.i18n {
+            return Err(Error::new_spanned(
+                i18n,
+                "not expected here; the #[error(i18n = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(help) = &self.attrs.help {
+            return Err(Error::new_spanned(
+                &help.fmt,
+                "not expected here; the #[error(help = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(alt) = &self.attrs.alt {
+            return Err(Error::new_spanned(
+                &alt.fmt,
+                "not expected here; the #[error(alt = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(same_as) = &self.attrs.same_as {
+            return Err(Error::new_spanned(
+                same_as,
+                "not expected here; the #[error(same_as = ...)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(suggestion) = &self.attrs.suggestion {
+            return Err(Error::new_spanned(
+                &suggestion.fmt,
+                "not expected here; the #[error(suggestion = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(url) = &self.attrs.url {
+            return Err(Error::new_spanned(
+                &url.fmt,
+                "not expected here; the #[error(url = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(context) = self.attrs.context {
+            return Err(Error::new_spanned(
+                context,
+                "not expected here; the #[error(context)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(boxed) = &self.attrs.boxed {
+            return Err(Error::new_spanned(
+                boxed.original,
+                "not expected here; the #[error(boxed)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(catch_all) = &self.attrs.catch_all {
+            return Err(Error::new_spanned(
+                catch_all.original,
+                "not expected here; the #[error(catch_all)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(panic) = self.attrs.panic {
+            return Err(Error::new_spanned(
+                panic,
+                "not expected here; the #[error(panic)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(no_prefix) = self.attrs.no_prefix {
+            return Err(Error::new_spanned(
+                no_prefix,
+                "not expected here; the #[error(no_prefix)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(no_source) = self.attrs.no_source {
+            return Err(Error::new_spanned(
+                no_source,
+                "not expected here; the #[error(no_source)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(retry) = &self.attrs.retry {
+            if retry.class == RetryClass::Source {
+                return Err(Error::new_spanned(
+                    retry.original,
+                    "not expected here; the #[error(transient = source)] attribute belongs on top of a struct or an enum variant",
+                ));
+            }
+        }
+        if let Some(help) = self.attrs.diagnostic.as_ref().and_then(|d| d.help.as_ref()) {
+            return Err(Error::new_spanned(
+                &help.fmt,
+                "not expected here; #[diagnostic(help(...))] belongs on top of a struct or an enum variant",
+            ));
+        }
+        let has_display = self.has_display();
+        for variant in &self.variants {
+            variant.validate()?;
+            if has_display
+                && variant.attrs.display.is_none()
+                && variant.attrs.transparent.is_none()
+                && variant.attrs.fmt.is_none()
+            {
+                return Err(Error::new_spanned(
+                    variant.original,
+                    "missing #[error(\"...\")] display attribute",
+                ));
+            }
+        }
+        let mut from_types = Set::new();
+        for variant in &self.variants {
+            if let Some(from_field) = variant.from_field() {
+                let repr = from_field.ty.to_token_stream().to_string();
+                if !from_types.insert(repr) {
+                    return Err(Error::new_spanned(
+                        from_field.original,
+                        "cannot derive From because another variant has the same source type",
+                    ));
+                }
+            }
+        }
+        if !self.generics.params.is_empty() {
+            let boxed_payload = self.variants.iter().find_map(|variant| {
+                variant.attrs.boxed_payload.as_ref()?;
+                variant.attrs.boxed.as_ref()
+            });
+            if let Some(boxed) = boxed_payload {
+                return Err(Error::new_spanned(
+                    boxed.original,
+                    "#[error(boxed { ... })] is not supported on generic enums; declare the payload struct and write #[error(boxed)]",
+                ));
+            }
+        }
+        for variant in &self.variants {
+            if let Some(payload) = variant.boxed_payload_type() {
+                let repr = payload.to_token_stream().to_string();
+                if !from_types.insert(repr) {
+                    return Err(Error::new_spanned(
+                        payload,
+                        "cannot derive From because another variant has the same source type",
+                    ));
+                }
+            }
+        }
+        for variant in &self.variants {
+            if let Some(from_value_field) = variant.from_value_field() {
+                let repr = from_value_field.ty.to_token_stream().to_string();
+                if !from_types.insert(repr) {
+                    return Err(Error::new_spanned(
+                        from_value_field.attrs.from_value,
+                        "cannot derive From because another variant converts from the same type",
+                    ));
+                }
+            }
+        }
+        for variant in &self.variants {
+            if let Some(try_from_field) = variant.try_from_field() {
+                let repr = try_from_field.ty.to_token_stream().to_string();
+                if !from_types.insert(repr) {
+                    return Err(Error::new_spanned(
+                        try_from_field.original,
+                        "cannot derive TryFrom because another variant converts from the same type",
+                    ));
+                }
+            }
+        }
+        self.validate_typed_fields()?;
+        self.validate_from_any()?;
+        self.validate_catch_all()?;
+        self.validate_panic()?;
+        self.validate_poison()?;
+        self.validate_context()?;
+        self.validate_ups()?;
+        self.validate_subsets()
+    }
+
+    fn validate_from_any(&self) -> Result<()> {
+        let mut catch_all = None;
+        for variant in &self.variants {
+            for field in &variant.fields {
+                let from_any = match field.attrs.from_any {
+                    Some(from_any) => from_any,
+                    None => continue,
+                };
+                if catch_all.is_some() {
+                    return Err(Error::new_spanned(
+                        from_any,
+                        "only one variant can catch all errors with #[naur(from_any)]",
+                    ));
+                }
+                if field.attrs.from.is_none() {
+                    return Err(Error::new_spanned(
+                        from_any,
+                        "#[naur(from_any)] requires the field to also be #[from]",
+                    ));
+                }
+                if !self.generics.params.is_empty() {
+                    return Err(Error::new_spanned(
+                        from_any,
+                        "#[naur(from_any)] is not supported on generic enums",
+                    ));
+                }
+                catch_all = Some(from_any);
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_catch_all(&self) -> Result<()> {
+        let mut catch_all_variant = None;
+        for variant in &self.variants {
+            let catch_all = match &variant.attrs.catch_all {
+                Some(catch_all) => catch_all,
+                None => continue,
+            };
+            if catch_all_variant.is_some() {
+                return Err(Error::new_spanned(
+                    catch_all.original,
+                    "only one variant can be #[error(catch_all)]",
+                ));
+            }
+            if !self.generics.params.is_empty() {
+                return Err(Error::new_spanned(
+                    catch_all.original,
+                    "#[error(catch_all)] is not supported on generic enums",
+                ));
+            }
+            catch_all_variant = Some(variant);
+        }
+        Ok(())
+    }
+
+    fn validate_panic(&self) -> Result<()> {
+        let mut panic_variant = None;
+        for variant in &self.variants {
+            let panic = match variant.attrs.panic {
+                Some(panic) => panic,
+                None => continue,
+            };
+            if panic_variant.is_some() {
+                return Err(Error::new_spanned(
+                    panic,
+                    "only one variant can be #[error(panic)]",
+                ));
+            }
+            if variant.attrs.transparent.is_some() {
+                return Err(Error::new_spanned(
+                    panic,
+                    "cannot have both #[error(transparent)] and #[error(panic)]",
+                ));
+            }
+            if variant.panic_fields().is_none() {
+                return Err(Error::new_spanned(
+                    panic,
+                    "#[error(panic)] requires the variant to hold the panic's payload, as a String or a Box<dyn Any + Send>, optionally followed by its location; with named fields, call them `payload` or `message`, and `location`",
+                ));
+            }
+            panic_variant = Some(variant);
+        }
+        Ok(())
+    }
+
+    fn validate_poison(&self) -> Result<()> {
+        let mut poison_variant = None;
+        for variant in &self.variants {
+            let poison = match variant.fields.iter().find_map(|field| field.attrs.poison) {
+                Some(poison) => poison,
+                None => continue,
+            };
+            if poison_variant.is_some() {
+                return Err(Error::new_spanned(
+                    poison,
+                    "only one variant can have a #[source(poison)], as each converts from every PoisonError",
+                ));
+            }
+            poison_variant = Some(variant);
+        }
+        Ok(())
+    }
+
+    fn validate_context(&self) -> Result<()> {
+        let mut context_variant = None;
+        for variant in &self.variants {
+            let context = match variant.attrs.context {
+                Some(context) => context,
+                None => continue,
+            };
+            if context_variant.is_some() {
+                return Err(Error::new_spanned(
+                    context,
+                    "only one variant can receive errors through #[error(context)]",
+                ));
+            }
+            if !self.generics.params.is_empty() {
+                return Err(Error::new_spanned(
+                    context,
+                    "#[error(context)] is not supported on generic enums",
+                ));
+            }
+            if variant.attrs.transparent.is_some() {
+                return Err(Error::new_spanned(
+                    context,
+                    "cannot have both #[error(transparent)] and #[error(context)]",
+                ));
+            }
+            let has_named_source = variant
+                .source_field()
+                .map_or(false, |source| matches!(source.member, Member::Named(_)));
+            let has_msg = variant
+                .fields
+                .iter()
+                .any(|field| matches!(&field.member, Member::Named(ident) if ident == "msg"));
+            if variant.fields.len() != 2 || !has_named_source || !has_msg {
+                return Err(Error::new_spanned(
+                    context,
+                    "#[error(context)] requires the variant to have exactly two named fields: a source, such as `source: Box<dyn Error + Send + Sync>`, and `msg: String`",
+                ));
+            }
+            context_variant = Some(variant);
+        }
+        Ok(())
+    }
+
+    fn validate_ups(&self) -> Result<()> {
+        check_ups(&self.attrs.ups, self.generics)?;
+        for variant in &self.variants {
+            check_ups(&variant.attrs.ups, self.generics)?;
+            if let Some(up) = variant.attrs.ups.first() {
+                if variant.fields.len() != 1 {
+                    return Err(Error::new_spanned(
+                        up.original,
+                        "#[error(up(...))] on a variant requires it to have exactly one field, which becomes the payload of the parent variant",
+                    ));
+                }
+            }
+        }
+        for parent in up::parents(self) {
+            if up::find(&self.attrs.ups, &parent).is_some() {
+                continue;
+            }
+            for variant in &self.variants {
+                if up::find(&variant.attrs.ups, &parent).is_none() {
+                    return Err(Error::new_spanned(
+                        variant.original,
+                        format!(
+                            "missing #[error(up(...))] into `{}`; add one to this variant, or one on top of the enum for the variants not converted individually",
+                            parent.to_token_stream().to_string().replace(' ', ""),
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_typed_fields(&self) -> Result<()> {
+        if self.attrs.typed_fields.is_none() {
+            return Ok(());
+        }
+        let mut types = Map::new();
+        for variant in &self.variants {
+            for field in &variant.fields {
+                let ident = match &field.member {
+                    Member::Named(ident) => ident,
+                    Member::Unnamed(_) => continue,
+                };
+                let repr = field.ty.to_token_stream().to_string();
+                let first = types.entry(ident.to_string()).or_insert(repr.clone());
+                if *first != repr {
+                    return Err(Error::new_spanned(
+                        field.original,
+                        format!(
+                            "#[naur(typed_fields)] requires every field named `{}` to have the same type",
+                            ident,
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_subsets(&self) -> Result<()> {
+        for subset in &self.attrs.subsets {
+            if !self.generics.params.is_empty() {
+                return Err(Error::new_spanned(
+                    subset.original,
+                    "#[naur(subset(...))] is not supported on generic enums",
+                ));
+            }
+            let mut listed = Set::new();
+            for ident in &subset.variants {
+                if !listed.insert(ident) {
+                    return Err(Error::new_spanned(ident, "duplicate variant in subset"));
+                }
+                if ident != "Internal" && !self.variants.iter().any(|v| v.ident == *ident) {
+                    return Err(Error::new_spanned(
+                        ident,
+                        format!("no variant named `{}` in `{}`", ident, self.ident),
+                    ));
+                }
+            }
+            if !subset.variants.iter().any(|ident| ident == "Internal") {
+                return Err(Error::new_spanned(
+                    &subset.ident,
+                    "subset must list an `Internal` variant for the unlisted variants to fold into",
+                ));
+            }
+            if let Some(internal) = self.variants.iter().find(|v| v.ident == "Internal") {
+                if !internal.fields.is_empty() {
+                    return Err(Error::new_spanned(
+                        internal.original,
+                        "the `Internal` variant must not have fields to be used as the fallback of a subset",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Variant<'_> {
+    fn validate(&self) -> Result<()> {
+        check_non_field_attrs(&self.attrs)?;
+        check_container_attrs(&self.attrs)?;
+        check_retry_source(&self.attrs, &self.fields)?;
+        if let (Some(no_source), Some(_)) = (self.attrs.no_source, self.source_field()) {
+            return Err(Error::new_spanned(
+                no_source,
+                "cannot have both #[error(no_source)] and a source field",
+            ));
+        }
+        if let Some(boxed) = &self.attrs.boxed {
+            if self.attrs.display.is_some() || self.attrs.fmt.is_some() {
+                return Err(Error::new_spanned(
+                    boxed.original,
+                    "cannot have both #[error(boxed)] and a message; the message is the payload's own",
+                ));
+            }
+            if let Some(transparent) = &self.attrs.transparent {
+                if !std::ptr::eq(transparent.original, boxed.original) {
+                    return Err(Error::new_spanned(
+                        transparent.original,
+                        "cannot have both #[error(transparent)] and #[error(boxed)]",
+                    ));
+                }
+            }
+            if self.boxed_payload_type().is_none() {
+                return Err(Error::new_spanned(
+                    self.original,
+                    "#[error(boxed)] requires exactly one field holding the payload in a Box, such as `Timeout(Box<TimeoutError>)`",
+                ));
+            }
+            if let Some(payload) = &self.attrs.boxed_payload {
+                if payload.message.is_none() {
+                    return Err(Error::new_spanned(
+                        boxed.original,
+                        "#[error(boxed { ... })] requires a message, which becomes the generated payload's",
+                    ));
+                }
+                if self.boxed_payload_ident().is_none() {
+                    return Err(Error::new_spanned(
+                        self.original,
+                        "#[error(boxed { ... })] requires the Box to name the payload struct to generate, such as `Timeout(Box<TimeoutDetails>)`",
+                    ));
+                }
+            }
+        }
+        if let Some(catch_all) = &self.attrs.catch_all {
+            if self.attrs.boxed.is_some() {
+                return Err(Error::new_spanned(
+                    catch_all.original,
+                    "cannot have both #[error(catch_all)] and #[error(boxed)]",
+                ));
+            }
+            if self.catch_all_type().is_none() {
+                return Err(Error::new_spanned(
+                    self.original,
+                    "#[error(catch_all)] requires exactly one field holding the error in a Box, such as `Other(Box<dyn Error + Send + Sync>)`",
+                ));
+            }
+        }
+        if self.attrs.transparent.is_some() {
+            if self.transparent_field().is_none() {
+                return Err(Error::new_spanned(self.original, TRANSPARENT_FIELDS));
+            }
+            if let [field] = self.fields.as_slice() {
+                if let Some(source) = field.attrs.source {
+                    return Err(Error::new_spanned(
+                        source,
+                        "transparent variant can't contain #[source]",
+                    ));
+                }
+            }
+            if let Some(sensitive) = self.fields.iter().find_map(|f| f.attrs.sensitive.as_ref()) {
+                return Err(Error::new_spanned(
+                    sensitive.original,
+                    "#[sensitive] is not supported on transparent errors, whose message is the field's own",
+                ));
+            }
+        }
+        check_field_attrs(&self.fields)?;
+        check_arc_source(&self.attrs, &self.fields)?;
+        check_diagnostic_fields(&self.fields)?;
+        if let Some(errors) = self.fields.iter().find_map(|field| field.attrs.errors) {
+            return Err(Error::new_spanned(
+                errors,
+                "#[errors] is only supported on fields of a struct",
+            ));
+        }
+        for field in &self.fields {
+            field.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Field<'_> {
+    fn validate(&self) -> Result<()> {
+        check_container_attrs(&self.attrs)?;
+        let throws = (self.attrs.trace.as_ref().map(|hook| hook.original))
+            .or(self.attrs.log.as_ref().map(|hook| hook.original))
+            .or(self.attrs.must_use)
+            .or(self.attrs.throws_pipe)
+            .or(self.attrs.throws_poll)
+            .or(self.attrs.context_struct)
+            .or(self.attrs.tuple_from)
+            .or(self.attrs.throws_doc.as_ref().map(|doc| doc.original))
+            .or(self
+                .attrs
+                .throws_feature
+                .as_ref()
+                .map(|feature| feature.original));
+        if let Some(original) = throws {
+            return Err(Error::new_spanned(
+                original,
+                "not expected here; the #[throws(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(display) = &self.attrs.display {
+            return Err(Error::new_spanned(
+                display.original,
+                "not expected here; the #[error(...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(fmt) = &self.attrs.fmt {
+            return Err(Error::new_spanned(
+                fmt,
+                "not expected here; the #[error(fmt = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(status) = &self.attrs.status {
+            return Err(Error::new_spanned(
+                status,
+                "not expected here; the #[error(status = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(code) = &self.attrs.code {
+            return Err(Error::new_spanned(
+                code,
+                "not expected here; the #[error(code = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(metric) = &self.attrs.metric {
+            return Err(Error::new_spanned(
+                metric,
+                "not expected here; the #[error(metric = ...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(i18n) = &self.attrs.i18n {
+            return Err(Error::new_spanned(
+                i18n,
+                "not expected here; the #[error(i18n = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(prefix) = &self.attrs.prefix {
+            return Err(Error::new_spanned(
+                prefix,
+                "not expected here; the #[error(prefix = ...)] attribute belongs on top of an enum",
+            ));
+        }
+        if let Some(default) = &self.attrs.default_display {
+            return Err(Error::new_spanned(
+                &default.template,
+                "not expected here; the #[error(default = ...)] attribute belongs on top of an enum",
+            ));
+        }
+        if let Some(help) = &self.attrs.help {
+            return Err(Error::new_spanned(
+                &help.fmt,
+                "not expected here; the #[error(help = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(alt) = &self.attrs.alt {
+            return Err(Error::new_spanned(
+                &alt.fmt,
+                "not expected here; the #[error(alt = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(same_as) = &self.attrs.same_as {
+            return Err(Error::new_spanned(
+                same_as,
+                "not expected here; the #[error(same_as = ...)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(suggestion) = &self.attrs.suggestion {
+            return Err(Error::new_spanned(
+                &suggestion.fmt,
+                "not expected here; the #[error(suggestion = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(url) = &self.attrs.url {
+            return Err(Error::new_spanned(
+                &url.fmt,
+                "not expected here; the #[error(url = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(context) = self.attrs.context {
+            return Err(Error::new_spanned(
+                context,
+                "not expected here; the #[error(context)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(boxed) = &self.attrs.boxed {
+            return Err(Error::new_spanned(
+                boxed.original,
+                "not expected here; the #[error(boxed)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(catch_all) = &self.attrs.catch_all {
+            return Err(Error::new_spanned(
+                catch_all.original,
+                "not expected here; the #[error(catch_all)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(panic) = self.attrs.panic {
+            return Err(Error::new_spanned(
+                panic,
+                "not expected here; the #[error(panic)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(no_prefix) = self.attrs.no_prefix {
+            return Err(Error::new_spanned(
+                no_prefix,
+                "not expected here; the #[error(no_prefix)] attribute belongs on top of an enum variant",
+            ));
+        }
+        if let Some(no_source) = self.attrs.no_source {
+            return Err(Error::new_spanned(
+                no_source,
+                "not expected here; the #[error(no_source)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(up) = self.attrs.ups.first() {
+            return Err(Error::new_spanned(
+                up.original,
+                "not expected here; the #[error(up(...))] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(diagnostic) = &self.attrs.diagnostic {
+            if diagnostic.code.is_some()
+                || diagnostic.help.is_some()
+                || diagnostic.severity.is_some()
+                || diagnostic.url.is_some()
+            {
+                return Err(Error::new_spanned(
+                    diagnostic.original,
+                    "not expected here; only label and source_code belong on a field, the other #[diagnostic(...)] options belong on top of a struct, an enum, or an enum variant",
+                ));
+            }
+        }
+        if let Some(grpc) = &self.attrs.grpc {
+            return Err(Error::new_spanned(
+                grpc,
+                "not expected here; the #[error(grpc = ...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        if let Some(retry) = &self.attrs.retry {
+            return Err(Error::new_spanned(
+                retry.original,
+                "not expected here; #[error(transient)] and #[error(permanent)] belong on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(py) = &self.attrs.py {
+            return Err(Error::new_spanned(
+                py,
+                "not expected here; the #[error(py = ...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(io_kind) = &self.attrs.io_kind {
+            return Err(Error::new_spanned(
+                io_kind,
+                "not expected here; the #[error(io_kind = ...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn check_non_field_attrs(attrs: &Attrs) -> Result<()> {
+    if let Some(from) = &attrs.from {
+        return Err(Error::new_spanned(
+            from,
+            "not expected here; the #[from] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(source) = &attrs.source {
+        return Err(Error::new_spanned(
+            source,
+            "not expected here; the #[source] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(backtrace) = &attrs.backtrace {
+        return Err(Error::new_spanned(
+            backtrace,
+            "not expected here; the #[backtrace] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(cause) = attrs.cause {
+        return Err(Error::new_spanned(
+            cause,
+            "not expected here; the #[cause] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(related) = &attrs.related {
+        return Err(Error::new_spanned(
+            related,
+            "not expected here; the #[related] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(errors) = &attrs.errors {
+        return Err(Error::new_spanned(
+            errors,
+            "not expected here; the #[errors] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(sensitive) = &attrs.sensitive {
+        return Err(Error::new_spanned(
+            sensitive.original,
+            "not expected here; the #[sensitive] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(provide) = attrs.provide {
+        return Err(Error::new_spanned(
+            provide,
+            "not expected here; the #[provide] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(timestamp) = attrs.timestamp {
+        return Err(Error::new_spanned(
+            timestamp,
+            "not expected here; the #[timestamp] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(context_frames) = attrs.context_frames {
+        return Err(Error::new_spanned(
+            context_frames,
+            "not expected here; the #[context_frames] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(from_value) = attrs.from_value {
+        return Err(Error::new_spanned(
+            from_value,
+            "not expected here; the #[from_value] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(capture) = &attrs.capture {
+        return Err(Error::new_spanned(
+            capture.original,
+            "not expected here; the #[capture] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(phantom) = attrs.phantom {
+        return Err(Error::new_spanned(
+            phantom,
+            "not expected here; the #[throws(phantom)] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(default) = attrs.throws_default {
+        return Err(Error::new_spanned(
+            default,
+            "not expected here; the #[throws(default)] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(error_id) = &attrs.error_id {
+        return Err(Error::new_spanned(
+            error_id.original,
+            "not expected here; the #[error_id] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(try_from) = &attrs.try_from {
+        return Err(Error::new_spanned(
+            try_from.original,
+            "not expected here; the #[naur(try_from(...))] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(from_any) = attrs.from_any {
+        return Err(Error::new_spanned(
+            from_any,
+            "not expected here; the #[naur(from_any)] attribute belongs on the #[from] field of an enum variant",
+        ));
+    }
+    if let Some(diagnostic) = &attrs.diagnostic {
+        if diagnostic.label.is_some() || diagnostic.source_code {
+            return Err(Error::new_spanned(
+                diagnostic.original,
+                "not expected here; #[diagnostic(label)] and #[diagnostic(source_code)] belong on a specific field",
+            ));
+        }
+    }
+    if let Some(display) = &attrs.display {
+        if display.displays_self() {
+            return Err(Error::new_spanned(
+                &display.template,
+                "`{self}` would format the error with the Display impl being derived, recursing forever; use `{self:?}` for its Debug representation",
+            ));
+        }
+        if attrs.transparent.is_some() {
+            return Err(Error::new_spanned(
+                display.original,
+                "cannot have both #[error(transparent)] and a display attribute",
+            ));
+        }
+        if let Some(fmt) = &attrs.fmt {
+            return Err(Error::new_spanned(
+                fmt,
+                "cannot have both a display message and #[error(fmt = ...)]",
+            ));
+        }
+    }
+    if let (Some(i18n), Some(_)) = (&attrs.i18n, attrs.transparent) {
+        return Err(Error::new_spanned(
+            i18n,
+            "cannot have both #[error(transparent)] and #[error(i18n = ...)]",
+        ));
+    }
+    if let (Some(fmt), Some(_)) = (&attrs.fmt, attrs.transparent) {
+        return Err(Error::new_spanned(
+            fmt,
+            "cannot have both #[error(transparent)] and #[error(fmt = ...)]",
+        ));
+    }
+    Ok(())
+}
+
+// Options of the #[naur(...)] attribute, which configure the derive as a
+// whole rather than an individual variant or field.
+fn check_container_attrs(attrs: &Attrs) -> Result<()> {
+    if let Some(attr) = attrs
+        .serialize
+        .or(attrs.fields)
+        .or_else(|| attrs.typed_fields.as_ref().map(|typed| typed.original))
+        .or(attrs.chain)
+        .or(attrs.alt_chain)
+        .or(attrs.single_line)
+        .or(attrs.boxed_conv)
+        .or_else(|| attrs.throws_naming.as_ref().map(|naming| naming.original))
+        .or_else(|| attrs.throws_prefix.as_ref().map(|prefix| prefix.original))
+        .or_else(|| (attrs.default_backtrace_capture.as_ref()).map(|capture| capture.original))
+        .or(attrs.templates)
+        .or(attrs.catalog)
+        .or(attrs.manifest)
+        .or_else(|| attrs.crate_path.as_ref().map(|path| path.original))
+        .or(attrs.wire)
+        .or(attrs.kind)
+        .or(attrs.ffi)
+        .or(attrs.accessors)
+        .or(attrs.arbitrary)
+        .or(attrs.constructors)
+        .or_else(|| attrs.eq.as_ref().map(|eq| eq.original))
+        .or_else(|| attrs.max_size.as_ref().map(|max_size| max_size.original))
+        .or_else(|| attrs.assert.as_ref().map(|assert| assert.original))
+        .or_else(|| attrs.code_range.as_ref().map(|range| range.original))
+        .or(attrs.cold)
+        .or(attrs.auto_transparent)
+        .or(attrs.debug_chain)
+        .or(attrs.redacted_debug)
+        .or(attrs.stable_debug)
+        .or(attrs.minimal)
+        .or(attrs.no_defaults)
+        .or_else(|| attrs.subsets.first().map(|subset| subset.original))
+        .or_else(|| attrs.aggregate.as_ref().map(|aggregate| aggregate.original))
+        .or_else(|| attrs.extensions.first().map(|extension| extension.original))
+    {
+        return Err(Error::new_spanned(
+            attr,
+            "not expected here; the #[naur(...)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(bound) = &attrs.bound {
+        return Err(Error::new_spanned(
+            bound.original,
+            "not expected here; the #[error(bound = ...)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(on_create) = &attrs.on_create {
+        return Err(Error::new_spanned(
+            on_create.original,
+            "not expected here; the #[throws(on_create = ...)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(vis) = &attrs.throws_vis {
+        return Err(Error::new_spanned(
+            vis.original,
+            "not expected here; the #[throws(vis = ...)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    Ok(())
+}
+
+// An #[error(prefix = ...)] belongs on the enum, from which each variant may
+// opt out with #[error(no_prefix)].
+fn check_prefix(input: &Enum) -> Result<()> {
+    for variant in &input.variants {
+        if let Some(prefix) = &variant.attrs.prefix {
+            return Err(Error::new_spanned(
+                prefix,
+                "not expected here; the #[error(prefix = ...)] attribute belongs on top of an enum",
+            ));
+        }
+        if let (Some(no_prefix), None) = (variant.attrs.no_prefix, &input.attrs.prefix) {
+            return Err(Error::new_spanned(
+                no_prefix,
+                "#[error(no_prefix)] requires an #[error(prefix = ...)] on the enum",
+            ));
+        }
+    }
+    Ok(())
+}
+
+// An #[error(default = ...)] on the enum gives the message of each variant
+// without one, which an enum-wide message or transparency would leave unused.
+fn check_default_display(input: &Enum) -> Result<()> {
+    if let Some(default) = &input.attrs.default_display {
+        if input.attrs.display.is_some() || input.attrs.fmt.is_some() {
+            return Err(Error::new_spanned(
+                &default.template,
+                "cannot have both a message for the whole enum and #[error(default = ...)]",
+            ));
+        }
+        if let Some(transparent) = &input.attrs.transparent {
+            return Err(Error::new_spanned(
+                transparent.original,
+                "cannot have both #[error(transparent)] and #[error(default = ...)]",
+            ));
+        }
+    }
+    for variant in &input.variants {
+        if let Some(default) = &variant.attrs.default_display {
+            return Err(Error::new_spanned(
+                &default.template,
+                "not expected here; the #[error(default = ...)] attribute belongs on top of an enum",
+            ));
+        }
+    }
+    Ok(())
+}
+
+// A type- or variant-level #[error(arc_source)] has already been passed on to
+// the source field, which must be declared as an Arc.
+fn check_arc_source(attrs: &Attrs, fields: &[Field]) -> Result<()> {
+    if let (Some(arc_source), Some(_)) = (attrs.arc_source, attrs.transparent) {
+        return Err(Error::new_spanned(
+            arc_source,
+            "cannot have both #[error(transparent)] and #[error(arc_source)]",
+        ));
+    }
+    let source_field = prop::source_field(fields);
+    if let (Some(arc_source), None) = (attrs.arc_source, source_field) {
+        return Err(Error::new_spanned(
+            arc_source,
+            "#[error(arc_source)] requires a source field",
+        ));
+    }
+    for field in fields {
+        let arc_source = match field.attrs.arc_source {
+            Some(arc_source) => arc_source,
+            None => continue,
+        };
+        if !source_field.map_or(false, |source_field| same_member(source_field, field)) {
+            return Err(Error::new_spanned(
+                arc_source,
+                "#[error(arc_source)] is only supported on the source field",
+            ));
+        }
+        if field.arc_source_type().is_none() {
+            return Err(Error::new_spanned(
+                field.ty,
+                "#[error(arc_source)] requires the source field to be declared as an Arc, such as `Arc<io::Error>`",
+            ));
+        }
+        if field.arc_dyn_source_type().is_some() {
+            return Err(Error::new_spanned(
+                arc_source,
+                "#[error(arc_source)] is not needed on an `Arc<dyn Error>` source, which is shared as it is",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_retry_source(attrs: &Attrs, fields: &[Field]) -> Result<()> {
+    if let Some(retry) = &attrs.retry {
+        if retry.class == RetryClass::Source
+            && attrs.transparent.is_none()
+            && prop::source_field(fields).is_none()
+        {
+            return Err(Error::new_spanned(
+                retry.original,
+                "#[error(transient = source)] requires a source to ask",
+            ));
+        }
+    }
+    Ok(())
+}
+
+// #[naur(minimal)] generates nothing beyond the Error, Display, and From impls,
+// so options which add to them, or which rely on what it leaves out, are
+// rejected rather than ignored.
+fn check_minimal<'a>(
+    attrs: &'a Attrs<'a>,
+    variant_attrs: impl IntoIterator<Item = &'a Attrs<'a>>,
+) -> Result<()> {
+    if attrs.minimal.is_none() {
+        return Ok(());
+    }
+    if let Some(attr) = attrs
+        .serialize
+        .or(attrs.fields)
+        .or_else(|| attrs.typed_fields.as_ref().map(|typed| typed.original))
+        .or(attrs.chain)
+        .or(attrs.alt_chain)
+        .or(attrs.boxed_conv)
+        .or_else(|| attrs.throws_naming.as_ref().map(|naming| naming.original))
+        .or_else(|| attrs.throws_prefix.as_ref().map(|prefix| prefix.original))
+        .or_else(|| attrs.throws_vis.as_ref().map(|vis| vis.original))
+        .or(attrs.templates)
+        .or(attrs.catalog)
+        .or(attrs.manifest)
+        .or(attrs.wire)
+        .or(attrs.kind)
+        .or(attrs.ffi)
+        .or(attrs.accessors)
+        .or(attrs.arbitrary)
+        .or(attrs.constructors)
+        .or_else(|| attrs.eq.as_ref().map(|eq| eq.original))
+        .or_else(|| attrs.subsets.first().map(|subset| subset.original))
+        .or_else(|| attrs.aggregate.as_ref().map(|aggregate| aggregate.original))
+        .or_else(|| attrs.extensions.first().map(|extension| extension.original))
+    {
+        return Err(Error::new_spanned(
+            attr,
+            "#[naur(minimal)] leaves out all optional output, so it cannot be combined with options which generate more",
+        ));
+    }
+    for attrs in Some(attrs).into_iter().chain(variant_attrs) {
+        if let Some(attr) = attrs.tuple_from.or(attrs.throws_pipe).or(attrs.throws_poll) {
+            return Err(Error::new_spanned(
+                attr,
+                "#[naur(minimal)] leaves out all optional output, so it cannot be combined with options which generate more",
+            ));
+        }
+        if let Some(trace) = &attrs.trace {
+            return Err(Error::new_spanned(
+                trace.original,
+                "#[throws(trace)] is not supported together with #[naur(minimal)], which leaves out the metric_label() it records",
+            ));
+        }
+        if let Some(metric) = &attrs.metric {
+            return Err(Error::new_spanned(
+                metric,
+                "#[error(metric = ...)] is not supported together with #[naur(minimal)], which leaves out the metric_label() it records",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_minimal_provide(attrs: &Attrs, fields: &[Field]) -> Result<()> {
+    if attrs.minimal.is_none() {
+        return Ok(());
+    }
+    if let Some(provide) = fields.iter().find_map(|field| field.attrs.provide) {
+        return Err(Error::new_spanned(
+            provide,
+            "#[provide] is not supported together with #[naur(minimal)], which leaves out Error::provide()",
+        ));
+    }
+    Ok(())
+}
+
+fn check_context_struct(attrs: &Attrs, generics: &Generics) -> Result<()> {
+    if let Some(context_struct) = attrs.context_struct {
+        if !generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                context_struct,
+                "#[throws(context_struct)] is not supported on generic errors",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_max_size(attrs: &Attrs, generics: &Generics) -> Result<()> {
+    if let Some(max_size) = &attrs.max_size {
+        if !generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                max_size.original,
+                "#[naur(max_size = ...)] is not supported on generic errors",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_arbitrary(attrs: &Attrs, generics: &Generics) -> Result<()> {
+    if let Some(arbitrary) = attrs.arbitrary {
+        if !generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                arbitrary,
+                "#[naur(arbitrary)] is not supported on generic errors",
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Error codes are how other services and runbooks tell errors apart, so no two
+// variants may give the same one, and #[naur(code_range = ...)] keeps a team's
+// codes within the numbers it has been allotted.
+fn check_codes(attrs: &Attrs, variants: &[Variant]) -> Result<()> {
+    let mut codes: Vec<&LitStr> = attrs.code.iter().collect();
+    let mut given = Map::new();
+    for variant in variants {
+        let code = match &variant.attrs.code {
+            Some(code) => code,
+            None => continue,
+        };
+        if let Some(other) = given.insert(code.value(), &variant.ident) {
+            return Err(Error::new_spanned(
+                code,
+                format!(
+                    "duplicate error code `{}`, also given to `{}`",
+                    code.value(),
+                    other,
+                ),
+            ));
+        }
+        codes.push(code);
+    }
+    let range = match &attrs.code_range {
+        Some(range) => range,
+        None => return Ok(()),
+    };
+    for code in codes {
+        // The number is the code's trailing digits, as in "E4001".
+        let value = code.value();
+        let digits = value.trim_end_matches(|ch: char| ch.is_ascii_digit()).len();
+        let n = match value[digits..].parse::<u64>() {
+            Ok(n) => n,
+            Err(_) => {
+                return Err(Error::new_spanned(
+                    code,
+                    format!(
+                        "expected a numeric error code, as required by #[naur(code_range = {})]",
+                        range.render(),
+                    ),
+                ));
+            }
+        };
+        if !range.contains(n) {
+            return Err(Error::new_spanned(
+                code,
+                format!(
+                    "error code `{}` is outside of #[naur(code_range = {})]",
+                    value,
+                    range.render(),
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Errors compared by code are only told apart by it, so each variant needs a
+// code of its own.
+fn check_eq_code(input: &Enum) -> Result<()> {
+    let eq = match &input.attrs.eq {
+        Some(eq) if eq.strategy == EqStrategy::Code => eq,
+        _ => return Ok(()),
+    };
+    if input.variants.is_empty() {
+        return Err(Error::new_spanned(
+            eq.original,
+            "#[naur(eq = \"code\")] requires at least one variant to give a code",
+        ));
+    }
+    let mut codes = Map::new();
+    for variant in &input.variants {
+        let code = match variant.attrs.code.as_ref().or(input.attrs.code.as_ref()) {
+            Some(code) => code,
+            None => {
+                return Err(Error::new_spanned(
+                    &variant.ident,
+                    "#[naur(eq = \"code\")] requires every variant to have an #[error(code = ...)]",
+                ));
+            }
+        };
+        if let Some(other) = codes.insert(code.value(), &variant.ident) {
+            return Err(Error::new_spanned(
+                code,
+                format!(
+                    "duplicate error code `{}`, also given to `{}`; #[naur(eq = \"code\")] would consider them equal",
+                    code.value(),
+                    other,
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// The error_id() method returns the identifier of whichever variant the error
+// is, so they all need to be of one type.
+fn check_error_id_types(input: &Enum) -> Result<()> {
+    let mut first: Option<&Field> = None;
+    for field in input
+        .variants
+        .iter()
+        .filter_map(|variant| variant.error_id_field())
+    {
+        match first {
+            None => first = Some(field),
+            Some(first)
+                if first.ty.to_token_stream().to_string()
+                    == field.ty.to_token_stream().to_string() => {}
+            Some(first) => {
+                return Err(Error::new_spanned(
+                    field.ty,
+                    format!(
+                        "every #[error_id] of an enum must be of the same type, `{}`",
+                        first.ty.to_token_stream(),
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_ups(ups: &[Up], generics: &Generics) -> Result<()> {
+    for (i, up) in ups.iter().enumerate() {
+        if !generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                up.original,
+                "#[error(up(...))] is not supported on generic errors",
+            ));
+        }
+        let parent = up::parent(&up.variant);
+        if up::find(&ups[..i], &parent).is_some() {
+            return Err(Error::new_spanned(
+                up.original,
+                "duplicate #[error(up(...))] into the same parent type",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_diagnostic_fields(fields: &[Field]) -> Result<()> {
+    let mut source_code = None;
+    for field in fields {
+        if let Some(diagnostic) = &field.attrs.diagnostic {
+            if diagnostic.source_code {
+                if source_code.is_some() {
+                    return Err(Error::new_spanned(
+                        diagnostic.original,
+                        "duplicate #[diagnostic(source_code)] field",
+                    ));
+                }
+                source_code = Some(field);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_alt_chain(attrs: &Attrs, generics: &Generics) -> Result<()> {
+    if let Some(alt_chain) = attrs.alt_chain {
+        if generics.type_params().next().is_some() {
+            return Err(Error::new_spanned(
+                alt_chain,
+                "#[naur(alt_chain)] is not supported on generic errors",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_alt(attrs: &Attrs, alt_chain: Option<&Attribute>) -> Result<()> {
+    if let Some(alt) = &attrs.alt {
+        if attrs.display.is_none() {
+            return Err(Error::new_spanned(
+                &alt.fmt,
+                "#[error(alt = ...)] requires an #[error(\"...\")] message to stand in for",
+            ));
+        }
+        if let Some(alt_chain) = alt_chain {
+            return Err(Error::new_spanned(
+                alt_chain,
+                "cannot have both #[naur(alt_chain)] and #[error(alt = ...)], which both format `{:#}`",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_debug_chain(attrs: &Attrs, generics: &Generics) -> Result<()> {
+    if let Some(debug_chain) = attrs.debug_chain {
+        if generics.type_params().next().is_some() {
+            return Err(Error::new_spanned(
+                debug_chain,
+                "#[naur(debug_chain)] is not supported on generic errors",
+            ));
+        }
+        if attrs.redacted_debug.is_some() {
+            return Err(Error::new_spanned(
+                debug_chain,
+                "cannot have both #[naur(debug_chain)] and #[naur(redacted_debug)], which both implement Debug",
+            ));
+        }
+        if attrs.stable_debug.is_some() {
+            return Err(Error::new_spanned(
+                debug_chain,
+                "cannot have both #[naur(debug_chain)] and #[naur(stable_debug)], which both implement Debug",
+            ));
+        }
+    }
+    if let (Some(_), Some(stable_debug)) = (attrs.redacted_debug, attrs.stable_debug) {
+        return Err(Error::new_spanned(
+            stable_debug,
+            "#[naur(stable_debug)] already redacts #[sensitive] fields; remove #[naur(redacted_debug)]",
+        ));
+    }
+    Ok(())
+}
+
+fn check_aggregate(attrs: &Attrs, generics: &Generics) -> Result<()> {
+    if let Some(aggregate) = &attrs.aggregate {
+        if !generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                aggregate.original,
+                "#[naur(aggregate)] is not supported on generic errors",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_field_attrs(fields: &[Field]) -> Result<()> {
+    let mut from_field = None;
+    let mut try_from_field = None;
+    let mut from_value_field = None;
+    let mut source_field = None;
+    let mut backtrace_field = None;
+    let mut related_field = None;
+    let mut errors_field = None;
+    let mut error_id_field = None;
+    let mut has_backtrace = false;
+    for field in fields {
+        if let (Some(_), Some(from_skip)) = (field.attrs.from, field.attrs.from_skip) {
+            return Err(Error::new_spanned(
+                from_skip,
+                "#[from(skip)] marks the source without deriving From; remove either it or #[from]",
+            ));
+        }
+        if let Some(from) = field.attrs.from {
+            if from_field.is_some() {
+                return Err(Error::new_spanned(from, "duplicate #[from] attribute"));
+            }
+            from_field = Some(field);
+        }
+        if let Some(try_from) = &field.attrs.try_from {
+            if try_from_field.is_some() {
+                return Err(Error::new_spanned(
+                    try_from.original,
+                    "duplicate #[naur(try_from)] attribute",
+                ));
+            }
+            try_from_field = Some(field);
+        }
+        if let Some(from_value) = field.attrs.from_value {
+            if from_value_field.is_some() {
+                return Err(Error::new_spanned(
+                    from_value,
+                    "duplicate #[from_value] attribute",
+                ));
+            }
+            from_value_field = Some(field);
+        }
+        if let Some(source) = field.attrs.source {
+            if source_field.is_some() {
+                return Err(Error::new_spanned(source, "duplicate #[source] attribute"));
+            }
+            source_field = Some(field);
+        }
+        if let Some(backtrace) = field.attrs.backtrace {
+            if backtrace_field.is_some() {
+                return Err(Error::new_spanned(
+                    backtrace,
+                    "duplicate #[backtrace] attribute",
+                ));
+            }
+            if field.attrs.backtrace_capture.is_some()
+                && (field.attrs.from.is_some() || field.attrs.source.is_some())
+            {
+                return Err(Error::new_spanned(
+                    backtrace,
+                    "#[backtrace(capture = ...)] is not supported on the source field, whose backtrace is the source's own",
+                ));
+            }
+            backtrace_field = Some(field);
+            has_backtrace = true;
+        }
+        if let Some(cause) = field.attrs.cause {
+            let is_source = field.attrs.from.is_some()
+                || field.attrs.source.is_some()
+                || matches!(&field.member, Member::Named(ident) if ident == "source");
+            if is_source {
+                return Err(Error::new_spanned(
+                    cause,
+                    "#[cause] is not supported on the source field, which is reported by Error::source()",
+                ));
+            }
+            if field.attrs.related.is_some() || field.attrs.errors.is_some() {
+                return Err(Error::new_spanned(
+                    cause,
+                    "a field cannot be both #[cause] and a collection of errors",
+                ));
+            }
+            if field.attrs.backtrace.is_some() || field.is_backtrace() {
+                return Err(Error::new_spanned(
+                    cause,
+                    "#[cause] is not supported on the backtrace field",
+                ));
+            }
+            if field.contains_generic {
+                return Err(Error::new_spanned(
+                    cause,
+                    "#[cause] is not supported on fields of a generic type",
+                ));
+            }
+        }
+        if let Some(related) = field.attrs.related {
+            if related_field.is_some() {
+                return Err(Error::new_spanned(
+                    related,
+                    "duplicate #[related] attribute",
+                ));
+            }
+            if field.attrs.from.is_some() || field.attrs.source.is_some() {
+                return Err(Error::new_spanned(
+                    related,
+                    "#[related] is not supported on the source field, which is reported by Error::source()",
+                ));
+            }
+            if field.contains_generic {
+                return Err(Error::new_spanned(
+                    related,
+                    "#[related] is not supported on fields of a generic type",
+                ));
+            }
+            related_field = Some(field);
+        }
+        if let Some(errors) = field.attrs.errors {
+            if errors_field.is_some() {
+                return Err(Error::new_spanned(errors, "duplicate #[errors] attribute"));
+            }
+            if field.attrs.related.is_some() {
+                return Err(Error::new_spanned(
+                    errors,
+                    "a field cannot be both #[errors] and #[related]",
+                ));
+            }
+            if field.contains_generic {
+                return Err(Error::new_spanned(
+                    errors,
+                    "#[errors] is not supported on fields of a generic type",
+                ));
+            }
+            errors_field = Some(field);
+        }
+        let captured: Vec<_> = [
+            field.attrs.timestamp.map(|attr| (attr, "#[timestamp]")),
+            (field.attrs.context_frames).map(|attr| (attr, "#[context_frames]")),
+            (field.attrs.capture.as_ref()).map(|capture| (capture.original, "#[capture]")),
+            (field.attrs.error_id.as_ref()).map(|error_id| (error_id.original, "#[error_id]")),
+            field.attrs.phantom.map(|attr| (attr, "#[throws(phantom)]")),
+            field
+                .attrs
+                .throws_default
+                .map(|attr| (attr, "#[throws(default)]")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if let [(_, first), (attr, second), ..] = captured.as_slice() {
+            return Err(Error::new_spanned(
+                attr,
+                format!("a field cannot be both a {} and a {}", first, second),
+            ));
+        }
+        if let Some(&(attr, name)) = captured.first() {
+            if field.attrs.from.is_some() || field.attrs.source.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    format!("{} is not supported on the source field", name),
+                ));
+            }
+            if field.attrs.backtrace.is_some() || field.is_backtrace() {
+                return Err(Error::new_spanned(
+                    attr,
+                    format!("{} is not supported on the backtrace field", name),
+                ));
+            }
+        }
+        if let Some(error_id) = &field.attrs.error_id {
+            if error_id_field.is_some() {
+                return Err(Error::new_spanned(
+                    error_id.original,
+                    "duplicate #[error_id] attribute",
+                ));
+            }
+            error_id_field = Some(field);
+        }
+        if let (Some(provide), true) = (field.attrs.provide, field.contains_generic) {
+            return Err(Error::new_spanned(
+                provide,
+                "#[provide] is not supported on fields of a generic type",
+            ));
+        }
+        if let Some(transparent) = field.attrs.transparent {
+            return Err(Error::new_spanned(
+                transparent.original,
+                "#[error(transparent)] needs to go outside the enum or struct, not on an individual field",
+            ));
+        }
+        has_backtrace |= field.is_backtrace();
+    }
+    if let (Some(from_field), Some(source_field)) = (from_field, source_field) {
+        if !same_member(from_field, source_field) {
+            return Err(Error::new_spanned(
+                from_field.attrs.from,
+                "#[from] is only supported on the source field, not any other field",
+            ));
+        }
+    }
+    // The source may also be inferred from a field's name.
+    let inferred_source_field = prop::source_field(fields);
+    // Timestamps, captured and #[throws(default)] fields are filled in by the
+    // From and TryFrom impls too.
+    let fields: Vec<&Field> = fields
+        .iter()
+        .filter(|field| !field.is_captured() && field.attrs.throws_default.is_none())
+        .collect();
+    if let Some(from_field) = from_field {
+        let max_expected_fields = match backtrace_field {
+            Some(backtrace_field) => 1 + !same_member(from_field, backtrace_field) as usize,
+            None => 1 + has_backtrace as usize,
+        };
+        if fields.len() > max_expected_fields {
+            return Err(Error::new_spanned(
+                from_field.attrs.from,
+                "deriving From requires no fields other than source and backtrace, unless the others are #[throws(default)]",
+            ));
+        }
+    }
+    if let Some(poison) = source_field.and_then(|field| field.attrs.poison) {
+        let source_field = source_field.unwrap();
+        if source_field.attrs.from.is_some() {
+            return Err(Error::new_spanned(
+                source_field.attrs.from,
+                "#[source(poison)] already derives From for every PoisonError; remove #[from]",
+            ));
+        }
+        let max_expected_fields = match backtrace_field {
+            Some(_) => 2,
+            None => 1 + has_backtrace as usize,
+        };
+        if fields.len() > max_expected_fields {
+            return Err(Error::new_spanned(
+                poison,
+                "#[source(poison)] derives From, which requires no fields other than source and backtrace",
+            ));
+        }
+    }
+    if let Some(try_from_field) = try_from_field {
+        let try_from = try_from_field.attrs.try_from.as_ref().unwrap().original;
+        if from_field.is_some() {
+            return Err(Error::new_spanned(
+                try_from,
+                "cannot derive both From and TryFrom for the same variant",
+            ));
+        }
+        let max_expected_fields = match backtrace_field {
+            Some(backtrace_field) => 1 + !same_member(try_from_field, backtrace_field) as usize,
+            None => 1 + has_backtrace as usize,
+        };
+        if fields.len() > max_expected_fields {
+            return Err(Error::new_spanned(
+                try_from,
+                "deriving TryFrom requires no fields other than the converted field and backtrace",
+            ));
+        }
+    }
+    if let Some(from_value_field) = from_value_field {
+        let from_value = from_value_field.attrs.from_value;
+        if inferred_source_field.map_or(false, |source_field| {
+            same_member(source_field, from_value_field)
+        }) {
+            return Err(Error::new_spanned(
+                from_value,
+                "#[from_value] is for a field which is not the source; use #[from] on the source field",
+            ));
+        }
+        if from_field.is_some() || try_from_field.is_some() {
+            return Err(Error::new_spanned(
+                from_value,
+                "cannot have both #[from_value] and #[from] or #[naur(try_from)] in the same variant",
+            ));
+        }
+        if from_value_field.attrs.backtrace.is_some() || from_value_field.is_backtrace() {
+            return Err(Error::new_spanned(
+                from_value,
+                "#[from_value] is not supported on the backtrace field",
+            ));
+        }
+        let max_expected_fields = 1 + (backtrace_field.is_some() || has_backtrace) as usize;
+        if fields.len() > max_expected_fields {
+            return Err(Error::new_spanned(
+                from_value,
+                "deriving From requires no fields other than the converted field and backtrace",
+            ));
+        }
+    }
+    if let Some(source_field) = source_field.or(from_field) {
+        if contains_non_static_lifetime(source_field.ty) {
+            return Err(Error::new_spanned(
+                &source_field.original.ty,
+                "non-static lifetimes are not allowed in the source of an error, because std::error::Error requires the source is dyn Error + 'static",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn same_member(one: &Field, two: &Field) -> bool {
+    match (&one.member, &two.member) {
+        (Member::Named(one), Member::Named(two)) => one == two,
+        (Member::Unnamed(one), Member::Unnamed(two)) => one.index == two.index,
+        _ => unreachable!(),
+    }
+}
+
+fn contains_non_static_lifetime(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => {
+            let bracketed = match &ty.path.segments.last().unwrap().arguments {
+                PathArguments::AngleBracketed(bracketed) => bracketed,
+                _ => return false,
+            };
+            for arg in &bracketed.args {
+                match arg {
+                    GenericArgument::Type(ty) if contains_non_static_lifetime(ty) => return true,
+                    GenericArgument::Lifetime(lifetime) if lifetime.ident != "static" => {
+                        return true
+                    }
+                    _ => {}
+                }
+            }
+            false
+        }
+        Type::Reference(ty) => ty
+            .lifetime
+            .as_ref()
+            .map_or(false, |lifetime| lifetime.ident != "static"),
+        _ => false, // maybe implement later if there are common other cases
+    }
+}
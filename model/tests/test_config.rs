@@ -0,0 +1,157 @@
+use quote::ToTokens;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use syn::{parse_quote, DeriveInput};
+use thiserror_model::config::Config;
+
+// A scratch directory holding a workspace at `workspace/` with a member crate
+// at `workspace/member/`, and nothing else.
+fn scratch(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("naur-config-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("workspace/member")).unwrap();
+    fs::write(
+        dir.join("workspace/Cargo.toml"),
+        "[workspace]\nmembers = [\"member\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("workspace/member/Cargo.toml"),
+        "[package]\nname = \"member\"\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn attrs(config: &Config, input: &DeriveInput) -> Vec<String> {
+    let input = config.apply(input);
+    let attrs = input.attrs.iter();
+    attrs
+        .map(|attr| attr.to_token_stream().to_string())
+        .collect()
+}
+
+#[test]
+fn test_workspace_root() {
+    let dir = scratch("root");
+    fs::write(dir.join("workspace/naur.toml"), "[naur]\nfields = true\n").unwrap();
+
+    let config = Config::load_from(&dir.join("workspace/member"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(Some(dir.join("workspace/naur.toml")), config.path);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_closest() {
+    let dir = scratch("closest");
+    fs::write(dir.join("workspace/naur.toml"), "[naur]\nfields = true\n").unwrap();
+    fs::write(
+        dir.join("workspace/member/naur.toml"),
+        "[naur]\nchain = true\n",
+    )
+    .unwrap();
+
+    let config = Config::load_from(&dir.join("workspace/member"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(Some(dir.join("workspace/member/naur.toml")), config.path);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_stops_at_workspace_root() {
+    let dir = scratch("outside");
+    fs::write(dir.join("naur.toml"), "[naur]\nfields = true\n").unwrap();
+
+    let config = Config::load_from(&dir.join("workspace/member")).unwrap();
+    assert!(config.is_none());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_invalid_file() {
+    let dir = scratch("invalid");
+    fs::write(dir.join("workspace/naur.toml"), "[naur]\nfields = 1\n").unwrap();
+
+    let error = Config::load_from(&dir.join("workspace/member"))
+        .err()
+        .unwrap();
+    let expected = format!(
+        "{}: line 2: expected true or false",
+        dir.join("workspace/naur.toml").display(),
+    );
+    assert_eq!(expected, error.to_string());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_defaults() {
+    let config = Config::parse(
+        r#"
+        [naur]
+        fields = true
+        throws_prefix = "or_"
+        backtrace_capture = "my_crate::trace::capture"
+
+        [throws]
+        trace = "warn"
+        vis = "pub(crate)"
+        "#,
+    )
+    .unwrap();
+
+    let input: DeriveInput = parse_quote! {
+        #[error("...")]
+        pub struct Error;
+    };
+    let expected = [
+        "# [naur (fields)]",
+        "# [naur (throws_prefix = \"or_\")]",
+        "# [naur (backtrace_capture = my_crate :: trace :: capture)]",
+        "# [throws (trace = \"warn\")]",
+        "# [throws (vis = \"pub(crate)\")]",
+        "# [error (\"...\")]",
+    ];
+    assert_eq!(expected[..], attrs(&config, &input)[..]);
+
+    let input: DeriveInput = parse_quote! {
+        #[throws(vis = "pub")]
+        #[naur(throws_prefix = "try_")]
+        #[error("...")]
+        pub struct Error;
+    };
+    let expected = [
+        "# [naur (fields)]",
+        "# [naur (backtrace_capture = my_crate :: trace :: capture)]",
+        "# [throws (trace = \"warn\")]",
+        "# [throws (vis = \"pub\")]",
+        "# [naur (throws_prefix = \"try_\")]",
+        "# [error (\"...\")]",
+    ];
+    assert_eq!(expected[..], attrs(&config, &input)[..]);
+}
+
+#[test]
+fn test_invalid_values() {
+    let error = |text: &str| Config::parse(text).err().unwrap().to_string();
+
+    assert_eq!(
+        "line 2: expected false or a string",
+        error("[naur]\nthrows_prefix = true\n"),
+    );
+    assert_eq!(
+        "line 2: expected the path of a function",
+        error("[naur]\nbacktrace_capture = \"not a path\"\n"),
+    );
+    assert_eq!(
+        "line 2: unrecognized #[throws(...)] option `prefix`",
+        error("[throws]\nprefix = \"or_\"\n"),
+    );
+}
@@ -0,0 +1,19 @@
+use crate::response::json_body;
+use actix_web::http::header::ContentType;
+use actix_web::http::StatusCode;
+use core::fmt::Display;
+
+pub use actix_web::{HttpResponse, ResponseError};
+
+#[doc(hidden)]
+pub fn status_code(status: u16) -> StatusCode {
+    StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[doc(hidden)]
+pub fn error_response(status: u16, code: Option<&str>, message: &dyn Display) -> HttpResponse {
+    let status = status_code(status);
+    HttpResponse::build(status)
+        .content_type(ContentType::json())
+        .body(json_body(status.as_u16(), code, message))
+}
@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+
+/// A one-off error carrying only a message, built by the [`naur!`] macro.
+///
+/// [`naur!`]: crate::naur
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct AdHocError {
+    message: String,
+}
+
+impl AdHocError {
+    /// An error with the given message.
+    pub fn new(message: impl Into<String>) -> Self {
+        AdHocError {
+            message: message.into(),
+        }
+    }
+
+    /// The error's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for AdHocError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
+}
+
+impl Debug for AdHocError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_tuple("AdHocError")
+            .field(&self.message)
+            .finish()
+    }
+}
+
+impl Error for AdHocError {}
+
+/// Builds an [`AdHocError`] from a format string, for one-off errors which do
+/// not deserve a variant of their own.
+///
+/// The error can be the `#[source]` of a derived error, or be boxed into the
+/// `Box<dyn Error + Send + Sync>` payload of a catch-all variant.
+///
+/// ```
+/// use std::path::Path;
+/// use thiserror::{naur, Error};
+///
+/// #[derive(Error, Debug)]
+/// pub enum StoreError {
+///     #[error("store is locked")]
+///     Locked,
+///     #[error(transparent)]
+///     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+/// }
+///
+/// fn open(path: &Path) -> Result<(), StoreError> {
+///     if !path.is_absolute() {
+///         let error = naur!("store path {} is relative", path.display());
+///         return Err(StoreError::Other(error.into()));
+///     }
+///     Ok(())
+/// }
+/// #
+/// # let error = open(Path::new("data")).unwrap_err();
+/// # assert_eq!("store path data is relative", error.to_string());
+/// ```
+#[macro_export]
+macro_rules! naur {
+    ($msg:literal $(,)?) => {
+        $crate::AdHocError::new(::std::format!($msg))
+    };
+    ($fmt:expr, $($arg:tt)*) => {
+        $crate::AdHocError::new(::std::format!($fmt, $($arg)*))
+    };
+}
@@ -0,0 +1,44 @@
+use std::fmt::{self, Display};
+
+#[doc(hidden)]
+pub fn display_aggregate<E: Display>(errors: &[E], formatter: &mut fmt::Formatter) -> fmt::Result {
+    match errors {
+        [] => formatter.write_str("no errors occurred"),
+        [error] => Display::fmt(error, formatter),
+        errors => {
+            write!(formatter, "{} errors occurred", errors.len())?;
+            for (i, error) in errors.iter().enumerate() {
+                let separator = if i == 0 { ": " } else { "; " };
+                write!(formatter, "{}{}", separator, error)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+// The Display of a struct with an #[errors] field and no message of its own,
+// which lists each error on a line of its own in alternate mode.
+#[doc(hidden)]
+pub fn display_errors<'a, C, E>(errors: &'a C, formatter: &mut fmt::Formatter) -> fmt::Result
+where
+    &'a C: IntoIterator<Item = &'a E>,
+    E: Display + 'a,
+{
+    match errors.into_iter().count() {
+        0 => formatter.write_str("no errors occurred"),
+        1 => (errors.into_iter()).try_for_each(|error| Display::fmt(error, formatter)),
+        count => {
+            write!(formatter, "{} errors occurred:", count)?;
+            let alternate = formatter.alternate();
+            for (i, error) in errors.into_iter().enumerate() {
+                let separator = match (alternate, i) {
+                    (true, _) => "\n- ",
+                    (false, 0) => " ",
+                    (false, _) => "; ",
+                };
+                write!(formatter, "{}{}", separator, error)?;
+            }
+            Ok(())
+        }
+    }
+}
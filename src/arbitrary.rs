@@ -0,0 +1,87 @@
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use std::backtrace::Backtrace;
+use std::error::Error;
+use std::io;
+use std::marker::PhantomData;
+
+// Builds a field of #[naur(arbitrary)] by autoref specialization: from the
+// input if the field's type implements Arbitrary, or else as a plain error if
+// it is a common source type, or else as its default.
+#[doc(hidden)]
+pub struct ArbitraryWrap<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait ArbitraryViaArbitrary<'a, T> {
+    fn arbitrary_field(&self, u: &mut Unstructured<'a>) -> Result<T>;
+}
+
+impl<'a, T> ArbitraryViaArbitrary<'a, T> for &&&ArbitraryWrap<T>
+where
+    T: Arbitrary<'a>,
+{
+    fn arbitrary_field(&self, u: &mut Unstructured<'a>) -> Result<T> {
+        T::arbitrary(u)
+    }
+}
+
+#[doc(hidden)]
+pub trait ArbitraryViaSource<'a, T> {
+    fn arbitrary_field(&self, u: &mut Unstructured<'a>) -> Result<T>;
+}
+
+impl<'a, T> ArbitraryViaSource<'a, T> for &&ArbitraryWrap<T>
+where
+    T: ArbitrarySource,
+{
+    fn arbitrary_field(&self, u: &mut Unstructured<'a>) -> Result<T> {
+        T::arbitrary_source(u)
+    }
+}
+
+#[doc(hidden)]
+pub trait ArbitraryViaDefault<'a, T> {
+    fn arbitrary_field(&self, u: &mut Unstructured<'a>) -> Result<T>;
+}
+
+impl<'a, T> ArbitraryViaDefault<'a, T> for &ArbitraryWrap<T>
+where
+    T: Default,
+{
+    fn arbitrary_field(&self, _u: &mut Unstructured<'a>) -> Result<T> {
+        Ok(T::default())
+    }
+}
+
+// Source and backtrace types which do not implement Arbitrary, but which can
+// be made to hold a generated message.
+#[doc(hidden)]
+pub trait ArbitrarySource: Sized {
+    fn arbitrary_source(u: &mut Unstructured) -> Result<Self>;
+}
+
+impl ArbitrarySource for io::Error {
+    fn arbitrary_source(u: &mut Unstructured) -> Result<Self> {
+        Ok(io::Error::new(io::ErrorKind::Other, String::arbitrary(u)?))
+    }
+}
+
+impl ArbitrarySource for Box<dyn Error + Send + Sync> {
+    fn arbitrary_source(u: &mut Unstructured) -> Result<Self> {
+        Ok(Box::from(String::arbitrary(u)?))
+    }
+}
+
+impl ArbitrarySource for Box<dyn Error> {
+    fn arbitrary_source(u: &mut Unstructured) -> Result<Self> {
+        Ok(Box::from(String::arbitrary(u)?))
+    }
+}
+
+// The arbitrary crate requires a newer compiler than the crate's minimum
+// supported version anyway.
+#[allow(clippy::incompatible_msrv)]
+impl ArbitrarySource for Backtrace {
+    fn arbitrary_source(_u: &mut Unstructured) -> Result<Self> {
+        Ok(Backtrace::disabled())
+    }
+}
@@ -43,7 +43,7 @@ impl<'a> AsDynError<'a> for dyn Error + Send + Sync + UnwindSafe + 'a {
 
 #[doc(hidden)]
 pub trait Sealed {}
-impl<'a, T: Error + 'a> Sealed for T {}
+impl<T: Error> Sealed for T {}
 impl<'a> Sealed for dyn Error + 'a {}
 impl<'a> Sealed for dyn Error + Send + 'a {}
 impl<'a> Sealed for dyn Error + Send + Sync + 'a {}
@@ -0,0 +1,42 @@
+/// Asserts that an error is a particular variant, panicking with the error
+/// that was found if it is not.
+///
+/// The error is matched by reference, so the fields bound by the pattern are
+/// references which an optional `if` guard can inspect, and the error is still
+/// usable afterward. Available with the `test-helpers` feature.
+///
+/// ```
+/// use thiserror::{assert_variant, Error};
+///
+/// #[derive(Error, Debug)]
+/// pub enum ConfigError {
+///     #[error("invalid value {value} for {key}")]
+///     InvalidValue { key: String, value: i64 },
+///     #[error("missing key {0}")]
+///     Missing(String),
+/// }
+///
+/// fn parse() -> Result<(), ConfigError> {
+///     Err(ConfigError::InvalidValue {
+///         key: "retries".to_owned(),
+///         value: -1,
+///     })
+/// }
+///
+/// let error = parse().unwrap_err();
+/// assert_variant!(error, ConfigError::InvalidValue { .. });
+/// assert_variant!(error, ConfigError::InvalidValue { key, value } if key == "retries" && *value < 0);
+/// ```
+#[macro_export]
+macro_rules! assert_variant {
+    ($error:expr, $($pattern:pat_param)|+ $(if $guard:expr)? $(,)?) => {
+        match &$error {
+            $($pattern)|+ $(if $guard)? => {}
+            error => ::core::panic!(
+                "assertion failed: error matches `{}`\n  error: {:?}",
+                ::core::stringify!($($pattern)|+ $(if $guard)?),
+                error,
+            ),
+        }
+    };
+}
@@ -0,0 +1,14 @@
+use crate::response::json_body;
+use core::fmt::Display;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::StatusCode;
+
+pub use axum_core::response::{IntoResponse, Response};
+
+#[doc(hidden)]
+pub fn into_response(status: u16, code: Option<&str>, message: &dyn Display) -> Response {
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let body = json_body(status.as_u16(), code, message);
+    let content_type = HeaderValue::from_static("application/json");
+    (status, [(CONTENT_TYPE, content_type)], body).into_response()
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+// The backtrace() generated for an error, reachable from the error which has
+// it as a source.
+#[doc(hidden)]
+pub trait ThiserrorBacktrace<B> {
+    fn thiserror_backtrace(&self) -> Option<&B>;
+}
+
+impl<B, T> ThiserrorBacktrace<B> for Box<T>
+where
+    T: ?Sized + ThiserrorBacktrace<B>,
+{
+    fn thiserror_backtrace(&self) -> Option<&B> {
+        (**self).thiserror_backtrace()
+    }
+}
+
+impl<B, T> ThiserrorBacktrace<B> for Arc<T>
+where
+    T: ?Sized + ThiserrorBacktrace<B>,
+{
+    fn thiserror_backtrace(&self) -> Option<&B> {
+        (**self).thiserror_backtrace()
+    }
+}
+
+// Autoref specialization: `(&&BacktraceWrap(source)).thiserror_backtrace()`
+// resolves to the source's backtrace() when it has one, and to None otherwise.
+#[doc(hidden)]
+pub struct BacktraceWrap<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait BacktraceViaSource<'a, B> {
+    fn thiserror_backtrace(&self) -> Option<&'a B>;
+}
+
+impl<'a, B, T> BacktraceViaSource<'a, B> for &BacktraceWrap<'a, T>
+where
+    T: ?Sized + ThiserrorBacktrace<B>,
+{
+    fn thiserror_backtrace(&self) -> Option<&'a B> {
+        self.0.thiserror_backtrace()
+    }
+}
+
+#[doc(hidden)]
+pub trait BacktraceViaNone<'a, B> {
+    fn thiserror_backtrace(&self) -> Option<&'a B>;
+}
+
+impl<'a, B, T> BacktraceViaNone<'a, B> for BacktraceWrap<'a, T>
+where
+    T: ?Sized,
+{
+    fn thiserror_backtrace(&self) -> Option<&'a B> {
+        None
+    }
+}
+
+// Wraps the generated `provide` method, which is left out where the toolchain
+// does not support `Error::provide` so that errors with a backtrace field
+// still compile on stable, where backtrace() reaches the field instead.
+#[cfg(error_generic_member_access)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __provide {
+    ($($method:tt)*) => {
+        $($method)*
+    };
+}
+
+#[cfg(not(error_generic_member_access))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __provide {
+    ($($method:tt)*) => {};
+}
@@ -0,0 +1,127 @@
+use crate::context::ContextFrames;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, ThreadId as StdThreadId};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// The value of a #[timestamp] or #[capture(...)] field, read when the error
+// is constructed. `K` is the kind of value, so that one field type, such as
+// u32 or String, may be captured in more than one way.
+#[doc(hidden)]
+pub trait Capture<K> {
+    fn capture() -> Self;
+}
+
+#[doc(hidden)]
+pub enum Timestamp {}
+
+#[doc(hidden)]
+pub enum ThreadName {}
+
+#[doc(hidden)]
+pub enum ThreadId {}
+
+#[doc(hidden)]
+pub enum Pid {}
+
+#[doc(hidden)]
+pub enum ErrorId {}
+
+#[doc(hidden)]
+pub enum Context {}
+
+impl Capture<Timestamp> for SystemTime {
+    fn capture() -> Self {
+        SystemTime::now()
+    }
+}
+
+impl Capture<Timestamp> for Instant {
+    fn capture() -> Self {
+        Instant::now()
+    }
+}
+
+impl<T: Capture<Timestamp>> Capture<Timestamp> for Option<T> {
+    fn capture() -> Self {
+        Some(T::capture())
+    }
+}
+
+// Unnamed threads are shown the way std shows them in panic messages.
+impl Capture<ThreadName> for String {
+    fn capture() -> Self {
+        thread::current().name().unwrap_or("<unnamed>").to_owned()
+    }
+}
+
+impl Capture<ThreadName> for Option<String> {
+    fn capture() -> Self {
+        thread::current().name().map(str::to_owned)
+    }
+}
+
+impl Capture<ThreadId> for StdThreadId {
+    fn capture() -> Self {
+        thread::current().id()
+    }
+}
+
+impl Capture<Pid> for u32 {
+    fn capture() -> Self {
+        std::process::id()
+    }
+}
+
+// Unpredictable, and distinct for every call within a process: the counter
+// tells calls apart, and a randomly keyed hash of it together with the time
+// and process id keeps them from lining up across processes.
+fn random_u64() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    hasher.write_u32(std::process::id());
+    hasher.finish()
+}
+
+impl Capture<Context> for ContextFrames {
+    fn capture() -> Self {
+        ContextFrames::capture()
+    }
+}
+
+impl Capture<ErrorId> for u64 {
+    fn capture() -> Self {
+        random_u64()
+    }
+}
+
+impl Capture<ErrorId> for u128 {
+    fn capture() -> Self {
+        (u128::from(random_u64()) << 64) | u128::from(random_u64())
+    }
+}
+
+// A short code for a person to read out, such as `3F2A-91C0-7B1E`.
+impl Capture<ErrorId> for String {
+    fn capture() -> Self {
+        let id = random_u64();
+        format!(
+            "{:04X}-{:04X}-{:04X}",
+            (id >> 32) & 0xFFFF,
+            (id >> 16) & 0xFFFF,
+            id & 0xFFFF,
+        )
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl Capture<ErrorId> for uuid::Uuid {
+    fn capture() -> Self {
+        uuid::Uuid::new_v4()
+    }
+}
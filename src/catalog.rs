@@ -0,0 +1,41 @@
+/// An entry of the `CATALOG` generated by `#[naur(catalog)]`, describing an
+/// error struct or one variant of an error enum.
+///
+/// With the `serde` feature enabled it serializes to a map of its fields, so
+/// that a catalog can be dumped as JSON for runbooks and API documentation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ErrorDescriptor {
+    /// The variant's name, or the type's name for a struct.
+    pub name: &'static str,
+    /// The code declared by `#[error(code = ...)]` or
+    /// `#[diagnostic(code(...))]`, if any.
+    pub code: Option<&'static str>,
+    /// The format string of the `#[error("...")]` attribute, as written.
+    /// Transparent errors have no message of their own.
+    pub template: Option<&'static str>,
+    /// The severity declared by `#[diagnostic(severity(...))]`: `"error"`,
+    /// `"warning"`, or `"advice"`. The `#[diagnostic(...)]` options are read
+    /// only with the `miette` feature enabled.
+    pub severity: Option<&'static str>,
+    /// The format string of the hint declared by `#[error(help = ...)]` or
+    /// `#[diagnostic(help(...))]`, as written.
+    pub help: Option<&'static str>,
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for ErrorDescriptor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        use ::serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(5))?;
+        map.serialize_entry("name", self.name)?;
+        map.serialize_entry("code", &self.code)?;
+        map.serialize_entry("template", &self.template)?;
+        map.serialize_entry("severity", &self.severity)?;
+        map.serialize_entry("help", &self.help)?;
+        map.end()
+    }
+}
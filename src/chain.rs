@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem;
+use std::ptr;
+
+// The most errors a Chain visits, counting the error it starts from.
+const MAX_DEPTH: usize = 256;
+
+/// Iterator over an error and its chain of sources, starting with the error
+/// itself.
+///
+/// Returned by the `chain()` method generated by `#[naur(chain)]`, or
+/// constructed for any error with [`Chain::new`].
+///
+/// The iterator ends early rather than loop forever on a chain which is
+/// cyclic, such as that of an error returning itself from `source()`: a
+/// source which is the same object as an error already visited ends the
+/// chain, as does reaching a depth of 256 errors.
+#[derive(Clone)]
+pub struct Chain<'a> {
+    head: &'a (dyn Error + 'static),
+    next: Option<&'a (dyn Error + 'static)>,
+    depth: usize,
+}
+
+impl<'a> Chain<'a> {
+    /// Iterates over `head` followed by each of its transitive sources.
+    pub fn new(head: &'a (dyn Error + 'static)) -> Self {
+        Chain {
+            head,
+            next: Some(head),
+            depth: 0,
+        }
+    }
+
+    // Whether the source is one of the errors already yielded, compared by
+    // address and vtable, since a source stored first in its error shares the
+    // error's address. Zero-sized errors may share both without being the same
+    // error, so only the depth limit applies to them.
+    fn visited(&self, source: &(dyn Error + 'static)) -> bool {
+        if mem::size_of_val(source) == 0 {
+            return false;
+        }
+        let mut error = Some(self.head);
+        for _ in 0..self.depth {
+            match error {
+                Some(visited) if ptr::eq(visited, source) => return true,
+                Some(visited) => error = visited.source(),
+                None => break,
+            }
+        }
+        false
+    }
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let error = self.next?;
+        self.depth += 1;
+        self.next = match error.source() {
+            Some(source) if self.depth < MAX_DEPTH && !self.visited(source) => Some(source),
+            _ => None,
+        };
+        Some(error)
+    }
+}
+
+impl FusedIterator for Chain<'_> {}
+
+impl fmt::Debug for Chain<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[doc(hidden)]
+pub fn chain_len(error: &(dyn Error + 'static)) -> usize {
+    Chain::new(error).count()
+}
+
+#[doc(hidden)]
+pub fn root_cause<'a>(error: &'a (dyn Error + 'static)) -> &'a (dyn Error + 'static) {
+    Chain::new(error).last().unwrap()
+}
+
+#[doc(hidden)]
+pub fn chain_summary(error: &(dyn Error + 'static)) -> String {
+    match Chain::new(error).skip(1).last() {
+        Some(root) => format!("{}: {}", error, root),
+        None => error.to_string(),
+    }
+}
+
+#[doc(hidden)]
+pub fn display_with_chain(
+    error: &(dyn Error + 'static),
+    formatter: &mut fmt::Formatter,
+) -> fmt::Result {
+    write!(formatter, "{}", error)?;
+    for source in Chain::new(error).skip(1) {
+        write!(formatter, "; caused by: {}", source)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,8 @@
+// Builds an error out of line, so that the code constructing it stays off the
+// hot path of the function returning it.
+#[doc(hidden)]
+#[cold]
+#[inline(never)]
+pub fn cold<T, F: FnOnce() -> T>(construct: F) -> T {
+    construct()
+}
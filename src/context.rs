@@ -0,0 +1,165 @@
+//! Breadcrumbs of what the current thread is in the middle of, recorded into
+//! the errors constructed meanwhile.
+//!
+//! A function entering a step of work names it with [`enter`], and the frame
+//! stays on the thread's stack until the returned guard is dropped. An error
+//! with a `#[context_frames]` field snapshots the stack when it is constructed
+//! by a `From` impl, a throw method, or a constructor, and a [`Report`] lists
+//! the frames of every error in the chain under a "Context:" heading, where
+//! the toolchain supports `Error::provide`.
+//!
+//! ```
+//! use thiserror::context::{self, ContextFrames};
+//! use thiserror::Error;
+//!
+//! #[derive(Error, Debug)]
+//! #[naur(constructors)]
+//! #[error("missing key {key}")]
+//! pub struct MissingKey {
+//!     key: &'static str,
+//!     #[context_frames]
+//!     frames: ContextFrames,
+//! }
+//!
+//! fn lookup(key: &'static str) -> Result<String, MissingKey> {
+//!     Err(MissingKey::new(key))
+//! }
+//!
+//! fn load_config() -> Result<String, MissingKey> {
+//!     let _frame = context::enter("loading config");
+//!     lookup("port")
+//! }
+//!
+//! let _frame = context::enter("starting server");
+//! let error = load_config().unwrap_err();
+//! assert_eq!("starting server > loading config", error.frames.to_string());
+//! ```
+//!
+//! [`Report`]: crate::Report
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::slice;
+
+thread_local! {
+    static FRAMES: RefCell<Vec<Cow<'static, str>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pushes a frame onto the current thread's context, where it stays until
+/// the returned guard is dropped.
+pub fn enter(frame: impl Into<Cow<'static, str>>) -> ContextGuard {
+    let frame = frame.into();
+    let depth = FRAMES.with(|frames| {
+        let mut frames = frames.borrow_mut();
+        frames.push(frame);
+        frames.len() - 1
+    });
+    ContextGuard {
+        depth,
+        not_send: PhantomData,
+    }
+}
+
+/// Keeps a frame entered by [`enter`] on the current thread's context.
+///
+/// Dropping the guard removes its frame, along with any frame entered after
+/// it whose guard is still alive. The guard cannot be sent to another thread,
+/// whose context it is not part of.
+#[must_use = "the frame is removed as soon as the guard is dropped"]
+pub struct ContextGuard {
+    depth: usize,
+    not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        // Fails only while the thread's locals are being destroyed, when
+        // there is no context left to remove the frame from.
+        let _ = FRAMES.try_with(|frames| frames.borrow_mut().truncate(self.depth));
+    }
+}
+
+/// The frames of the current thread's context at the time an error was
+/// constructed, outermost first, as recorded into a `#[context_frames]` field.
+///
+/// Displayed, the frames are separated by `" > "`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContextFrames {
+    frames: Vec<Cow<'static, str>>,
+}
+
+impl ContextFrames {
+    /// A snapshot of the current thread's context.
+    pub fn capture() -> Self {
+        let frames = FRAMES
+            .try_with(|frames| frames.borrow().clone())
+            .unwrap_or_default();
+        ContextFrames { frames }
+    }
+
+    /// Whether no frame had been entered.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The number of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// The frames, outermost first.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            frames: self.frames.iter(),
+        }
+    }
+}
+
+impl Display for ContextFrames {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, frame) in self.frames.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(" > ")?;
+            }
+            formatter.write_str(frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a ContextFrames {
+    type Item = &'a str;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the frames of a [`ContextFrames`], returned by
+/// [`ContextFrames::iter`].
+pub struct Iter<'a> {
+    frames: slice::Iter<'a, Cow<'static, str>>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.frames.next().map(|frame| &**frame)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.frames.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.frames.next_back().map(|frame| &**frame)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
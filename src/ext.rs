@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+use std::error::Error;
+
+/// What `#[derive(Error)]` knows about an error beyond its message and
+/// source, behind one trait object.
+///
+/// The derive implements this trait for every error, overriding each method
+/// whose attribute the error declares, so that code such as middleware can
+/// handle errors of any derived type through `&dyn ErrorExt` without knowing
+/// the concrete types. Each method has a default, for errors implementing the
+/// trait by hand.
+///
+/// ```
+/// use thiserror::{Error, ErrorExt};
+///
+/// #[derive(Error, Debug)]
+/// pub enum UploadError {
+///     #[error("storage is unavailable")]
+///     #[error(code = "UPLOAD-503")]
+///     #[error(transient)]
+///     Unavailable,
+///     #[error("file is too large")]
+///     #[error(code = "UPLOAD-413")]
+///     #[error(help = "files may be at most 10 MiB")]
+///     TooLarge,
+/// }
+///
+/// fn log(error: &dyn ErrorExt) -> String {
+///     format!(
+///         "[{}] {}: {}",
+///         error.code().unwrap_or("-"),
+///         error.kind_name().unwrap_or("-"),
+///         error,
+///     )
+/// }
+///
+/// let error = UploadError::Unavailable;
+/// assert_eq!("[UPLOAD-503] Unavailable: storage is unavailable", log(&error));
+/// assert!(error.is_transient());
+/// ```
+pub trait ErrorExt: Error {
+    /// The error code declared by `#[error(code = ...)]` or
+    /// `#[diagnostic(code(...))]`.
+    fn code(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The severity declared by `#[diagnostic(severity(...))]`: `"error"`,
+    /// `"warning"`, or `"advice"`.
+    fn severity(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The hint declared by `#[error(help = ...)]`.
+    fn help(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    /// The name of the error type, or of the current variant of an enum.
+    fn kind_name(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Whether the error is worth retrying, as declared by
+    /// `#[error(transient)]` or `#[error(permanent)]`.
+    fn is_transient(&self) -> bool {
+        false
+    }
+}
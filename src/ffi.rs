@@ -0,0 +1,20 @@
+use std::fmt::Display;
+use std::os::raw::c_char;
+use std::ptr;
+
+// Writes as much of the message as fits into `buf`, cut at a character
+// boundary and followed by a NUL, and returns the length of the whole message
+// in bytes, as snprintf does.
+#[doc(hidden)]
+pub unsafe fn message_into(error: &dyn Display, buf: *mut c_char, len: usize) -> usize {
+    let message = error.to_string();
+    if !buf.is_null() && len > 0 {
+        let mut n = message.len().min(len - 1);
+        while !message.is_char_boundary(n) {
+            n -= 1;
+        }
+        ptr::copy_nonoverlapping(message.as_ptr().cast::<c_char>(), buf, n);
+        *buf.add(n) = 0;
+    }
+    message.len()
+}
@@ -0,0 +1,80 @@
+use std::fmt::{self, Debug, Display};
+
+/// The value of an error field, as yielded by the `fields()` method generated
+/// by `#[naur(fields)]`.
+///
+/// Fields whose type implements `Display` are rendered with it; all other
+/// fields fall back to their `Debug` representation.
+#[derive(Copy, Clone)]
+pub enum FieldValue<'a> {
+    Display(&'a dyn Display),
+    Debug(&'a dyn Debug),
+}
+
+impl Display for FieldValue<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldValue::Display(value) => Display::fmt(value, formatter),
+            FieldValue::Debug(value) => Debug::fmt(value, formatter),
+        }
+    }
+}
+
+impl Debug for FieldValue<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldValue::Display(value) => formatter
+                .debug_tuple("Display")
+                .field(&format_args!("{}", value))
+                .finish(),
+            FieldValue::Debug(value) => formatter.debug_tuple("Debug").field(value).finish(),
+        }
+    }
+}
+
+/// A field of the error type `E`, identified by one of the zero-sized marker
+/// types generated by `#[naur(typed_fields)]`.
+///
+/// Used through the generated `get::<F>()` method, which looks the field up in
+/// whichever variant of the error carries it.
+pub trait ErrorField<E: ?Sized> {
+    /// The type of the field.
+    type Value: ?Sized;
+
+    /// The field's value, or None if the current variant has no such field.
+    fn get(error: &E) -> Option<&Self::Value>;
+}
+
+// Autoref specialization: `(&&FieldValueWrap(field)).field_value()` resolves
+// to the Display impl when the field's type implements Display, and to the
+// Debug impl otherwise.
+#[doc(hidden)]
+pub struct FieldValueWrap<T>(pub T);
+
+#[doc(hidden)]
+pub trait FieldValueViaDisplay<'a> {
+    fn field_value(&self) -> FieldValue<'a>;
+}
+
+impl<'a, T> FieldValueViaDisplay<'a> for &FieldValueWrap<&'a T>
+where
+    T: Display + 'a,
+{
+    fn field_value(&self) -> FieldValue<'a> {
+        FieldValue::Display(self.0)
+    }
+}
+
+#[doc(hidden)]
+pub trait FieldValueViaDebug<'a> {
+    fn field_value(&self) -> FieldValue<'a>;
+}
+
+impl<'a, T> FieldValueViaDebug<'a> for FieldValueWrap<&'a T>
+where
+    T: Debug + 'a,
+{
+    fn field_value(&self) -> FieldValue<'a> {
+        FieldValue::Debug(self.0)
+    }
+}
@@ -0,0 +1,43 @@
+use crate::FieldValue;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use std::sync::RwLock;
+
+// The dependencies of the fluent feature require a newer compiler than the
+// crate's minimum supported version anyway.
+#[allow(clippy::incompatible_msrv)]
+static BUNDLE: RwLock<Option<FluentBundle<FluentResource>>> = RwLock::new(None);
+
+/// Registers the Fluent bundle used to localize the Display message of errors
+/// that declare an `#[error(i18n = "...")]` message key.
+///
+/// Registering a bundle replaces the previous one. Messages missing from the
+/// bundle, and every message before a bundle is registered, fall back to the
+/// error's own `#[error("...")]` template.
+pub fn set_fluent_bundle(bundle: FluentBundle<FluentResource>) {
+    let mut guard = match BUNDLE.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *guard = Some(bundle);
+}
+
+#[doc(hidden)]
+pub fn localize(key: &str, fields: &[(&str, FieldValue)]) -> Option<String> {
+    let guard = match BUNDLE.read() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let bundle = guard.as_ref()?;
+    let pattern = bundle.get_message(key)?.value()?;
+    let mut args = FluentArgs::with_capacity(fields.len());
+    for (name, value) in fields {
+        args.set(
+            *name,
+            FluentValue::try_number(&value.to_string()).into_owned(),
+        );
+    }
+    let mut errors = Vec::new();
+    let message = bundle.format_pattern(pattern, Some(&args), &mut errors);
+    Some(message.into_owned())
+}
@@ -0,0 +1,72 @@
+/// Conversion of a source error, together with the other fields of the
+/// variant holding it, into the error deriving `Error`.
+///
+/// Every source-carrying struct and enum variant gets an impl for its source
+/// type, taking its other fields as the context: `()` if there are none, the
+/// field itself if there is one, or a tuple of them in declaration order.
+/// Unlike the throw methods, which are named after each variant, this lets a
+/// helper be generic over any error which its source converts into. Variants
+/// with the same source and context types as another variant of the same enum
+/// are left out, since an impl could not tell them apart.
+///
+/// ```
+/// # use std::io;
+/// use thiserror::{Error, IntoError, OrThrow};
+///
+/// #[derive(Error, Debug)]
+/// pub enum ConfigError {
+///     #[error("failed to read {0}")]
+///     Read(#[source] io::Error, String),
+/// }
+///
+/// #[derive(Error, Debug)]
+/// #[error("failed to load {path}")]
+/// pub struct AssetError {
+///     path: String,
+///     source: io::Error,
+/// }
+///
+/// fn read<E>(path: &str) -> Result<String, E>
+/// where
+///     io::Error: IntoError<E, String>,
+/// {
+///     std::fs::read_to_string(path).or_throw_with(path.to_owned())
+/// }
+///
+/// let error: ConfigError = read("/nonexistent").unwrap_err();
+/// let error: AssetError = read("/nonexistent").unwrap_err();
+/// ```
+pub trait IntoError<E, C = ()> {
+    /// Wraps this source error in `E`, with the given context for its other
+    /// fields.
+    fn into_error_with(self, context: C) -> E;
+}
+
+/// Converts the error of a `Result` by its [`IntoError`] impl.
+pub trait OrThrow<T, S> {
+    /// Wraps the error in `E`, which holds no fields besides the source.
+    fn or_throw<E>(self) -> Result<T, E>
+    where
+        S: IntoError<E>;
+
+    /// Wraps the error in `E`, with the given context for its other fields.
+    fn or_throw_with<E, C>(self, context: C) -> Result<T, E>
+    where
+        S: IntoError<E, C>;
+}
+
+impl<T, S> OrThrow<T, S> for Result<T, S> {
+    fn or_throw<E>(self) -> Result<T, E>
+    where
+        S: IntoError<E>,
+    {
+        self.map_err(|source| source.into_error_with(()))
+    }
+
+    fn or_throw_with<E, C>(self, context: C) -> Result<T, E>
+    where
+        S: IntoError<E, C>,
+    {
+        self.map_err(|source| source.into_error_with(context))
+    }
+}
@@ -0,0 +1,198 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Awaits several fallible futures concurrently, converting each one's error
+/// into a common error type through `From`.
+///
+/// The joined future resolves to `Ok` with a tuple of every output once all of
+/// the futures succeed, or to the first error produced, in which case the
+/// remaining futures are dropped. The common error type is named after `=>`,
+/// or inferred from context if omitted. Enums deriving `Error` with a `#[from]`
+/// variant for each source error type are the intended target, either written
+/// by hand or generated by [`join_error!`].
+///
+/// ```
+/// # use std::num::ParseIntError;
+/// # use std::str::Utf8Error;
+/// use thiserror::{try_join_typed, Error};
+///
+/// #[derive(Error, Debug)]
+/// pub enum LoadError {
+///     #[error("bad number")]
+///     Parse(#[from] ParseIntError),
+///     #[error("bad text")]
+///     Utf8(#[from] Utf8Error),
+/// }
+///
+/// async fn load() -> Result<(i32, String), LoadError> {
+///     let number = async { "42".parse::<i32>() };
+///     let text = async { std::str::from_utf8(b"hi").map(str::to_owned) };
+///     let (number, text) = try_join_typed!(number, text => LoadError).await?;
+///     Ok((number, text))
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_join_typed {
+    ($($future:expr),+ $(,)? => $error:ty) => {
+        $crate::__try_join_typed!(
+            @munch [] [__f0 __f1 __f2 __f3 __f4 __f5 __f6 __f7 __f8 __f9 __f10 __f11 __f12 __f13 __f14 __f15]
+            ($error) $($future,)+
+        )
+    };
+    ($($future:expr),+ $(,)?) => {
+        $crate::__try_join_typed!(
+            @munch [] [__f0 __f1 __f2 __f3 __f4 __f5 __f6 __f7 __f8 __f9 __f10 __f11 __f12 __f13 __f14 __f15]
+            (_) $($future,)+
+        )
+    };
+}
+
+/// Defines the combined error of a join: an enum deriving `Error` with one
+/// variant per error type of the joined futures.
+///
+/// Each variant holds its error as a `#[from]` source and displays it
+/// transparently, so that `try_join_typed!` converts every future's error
+/// into the enum without writing the enum and its attributes by hand.
+/// Attributes on the enum, such as docs or further derives, and docs on the
+/// variants are kept.
+///
+/// ```
+/// # use std::num::ParseIntError;
+/// # use std::str::Utf8Error;
+/// use thiserror::{join_error, try_join_typed};
+///
+/// join_error! {
+///     /// An error from any of the steps of `load`.
+///     pub enum LoadError {
+///         Parse(ParseIntError),
+///         Utf8(Utf8Error),
+///     }
+/// }
+///
+/// async fn load() -> Result<(i32, String), LoadError> {
+///     let number = async { "42".parse::<i32>() };
+///     let text = async { std::str::from_utf8(b"hi").map(str::to_owned) };
+///     try_join_typed!(number, text => LoadError).await
+/// }
+/// ```
+#[macro_export]
+macro_rules! join_error {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[doc = $doc:expr])*
+                $variant:ident($error:ty)
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(::core::fmt::Debug, $crate::Error)]
+        $vis enum $name {
+            $(
+                $(#[doc = $doc])*
+                #[error(transparent)]
+                $variant(#[from] $error),
+            )+
+        }
+    };
+}
+
+// Pairs each future with one of the identifiers in the second bracket, then
+// expands the join once every future has been named.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __try_join_typed {
+    (@munch [$($done:tt)*] [$name:ident $($names:ident)*] ($($error:tt)*) $future:expr, $($rest:tt)*) => {
+        $crate::__try_join_typed!(@munch [$($done)* ($name $future)] [$($names)*] ($($error)*) $($rest)*)
+    };
+    (@munch [$(($name:ident $future:expr))*] [$($unused:ident)*] ($($error:tt)*)) => {
+        async move {
+            $(
+                let mut $name = $crate::__private::MaybeDone::new($future);
+            )*
+            $crate::__private::PollFn::new(|cx: &mut ::core::task::Context<'_>| -> ::core::task::Poll<::core::result::Result<_, $($error)*>> {
+                let mut pending = false;
+                $(
+                    match $name.poll(cx) {
+                        ::core::task::Poll::Pending => pending = true,
+                        ::core::task::Poll::Ready(::core::result::Result::Ok(())) => {}
+                        ::core::task::Poll::Ready(::core::result::Result::Err(error)) => {
+                            return ::core::task::Poll::Ready(::core::result::Result::Err(
+                                ::core::convert::From::from(error),
+                            ));
+                        }
+                    }
+                )*
+                if pending {
+                    return ::core::task::Poll::Pending;
+                }
+                ::core::task::Poll::Ready(::core::result::Result::Ok(($($name.take(),)*)))
+            })
+            .await
+        }
+    };
+}
+
+#[doc(hidden)]
+pub enum MaybeDone<F: Future> {
+    Future(Pin<Box<F>>),
+    Done(F::Output),
+    Gone,
+}
+
+impl<F, T, E> MaybeDone<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    pub fn new(future: F) -> Self {
+        MaybeDone::Future(Box::pin(future))
+    }
+
+    pub fn poll(&mut self, cx: &mut Context) -> Poll<Result<(), E>> {
+        let future = match self {
+            MaybeDone::Future(future) => future,
+            MaybeDone::Done(_) => return Poll::Ready(Ok(())),
+            MaybeDone::Gone => panic!("try_join_typed polled after completion"),
+        };
+        match future.as_mut().poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(value)) => {
+                *self = MaybeDone::Done(Ok(value));
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(error)) => {
+                *self = MaybeDone::Gone;
+                Poll::Ready(Err(error))
+            }
+        }
+    }
+
+    pub fn take(&mut self) -> T {
+        match std::mem::replace(self, MaybeDone::Gone) {
+            MaybeDone::Done(Ok(value)) => value,
+            _ => panic!("try_join_typed output taken before completion"),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct PollFn<F>(F);
+
+impl<F> PollFn<F> {
+    pub fn new(f: F) -> Self {
+        PollFn(f)
+    }
+}
+
+impl<F, T> Future for PollFn<F>
+where
+    F: FnMut(&mut Context) -> Poll<T> + Unpin,
+{
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<T> {
+        (self.0)(cx)
+    }
+}
@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// The error from parsing a `#[naur(kind)]` kind out of a string which is
+/// neither the name of one of the variants nor one of their error codes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParseKindError {
+    input: String,
+}
+
+impl ParseKindError {
+    /// An error for the given unrecognized string.
+    pub fn new(input: impl Into<String>) -> Self {
+        ParseKindError {
+            input: input.into(),
+        }
+    }
+
+    /// The string which was not recognized.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl Display for ParseKindError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "unrecognized error kind `{}`", self.input)
+    }
+}
+
+impl Error for ParseKindError {}
@@ -57,6 +57,21 @@
 //!     - `#[error("{0}")]`&ensp;⟶&ensp;`write!("{}", self.0)`
 //!     - `#[error("{var:?}")]`&ensp;⟶&ensp;`write!("{:?}", self.var)`
 //!     - `#[error("{0:?}")]`&ensp;⟶&ensp;`write!("{:?}", self.0)`
+//!     - `#[error("{var.field}")]`&ensp;⟶&ensp;`write!("{}", self.var.field)`
+//!     - `#[error("{var.len() + 1}")]`&ensp;⟶&ensp;`write!("{}", self.var.len() + 1)`
+//!     - `#[error("{var:>width$}")]`&ensp;⟶&ensp;`write!("{:>1$}", self.var, self.width)`
+//!
+//!   A field declared with a raw identifier, such as `r#type`, may be written
+//!   either as `{r#type}` or as `{type}`.
+//!
+//!   A `Path` or `PathBuf` field, which does not implement Display itself, is
+//!   shown through its `display()` method, including under a format spec such as
+//!   `{path:>20}`.
+//!
+//!   `{self:?}` interpolates the whole error with its Debug representation,
+//!   which suits small enums whose variant names say it all, and an expression
+//!   such as `{self.kind()}` may call its methods. A plain `{self}` is rejected,
+//!   since the Display impl would call itself.
 //!
 //!   These shorthands can be used together with any additional format args,
 //!   which may be arbitrary expressions. For example:
@@ -98,6 +113,17 @@
 //!   }
 //!   ```
 //!
+//!   Messages that do not fit in a format string, such as ones with
+//!   conditionals or pluralization, can come from a function instead.
+//!   `#[error(fmt = path)]` calls `path` with a reference to each field in
+//!   declaration order, followed by the `Formatter`.
+//!
+//!   Messages kept together in one place, for review or translation, can be
+//!   named by path with `#[error(const = messages::IO_FAILURE)]`. The
+//!   constant's text is only known once it is compiled, so its `{field}` and
+//!   `{0}` placeholders are filled in when the error is displayed, and format
+//!   specs are not supported.
+//!
 //! - A `From` impl is generated for each variant containing a `#[from]`
 //!   attribute.
 //!
@@ -118,6 +144,11 @@
 //!   # };
 //!   ```
 //!
+//!   Two variants may hold the same source type, but only one of them can derive
+//!   `From` for it. Mark the source of the others `#[from(skip)]`, which makes it
+//!   the source as `#[source]` would, so that each variant still gets its own
+//!   `throw_*` method to pick it explicitly at the call site.
+//!
 //! - The Error trait's `source()` method is implemented to return whichever
 //!   field has a `#[source]` attribute or is named `source`, if any. This is
 //!   for identifying the underlying lower level error that caused your error.
@@ -127,6 +158,12 @@
 //!
 //!   Any error type that implements `std::error::Error` or dereferences to `dyn
 //!   std::error::Error` will work as a source.
+//!   This includes `anyhow::Error` and `eyre::Report`, which do not implement the
+//!   trait themselves: `source()` returns them with their own chain behind them,
+//!   and the throw methods are implemented on `Result<T, anyhow::Error>` as for
+//!   any other source. In the other direction, both convert from a derived error
+//!   through their own `From` impls, keeping its chain of sources, so no feature
+//!   is needed for either crate.
 //!
 //!   ```rust
 //!   # use std::fmt::{self, Display};
@@ -146,6 +183,13 @@
 //!   # }
 //!   ```
 //!
+//! - A variant or struct whose only field is a well-known std error type, with
+//!   no `#[from]` or `#[source]` attribute, gets a warning suggesting one of
+//!   them, since the field would never be reported by `Error::source()`. A
+//!   message which interpolates the field, as in `#[error("bad port: {0}")]`,
+//!   shows the error on purpose and gets no warning, and `#[error(no_source)]`
+//!   on the struct or variant silences the warning explicitly.
+//!
 //! - The Error trait's `provide()` method is implemented to provide whichever
 //!   field has a type named `Backtrace`, if any, as a
 //!   `std::backtrace::Backtrace`.
@@ -179,6 +223,28 @@
 //!   # };
 //!   ```
 //!
+//! - `provide()` needs a nightly toolchain, so on stable the generated
+//!   `backtrace()` method reaches the backtrace instead. It returns the error's
+//!   own backtrace field, or else the backtrace of its source when the source is
+//!   an error derived with a backtrace of its own. An error gets the method when
+//!   it has a backtrace field, which may be a source marked `#[backtrace]`.
+//!
+//!   ```rust
+//!   # const IGNORE: &str = stringify! {
+//!   #[derive(Error, Debug)]
+//!   pub enum AppError {
+//!       Db(#[from] #[backtrace] DbError),  // DbError has a backtrace field
+//!       Io {
+//!           #[from]
+//!           source: io::Error,
+//!           backtrace: Backtrace,
+//!       },
+//!   }
+//!
+//!   // if let Some(backtrace) = error.backtrace() { ... }
+//!   # };
+//!   ```
+//!
 //! - Errors may use `error(transparent)` to forward the source and Display
 //!   methods straight through to an underlying error without adding an
 //!   additional message. This would be appropriate for enums that need an
@@ -223,35 +289,1819 @@
 //!   }
 //!   ```
 //!
-//! - See also the [`anyhow`] library for a convenient single error type to use
-//!   in application code.
+//!   A transparent error may also carry context which it does not display, such
+//!   as where it was raised. With more than one field, the error delegates to its
+//!   source field, the one marked `#[source]` or named `source`, and the others
+//!   are filled in by its throw method.
 //!
-//!   [`anyhow`]: https://github.com/dtolnay/anyhow
-
-#![doc(html_root_url = "https://docs.rs/thiserror/1.0.50")]
-#![allow(
-    clippy::module_name_repetitions,
-    clippy::needless_lifetimes,
-    clippy::return_self_not_must_use,
-    clippy::wildcard_imports
-)]
-#![cfg_attr(error_generic_member_access, feature(error_generic_member_access))]
-
-mod aserror;
-mod display;
-#[cfg(error_generic_member_access)]
-mod provide;
-
-pub use thiserror_impl::*;
-
-// Not public API.
-#[doc(hidden)]
-pub mod __private {
-    #[doc(hidden)]
-    pub use crate::aserror::AsDynError;
-    #[doc(hidden)]
-    pub use crate::display::AsDisplay;
-    #[cfg(error_generic_member_access)]
-    #[doc(hidden)]
-    pub use crate::provide::ThiserrorProvide;
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(transparent)]
+//!   pub struct RequestError {
+//!       source: anyhow::Error,
+//!       request_id: u64,
+//!   }
+//!
+//!   // result.throw_request(request_id)?
+//!   ```
+//!
+//! - Errors may declare an HTTP status and a machine-readable code using
+//!   `#[error(status = ..., code = "...")]` on the struct, on the enum as a
+//!   default for every variant, or on individual variants. This generates
+//!   `status_code()` (500 for variants without a status) and `code()`
+//!   accessors. With the `axum` feature enabled, the error additionally
+//!   implements axum's `IntoResponse`, rendering the status, code and Display
+//!   message as a JSON body. The `actix` feature likewise implements
+//!   actix-web's `ResponseError`, with the same status and JSON body.
+//!
+//!   ```
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum ApiError {
+//!       #[error("user {0} not found")]
+//!       #[error(status = 404, code = "USER_NOT_FOUND")]
+//!       NotFound(u64),
+//!       #[error("internal error")]
+//!       Internal,
+//!   }
+//!   #
+//!   # assert_eq!(ApiError::NotFound(1).status_code(), 404);
+//!   # assert_eq!(ApiError::Internal.status_code(), 500);
+//!   ```
+//!
+//! - No two variants may declare the same `code`, which is checked when the error
+//!   is compiled. `#[naur(code_range = 4000..5000)]` on the struct or enum
+//!   further requires every code of the type to fall within the range, the
+//!   number being the code's trailing digits so that `"E4001"` counts as 4001.
+//!   Teams allotted a block of codes then find collisions at build time rather
+//!   than in production.
+//!
+//! - Errors declaring a status or code also get `to_problem()`, returning a
+//!   `thiserror::Problem`: an RFC 7807 problem document whose `type` is the error
+//!   code (or `about:blank`), `status` the declared status, and `detail` the
+//!   Display message. With the `serde` feature enabled it serializes as
+//!   `application/problem+json`.
+//!
+//! - With the `tonic` feature enabled, errors that declare a gRPC status code
+//!   using `#[error(grpc = NotFound)]` (any variant of `tonic::Code`) get a
+//!   `From<MyError> for tonic::Status` impl which uses the Display message as
+//!   the status message. Variants without a declared code map to `Internal`.
+//!
+//! - With the `pyo3` feature enabled, errors that declare a Python exception
+//!   type using `#[error(py = pyo3::exceptions::PyValueError)]` get a
+//!   `From<MyError> for pyo3::PyErr` impl raising that exception with the
+//!   Display message. Any exception type with a `new_err` constructor works,
+//!   including those made by `create_exception!`. Variants without a declared
+//!   type raise `RuntimeError`.
+//!
+//! - Errors that declare an I/O error kind using `#[error(io_kind = NotFound)]`
+//!   (any variant of `std::io::ErrorKind`) get a `From<MyError> for io::Error`
+//!   impl wrapping the error with that kind, for libraries whose APIs speak
+//!   `io::Error`, such as `Read` and `Write` impls. The kind may be declared on
+//!   the struct, the enum, or a variant; variants without one map to `Other`.
+//!   Without converting, `io_kind()` returns the declared kind, or `None` for a
+//!   variant without one, so that network and file layers can classify errors
+//!   for retries and timeouts in the standard taxonomy. The conversion takes its
+//!   kind from `io_kind()`, so the two always agree.
+//!
+//! - `#[error(transient)]` and `#[error(permanent)]` on the struct, enum, or a
+//!   variant generate `is_transient()`, which tells retry logic whether the error
+//!   is worth another attempt. Variants without a classification are permanent
+//!   unless the enum says otherwise. `#[error(transient = source)]` asks the
+//!   source instead, through an `is_transient()` of its own, which suits
+//!   transparent variants wrapping another classified error.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   # #[derive(Error, Debug)]
+//!   # #[error("request failed")]
+//!   # #[error(transient)]
+//!   # pub struct RequestError;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum FetchError {
+//!       #[error("timed out")]
+//!       #[error(transient)]
+//!       Timeout,
+//!       #[error("not found")]
+//!       NotFound,
+//!       #[error(transparent)]
+//!       #[error(transient = source)]
+//!       Request(#[from] RequestError),
+//!   }
+//!   ```
+//!
+//! - With the `serde` feature enabled, `#[naur(serialize)]` on the struct or
+//!   enum generates a `serde::Serialize` impl producing a structured
+//!   representation of the error: `variant` (the variant name, or the type
+//!   name for structs), the rendered `message`, the `code` if any, and
+//!   `sources`, the rendered messages of the whole source chain.
+//!
+//! - With the `schemars` feature enabled, `#[naur(serialize)]` additionally
+//!   generates a `schemars::JsonSchema` impl describing the serialized form, with
+//!   one alternative per variant pinning its `variant` name and `code`, so API
+//!   documentation tooling can include the error shapes.
+//!
+//! - With the `arbitrary` feature enabled, `#[naur(arbitrary)]` on the struct or
+//!   enum generates an `arbitrary::Arbitrary` impl which picks a variant and fills
+//!   in its fields from the fuzzer's input, so that serialization and rendering
+//!   code can be fuzzed with every kind of error. A field whose type is not
+//!   `Arbitrary` is built as a plain error holding a generated message if it is
+//!   an `io::Error` or a boxed `dyn Error`, as a disabled `Backtrace`, or else
+//!   with its `Default` value.
+//!
+//! - `#[naur(subset(PublicError: InvalidInput, NotFound, Internal))]` on an enum
+//!   generates a second enum `PublicError` containing only the listed variants,
+//!   plus `From<MyError> for PublicError`. Every unlisted variant folds into
+//!   `Internal`, so internal error detail cannot leak through a public API by
+//!   accident. The `Internal` variant is generated if the original enum does not
+//!   have one. The subset implements `Error` and `Display` like the original, but
+//!   gets no throw methods, which would be ambiguous with the original's.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[naur(subset(PublicError: NotFound, Internal))]
+//!   pub enum MyError {
+//!       #[error("user {0} not found")]
+//!       NotFound(u64),
+//!       #[error("database at {host} unreachable")]
+//!       Database { host: String, source: io::Error },
+//!   }
+//!   ```
+//!
+//! - `#[naur(aggregate)]` generates a companion `MyErrorAggregate` type for
+//!   collecting several errors at once. It implements `Error`, `FromIterator`,
+//!   `Extend` and `From<MyError>`, and exposes the collected errors through
+//!   `errors()`. The collection defaults to `Vec<MyError>`. Use
+//!   `#[naur(aggregate(container = SmallVec<[Self; 2]>))]` for a different one,
+//!   where `Self` stands for the error type. With a fixed-capacity container
+//!   such as `ArrayVec<Self, 4>`, `push` panics once the container is full.
+//!
+//! - `#[naur(fields)]` generates a `fields()` method yielding the name and value
+//!   of each field of the error, other than its source and backtrace, as
+//!   `(&'static str, thiserror::FieldValue)` pairs for structured logging.
+//!   Values render with `Display` when the field type implements it, and with
+//!   `Debug` otherwise.
+//!
+//! - `try_join_typed!(a, b => MyError)` awaits several fallible futures
+//!   concurrently. Each future's error converts into `MyError` through `From`, so
+//!   an enum with a `#[from]` variant per source error replaces the
+//!   `map_err(Into::into)` calls around a join. `join_error!` generates that
+//!   enum from the list of error types, with a transparent variant for each.
+//!
+//!   ```rust
+//!   # use std::num::ParseIntError;
+//!   # use std::str::Utf8Error;
+//!   # use thiserror::{join_error, try_join_typed};
+//!   #
+//!   join_error! {
+//!       pub enum LoadError {
+//!           Parse(ParseIntError),
+//!           Utf8(Utf8Error),
+//!       }
+//!   }
+//!   #
+//!   # async fn load() -> Result<(), LoadError> {
+//!   # let number = async { "42".parse::<i32>() };
+//!   # let text = async { std::str::from_utf8(b"hi") };
+//!
+//!   let (number, text) = try_join_typed!(number, text => LoadError).await?;
+//!   # Ok(())
+//!   # }
+//!   ```
+//!
+//! - With the `tracing` feature enabled, `#[throws(trace)]` on the struct, enum,
+//!   or a variant makes the generated `From` impls and throw methods emit a
+//!   `tracing` event whenever they construct the error. The event carries the
+//!   variant name, the code if any, the fields other than the source, and the
+//!   Display message. The level defaults to error; choose another with
+//!   `#[throws(trace = "warn")]`.
+//!
+//! - `#[naur(chain)]` generates `chain_len()`, the number of errors in the source
+//!   chain counting the error itself, and `chain_summary()`, the error's message
+//!   followed by the message of the root cause. Full chain strings are too long
+//!   for metrics labels and alert titles, but top plus root fits. It also
+//!   generates `chain()`, a [`Chain`] iterator over the error followed by each of
+//!   its transitive sources, and `root_cause()`, the last error of that chain,
+//!   which is the error itself if it has no source. Finally
+//!   `source_downcast::<T>()` returns the first source anywhere in the chain
+//!   which is a `T`, and `direct_source_downcast::<T>()` checks only the
+//!   immediate source. The chain stops at a source which is an error already
+//!   visited, or after 256 errors, so that a `source()` implementation which
+//!   loops back on itself cannot hang the code walking the chain, including
+//!   `Report`.
+//!
+//! - `#[naur(boxed_conv)]` generates `boxed()`, which turns the error into a
+//!   `Box<dyn Error + Send + Sync>` for APIs that accept any error, without an
+//!   `as` cast that spells out the trait object. Converting with `.into()`
+//!   or `?` already works through std's blanket `From` impl.
+//!
+//! - With the `log` feature enabled, `#[throws(log)]` makes the generated `From`
+//!   impls and throw methods emit a `log` record whenever they construct the
+//!   error, reading `message: source` when the error has a source. The level
+//!   defaults to error; choose another with `#[throws(log = "warn")]`.
+//!
+//! - `#[naur(try_from(validate = path::to_fn))]` on a field generates `TryFrom`
+//!   instead of `From` for a value that only sometimes makes a valid error. The
+//!   function is called with a reference to the value and returns a `bool`. When
+//!   it returns false, the conversion fails and hands the value back.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   fn is_error_status(status: &u16) -> bool {
+//!       (400..600).contains(status)
+//!   }
+//!
+//!   #[derive(Error, Debug)]
+//!   pub enum FetchError {
+//!       #[error("server responded with {0}")]
+//!       Status(#[naur(try_from(validate = is_error_status))] u16),
+//!   }
+//!   ```
+//!
+//! - `#[from_value]` on a field that is not an error, in a variant with no other
+//!   fields, derives `From` for the field's type. `#[from]` is reserved for the
+//!   source, which rules it out for variants that merely wrap a value.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum ConfigError {
+//!       #[error("invalid port {0}")]
+//!       InvalidPort(#[from_value] u16),
+//!   }
+//!
+//!   fn check_port(port: u16) -> Result<u16, ConfigError> {
+//!       if port < 1024 {
+//!           return Err(port.into());
+//!       }
+//!       Ok(port)
+//!   }
+//!   ```
+//!
+//! - With the `metrics` feature enabled, `#[error(metric = "db.query.failed")]`
+//!   on the struct, enum, or a variant makes the generated `From` impls and throw
+//!   methods increment that `metrics` counter whenever they construct the error,
+//!   labeled with the name of the variant. A metric on a variant takes precedence
+//!   over one on the enum.
+//!
+//! - `#[throws(on_create = path::to::hook)]` on the struct or enum calls the
+//!   function with `&Self` right after any generated `From` impl, throw method,
+//!   or constructor builds the error, before the tracing, log, and metrics hooks.
+//!   It is a single place to count errors, attach custom telemetry, or set a
+//!   debugger breakpoint, without instrumenting every call site. Errors built by
+//!   hand with a struct literal do not go through it.
+//!
+//!   ```rust
+//!   # use std::num::ParseIntError;
+//!   # use thiserror::Error;
+//!   #
+//!   fn on_error(error: &AppError) {
+//!       eprintln!("created: {}", error);
+//!   }
+//!
+//!   #[derive(Error, Debug)]
+//!   #[throws(on_create = on_error)]
+//!   pub enum AppError {
+//!       #[error("invalid port")]
+//!       Port(#[from] ParseIntError),
+//!   }
+//!   ```
+//!
+//! - With `#[throws(pipe)]` on the struct, the enum, or a variant, each throw
+//!   method gets a `pipe_throw_*` counterpart on `thiserror::Pipeline` which runs
+//!   one fallible stage on the current value and maps the stage's error into the
+//!   variant. Layered code becomes one chain of `?` instead of alternating calls
+//!   and `.throw_*` adapters.
+//!
+//!   ```rust
+//!   # use std::num::{ParseIntError, TryFromIntError};
+//!   # use thiserror::{Error, Pipeline};
+//!   #
+//!   # #[derive(Error, Debug)]
+//!   # #[throws(pipe)]
+//!   # pub enum ConfigError {
+//!   #     #[error("invalid number in {key}")]
+//!   #     Parse { key: String, source: ParseIntError },
+//!   #     #[error("port out of range")]
+//!   #     Range(#[source] TryFromIntError),
+//!   # }
+//!   #
+//!   # fn port(raw: &str) -> Result<u16, ConfigError> {
+//!   let port = Pipeline::new(raw)
+//!       .pipe_throw_parse(str::parse::<i64>, "port".to_owned())?
+//!       .pipe_throw_range(u16::try_from)?
+//!       .into_inner();
+//!   # Ok(port)
+//!   # }
+//!   ```
+//!
+//! - With `#[throws(poll)]`, the throw method is also there on
+//!   `Poll<Result<T, E>>`, through a sibling trait named like
+//!   `ReadErrorPollThrows`. It maps only the error of a ready result and leaves
+//!   `Poll::Pending` alone, so a hand-written `Future` or `AsyncRead` can convert
+//!   its error in one step.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use std::task::{Context, Poll};
+//!   # use thiserror::Error;
+//!   #
+//!   # #[derive(Error, Debug)]
+//!   # #[error("failed to read {path}")]
+//!   # #[throws(poll)]
+//!   # pub struct ReadError {
+//!   #     path: String,
+//!   #     source: io::Error,
+//!   # }
+//!   #
+//!   # struct Inner;
+//!   #
+//!   # impl Inner {
+//!   #     fn poll_read(&mut self, _cx: &mut Context) -> Poll<io::Result<usize>> {
+//!   #         Poll::Pending
+//!   #     }
+//!   # }
+//!   #
+//!   # struct Reader {
+//!   #     inner: Inner,
+//!   #     path: String,
+//!   # }
+//!   #
+//!   # impl Reader {
+//!   fn poll_read(&mut self, cx: &mut Context) -> Poll<Result<usize, ReadError>> {
+//!       self.inner.poll_read(cx).throw_read(self.path.clone())
+//!   }
+//!   # }
+//!   ```
+//!
+//! - The throw methods are named after the struct or variant in snake case, with
+//!   each capital starting a word and a trailing `Error` trimmed, so
+//!   `ReadError` gets `throw_read`. `#[naur(throws_naming(...))]` adjusts this:
+//!   `keep_suffix` keeps the `Error`, `acronyms` treats a run of capitals as one
+//!   word so that `HTTPRequest` gets `throw_http_request` rather than
+//!   `throw_h_t_t_p_request`, and `full` prefixes each variant's name with the
+//!   enum's, as in `throw_store_not_found`, to keep enums with like-named
+//!   variants apart. `#[naur(throws_prefix = "or_")]` replaces the `throw_`
+//!   prefix to match house style, giving `or_read`, `or_read_with`, and
+//!   `pipe_or_read` under `#[throws(pipe)]`. The throw traits are private to the
+//!   module of the error unless `#[throws(vis = "pub(crate)")]` gives them a
+//!   visibility, so that code elsewhere can import them.
+//!
+//! - With the `otel` feature enabled, every error constructed by a generated
+//!   `From` impl or throw method is recorded as an `exception` event on the active
+//!   OpenTelemetry span, with `exception.type`, `exception.message`, and the
+//!   `error.code` if one is declared. No attribute is needed. The span's status is
+//!   left unchanged, since the caller may still handle the error.
+//!
+//! - `#[error(prefix = "config: ")]` on an enum starts the message of every
+//!   variant with the same text, written once instead of repeated in each
+//!   `#[error("...")]`. Transparent variants get it too. `#[error(no_prefix)]` on
+//!   a variant leaves its message as is.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(prefix = "config: ")]
+//!   pub enum ConfigError {
+//!       #[error("missing key {0}")]
+//!       MissingKey(String),
+//!       #[error(transparent)]
+//!       Io(#[from] io::Error),
+//!       #[error("no configuration found")]
+//!       #[error(no_prefix)]
+//!       NotFound,
+//!   }
+//!   ```
+//!
+//! - `#[error(same_as = OtherVariant)]` on a variant reuses the message template
+//!   of another variant of the same enum instead of copying it. The template's
+//!   placeholders are resolved against the fields of the variant sharing it, by
+//!   name for named fields and by position for tuple fields, so the two variants
+//!   need the fields which the template mentions. A mismatch is reported at
+//!   compile time.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum StoreError {
+//!       #[error("failed to read {key} from {backend}")]
+//!       Read { key: String, backend: String },
+//!       #[error(same_as = Read)]
+//!       ReadReplica { key: String, backend: String },
+//!   }
+//!   ```
+//!
+//! - `#[error(default = "{variant} failed")]` on an enum gives every variant
+//!   without an `#[error(...)]` of its own a message from the same template, with
+//!   `{variant}` standing for the variant's name. Other placeholders refer to the
+//!   variant's fields as usual.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(default = "{variant} failed")]
+//!   pub enum JobError {
+//!       Fetch,  // "Fetch failed"
+//!       Parse,  // "Parse failed"
+//!       #[error("upload of {0} bytes failed")]
+//!       Upload(usize),
+//!   }
+//!   ```
+//!
+//! - `#[naur(auto_transparent)]` on an enum makes every variant whose only field
+//!   is its source behave as `#[error(transparent)]`, so an enum made mostly of
+//!   thin wrappers needs no attribute on each of them. A variant with a message of
+//!   its own keeps it.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   # #[derive(Error, Debug)]
+//!   # #[error("invalid config")]
+//!   # pub struct ConfigError;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[naur(auto_transparent)]
+//!   pub enum AppError {
+//!       Io(#[from] io::Error),
+//!       Config(#[from] ConfigError),
+//!       #[error("shutting down")]
+//!       Shutdown,
+//!   }
+//!   ```
+//!
+//! - `#[naur(templates)]` exposes the format strings of the `#[error("...")]`
+//!   attributes as written, for localization and lint tooling that audits
+//!   placeholders and message style. A struct gets `MESSAGE_TEMPLATE: &str` and
+//!   an enum gets `MESSAGE_TEMPLATES: &[(&str, &str)]`, pairing each variant name
+//!   with its template.
+//!
+//! - `#[naur(catalog)]` generates `CATALOG: &[thiserror::ErrorDescriptor]`, one
+//!   entry per variant (or one for a struct) with its name, code, message
+//!   template, severity, and help text. Serialize it with the `serde` feature to
+//!   dump a machine-readable list of every error a type can produce, for
+//!   runbooks and API docs. Severity and `#[diagnostic(...)]` codes and help are
+//!   filled in with the `miette` feature enabled.
+//!
+//! - `#[naur(manifest)]` generates `fn manifest() -> thiserror::TypeManifest`,
+//!   the full structure of the type: each variant with its fields and their
+//!   types, its message template, code, HTTP status, and help. Where the catalog
+//!   lists what errors a type can produce, the manifest describes their shape, so
+//!   a tool binary can serialize it with the `serde` feature and generate
+//!   documentation or client SDKs from it.
+//!
+//! - With the `registry` feature enabled, every non-generic error type also
+//!   registers its catalog descriptors at link time, without `#[naur(catalog)]`.
+//!   `thiserror::registered_errors()` then iterates every error struct and
+//!   variant linked into the binary, with its name, code, message template,
+//!   severity, and help. An application can check for colliding codes at startup,
+//!   or print every error it may report for a `--list-errors` flag.
+//!
+//!   ```rust
+//!   # #[cfg(feature = "registry")]
+//!   fn list_errors() {
+//!       for descriptor in thiserror::registered_errors() {
+//!           println!("{}\t{}", descriptor.code.unwrap_or("-"), descriptor.name);
+//!       }
+//!   }
+//!   ```
+//!
+//! - Every derived error also implements `thiserror::ErrorExt`, whose `code()`,
+//!   `severity()`, `help()`, `kind_name()` and `is_transient()` return what the
+//!   error's attributes declare, or else a default. Middleware can then handle
+//!   errors of any derived type through `&dyn ErrorExt`, without knowing the
+//!   concrete types. `kind_name()` is the name of the struct or of the current
+//!   variant. `help()` is left at its default where the hint needs bounds beyond
+//!   those of the Error impl.
+//!
+//!   ```rust
+//!   # use thiserror::ErrorExt;
+//!   #
+//!   fn respond(error: &dyn ErrorExt) -> String {
+//!       match error.code() {
+//!           Some(code) => format!("{}: {}", code, error),
+//!           None => error.to_string(),
+//!       }
+//!   }
+//!   ```
+//!
+//! - `#[naur(from_any)]` on the `#[from]` field of one variant, typically a
+//!   `Box<dyn Error + Send + Sync>`, turns that variant into a catch-all. The
+//!   derive declares a marker trait named after the enum, such as
+//!   `AppErrorCatchAll`. Any error type implementing the marker converts into the
+//!   variant through `?`. A single blanket `From` impl for every error would
+//!   conflict with the other `#[from]` variants, which is why each type opts in.
+//!
+//!   ```rust
+//!   # use std::error::Error;
+//!   # use std::io;
+//!   # use std::num::ParseIntError;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum AppError {
+//!       #[error("invalid number")]
+//!       Parse(#[from] ParseIntError),
+//!       #[error(transparent)]
+//!       Other(#[from] #[naur(from_any)] Box<dyn Error + Send + Sync>),
+//!   }
+//!
+//!   impl AppErrorCatchAll for io::Error {}
+//!   ```
+//!
+//! - `#[error(catch_all)]` on a variant holding a `Box<dyn Error + Send + Sync>`
+//!   sets it up as the enum's "something else went wrong" arm. The box is the
+//!   source and gets the `From` impl, and without a message of its own the
+//!   variant is transparent. Its throw method, `throw_other()` for a variant
+//!   named `Other`, works on any `Result` whose error converts into the box,
+//!   which is every `Error + Send + Sync` type. Add `#[naur(from_any)]` to its
+//!   field to also catch errors with `?`.
+//!
+//!   ```rust
+//!   # use std::error::Error;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum AppError {
+//!       #[error("not found")]
+//!       NotFound,
+//!       #[error(catch_all)]
+//!       Other(Box<dyn Error + Send + Sync>),
+//!   }
+//!
+//!   fn port(text: &str) -> Result<u16, AppError> {
+//!       text.parse().throw_other()
+//!   }
+//!   ```
+//!
+//! - `#[error(panic)]` on a variant generates `MyError::catch_unwind(f)`, which
+//!   runs the closure and turns a panic in it into that variant. The variant
+//!   holds the payload, as a `String` with the panic's message or as the
+//!   `Box<dyn Any + Send>` itself, optionally followed by the location of the
+//!   panic as a `String` or `Option<String>`. With named fields, these are called
+//!   `message` or `payload`, and `location`. The location is recorded by a panic
+//!   hook, which is global to the process and therefore only installed by an
+//!   explicit call to `thiserror::install_panic_hook()`, typically at the start
+//!   of `main`. It passes each panic on to the previous hook. Without it, the
+//!   location is `None`, or `"<unknown>"` as a `String`.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   # struct Plugin;
+//!   # struct Output;
+//!   # impl Plugin {
+//!   #     fn run(&self) -> Output {
+//!   #         Output
+//!   #     }
+//!   # }
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum PluginError {
+//!       #[error(panic)]
+//!       #[error("plugin panicked at {location}: {message}")]
+//!       Panicked { message: String, location: String },
+//!   }
+//!
+//!   fn init() {
+//!       // Global to the process, so installed once at startup.
+//!       thiserror::install_panic_hook();
+//!   }
+//!
+//!   fn run(plugin: &Plugin) -> Result<Output, PluginError> {
+//!       PluginError::catch_unwind(|| plugin.run())
+//!   }
+//!   ```
+//!
+//! - `#[error(doc)]` uses the first line of the doc comment as the message, with
+//!   the same interpolation as an explicit format string, so that documentation
+//!   and error message cannot drift apart.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum FetchError {
+//!       /// Request to {url} timed out after {secs}s
+//!       #[error(doc)]
+//!       Timeout { url: String, secs: u64 },
+//!   }
+//!   ```
+//!
+//! - Every error with a derived Display impl also gets
+//!   `write_message(&self, out: &mut impl fmt::Write)`, which renders the message
+//!   into a caller-provided buffer instead of allocating a `String`, and
+//!   `message_len_hint()`, the length of that message in bytes, for sizing the
+//!   buffer up front. `message()` returns the message as a
+//!   `Cow<'static, str>`, borrowed without allocating for a struct or variant
+//!   whose message interpolates nothing, and formatted only otherwise.
+//!   `static_message()` returns that message as an `Option<&'static str>`, and
+//!   `None` for a message that interpolates fields, without formatting anything.
+//!
+//! - With the `fluent` feature enabled, `#[error(i18n = "errors-open")]` on a
+//!   struct or a variant names a message in the Fluent bundle registered through
+//!   `thiserror::set_fluent_bundle`. Display renders that message with the error's
+//!   fields as Fluent variables, and falls back to the `#[error("...")]` template
+//!   when no bundle is registered or the bundle lacks the key. The generated
+//!   `message_key()` returns the declared key.
+//!
+//! - Every error gets `variant_name()`, the name of the struct or of the current
+//!   variant as a `&'static str`, and `metric_label()`, which returns the same
+//!   name. They never include field values, which keeps them safe to use as
+//!   metric labels and log fields. The tracing and metrics hooks label their
+//!   events with the name.
+//!
+//! - With the `serde` feature enabled, `#[naur(wire)]` on the struct or enum
+//!   generates a `MyErrorWire` struct holding the variant name, message, code, and
+//!   source messages as plain strings, along with `From<&MyError>`, Display,
+//!   Error, Serialize, and Deserialize impls. It serializes the same way as
+//!   `#[naur(serialize)]`, so services can keep or receive a copy of an error whose
+//!   sources are not serializable.
+//!
+//! - `#[error(help = "try running with --force")]` on a struct or a variant
+//!   attaches an actionable hint, returned by the generated
+//!   `help() -> Option<Cow<'static, str>>`. The hint interpolates fields the same
+//!   way as the message, and is borrowed rather than allocated when it has no
+//!   placeholders.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum CliError {
+//!       #[error("{path:?} already exists")]
+//!       #[error(help = "run with --force to overwrite {path}")]
+//!       AlreadyExists { path: String },
+//!   }
+//!   ```
+//!
+//! - `#[error(suggestion = "...")]` and `#[error(url = "...")]` work the same way
+//!   as `help`, generating `suggestion()` and `url()`, for troubleshooting steps
+//!   and links that do not belong in the message. A `Report` prints them as
+//!   sections of their own after the sources, where the toolchain supports
+//!   `Error::provide`. Any other section can be added with
+//!   `report.section("Note", body)`.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("failed to connect to {host}")]
+//!   #[error(suggestion = "check that {host} is reachable")]
+//!   #[error(url = "https://docs.example.com/errors/connect")]
+//!   pub struct ConnectError {
+//!       host: String,
+//!   }
+//!   ```
+//!
+//! - `#[naur(extend_with = my_macro)]` on the struct or enum invokes `my_macro!`
+//!   with the parsed error model, so that an organization can generate its own
+//!   impls on top of the derive. The attribute can be repeated to invoke several
+//!   macros. The macro receives the following input, where a struct appears as a
+//!   single variant whose path is the struct itself:
+//!
+//!   ```text
+//!   kind = enum,
+//!   ident = StoreError,
+//!   impl_generics = [...],
+//!   ty_generics = [...],
+//!   where_clause = [...],
+//!   variants = [{
+//!       ident = Read,
+//!       path = [StoreError::Read],
+//!       message = Some("failed to read {path}"),
+//!       code = Some("STORE_READ"),
+//!       status = None,
+//!       fields = [{
+//!           member = path,
+//!           ty = [String],
+//!           source = false,
+//!           from = false,
+//!           backtrace = false,
+//!       }, ...],
+//!   }, ...],
+//!   ```
+//!
+//! - With the `miette` feature enabled, `#[diagnostic(...)]` attributes make the
+//!   derive also implement `miette::Diagnostic`, keeping the throw methods and
+//!   other generated code. On a struct, enum, or variant they accept
+//!   `code(path)`, `help("...")` with field interpolation, `severity(Warning)`,
+//!   and `url("...")`. A variant's options take precedence over the enum's. On a
+//!   field they accept `label` or `label = "..."` for a `SourceSpan` to highlight,
+//!   and `source_code` for the input the spans point into. Without an explicit
+//!   code or help, the diagnostic uses `#[error(code = ...)]` and
+//!   `#[error(help = ...)]`. Call the trait methods as `Diagnostic::code(&error)`,
+//!   because the generated inherent `code()` and `help()` take precedence.
+//!   Without the feature, `#[diagnostic]` is left alone for miette's own derive.
+//!
+//! - `#[error(alt = "...")]` next to the message of a struct or variant gives it
+//!   a second, more detailed template, which formatting with `{:#}` writes
+//!   instead. A UI can show the terse `{}` message while logs record the full
+//!   detail, without a second Display impl. It takes the same fields and
+//!   expressions as the message, and cannot be combined with `#[naur(alt_chain)]`.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("request timed out")]
+//!   #[error(alt = "request to {url} timed out after {elapsed_ms} ms")]
+//!   pub struct Timeout {
+//!       url: String,
+//!       elapsed_ms: u64,
+//!   }
+//!   ```
+//!
+//! - `#[naur(alt_chain)]` on the struct or enum makes formatting with `{:#}`
+//!   append the source chain to the message, as in
+//!   `failed to load config; caused by: failed to read app.toml; caused by: no
+//!   such file`. Plain `{}` output stays the same. Generic errors do not support
+//!   this attribute.
+//!
+//! - `#[naur(single_line)]` on the struct or enum keeps the Display message on
+//!   one line, for line-oriented log pipelines. Each line break written by an
+//!   interpolated field or a source, along with the indentation after it, is
+//!   collapsed into a single space. Set it in `naur.toml` to cover every error of
+//!   a workspace.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[naur(single_line)]
+//!   #[error("failed to parse {path}:\n{detail}")]
+//!   pub struct ParseError {
+//!       path: String,
+//!       detail: String,
+//!   }
+//!
+//!   // "failed to parse app.toml: line 3 unexpected `=`"
+//!   ```
+//!
+//! - `#[naur(typed_fields)]` on the struct or enum generates a zero-sized marker
+//!   type for each named field, in a module named after the error such as
+//!   `store_error_fields`, or in the module given by
+//!   `#[naur(typed_fields = name)]`. It also generates `get::<F>()`, which returns
+//!   the field identified by marker `F` from whichever variant carries it, or
+//!   None. Fields that share a name must have the same type in every variant.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use std::path::PathBuf;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[naur(typed_fields)]
+//!   pub enum StoreError {
+//!       #[error("failed to read {path:?}")]
+//!       Read { path: PathBuf, source: io::Error },
+//!       #[error("failed to write {path:?}")]
+//!       Write { path: PathBuf },
+//!       #[error("store is locked")]
+//!       Locked,
+//!   }
+//!
+//!   fn report(error: &StoreError) {
+//!       if let Some(path) = error.get::<store_error_fields::Path>() {
+//!           eprintln!("while accessing {}", path.display());
+//!       }
+//!   }
+//!   ```
+//!
+//! - A `naur.toml` file in the crate's directory, or in any directory above it
+//!   up to the root of the workspace, sets defaults for the container options of
+//!   every derive. Each entry in the `[naur]` and `[throws]` tables turns the
+//!   option of the same name on or off, or gives the value of an option which
+//!   takes one, such as the throw methods' prefix, the throw traits'
+//!   visibility, or the function which captures backtraces. Options written on
+//!   a type take precedence, and `#[naur(no_defaults)]` opts a type out
+//!   entirely.
+//!
+//!   ```toml
+//!   [naur]
+//!   fields = true
+//!   chain = true
+//!   throws_prefix = "or_"
+//!   backtrace_capture = "my_crate::trace::capture"
+//!
+//!   [throws]
+//!   trace = "warn"
+//!   vis = "pub(crate)"
+//!   ```
+//!
+//! - [`Report<E>`] wraps an error for printing to a person. Returning
+//!   `Result<(), Report<MyError>>` from `main` prints the message, each source
+//!   on its own numbered line, and the location of the `?` that produced the
+//!   report, without depending on anyhow or eyre. On toolchains that support
+//!   `Error::provide`, a backtrace provided by the error is printed as well.
+//!
+//! - `thiserror::ChainFormatter` renders any error over several lines: its
+//!   message, then each source on a numbered line indented beneath it, with the
+//!   later lines of a multi-line message indented to match. `.width(80)` wraps
+//!   long lines between words and `.indent(2)` changes the indentation. `Report`
+//!   prints in the same format, and wraps at the width of the format string, as
+//!   in `{:80?}`.
+//!
+//!   ```rust
+//!   # use thiserror::ChainFormatter;
+//!   # let error = std::io::Error::new(std::io::ErrorKind::Other, "oh no!");
+//!   eprintln!("{}", ChainFormatter::new(&error).width(80));
+//!   ```
+//!
+//! - With the `color` feature, `Report` colors its output when standard error is
+//!   a terminal that supports it: the message in bold red, the headings in bold,
+//!   and the number of each source in yellow. `NO_COLOR` and `CLICOLOR_FORCE` are
+//!   respected, and `.color(false)` on the report turns coloring off.
+//!   `ChainFormatter` colors only when asked to with `.color(true)`.
+//!
+//! - `naur!("failed to open {path}")` builds an [`AdHocError`] from a
+//!   format string. It is an escape hatch for one-off errors that do not deserve a
+//!   variant of their own. The error can be the `#[source]` of a derived error, or
+//!   be boxed into the `Box<dyn Error + Send + Sync>` payload of a catch-all
+//!   variant.
+//!
+//! - `#[error(context)]` on one enum variant with a source field and a
+//!   `msg: String` field generates a `{Enum}Context` extension trait for every
+//!   `Result` whose error is `Error + Send + Sync + 'static`. Its `.context(...)`
+//!   and `.with_context(|| ...)` methods box the error into that variant along
+//!   with the message. This gives anyhow-style ergonomics while keeping the
+//!   concrete error type.
+//!
+//!   ```rust
+//!   # use std::fs;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum AppError {
+//!       #[error("config is missing key {0}")]
+//!       MissingKey(String),
+//!       #[error("{msg}")]
+//!       #[error(context)]
+//!       Context {
+//!           msg: String,
+//!           source: Box<dyn std::error::Error + Send + Sync>,
+//!       },
+//!   }
+//!
+//!   fn load() -> Result<String, AppError> {
+//!       let config = fs::read_to_string("/etc/app.toml").context("reading config")?;
+//!       Ok(config)
+//!   }
+//!   ```
+//!
+//! - A `#[cause]` field holds an error which led to this one besides its source,
+//!   for operations such as a merge which genuinely have two upstream failures.
+//!   `source()` still returns the primary source, and the derive generates
+//!   `causes()`, an iterator over the `#[cause]` fields as `&dyn Error`, leaving
+//!   out an `Option` which is `None`. Where the toolchain supports
+//!   `Error::provide`, `ChainFormatter` and `Report` list them under "Also caused
+//!   by:" after the sources.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("failed to merge {left} into {right}")]
+//!   pub struct MergeError {
+//!       left: String,
+//!       right: String,
+//!       #[source]
+//!       read_left: io::Error,
+//!       #[cause]
+//!       read_right: io::Error,
+//!   }
+//!   ```
+//!
+//! - A `#[related]` field holding a collection of errors, such as `Vec<E>`,
+//!   represents failures reported alongside the error rather than as its source.
+//!   The derive generates `related()`, an iterator over them as `&dyn Error`,
+//!   and appends their count to the Display message, as in
+//!   `failed to import users.csv (2 related errors)`. With the `miette` feature,
+//!   a type that implements `Diagnostic` also forwards them as related
+//!   diagnostics.
+//!
+//! - An `#[errors]` field holding a collection of errors, such as `Vec<E>`, makes
+//!   the struct an aggregate of them. Without a message of its own, its Display
+//!   summarizes them as in `2 errors occurred: a; b`, or with `{:#}` lists each
+//!   on a line of its own. `source()` returns the first of the errors and the
+//!   derive generates `errors()`, an iterator over them.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   # #[derive(Error, Debug)]
+//!   # #[error("invalid field")]
+//!   # pub struct FieldError;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub struct ValidationErrors {
+//!       #[errors]
+//!       errors: Vec<FieldError>,
+//!   }
+//!   ```
+//!
+//! - `#[error(up(app::AppError::Database))]` on top of a struct or enum
+//!   generates `From<ThisError> for app::AppError`, which wraps the error in the
+//!   named parent variant so that `?` flows up through the layers. On an enum
+//!   variant with a single field, it instead moves that field into the named
+//!   parent variant, overriding the enum's own target for that variant. The
+//!   attribute can be repeated to convert into several parent types.
+//!
+//! - `#[derive(ErrorSet)]` composes per-module errors into a service-level enum.
+//!   Each `#[set]` variant holds one member error. It delegates Display and
+//!   `source()` to the member as if it were `#[error(transparent)]`, and gets a
+//!   `From` impl as if its field were `#[from]`. It also gets the usual throw
+//!   method on results of the member error. Variants without `#[set]` take the
+//!   same attributes as under `#[derive(Error)]`.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::{Error, ErrorSet};
+//!   #
+//!   # #[derive(Error, Debug)]
+//!   # #[error("database error")]
+//!   # pub struct DbError;
+//!   #
+//!   #[derive(ErrorSet, Debug)]
+//!   pub enum ApiError {
+//!       #[set]
+//!       Db(DbError),
+//!       #[set]
+//!       Io(io::Error),
+//!       #[error("unauthorized")]
+//!       Unauthorized,
+//!   }
+//!   ```
+//!
+//! - `#[naur(kind)]` on an enum generates a fieldless `{Enum}Kind` enum that
+//!   mirrors its variants, and a `kind()` method returning the current variant's
+//!   kind. The kind implements Display (the variant name), Debug, Clone, Copy,
+//!   PartialEq, Eq, and Hash. Retry policies and metrics can then match on the
+//!   variant without destructuring its payload. The kind also implements
+//!   `FromStr`, accepting the variant name or the variant's
+//!   `#[error(code = "...")]`, so that tooling reading codes out of logs can map
+//!   them back to kinds. An unrecognized string is a `thiserror::ParseKindError`.
+//!   Going the other way, the enum implements `From<{Enum}Kind>` when none of its
+//!   variants have fields, and otherwise `TryFrom<{Enum}Kind>`, which builds the
+//!   fieldless variants and gives back the kind of any other. Code can then
+//!   classify a failure by kind first and only construct the error later.
+//!   `{Enum}Kind::ALL` lists every kind in declaration order, and
+//!   `{Enum}Kind::iter()` iterates over them, with `name()` and `code()` on
+//!   each, so that a test can check that every variant has a help text or a
+//!   documented code.
+//!
+//! - On an enum with a primitive representation such as `#[repr(u16)]`, a
+//!   `discriminant()` method returns the current variant's numeric code, as
+//!   written with `= 404` or counted on from the previous variant. Together with
+//!   `#[naur(kind)]`, the kind enum gets the same representation and codes, and
+//!   implements `TryFrom<u16>` so that wire protocols can turn a code back into a
+//!   kind, with the unknown code as the error.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[repr(u16)]
+//!   #[naur(kind)]
+//!   pub enum WireError {
+//!       #[error("not found: {0}")]
+//!       NotFound(String) = 404,
+//!       #[error("gone")]
+//!       Gone,  // 405
+//!   }
+//!
+//!   // WireErrorKind::try_from(error.discriminant()) == Ok(error.kind())
+//!   ```
+//!
+//! - `#[naur(ffi)]` on an enum generates the surface a C ABI needs: a
+//!   `#[repr(C)]` `{Enum}Code` enum numbering the variants from 1, leaving 0
+//!   for success, and a `to_ffi_code()` method returning the current variant's
+//!   code. The unsafe `message_into(buf, len)` writes the Display message into
+//!   a caller-provided C buffer, truncated and NUL-terminated, and returns the
+//!   message's full length the way `snprintf` does.
+//!
+//! - `#[naur(accessors)]` on an enum generates `is_*()` for each variant, with
+//!   the variant name in snake case. Variants with fields also get `as_*()` and
+//!   `into_*()`, which return the variant's fields by reference or by value. A
+//!   variant with one field returns it directly, and a variant with several
+//!   fields returns them as a tuple in declaration order, as in
+//!   `as_invalid_msg() -> Option<(&String, &i32, &io::Error)>`. To take an error
+//!   apart, `into_source()` returns its source, in an `Option` on an enum. If the
+//!   enum's variants hold different types of source, it is returned as a
+//!   `Box<dyn Error + Send + Sync>`, so each of them must be `Send + Sync +
+//!   'static`; for a source of a generic type parameter, `into_source()` is only
+//!   available where it is. `into_parts()` on a struct, or `into_*_parts()` for
+//!   each variant, returns the fields other than the backtrace with the source
+//!   moved last, so that recovery code can retry with the original error and its
+//!   context. On a struct, only these two are generated.
+//!
+//! - `#[naur(constructors)]` generates an associated function for each variant,
+//!   named after it in snake case, which builds the error from its fields:
+//!   `MyError::invalid_msg(source, msg, value)`. The source comes first, then
+//!   the other fields in declaration order, and a backtrace is captured rather
+//!   than passed. On a struct the function is `new`. This is for building an
+//!   error to send somewhere, without a `Result` to call a throw method on.
+//!
+//! - With the `test-helpers` feature, which is meant to be enabled only in
+//!   `[dev-dependencies]`, each enum also gets `expect_*()` methods. They return
+//!   the variant's fields like `as_*()` does, or panic showing the error that was
+//!   found instead. The `assert_variant!` macro checks an error against a pattern
+//!   and an optional guard, without a `match` in every test.
+//!
+//!   ```rust
+//!   # const IGNORE: &str = stringify! {
+//!   let error = load("app.toml").unwrap_err();
+//!   assert_variant!(error, ConfigError::InvalidValue { key, .. } if key == "retries");
+//!   let (key, value) = error.expect_invalid_value();
+//!   # };
+//!   ```
+//!
+//! - `#[naur(eq = "kind")]` on an enum implements `PartialEq` and `Eq` by
+//!   comparing only which variant two errors are, ignoring their fields.
+//!   `#[naur(eq = "fields")]` implements `PartialEq` comparing the variant and
+//!   its fields, leaving out the source, backtrace, and related errors, which are
+//!   rarely comparable. Either makes error enums that hold an `io::Error`
+//!   usable with `assert_eq!`.
+//!
+//!   `#[naur(eq = "code")]` on an enum whose variants each have an
+//!   `#[error(code = ...)]` implements `PartialEq`, `Eq`, and `Hash` by the code
+//!   alone, so that errors can be deduplicated or counted by code in a `HashMap`.
+//!   Two variants with the same code are rejected at compile time.
+//!
+//! - `#[error(arc_source)]` on a source field declared as `Arc<T>` lets the
+//!   generated `From` impls and throw methods accept a plain `T`, wrapping it in
+//!   the `Arc`, while `source()` returns the `T` itself. Written on a struct, an
+//!   enum, or a variant, it applies to the source field of each. With every
+//!   source shared this way, the error can derive `Clone` and be handed to
+//!   several tasks waiting on the same failure.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use std::sync::Arc;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug, Clone)]
+//!   #[error("failed to fetch {url}")]
+//!   pub struct FetchError {
+//!       url: String,
+//!       #[source]
+//!       #[error(arc_source)]
+//!       source: Arc<io::Error>,
+//!   }
+//!   ```
+//!
+//! - A source field declared as `Arc<dyn Error + Send + Sync>` is already shared
+//!   and needs no attribute. `source()` looks through the `Arc` to the error it
+//!   holds, and the throw method works on any `Result` whose error converts into
+//!   a `Box<dyn Error + Send + Sync>`, arcing the box. `#[from]` derives
+//!   `From` for the `Arc` itself; add `#[naur(from_any)]` to also catch any error
+//!   with `?` by arcing it. The `Arc` has to be written out rather than hidden
+//!   behind a type alias.
+//!
+//!   ```rust
+//!   # use std::error::Error;
+//!   # use std::net::TcpStream;
+//!   # use std::sync::Arc;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug, Clone)]
+//!   #[error("request {id} failed")]
+//!   pub struct RequestError {
+//!       id: u32,
+//!       source: Arc<dyn Error + Send + Sync>,
+//!   }
+//!
+//!   fn send(id: u32) -> Result<(), RequestError> {
+//!       TcpStream::connect("127.0.0.1:80").throw_request(id)?;
+//!       Ok(())
+//!   }
+//!   ```
+//!
+//! - `#[source(ty = path::RealType)]` names the type which the `From` impls and
+//!   throw traits of a source field are implemented for, while the field keeps
+//!   its declared type. Use it where the field is declared through a type alias
+//!   or an associated type: the derive only sees the tokens as written, and two
+//!   variants whose sources look different but are the same type would otherwise
+//!   get conflicting impls.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   # pub trait Backend {
+//!   #     type Error;
+//!   # }
+//!   #
+//!   # pub struct Disk;
+//!   #
+//!   # impl Backend for Disk {
+//!   #     type Error = io::Error;
+//!   # }
+//!   #
+//!   type StoreIoError = <Disk as Backend>::Error;
+//!
+//!   #[derive(Error, Debug)]
+//!   pub enum StoreError {
+//!       #[error("failed to write {0}")]
+//!       Write(String, #[source(ty = io::Error)] StoreIoError),
+//!       #[error("failed to read {0}")]
+//!       Read(String, #[source] io::Error),
+//!   }
+//!   ```
+//!
+//! - `std::sync::PoisonError` borrows the lock through its guard, so it cannot be
+//!   a source. `#[source(poison)]` on a `thiserror::Poisoned` field keeps the
+//!   poison error's message and the name of its guard type instead, and derives
+//!   `From<PoisonError<G>>` for every guard type, so `?` works on the result of
+//!   locking any `Mutex` or `RwLock`.
+//!
+//!   ```rust
+//!   # use std::sync::Mutex;
+//!   # use thiserror::{Error, Poisoned};
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum CacheError {
+//!       #[error("cache lock poisoned")]
+//!       Poisoned(#[source(poison)] Poisoned),
+//!   }
+//!
+//!   fn len(cache: &Mutex<Vec<u8>>) -> Result<usize, CacheError> {
+//!       Ok(cache.lock()?.len())
+//!   }
+//!   ```
+//!
+//! - `#[error(boxed { ... })]` on a variant keeps a large variant from growing
+//!   every `Result` that carries the enum. The braces list the variant's fields,
+//!   which the derive puts in a generated payload struct, named by the `Box` the
+//!   variant holds. The payload gets the variant's message and implements `Error`
+//!   and `Debug`. Display and `source()` go through the box to the payload's, a
+//!   `From` impl takes the payload unboxed, and the variant's throw methods take
+//!   the fields other than the source and build the payload and its box.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum RequestError {
+//!       #[error("request to {url} timed out")]
+//!       #[error(boxed {
+//!           pub url: String,
+//!           pub headers: Vec<(String, String)>,
+//!           #[source]
+//!           pub cause: io::Error,
+//!       })]
+//!       Timeout(Box<TimeoutDetails>),
+//!       #[error("cancelled")]
+//!       Cancelled,
+//!   }
+//!
+//!   // send(&request).throw_timeout(url, headers)?
+//!   ```
+//!
+//!   A payload written by hand, such as another error deriving `Error`, is
+//!   boxed with a plain `#[error(boxed)]` on a variant whose only field is a
+//!   `Box` of it, like `Timeout(Box<Timeout>)`. Then `?` on the payload's own
+//!   throw methods and `From` impls reaches the enum without a `Box::new` in
+//!   sight.
+//!
+//! - `#[naur(max_size = 24)]` fails the build if the error takes more than 24
+//!   bytes, keeping every `Result` that carries it from quietly growing. On an
+//!   enum the fields of each variant are checked first, so the error names the
+//!   variant which is too large, typically a candidate for `#[error(boxed)]`.
+//!
+//! - `#[naur(assert = "Send + Sync + 'static")]` fails the build unless every
+//!   field satisfies the given bounds, so that an error which stops being `Send`,
+//!   say by gaining an `Rc` field, is reported at that field rather than in some
+//!   distant async code. Fields whose type involves a type parameter are not
+//!   checked, as whether they satisfy the bounds depends on the parameter.
+//!
+//! - `#[naur(cold)]` makes the generated `From` impls, throw methods, and
+//!   context methods build the error inside a `#[cold]`, `#[inline(never)]`
+//!   function, keeping the code that constructs errors out of the happy path of
+//!   every function returning them.
+//!
+//! - A message which interpolates nothing, like `#[error("pool is closed")]`, is
+//!   written with a single `write_str` rather than through `write!`. Enums also
+//!   get a `MESSAGES` constant pairing the name of each such variant with its
+//!   message, for callers that want the text without an instance of the error.
+//!
+//! - `#[naur(minimal)]` generates only the `Error`, `Display`, and `From` impls,
+//!   and whatever the variants' own attributes ask for. The throw methods and
+//!   their traits, `provide`, and the inherent helpers such as `variant_name()`
+//!   are left out, which keeps expansion of very large error enums fast.
+//!   Defaults from `naur.toml` do not apply, and container options which would
+//!   add output are rejected.
+//!
+//! - A `#[deprecated]` variant passes its deprecation, note included, on to the
+//!   throw methods which construct it, so their callers are warned as well.
+//!   `From` impls cannot be deprecated in Rust, and stay as they are.
+//!
+//! - `#[throws(must_use)]` marks the generated `throw_*`, `throw_*_with` and
+//!   `pipe_throw_*` methods `#[must_use]`, so that a dropped result is reported
+//!   against the method by name. `#[throws(doc = "...")]` documents the generated
+//!   traits, and `#[throws(doc(hidden))]` hides them from rustdoc. Either may be
+//!   given on the struct or enum, or on a variant to override its enum.
+//!
+//! - `#[throws(feature = "...")]` compiles the throw traits and their impls, along
+//!   with any context struct, `IntoError` and tuple `From` impls, only when the
+//!   named cargo feature of the crate deriving the error is enabled. A library can
+//!   then offer its throw methods as an optional API, and dependents which leave
+//!   the feature off do not build them. It may be given on the struct or enum, or
+//!   on a variant to override its enum.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("cannot read config")]
+//!   #[throws(feature = "fluent-errors")]
+//!   pub struct ConfigError {
+//!       source: io::Error,
+//!   }
+//!   ```
+//!
+//! - `#[throws(context_struct)]` has the throw methods take a single struct of the
+//!   fields other than the source, rather than one parameter per field, so that
+//!   call sites name what they pass. For a variant `InvalidMsg` of `ConfigError`
+//!   the struct is `ConfigErrorInvalidMsgContext`, and for a struct `ReadError`
+//!   it is `ReadErrorContext`, so that like-named variants of different enums do
+//!   not collide. It has the same fields in the same order, and it converts from
+//!   a tuple of them. The struct has the same visibility as the throw traits,
+//!   private to the module unless `#[throws(vis = "...")]` says otherwise.
+//!
+//!   ```rust
+//!   # use std::num::ParseIntError;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum ConfigError {
+//!       #[error("invalid {msg}: {value}")]
+//!       #[throws(context_struct)]
+//!       InvalidMsg {
+//!           msg: String,
+//!           value: i32,
+//!           source: ParseIntError,
+//!       },
+//!   }
+//!
+//!   fn parse(msg: String, text: &str) -> Result<i32, ConfigError> {
+//!       let value = 0;
+//!       text.parse().throw_invalid_msg(ConfigErrorInvalidMsgContext { msg, value })
+//!   }
+//!   ```
+//!
+//! - `#[throws(tuple_from)]` also implements `From` a tuple of the source followed
+//!   by the other fields, for each variant with fields besides its source, so
+//!   that code which only knows `Into`, such as a generic helper, can construct
+//!   the error the same way its throw methods do.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[throws(tuple_from)]
+//!   pub enum ConfigError {
+//!       #[error("invalid {msg}: {value}")]
+//!       InvalidMsg {
+//!           msg: String,
+//!           value: i32,
+//!           source: io::Error,
+//!       },
+//!   }
+//!
+//!   fn invalid(io_err: io::Error, msg: String, value: i32) -> Result<(), ConfigError> {
+//!       Err((io_err, msg, value).into())
+//!   }
+//!   ```
+//!
+//! - Each source-carrying struct or variant also implements
+//!   `thiserror::IntoError<MyError, C>` for its source type, where `C` is the
+//!   other fields: `()`, the one field, or a tuple of them. Helpers can then be
+//!   generic over every error their source converts into, using `or_throw()` and
+//!   `or_throw_with(context)` from `thiserror::OrThrow`. Variants of one enum
+//!   which share their source and context types are left out.
+//!
+//!   ```rust
+//!   # use std::{fs, io};
+//!   # use thiserror::{IntoError, OrThrow};
+//!   #
+//!   fn load<E>(path: &str) -> Result<String, E>
+//!   where
+//!       io::Error: IntoError<E, String>,
+//!   {
+//!       fs::read_to_string(path).or_throw_with(path.to_owned())
+//!   }
+//!   ```
+//!
+//! - A field marked `#[sensitive]` is printed as `[REDACTED]` wherever the
+//!   message would show it, or as other text with `#[sensitive = "***"]`. Add
+//!   `#[naur(redacted_debug)]`, in place of `#[derive(Debug)]`, to also have a
+//!   Debug impl which shows the field the same way. The field is redacted in the
+//!   `#[naur(fields)]` accessors, the `#[throws(trace)]` event, and a localized
+//!   message too. Only a custom `#[error(fmt = ...)]` function, being your own
+//!   code, still receives the value.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error)]
+//!   #[naur(redacted_debug)]
+//!   #[error("login failed for {user} with password {password}")]
+//!   pub struct LoginError {
+//!       user: String,
+//!       #[sensitive]
+//!       password: String,
+//!   }
+//!   ```
+//!
+//! - `#[naur(stable_debug)]`, in place of `#[derive(Debug)]`, implements Debug
+//!   for snapshot tests, whose output should not churn on irrelevant details.
+//!   Named fields are printed sorted by name, backtraces are left out, and the
+//!   source and any `#[cause]` are shown as their declared type with their
+//!   message, dropping the `(os error N)` which varies between platforms.
+//!   `#[sensitive]` fields are redacted as with `#[naur(redacted_debug)]`.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error)]
+//!   #[naur(stable_debug)]
+//!   #[error("failed to open {path}")]
+//!   pub struct OpenError {
+//!       path: String,
+//!       source: io::Error,
+//!   }
+//!
+//!   // OpenError { path: "app.toml", source: io::Error("No such file or directory") }
+//!   ```
+//!
+//! - `#[naur(debug_chain)]`, in place of `#[derive(Debug)]`, implements Debug the
+//!   way `anyhow` does: the error's message, then each of its sources under
+//!   `Caused by:`, then a captured backtrace where the toolchain supports
+//!   `Error::provide`. This is what `main` prints when it returns the error, and
+//!   is the same as a `thiserror::Report` shows, less its location.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error)]
+//!   #[naur(debug_chain)]
+//!   #[error("failed to load config")]
+//!   pub struct ConfigError {
+//!       #[from]
+//!       source: io::Error,
+//!   }
+//!   ```
+//!
+//! - Where the toolchain supports `Error::provide`, a field marked `#[provide]` is
+//!   provided by its type alongside any backtrace, so that a caller can retrieve
+//!   context such as a request ID with `std::error::request_ref`. A field of type
+//!   `Option<T>` provides a `T` when it holds one.
+//!
+//!   ```rust
+//!   # const IGNORE: &str = stringify! {
+//!   #[derive(Error, Debug)]
+//!   #[error("request failed")]
+//!   pub struct RequestError {
+//!       #[provide]
+//!       id: RequestId,
+//!       source: io::Error,
+//!   }
+//!   # };
+//!   ```
+//!
+//! - `#[backtrace(capture = path::to::capture)]` on a backtrace field makes the
+//!   generated `From` impls and throw methods fill it by calling the given
+//!   function instead of `std::backtrace::Backtrace::capture()`, such as to
+//!   capture cheaper or differently filtered traces. The field holds whatever
+//!   type the function returns, and `provide()` provides it as that type.
+//!   `#[naur(backtrace_capture = path::to::capture)]` on the struct or enum sets
+//!   the function for every backtrace field without one of its own.
+//!
+//!   ```rust
+//!   # const IGNORE: &str = stringify! {
+//!   #[derive(Error, Debug)]
+//!   #[error("failed to read {path}")]
+//!   pub struct ReadError {
+//!       path: String,
+//!       source: io::Error,
+//!       #[backtrace(capture = my_crate::trace::capture)]
+//!       trace: my_crate::trace::Trace,
+//!   }
+//!   # };
+//!   ```
+//!
+//! - `#[timestamp]` on a `SystemTime` or `Instant` field, or an `Option` of one,
+//!   makes the generated `From` impls, throw methods, and constructors fill it
+//!   with the time at which the error is constructed, so it is no longer a
+//!   parameter of the throw methods. This records when a failure happened
+//!   rather than when it was finally logged.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use std::time::SystemTime;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("request {id} timed out")]
+//!   pub struct TimeoutError {
+//!       id: u64,
+//!       source: io::Error,
+//!       #[timestamp]
+//!       at: SystemTime,
+//!   }
+//!   ```
+//!
+//! - `#[capture(thread_name)]`, `#[capture(thread_id)]`, and `#[capture(pid)]`
+//!   fill a field the same way with the current thread's name, as a `String`
+//!   (`"<unnamed>"` for unnamed threads) or an `Option<String>`, its `ThreadId`,
+//!   or the process id as a `u32`, instead of formatting them into messages by
+//!   hand.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("job {id} failed")]
+//!   pub struct JobError {
+//!       id: u64,
+//!       source: io::Error,
+//!       #[capture(thread_name)]
+//!       thread: String,
+//!       #[capture(pid)]
+//!       pid: u32,
+//!   }
+//!   ```
+//!
+//! - `thiserror::context::enter("loading config")` pushes a frame onto the
+//!   current thread's context until the returned guard is dropped. A
+//!   `#[context_frames]` field of type `thiserror::context::ContextFrames` is
+//!   filled the same way with a snapshot of those frames, giving breadcrumbs of
+//!   what the program was doing without threading strings through every call.
+//!   Where the toolchain supports `Error::provide`, a `Report` lists the frames
+//!   under a "Context:" heading.
+//!
+//!   ```rust
+//!   # use thiserror::context::{self, ContextFrames};
+//!   # use thiserror::Error;
+//!   #
+//!   # pub struct Config;
+//!   #
+//!   # fn read_config_file() -> String {
+//!   #     String::new()
+//!   # }
+//!   #
+//!   # fn parse(_: &str) -> Result<Config, MissingKey> {
+//!   #     Ok(Config)
+//!   # }
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("missing key {0}")]
+//!   pub struct MissingKey(&'static str, #[context_frames] ContextFrames);
+//!
+//!   fn load_config() -> Result<Config, MissingKey> {
+//!       let _frame = context::enter("loading config");
+//!       let config = parse(&read_config_file())?;
+//!       Ok(config)
+//!   }
+//!   ```
+//!
+//! - Fields declared as `PhantomData<T>` are left out of the throw methods'
+//!   parameters and filled in with `PhantomData` by the generated `From` impls and
+//!   constructors, so a marker for a generic parameter costs callers nothing.
+//!   `#[throws(phantom)]` does the same for a field whose type hides the
+//!   `PhantomData` behind an alias.
+//!
+//!   ```rust
+//!   # use std::fs;
+//!   # use std::io;
+//!   # use std::marker::PhantomData;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("query {query} failed")]
+//!   pub struct QueryError<Db> {
+//!       query: String,
+//!       source: io::Error,
+//!       db: PhantomData<Db>,
+//!   }
+//!
+//!   fn run<Db>(query: &str) -> Result<(), QueryError<Db>> {
+//!       fs::read(query).throw_query(query.to_owned())?;
+//!       Ok(())
+//!   }
+//!   ```
+//!
+//! - `#[throws(default)]` on a field fills it with `Default::default()` wherever
+//!   the error is constructed for you, and leaves it out of the throw methods'
+//!   parameters. `#[from]` is then allowed on a source whose other fields are
+//!   all marked this way, so that a variant with extra context does not have to
+//!   be split off just to derive `From`.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum SyncError {
+//!       #[error("read failed after {attempts} attempts")]
+//!       Read {
+//!           #[from]
+//!           source: io::Error,
+//!           #[throws(default)]
+//!           attempts: u32,
+//!       },
+//!   }
+//!   ```
+//!
+//! - `#[error_id]` on a field fills it with a new identifier for every error
+//!   constructed, which `error_id()` returns and the message can show, for a
+//!   person to quote when reporting a failure. A `String` gets a short code like
+//!   `3F2A-91C0-7B1E`, a `u64` or `u128` a random number, and, with the `uuid`
+//!   feature enabled, a `Uuid` a random v4 UUID. `#[error_id(generate = path)]`
+//!   calls a function of your own instead. On an enum, `error_id()` returns an
+//!   `Option` unless every variant has an identifier.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("upload failed, reference {reference}")]
+//!   pub struct UploadError {
+//!       source: io::Error,
+//!       #[error_id]
+//!       reference: String,
+//!   }
+//!   ```
+//!
+//! - On a generic type, the `Error` and `Display` impls are bounded by what the
+//!   fields need, inferred from the message and the source. Where that inference
+//!   goes wrong, such as on a recursive type whose inferred bounds never resolve,
+//!   `#[error(bound = "...")]` on the struct or enum gives the where clause
+//!   explicitly, in place of the inferred bounds. It applies to the throw methods
+//!   too.
+//!
+//!   ```rust
+//!   # use std::fmt::{Debug, Display};
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(bound = "T: Debug + Display + 'static")]
+//!   pub enum Tree<T> {
+//!       #[error("leaf {0}")]
+//!       Leaf(T),
+//!       #[error("node")]
+//!       Node(#[source] Box<Tree<T>>),
+//!   }
+//!   ```
+//!
+//! - A framework which re-exports the derive to its users, who then do not
+//!   depend on this crate under its own name, sets `#[naur(crate = "...")]` to
+//!   the path of its re-export. The generated code then refers to the runtime
+//!   crate through that path instead of `thiserror::`.
+//!
+//!   ```rust
+//!   # mod my_framework {
+//!   #     pub(crate) use thiserror as naur;
+//!   # }
+//!   #
+//!   #[derive(my_framework::naur::Error, Debug)]
+//!   #[naur(crate = "my_framework::naur")]
+//!   #[error("request failed")]
+//!   pub struct RequestError;
+//!   ```
+//!
+//! - See also the [`anyhow`] library for a convenient single error type to use
+//!   in application code.
+//!
+//!   [`anyhow`]: https://github.com/dtolnay/anyhow
+
+#![doc(html_root_url = "https://docs.rs/thiserror/1.0.50")]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::needless_lifetimes,
+    clippy::return_self_not_must_use,
+    clippy::wildcard_imports
+)]
+#![cfg_attr(error_generic_member_access, feature(error_generic_member_access))]
+
+#[cfg(feature = "actix")]
+mod actix;
+mod adhoc;
+mod aggregate;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod aserror;
+#[cfg(feature = "test-helpers")]
+mod assert;
+#[cfg(feature = "axum")]
+mod axum;
+mod backtrace;
+mod capture;
+mod catalog;
+mod chain;
+mod cold;
+pub mod context;
+mod display;
+mod ext;
+mod ffi;
+mod fields;
+#[cfg(feature = "fluent")]
+mod fluent;
+mod into_error;
+mod join;
+mod kind;
+mod manifest;
+mod message;
+#[cfg(feature = "otel")]
+mod otel;
+mod panic;
+mod pipeline;
+mod poison;
+mod problem;
+#[cfg(error_generic_member_access)]
+mod provide;
+#[cfg(feature = "registry")]
+mod registry;
+mod related;
+mod report;
+#[cfg(any(feature = "actix", feature = "axum"))]
+mod response;
+#[cfg(feature = "schemars")]
+mod schemars;
+mod sensitive;
+#[cfg(feature = "serde")]
+mod serde;
+mod template;
+
+pub use crate::adhoc::AdHocError;
+pub use crate::catalog::ErrorDescriptor;
+pub use crate::chain::Chain;
+pub use crate::ext::ErrorExt;
+pub use crate::fields::{ErrorField, FieldValue};
+#[cfg(feature = "fluent")]
+pub use crate::fluent::set_fluent_bundle;
+pub use crate::into_error::{IntoError, OrThrow};
+pub use crate::kind::ParseKindError;
+pub use crate::manifest::{FieldManifest, TypeManifest, VariantManifest};
+pub use crate::panic::install_panic_hook;
+pub use crate::pipeline::Pipeline;
+pub use crate::poison::Poisoned;
+pub use crate::problem::Problem;
+#[cfg(feature = "registry")]
+pub use crate::registry::registered_errors;
+pub use crate::report::{ChainFormatter, Report};
+pub use thiserror_impl::*;
+
+// Not public API.
+#[doc(hidden)]
+pub mod __private {
+    #[doc(hidden)]
+    pub use crate::aggregate::{display_aggregate, display_errors};
+    #[cfg(feature = "actix")]
+    #[doc(hidden)]
+    pub mod actix {
+        #[doc(hidden)]
+        pub use crate::actix::{error_response, status_code, HttpResponse, ResponseError};
+        #[doc(hidden)]
+        pub use actix_web::http::StatusCode;
+    }
+    #[cfg(feature = "arbitrary")]
+    #[doc(hidden)]
+    pub mod arbitrary {
+        #[doc(hidden)]
+        pub use crate::arbitrary::{
+            ArbitraryViaArbitrary, ArbitraryViaDefault, ArbitraryViaSource, ArbitraryWrap,
+        };
+        #[doc(hidden)]
+        pub use ::arbitrary::{Arbitrary, Error, Result, Unstructured};
+    }
+    #[doc(hidden)]
+    pub use crate::aserror::AsDynError;
+    #[doc(hidden)]
+    pub use crate::backtrace::{
+        BacktraceViaNone, BacktraceViaSource, BacktraceWrap, ThiserrorBacktrace,
+    };
+    #[doc(hidden)]
+    pub use crate::chain::{chain_len, chain_summary, display_with_chain, root_cause};
+    #[doc(hidden)]
+    pub use crate::cold::cold;
+    #[cfg(feature = "axum")]
+    #[doc(hidden)]
+    pub mod axum {
+        #[doc(hidden)]
+        pub use crate::axum::{into_response, IntoResponse, Response};
+    }
+    #[doc(hidden)]
+    pub mod capture {
+        #[doc(hidden)]
+        pub use crate::capture::{Capture, Context, ErrorId, Pid, ThreadId, ThreadName, Timestamp};
+    }
+    #[doc(hidden)]
+    pub use crate::display::AsDisplay;
+    #[doc(hidden)]
+    pub use crate::ffi::message_into;
+    #[doc(hidden)]
+    pub use crate::fields::{FieldValueViaDebug, FieldValueViaDisplay, FieldValueWrap};
+    #[cfg(feature = "fluent")]
+    #[doc(hidden)]
+    pub use crate::fluent::localize;
+    #[doc(hidden)]
+    pub use crate::join::{MaybeDone, PollFn};
+    #[doc(hidden)]
+    pub use crate::message::{message_len, single_line};
+    #[cfg(feature = "otel")]
+    #[doc(hidden)]
+    pub use crate::otel::record_exception;
+    #[doc(hidden)]
+    pub use crate::panic::{catch_unwind, Panic, PanicLocation, PanicPayload};
+    #[doc(hidden)]
+    pub use crate::problem::problem;
+    #[cfg(error_generic_member_access)]
+    #[doc(hidden)]
+    pub use crate::provide::ThiserrorProvide;
+    #[doc(hidden)]
+    pub use crate::related::display_related_count;
+    #[doc(hidden)]
+    pub use crate::report::debug_chain;
+    #[cfg(error_generic_member_access)]
+    #[doc(hidden)]
+    pub use crate::report::provide_causes;
+    #[cfg(error_generic_member_access)]
+    #[doc(hidden)]
+    pub use crate::report::provide_sections;
+    #[doc(hidden)]
+    pub use crate::sensitive::{Redacted, StableSource};
+    #[doc(hidden)]
+    pub use crate::template::ConstMessage;
+    #[cfg(feature = "log")]
+    #[doc(hidden)]
+    pub use ::log;
+    #[cfg(feature = "metrics")]
+    #[doc(hidden)]
+    pub use ::metrics;
+    #[cfg(feature = "miette")]
+    #[doc(hidden)]
+    pub use ::miette;
+    #[cfg(feature = "pyo3")]
+    #[doc(hidden)]
+    pub use ::pyo3;
+    #[cfg(feature = "registry")]
+    #[doc(hidden)]
+    pub mod registry {
+        #[doc(hidden)]
+        pub use crate::registry::REGISTRY;
+        #[doc(hidden)]
+        pub use ::linkme;
+    }
+    #[cfg(feature = "schemars")]
+    #[doc(hidden)]
+    pub mod schemars {
+        #[doc(hidden)]
+        pub use crate::schemars::error_schema;
+        #[doc(hidden)]
+        pub use ::schemars::{JsonSchema, Schema, SchemaGenerator};
+    }
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    pub mod serde {
+        #[doc(hidden)]
+        pub use crate::serde::{
+            deserialize_wire, serialize_error, serialize_wire, source_messages,
+        };
+        #[doc(hidden)]
+        pub use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+    }
+    #[cfg(feature = "tonic")]
+    #[doc(hidden)]
+    pub mod tonic {
+        #[doc(hidden)]
+        pub use ::tonic::{Code, Status};
+    }
+    #[cfg(feature = "tracing")]
+    #[doc(hidden)]
+    pub use ::tracing;
 }
@@ -0,0 +1,103 @@
+/// The description of an error type returned by the `manifest()` function
+/// generated by `#[naur(manifest)]`.
+///
+/// With the `serde` feature enabled it serializes to a map of its fields, so
+/// that build tooling can write it out as JSON for documentation and SDK
+/// generation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TypeManifest {
+    /// The name of the type.
+    pub name: &'static str,
+    /// Whether the type is an enum, rather than a struct.
+    pub is_enum: bool,
+    /// Every variant of an enum in declaration order, or a single entry named
+    /// after the type for a struct.
+    pub variants: &'static [VariantManifest],
+}
+
+/// One variant of an error enum, or an error struct, in a [`TypeManifest`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VariantManifest {
+    /// The variant's name, or the type's name for a struct.
+    pub name: &'static str,
+    /// The code declared by `#[error(code = ...)]` or
+    /// `#[diagnostic(code(...))]`, if any.
+    pub code: Option<&'static str>,
+    /// The HTTP status declared by `#[error(status = ...)]`. A variant of an
+    /// enum with statuses which does not declare one is given 500, as by
+    /// `status_code()`.
+    pub status: Option<u16>,
+    /// The format string of the `#[error("...")]` attribute, as written.
+    /// Transparent errors have no message of their own.
+    pub template: Option<&'static str>,
+    /// The format string of the hint declared by `#[error(help = ...)]` or
+    /// `#[diagnostic(help(...))]`, as written.
+    pub help: Option<&'static str>,
+    /// The fields in declaration order.
+    pub fields: &'static [FieldManifest],
+}
+
+/// One field of an error struct or variant in a [`TypeManifest`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FieldManifest {
+    /// The field's name, or its index in a tuple struct or variant.
+    pub name: &'static str,
+    /// The field's type, as written.
+    pub ty: &'static str,
+    /// Whether the field is the error's source.
+    pub source: bool,
+    /// Whether the field is the error's backtrace.
+    pub backtrace: bool,
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for TypeManifest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        use ::serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("name", self.name)?;
+        map.serialize_entry("is_enum", &self.is_enum)?;
+        map.serialize_entry("variants", self.variants)?;
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for VariantManifest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        use ::serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(6))?;
+        map.serialize_entry("name", self.name)?;
+        map.serialize_entry("code", &self.code)?;
+        map.serialize_entry("status", &self.status)?;
+        map.serialize_entry("template", &self.template)?;
+        map.serialize_entry("help", &self.help)?;
+        map.serialize_entry("fields", self.fields)?;
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for FieldManifest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        use ::serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("name", self.name)?;
+        map.serialize_entry("ty", self.ty)?;
+        map.serialize_entry("source", &self.source)?;
+        map.serialize_entry("backtrace", &self.backtrace)?;
+        map.end()
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt::{self, Display, Write};
+
+#[doc(hidden)]
+pub fn message_len<T: ?Sized + Display>(error: &T) -> usize {
+    let mut counter = Counter(0);
+    let _ = write!(counter, "{}", error);
+    counter.0
+}
+
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+// The Display of #[naur(single_line)], which writes the message with each line
+// break, and the indentation after it, collapsed into a single space.
+#[doc(hidden)]
+pub fn single_line<F>(formatter: &mut fmt::Formatter, fmt: F) -> fmt::Result
+where
+    F: Fn(&mut fmt::Formatter) -> fmt::Result,
+{
+    struct Message<F>(F);
+
+    impl<F> Display for Message<F>
+    where
+        F: Fn(&mut fmt::Formatter) -> fmt::Result,
+    {
+        fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            (self.0)(formatter)
+        }
+    }
+
+    let mut out = SingleLine {
+        formatter,
+        written: false,
+        line_break: false,
+    };
+    if out.formatter.alternate() {
+        write!(out, "{:#}", Message(fmt))
+    } else {
+        write!(out, "{}", Message(fmt))
+    }
+}
+
+struct SingleLine<'a, 'b> {
+    formatter: &'a mut fmt::Formatter<'b>,
+    written: bool,
+    line_break: bool,
+}
+
+impl Write for SingleLine<'_, '_> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        while !s.is_empty() {
+            if self.line_break {
+                s = s.trim_start_matches(['\n', '\r', ' ', '\t']);
+                if s.is_empty() {
+                    break;
+                }
+                self.line_break = false;
+                if self.written {
+                    self.formatter.write_char(' ')?;
+                }
+            }
+            let end = s.find(['\n', '\r']).unwrap_or(s.len());
+            if end > 0 {
+                self.formatter.write_str(&s[..end])?;
+                self.written = true;
+            }
+            if end < s.len() {
+                self.line_break = true;
+            }
+            s = &s[end..];
+        }
+        Ok(())
+    }
+}
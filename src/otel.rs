@@ -0,0 +1,27 @@
+use opentelemetry::trace::get_active_span;
+use opentelemetry::KeyValue;
+use std::fmt::Display;
+
+// Records an error constructed by a generated From impl or throw method as an
+// exception event on the active OpenTelemetry span. The span's status is left
+// alone, since the caller may still handle the error.
+#[doc(hidden)]
+pub fn record_exception(
+    error_type: &'static str,
+    code: Option<&'static str>,
+    message: Option<&dyn Display>,
+) {
+    get_active_span(|span| {
+        if !span.is_recording() {
+            return;
+        }
+        let mut attributes = vec![KeyValue::new("exception.type", error_type)];
+        if let Some(message) = message {
+            attributes.push(KeyValue::new("exception.message", message.to_string()));
+        }
+        if let Some(code) = code {
+            attributes.push(KeyValue::new("error.code", code));
+        }
+        span.add_event("exception", attributes);
+    });
+}
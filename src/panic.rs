@@ -0,0 +1,94 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, UnwindSafe};
+use std::sync::Once;
+
+// A panic caught by the `catch_unwind` of an #[error(panic)] variant.
+#[doc(hidden)]
+pub struct Panic {
+    pub payload: Box<dyn Any + Send>,
+    pub location: Option<String>,
+}
+
+thread_local! {
+    static LOCATION: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// Installs the panic hook which records where a panic happened, for the
+/// location of `#[error(panic)]` variants.
+///
+/// The panic hook is global to the process, so this is never done implicitly.
+/// The new hook passes each panic on to the hook in place before it, so panic
+/// messages are printed as before. Calling this more than once has no further
+/// effect. Until it is called, panics caught by `catch_unwind` have no
+/// location: `None` for an `Option<String>` field, and `"<unknown>"` for a
+/// `String` one.
+pub fn install_panic_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info.location().map(ToString::to_string);
+            LOCATION.with(|cell| cell.set(location));
+            previous(info);
+        }));
+    });
+}
+
+// Like std::panic::catch_unwind, except that the location of the panic is kept
+// too, as recorded by the hook of install_panic_hook.
+#[doc(hidden)]
+pub fn catch_unwind<T, F>(f: F) -> Result<T, Panic>
+where
+    F: FnOnce() -> T + UnwindSafe,
+{
+    LOCATION.with(|cell| cell.set(None));
+    panic::catch_unwind(f).map_err(|payload| Panic {
+        payload,
+        location: LOCATION.with(Cell::take),
+    })
+}
+
+#[doc(hidden)]
+pub trait PanicPayload {
+    fn from_payload(payload: Box<dyn Any + Send>) -> Self;
+}
+
+impl PanicPayload for Box<dyn Any + Send> {
+    fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        payload
+    }
+}
+
+// The message given to panic!, or what std prints for a payload of another
+// type.
+impl PanicPayload for String {
+    fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_owned()
+        } else {
+            match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(_) => "Box<dyn Any>".to_owned(),
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait PanicLocation {
+    fn from_location(location: Option<String>) -> Self;
+}
+
+impl PanicLocation for Option<String> {
+    fn from_location(location: Option<String>) -> Self {
+        location
+    }
+}
+
+impl PanicLocation for String {
+    fn from_location(location: Option<String>) -> Self {
+        location.unwrap_or_else(|| "<unknown>".to_owned())
+    }
+}
@@ -0,0 +1,46 @@
+/// A value threaded through a sequence of fallible stages.
+///
+/// Every source-carrying struct and enum variant deriving `Error` under
+/// `#[throws(pipe)]` gets a `pipe_throw_*` method on `Pipeline`, named like its
+/// throw method. The method runs one stage on the current value and maps the
+/// stage's error into that variant, so layered code reads as one chain of `?`.
+///
+/// ```
+/// # use std::num::{ParseIntError, TryFromIntError};
+/// use thiserror::{Error, Pipeline};
+///
+/// #[derive(Error, Debug)]
+/// #[throws(pipe)]
+/// pub enum ConfigError {
+///     #[error("invalid number in {key}")]
+///     Parse { key: String, source: ParseIntError },
+///     #[error("port out of range")]
+///     Range(#[source] TryFromIntError),
+/// }
+///
+/// fn port(raw: &str) -> Result<u16, ConfigError> {
+///     let port = Pipeline::new(raw)
+///         .pipe_throw_parse(str::parse::<i64>, "port".to_owned())?
+///         .pipe_throw_range(u16::try_from)?
+///         .into_inner();
+///     Ok(port)
+/// }
+/// #
+/// # assert_eq!(8080, port("8080").unwrap());
+/// # assert!(matches!(port("x"), Err(ConfigError::Parse { .. })));
+/// # assert!(matches!(port("70000"), Err(ConfigError::Range(_))));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pipeline<T>(T);
+
+impl<T> Pipeline<T> {
+    /// Starts a pipeline at the given value.
+    pub fn new(value: T) -> Self {
+        Pipeline(value)
+    }
+
+    /// Ends the pipeline, returning the value produced by the last stage.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
@@ -0,0 +1,57 @@
+use std::any;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::PoisonError;
+
+/// The error of a poisoned lock, kept without the guard which made
+/// `PoisonError` unusable as a source.
+///
+/// A field marked `#[source(poison)]` holds one of these, and its struct or
+/// variant gets a `From<PoisonError<G>>` impl for every guard type `G`, so
+/// that `?` works on the result of locking any `Mutex` or `RwLock`.
+///
+/// ```
+/// use std::sync::Mutex;
+/// use thiserror::{Error, Poisoned};
+///
+/// #[derive(Error, Debug)]
+/// pub enum CacheError {
+///     #[error("cache lock poisoned")]
+///     Poisoned(#[source(poison)] Poisoned),
+/// }
+///
+/// fn len(cache: &Mutex<Vec<u8>>) -> Result<usize, CacheError> {
+///     Ok(cache.lock()?.len())
+/// }
+/// #
+/// # assert_eq!(0, len(&Mutex::new(Vec::new())).unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Poisoned {
+    message: String,
+    guard: &'static str,
+}
+
+impl Poisoned {
+    /// Records the message and guard type of the poison error.
+    pub fn new<G>(error: &PoisonError<G>) -> Self {
+        Poisoned {
+            message: error.to_string(),
+            guard: any::type_name::<G>(),
+        }
+    }
+
+    /// The name of the type of guard which the lock handed out, such as
+    /// `std::sync::MutexGuard<'_, Vec<u8>>`, for telling which lock it was.
+    pub fn guard_type(&self) -> &'static str {
+        self.guard
+    }
+}
+
+impl Display for Poisoned {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
+}
+
+impl Error for Poisoned {}
@@ -0,0 +1,66 @@
+use std::fmt::{self, Display};
+
+/// An RFC 7807 problem details document.
+///
+/// Produced by the `to_problem()` method generated for errors which declare
+/// an `#[error(status = ...)]` or `#[error(code = ...)]`. With the `serde`
+/// feature enabled it serializes to an `application/problem+json` body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// The problem type: the error's code, or `"about:blank"` if it has none.
+    pub problem_type: String,
+    /// A short, human-readable summary of the problem type.
+    pub title: Option<String>,
+    /// The HTTP status code, if the error declares one.
+    pub status: Option<u16>,
+    /// The error's Display message.
+    pub detail: String,
+    /// A URI reference identifying this specific occurrence of the problem.
+    pub instance: Option<String>,
+}
+
+impl Problem {
+    /// The media type of a serialized problem document.
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+}
+
+impl Display for Problem {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.detail)
+    }
+}
+
+#[doc(hidden)]
+pub fn problem(code: Option<&str>, status: Option<u16>, error: &dyn Display) -> Problem {
+    Problem {
+        problem_type: code.unwrap_or("about:blank").to_owned(),
+        title: None,
+        status,
+        detail: error.to_string(),
+        instance: None,
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Problem {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        use ::serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", &self.problem_type)?;
+        if let Some(title) = &self.title {
+            map.serialize_entry("title", title)?;
+        }
+        if let Some(status) = &self.status {
+            map.serialize_entry("status", status)?;
+        }
+        map.serialize_entry("detail", &self.detail)?;
+        if let Some(instance) = &self.instance {
+            map.serialize_entry("instance", instance)?;
+        }
+        map.end()
+    }
+}
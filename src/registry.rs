@@ -0,0 +1,37 @@
+use crate::catalog::ErrorDescriptor;
+
+// The descriptors of each error type linked into the binary, one entry per
+// type, registered by the derive.
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static REGISTRY: [&'static [ErrorDescriptor]];
+
+/// Every error struct and enum variant deriving `Error` anywhere in the
+/// binary, described by name, code, message template, severity, and help.
+///
+/// Each non-generic error type registers its descriptors at link time, so
+/// an application can check for duplicate codes at startup, or list every
+/// error it may report, without naming the types. The order is unspecified.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use thiserror::Error;
+///
+/// #[derive(Error, Debug)]
+/// pub enum UploadError {
+///     #[error("file is too large")]
+///     #[error(code = "UPLOAD-413")]
+///     TooLarge,
+/// }
+///
+/// let mut codes = HashSet::new();
+/// for descriptor in thiserror::registered_errors() {
+///     if let Some(code) = descriptor.code {
+///         assert!(codes.insert(code), "duplicate error code {}", code);
+///     }
+/// }
+/// assert!(codes.contains("UPLOAD-413"));
+/// ```
+pub fn registered_errors() -> impl Iterator<Item = &'static ErrorDescriptor> {
+    REGISTRY.iter().flat_map(|descriptors| descriptors.iter())
+}
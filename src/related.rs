@@ -0,0 +1,10 @@
+use std::fmt;
+
+#[doc(hidden)]
+pub fn display_related_count(formatter: &mut fmt::Formatter, count: usize) -> fmt::Result {
+    match count {
+        0 => Ok(()),
+        1 => formatter.write_str(" (1 related error)"),
+        _ => write!(formatter, " ({} related errors)", count),
+    }
+}
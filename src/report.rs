@@ -0,0 +1,497 @@
+#[cfg(error_generic_member_access)]
+use crate::chain::chain_summary;
+use crate::chain::Chain;
+#[cfg(error_generic_member_access)]
+use crate::context::ContextFrames;
+#[cfg(error_generic_member_access)]
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::panic::Location;
+
+/// An error wrapped for reporting to a person, such as from `main`.
+///
+/// The Debug representation, which is what `main` prints when it returns
+/// `Err`, lists the error's message, each of its sources on a line of its own,
+/// and the location at which the report was created. Where the toolchain
+/// supports `Error::provide`, a backtrace provided by the error is printed too.
+///
+/// ```no_run
+/// # use std::io;
+/// use thiserror::{Error, Report};
+///
+/// #[derive(Error, Debug)]
+/// #[error("failed to load config")]
+/// pub struct ConfigError {
+///     #[from]
+///     source: io::Error,
+/// }
+///
+/// fn load() -> Result<String, ConfigError> {
+///     Ok(std::fs::read_to_string("/etc/app.toml")?)
+/// }
+///
+/// fn main() -> Result<(), Report<ConfigError>> {
+///     let config = load()?;
+///     println!("{}", config);
+///     Ok(())
+/// }
+/// ```
+///
+/// Printed with Display, the report shows only the error's message, or with
+/// `{:#}` the message followed by each source's, separated by colons.
+/// The Debug representation is that of [`ChainFormatter`], which wraps at the
+/// width of the format string, as in `{:80?}`.
+pub struct Report<E> {
+    error: E,
+    location: &'static Location<'static>,
+    sections: Vec<(String, String)>,
+    #[cfg(feature = "color")]
+    color: Option<bool>,
+}
+
+impl<E> Report<E> {
+    /// Wraps the error, recording the caller's location.
+    #[track_caller]
+    pub fn new(error: E) -> Self {
+        Report {
+            error,
+            location: Location::caller(),
+            sections: Vec::new(),
+            #[cfg(feature = "color")]
+            color: None,
+        }
+    }
+
+    /// Adds a section printed after the error's sources under a heading of
+    /// its own, such as a suggestion for how to fix the problem.
+    ///
+    /// Where the toolchain supports `Error::provide`, the suggestion and link
+    /// declared by `#[error(suggestion = ...)]` and `#[error(url = ...)]` on
+    /// any error in the chain are printed as sections without being added, as
+    /// are the frames of a `#[context_frames]` field, under "Context".
+    pub fn section(mut self, heading: impl Into<String>, body: impl Display) -> Self {
+        self.sections.push((heading.into(), body.to_string()));
+        self
+    }
+
+    /// Whether the Debug representation is colored. By default it is colored
+    /// when standard error is a terminal which supports color, respecting the
+    /// `NO_COLOR` and `CLICOLOR_FORCE` environment variables.
+    #[cfg(feature = "color")]
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// The wrapped error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// The location at which the report was created, which for a report
+    /// produced by `?` is the location of the `?`.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Unwraps the report, returning the error.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> From<E> for Report<E>
+where
+    E: Error + 'static,
+{
+    #[track_caller]
+    fn from(error: E) -> Self {
+        Report::new(error)
+    }
+}
+
+impl<E> Display for Report<E>
+where
+    E: Error + 'static,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.error, formatter)?;
+        if formatter.alternate() {
+            for source in Chain::new(&self.error).skip(1) {
+                write!(formatter, ": {}", source)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E> Debug for Report<E>
+where
+    E: Error + 'static,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(feature = "color")]
+        let color = self.color.unwrap_or_else(stderr_supports_color);
+        #[cfg(not(feature = "color"))]
+        let color = false;
+
+        let chain = ChainFormatter {
+            color,
+            ..ChainFormatter::new(&self.error)
+        };
+        Display::fmt(&chain, formatter)?;
+        let mut sections = self.sections.clone();
+        for section in provided_sections(&self.error) {
+            if !sections.contains(&section) {
+                sections.push(section);
+            }
+        }
+        for (heading, body) in &sections {
+            write_section(formatter, color, &format!("{}:", heading), body)?;
+        }
+        write_section(formatter, color, "Location:", &self.location.to_string())?;
+        write_backtrace(&self.error, formatter, color)
+    }
+}
+
+fn write_section(
+    formatter: &mut fmt::Formatter,
+    color: bool,
+    heading: &str,
+    body: &str,
+) -> fmt::Result {
+    formatter.write_str("\n\n")?;
+    write_styled(formatter, color, Paint::Heading, heading)?;
+    for line in body.lines() {
+        write!(formatter, "\n    {}", line)?;
+    }
+    Ok(())
+}
+
+// The suggestion and link of an error, provided for a Report to print.
+#[cfg(error_generic_member_access)]
+struct ProvidedSections {
+    suggestion: Option<Cow<'static, str>>,
+    url: Option<Cow<'static, str>>,
+}
+
+#[cfg(error_generic_member_access)]
+#[doc(hidden)]
+pub fn provide_sections(
+    request: &mut std::error::Request,
+    suggestion: Option<Cow<'static, str>>,
+    url: Option<Cow<'static, str>>,
+) {
+    request.provide_value(ProvidedSections { suggestion, url });
+}
+
+// The context frames, suggestions and links provided by the errors along the
+// chain, where the toolchain supports `Error::provide`.
+fn provided_sections(error: &(dyn Error + 'static)) -> Vec<(String, String)> {
+    let mut sections = Vec::new();
+    #[cfg(error_generic_member_access)]
+    for error in Chain::new(error) {
+        if let Some(frames) = std::error::request_ref::<ContextFrames>(error) {
+            if !frames.is_empty() {
+                let frames: Vec<&str> = frames.iter().collect();
+                sections.push(("Context".to_owned(), frames.join("\n")));
+            }
+        }
+        if let Some(provided) = std::error::request_value::<ProvidedSections>(error) {
+            if let Some(suggestion) = provided.suggestion {
+                sections.push(("Suggestion".to_owned(), suggestion.into_owned()));
+            }
+            if let Some(url) = provided.url {
+                sections.push(("More information".to_owned(), url.into_owned()));
+            }
+        }
+    }
+    #[cfg(not(error_generic_member_access))]
+    let _ = (error, &mut sections);
+
+    sections
+}
+
+// The messages of an error's #[cause] fields, each followed by its own
+// sources, provided for a ChainFormatter to list after the error's sources.
+#[cfg(error_generic_member_access)]
+struct ProvidedCauses(Vec<String>);
+
+#[cfg(error_generic_member_access)]
+#[doc(hidden)]
+pub fn provide_causes<'a, I>(request: &mut std::error::Request, causes: impl FnOnce() -> I)
+where
+    I: IntoIterator<Item = &'a (dyn Error + 'static)>,
+{
+    request
+        .provide_value_with(|| ProvidedCauses(causes().into_iter().map(chain_summary).collect()));
+}
+
+// The causes provided by the errors along the chain, where the toolchain
+// supports `Error::provide`.
+fn provided_causes(error: &(dyn Error + 'static)) -> Vec<String> {
+    let mut causes = Vec::new();
+    #[cfg(error_generic_member_access)]
+    for error in Chain::new(error) {
+        if let Some(provided) = std::error::request_value::<ProvidedCauses>(error) {
+            causes.extend(provided.0);
+        }
+    }
+    #[cfg(not(error_generic_member_access))]
+    let _ = (error, &mut causes);
+
+    causes
+}
+
+#[cfg(feature = "color")]
+fn stderr_supports_color() -> bool {
+    anstream::AutoStream::choice(&std::io::stderr()) != anstream::ColorChoice::Never
+}
+
+// The Debug representation generated by #[naur(debug_chain)], which is that of
+// a Report without the location.
+#[doc(hidden)]
+pub fn debug_chain(error: &(dyn Error + 'static), formatter: &mut fmt::Formatter) -> fmt::Result {
+    Display::fmt(&ChainFormatter::new(error), formatter)?;
+    write_backtrace(error, formatter, false)
+}
+
+/// Renders an error and its sources over several lines, for a person to read.
+///
+/// The error's message comes first, followed by each of its sources on a
+/// numbered line of its own, indented beneath it. A message spanning several
+/// lines has its later lines indented to match, and with a width set, lines
+/// longer than the width are wrapped between words.
+///
+/// ```
+/// # use std::io;
+/// use thiserror::{ChainFormatter, Error};
+///
+/// #[derive(Error, Debug)]
+/// #[error("failed to load config")]
+/// pub struct ConfigError {
+///     #[from]
+///     source: io::Error,
+/// }
+///
+/// let error = ConfigError::from(io::Error::new(
+///     io::ErrorKind::PermissionDenied,
+///     "permission denied while opening /etc/app.toml for reading",
+/// ));
+/// let expected = "\
+/// failed to load config
+///
+/// Caused by:
+///     0: permission denied while opening
+///        /etc/app.toml for reading";
+/// assert_eq!(expected, ChainFormatter::new(&error).width(40).to_string());
+/// ```
+///
+/// This is the format in which [`Report`] prints the error and its sources.
+/// Without a width of its own, the formatter wraps at the width of the format
+/// string if there is one, so that `{:80}`, or `{:80?}` for a `Report`, wraps
+/// at 80 columns.
+///
+/// With the `color` feature, the formatter can also color its output for a
+/// terminal: the error's message in bold red, the "Caused by:" heading in
+/// bold, and the number of each source in yellow.
+pub struct ChainFormatter<'a> {
+    error: &'a (dyn Error + 'static),
+    indent: usize,
+    width: Option<usize>,
+    color: bool,
+}
+
+impl<'a> ChainFormatter<'a> {
+    /// Renders the error and its sources, indenting the sources by four
+    /// spaces and leaving long lines as they are.
+    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
+        ChainFormatter {
+            error,
+            indent: 4,
+            width: None,
+            color: false,
+        }
+    }
+
+    /// The number of spaces by which the numbered sources are indented.
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// The number of columns, counted in chars, at which to wrap lines. A
+    /// single word longer than that is kept whole.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Whether to color the output with ANSI escape codes. Off by default,
+    /// unlike for a [`Report`], since the formatter does not know where its
+    /// output is going.
+    #[cfg(feature = "color")]
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Display for ChainFormatter<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.width.or_else(|| formatter.width());
+        let headline = Some(Paint::Headline).filter(|_| self.color);
+        write_message(formatter, &self.error.to_string(), 0, width, headline)?;
+        let sources = Chain::new(self.error).skip(1).map(ToString::to_string);
+        self.write_list(formatter, "Caused by:", sources, width)?;
+        let causes = provided_causes(self.error);
+        self.write_list(formatter, "Also caused by:", causes, width)
+    }
+}
+
+impl ChainFormatter<'_> {
+    // Writes the numbered messages under the heading, if there are any.
+    fn write_list(
+        &self,
+        formatter: &mut fmt::Formatter,
+        heading: &str,
+        messages: impl IntoIterator<Item = String>,
+        width: Option<usize>,
+    ) -> fmt::Result {
+        let mut messages = messages.into_iter().enumerate().peekable();
+        if messages.peek().is_none() {
+            return Ok(());
+        }
+        formatter.write_str("\n\n")?;
+        write_styled(formatter, self.color, Paint::Heading, heading)?;
+        for (i, message) in messages {
+            let number = format!("{}:", i);
+            write!(formatter, "\n{:indent$}", "", indent = self.indent)?;
+            write_styled(formatter, self.color, Paint::Number, &number)?;
+            formatter.write_str(" ")?;
+            let indent = self.indent + number.len() + 1;
+            write_message(formatter, &message, indent, width, None)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes the message with each line after the first indented by `indent`
+// spaces, wrapping lines which would run past `width` columns.
+fn write_message(
+    formatter: &mut fmt::Formatter,
+    message: &str,
+    indent: usize,
+    width: Option<usize>,
+    paint: Option<Paint>,
+) -> fmt::Result {
+    let available = width.map(|width| width.saturating_sub(indent).max(1));
+    let mut first = true;
+    for line in message.lines() {
+        for piece in wrap(line, available) {
+            if !first {
+                write!(formatter, "\n{:indent$}", "", indent = indent)?;
+            }
+            match paint {
+                Some(paint) => write_styled(formatter, true, paint, piece)?,
+                None => formatter.write_str(piece)?,
+            }
+            first = false;
+        }
+    }
+    Ok(())
+}
+
+// Breaks the line at the last space before `width` chars, or after a word
+// too long to fit.
+fn wrap(line: &str, width: Option<usize>) -> Vec<&str> {
+    let width = match width {
+        Some(width) => width,
+        None => return vec![line],
+    };
+    let mut pieces = Vec::new();
+    let mut rest = line;
+    while let Some((limit, _)) = rest.char_indices().nth(width) {
+        let split = if rest[limit..].starts_with(' ') {
+            Some(limit)
+        } else {
+            match rest[..limit].rfind(' ') {
+                Some(0) | None => rest[limit..].find(' ').map(|i| limit + i),
+                split => split,
+            }
+        };
+        let split = match split {
+            Some(split) => split,
+            None => break,
+        };
+        pieces.push(rest[..split].trim_end_matches(' '));
+        rest = rest[split..].trim_start_matches(' ');
+    }
+    pieces.push(rest);
+    pieces
+}
+
+// Where the toolchain supports `Error::provide`, the first backtrace provided
+// along the chain, if it was captured.
+fn write_backtrace(
+    error: &(dyn Error + 'static),
+    formatter: &mut fmt::Formatter,
+    color: bool,
+) -> fmt::Result {
+    #[cfg(error_generic_member_access)]
+    {
+        use std::backtrace::{Backtrace, BacktraceStatus};
+
+        let backtrace =
+            Chain::new(error).find_map(|error| std::error::request_ref::<Backtrace>(error));
+        if let Some(backtrace) = backtrace {
+            if let BacktraceStatus::Captured = backtrace.status() {
+                formatter.write_str("\n\n")?;
+                write_styled(formatter, color, Paint::Heading, "Stack backtrace:")?;
+                write!(formatter, "\n{}", backtrace)?;
+            }
+        }
+    }
+    #[cfg(not(error_generic_member_access))]
+    let _ = (error, formatter, color);
+
+    Ok(())
+}
+
+// The parts of a rendered chain which are colored, when coloring.
+#[derive(Copy, Clone)]
+enum Paint {
+    Headline,
+    Heading,
+    Number,
+}
+
+fn write_styled(
+    formatter: &mut fmt::Formatter,
+    color: bool,
+    paint: Paint,
+    text: &str,
+) -> fmt::Result {
+    #[cfg(feature = "color")]
+    if color {
+        use anstyle::{AnsiColor, Style};
+
+        let style = match paint {
+            Paint::Headline => AnsiColor::Red.on_default().bold(),
+            Paint::Heading => Style::new().bold(),
+            Paint::Number => AnsiColor::Yellow.on_default(),
+        };
+        return write!(
+            formatter,
+            "{}{}{}",
+            style.render(),
+            text,
+            style.render_reset(),
+        );
+    }
+    #[cfg(not(feature = "color"))]
+    let _ = (color, paint);
+
+    formatter.write_str(text)
+}
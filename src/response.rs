@@ -0,0 +1,34 @@
+use core::fmt::{Display, Write as _};
+
+// The JSON body of a response rendering an error with an HTTP status:
+// `{"status":404,"code":"USER_NOT_FOUND","message":"user 7 not found"}`.
+pub(crate) fn json_body(status: u16, code: Option<&str>, message: &dyn Display) -> String {
+    let mut body = String::new();
+    let _ = write!(body, "{{\"status\":{},\"code\":", status);
+    match code {
+        Some(code) => write_json_string(&mut body, code),
+        None => body.push_str("null"),
+    }
+    body.push_str(",\"message\":");
+    write_json_string(&mut body, &message.to_string());
+    body.push('}');
+    body
+}
+
+fn write_json_string(out: &mut String, string: &str) {
+    out.push('"');
+    for ch in string.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch < ' ' => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
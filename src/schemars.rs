@@ -0,0 +1,31 @@
+use ::schemars::{json_schema, Schema};
+
+// Mirrors the representation written by crate::serde::serialize_error: one
+// alternative per variant, each pinning the variant name and its code.
+#[doc(hidden)]
+pub fn error_schema(variants: &[(&'static str, Option<&'static str>)]) -> Schema {
+    let alternatives: Vec<Schema> = variants
+        .iter()
+        .map(|(variant, code)| {
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "variant": { "const": variant },
+                    "message": { "type": "string" },
+                    "code": { "const": code },
+                    "sources": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                    },
+                },
+                "required": ["variant", "message", "code", "sources"],
+            })
+        })
+        .collect();
+
+    if alternatives.is_empty() {
+        json_schema!(false)
+    } else {
+        json_schema!({ "oneOf": alternatives })
+    }
+}
@@ -0,0 +1,42 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+
+// Stands in for a #[sensitive] field wherever the generated Display and Debug
+// impls would otherwise print its value.
+#[doc(hidden)]
+pub struct Redacted(pub &'static str);
+
+impl Display for Redacted {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.0)
+    }
+}
+
+impl Debug for Redacted {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.0)
+    }
+}
+
+// Stands in for a source or cause in the Debug impl of #[naur(stable_debug)]:
+// the declared type and the message of the error, leaving out the OS error
+// number which differs between platforms.
+#[doc(hidden)]
+pub struct StableSource<'a>(pub &'static str, pub &'a (dyn Error + 'static));
+
+impl Debug for StableSource<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let message = self.1.to_string();
+        formatter
+            .debug_tuple(self.0)
+            .field(&without_os_error(&message))
+            .finish()
+    }
+}
+
+fn without_os_error(message: &str) -> &str {
+    match message.rfind(" (os error ") {
+        Some(start) if message.ends_with(')') => &message[..start],
+        _ => message,
+    }
+}
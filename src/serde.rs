@@ -0,0 +1,100 @@
+use crate::chain::Chain;
+use ::serde::de::{Deserialize, Deserializer};
+use ::serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+use std::error::Error;
+
+#[doc(hidden)]
+pub fn serialize_error<S>(
+    serializer: S,
+    name: &'static str,
+    variant: &'static str,
+    code: Option<&'static str>,
+    error: &dyn Error,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct(name, 4)?;
+    state.serialize_field("variant", variant)?;
+    state.serialize_field("message", &Message(error))?;
+    state.serialize_field("code", &code)?;
+    state.serialize_field("sources", &Sources(error.source()))?;
+    state.end()
+}
+
+struct Message<'a>(&'a dyn Error);
+
+impl Serialize for Message<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self.0)
+    }
+}
+
+struct Sources<'a>(Option<&'a (dyn Error + 'static)>);
+
+impl Serialize for Sources<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None)?;
+        for error in self.0.into_iter().flat_map(Chain::new) {
+            seq.serialize_element(&Message(error))?;
+        }
+        seq.end()
+    }
+}
+
+#[doc(hidden)]
+pub fn source_messages(error: &dyn Error) -> Vec<String> {
+    error
+        .source()
+        .into_iter()
+        .flat_map(Chain::new)
+        .map(ToString::to_string)
+        .collect()
+}
+
+// The same representation as serialize_error, from the fields of a type
+// generated by #[naur(wire)].
+#[doc(hidden)]
+pub fn serialize_wire<S>(
+    serializer: S,
+    name: &'static str,
+    variant: &str,
+    message: &str,
+    code: Option<&str>,
+    sources: &[String],
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct(name, 4)?;
+    state.serialize_field("variant", variant)?;
+    state.serialize_field("message", message)?;
+    state.serialize_field("code", &code)?;
+    state.serialize_field("sources", sources)?;
+    state.end()
+}
+
+#[doc(hidden)]
+#[derive(::serde::Deserialize)]
+pub struct Wire {
+    pub variant: String,
+    pub message: String,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub sources: Vec<String>,
+}
+
+#[doc(hidden)]
+pub fn deserialize_wire<'de, D>(deserializer: D) -> Result<Wire, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Wire::deserialize(deserializer)
+}
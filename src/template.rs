@@ -0,0 +1,52 @@
+use crate::fields::FieldValue;
+use std::fmt::{self, Display};
+
+// The message of #[error(const = ...)]. The constant's text is not known until
+// it is compiled, so its placeholders are filled in when it is displayed
+// rather than by format_args. Only `{field}` and `{0}` are supported; anything
+// else, such as a format spec, is written out as it is.
+#[doc(hidden)]
+pub struct ConstMessage<'a, const N: usize> {
+    template: &'a str,
+    fields: [(&'static str, FieldValue<'a>); N],
+}
+
+impl<'a, const N: usize> ConstMessage<'a, N> {
+    pub fn new(template: &'a str, fields: [(&'static str, FieldValue<'a>); N]) -> Self {
+        ConstMessage { template, fields }
+    }
+
+    fn field(&self, name: &str) -> Option<&FieldValue<'a>> {
+        self.fields
+            .iter()
+            .find_map(|(field, value)| if *field == name { Some(value) } else { None })
+    }
+}
+
+impl<const N: usize> Display for ConstMessage<'_, N> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut read = self.template;
+        while let Some(brace) = read.find(['{', '}']) {
+            formatter.write_str(&read[..brace])?;
+            read = &read[brace..];
+            if read.starts_with("{{") || read.starts_with("}}") {
+                formatter.write_str(&read[..1])?;
+                read = &read[2..];
+                continue;
+            }
+            if read.starts_with('{') {
+                let value = read
+                    .find('}')
+                    .and_then(|end| Some((end, self.field(&read[1..end])?)));
+                if let Some((end, value)) = value {
+                    Display::fmt(value, formatter)?;
+                    read = &read[end + 1..];
+                    continue;
+                }
+            }
+            formatter.write_str(&read[..1])?;
+            read = &read[1..];
+        }
+        formatter.write_str(read)
+    }
+}
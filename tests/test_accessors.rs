@@ -0,0 +1,172 @@
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(accessors)]
+#[error("failed to send {len} bytes to {peer}")]
+pub struct SendError {
+    source: io::Error,
+    peer: String,
+    len: usize,
+}
+
+#[derive(Error, Debug)]
+#[naur(accessors)]
+pub enum FetchError {
+    #[error("failed to read {path}")]
+    Read {
+        #[source]
+        cause: io::Error,
+        path: String,
+    },
+    #[error("connection lost")]
+    Lost(#[source] Option<io::Error>),
+    #[error(transparent)]
+    Io(io::Error),
+    #[error("not modified")]
+    NotModified,
+}
+
+#[derive(Error, Debug)]
+#[naur(accessors)]
+pub enum RequestError {
+    #[error("invalid message {msg}: {code}")]
+    InvalidMsg {
+        msg: String,
+        code: i32,
+        source: io::Error,
+    },
+    #[error("rejected {0} with {1}")]
+    Rejected(String, u16),
+    #[error("timed out after {secs}s")]
+    Timeout { secs: u64 },
+    #[error("cancelled")]
+    Cancelled,
+}
+
+#[derive(Error, Debug)]
+#[naur(accessors)]
+pub enum LoadError<E: std::error::Error + 'static> {
+    #[error("failed to read config")]
+    Read(#[source] io::Error),
+    #[error("invalid port")]
+    Port(#[source] Option<ParseIntError>),
+    #[error("plugin failed")]
+    Plugin(#[source] E),
+    #[error("empty config")]
+    Empty,
+}
+
+fn invalid_msg() -> RequestError {
+    let io = io::Error::new(io::ErrorKind::InvalidData, "bad frame");
+    RequestError::InvalidMsg {
+        msg: "hello".to_owned(),
+        code: 7,
+        source: io,
+    }
+}
+
+#[test]
+fn test_is() {
+    assert!(invalid_msg().is_invalid_msg());
+    assert!(!invalid_msg().is_timeout());
+    assert!(RequestError::Timeout { secs: 1 }.is_timeout());
+    assert!(RequestError::Cancelled.is_cancelled());
+}
+
+#[test]
+fn test_as() {
+    let error = invalid_msg();
+    let (msg, code, source) = error.as_invalid_msg().unwrap();
+    assert_eq!("hello", msg);
+    assert_eq!(7, *code);
+    assert_eq!(io::ErrorKind::InvalidData, source.kind());
+    assert!(error.as_timeout().is_none());
+
+    assert_eq!(Some(&30), RequestError::Timeout { secs: 30 }.as_timeout());
+
+    let error = RequestError::Rejected("upload".to_owned(), 413);
+    assert_eq!(Some((&"upload".to_owned(), &413)), error.as_rejected());
+}
+
+#[test]
+fn test_into() {
+    let (msg, code, source) = invalid_msg().into_invalid_msg().unwrap();
+    assert_eq!("hello", msg);
+    assert_eq!(7, code);
+    assert_eq!("bad frame", source.to_string());
+
+    assert_eq!(Some(30), RequestError::Timeout { secs: 30 }.into_timeout());
+    assert_eq!(None, RequestError::Cancelled.into_timeout());
+
+    let error = RequestError::Rejected("upload".to_owned(), 413);
+    assert_eq!(Some(("upload".to_owned(), 413)), error.into_rejected());
+}
+
+#[test]
+fn test_into_source() {
+    let error = SendError {
+        source: io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"),
+        peer: "db".to_owned(),
+        len: 64,
+    };
+    assert_eq!(io::ErrorKind::BrokenPipe, error.into_source().kind());
+
+    let error = FetchError::Read {
+        cause: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        path: "app.toml".to_owned(),
+    };
+    assert_eq!(io::ErrorKind::NotFound, error.into_source().unwrap().kind());
+    let error = FetchError::Io(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+    assert_eq!(io::ErrorKind::TimedOut, error.into_source().unwrap().kind());
+    assert!(FetchError::Lost(None).into_source().is_none());
+    assert!(FetchError::NotModified.into_source().is_none());
+}
+
+#[test]
+fn test_into_parts() {
+    let error = SendError {
+        source: io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"),
+        peer: "db".to_owned(),
+        len: 64,
+    };
+    let (peer, len, source) = error.into_parts();
+    assert_eq!("db", peer);
+    assert_eq!(64, len);
+    assert_eq!(io::ErrorKind::BrokenPipe, source.kind());
+
+    let error = FetchError::Read {
+        cause: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        path: "app.toml".to_owned(),
+    };
+    let (path, cause) = error.into_read_parts().unwrap();
+    assert_eq!("app.toml", path);
+    assert_eq!(io::ErrorKind::NotFound, cause.kind());
+    assert!(FetchError::NotModified.into_read_parts().is_none());
+    assert!(FetchError::Lost(None).into_lost_parts().unwrap().is_none());
+}
+
+#[test]
+fn test_into_boxed_source() {
+    type Error = LoadError<fmt::Error>;
+
+    let error = Error::Read(io::Error::new(io::ErrorKind::NotFound, "not found"));
+    let source = error.into_source().unwrap();
+    assert_eq!(
+        io::ErrorKind::NotFound,
+        source.downcast::<io::Error>().unwrap().kind()
+    );
+
+    let parse_error = "x".parse::<u16>().unwrap_err();
+    let error = Error::Port(Some(parse_error.clone()));
+    let source = error.into_source().unwrap();
+    assert_eq!(parse_error, *source.downcast::<ParseIntError>().unwrap());
+
+    let source = Error::Plugin(fmt::Error).into_source().unwrap();
+    assert!(source.is::<fmt::Error>());
+
+    assert!(Error::Port(None).into_source().is_none());
+    assert!(Error::Empty.into_source().is_none());
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "actix")]
+
+use actix_web::body::MessageBody as _;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::{HttpResponse, ResponseError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ApiError {
+    #[error("user {0} not found")]
+    #[error(status = 404, code = "USER_NOT_FOUND")]
+    NotFound(u64),
+    #[error("bad \"input\"")]
+    #[error(status = 400)]
+    BadInput,
+}
+
+#[derive(Error, Debug)]
+#[error("teapot")]
+#[error(status = 418)]
+pub struct Teapot;
+
+fn body_string(response: HttpResponse) -> String {
+    let bytes = response.into_body().try_into_bytes().unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[test]
+fn test_error_response() {
+    let error = ApiError::NotFound(7);
+    assert_eq!(404, ResponseError::status_code(&error).as_u16());
+    let response = error.error_response();
+    assert_eq!(404, response.status().as_u16());
+    assert_eq!(
+        "application/json",
+        response.headers().get(CONTENT_TYPE).unwrap()
+    );
+    assert_eq!(
+        r#"{"status":404,"code":"USER_NOT_FOUND","message":"user 7 not found"}"#,
+        body_string(response),
+    );
+
+    let response = ApiError::BadInput.error_response();
+    assert_eq!(400, response.status().as_u16());
+    assert_eq!(
+        r#"{"status":400,"code":null,"message":"bad \"input\""}"#,
+        body_string(response),
+    );
+}
+
+#[test]
+fn test_struct() {
+    let response = Teapot.error_response();
+    assert_eq!(418, response.status().as_u16());
+    assert_eq!(
+        r#"{"status":418,"code":null,"message":"teapot"}"#,
+        body_string(response),
+    );
+}
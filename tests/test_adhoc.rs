@@ -0,0 +1,40 @@
+use std::error::Error as _;
+use thiserror::{naur, AdHocError, Error};
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("failed to open {name}")]
+    Open { name: String, source: AdHocError },
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[test]
+fn test_format() {
+    let path = "/var/lib/store";
+    assert_eq!(
+        "failed to open /var/lib/store",
+        naur!("failed to open {path}").to_string()
+    );
+    assert_eq!("1 of 2 shards", naur!("{} of {} shards", 1, 2).to_string());
+    assert_eq!("no braces {}", naur!("no braces {{}}",).message());
+    assert_eq!("AdHocError(\"locked\")", format!("{:?}", naur!("locked")),);
+}
+
+#[test]
+fn test_source() {
+    let error = StoreError::Open {
+        name: "users".to_owned(),
+        source: naur!("lock file is held by pid {}", 42),
+    };
+    let source = error.source().unwrap();
+    assert_eq!("lock file is held by pid 42", source.to_string());
+    assert!(source.is::<AdHocError>());
+}
+
+#[test]
+fn test_catch_all() {
+    let error = StoreError::Other(naur!("disk full").into());
+    assert_eq!("disk full", error.to_string());
+    assert!(matches!(error, StoreError::Other(_)));
+}
@@ -0,0 +1,73 @@
+use arrayvec::ArrayVec;
+use smallvec::SmallVec;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(aggregate)]
+pub enum FieldError {
+    #[error("`{0}` is required")]
+    Missing(&'static str),
+    #[error("`{0}` is too long")]
+    TooLong(&'static str),
+}
+
+#[derive(Error, Debug)]
+#[error("invalid byte at {0}")]
+#[naur(aggregate(container = SmallVec<[Self; 2]>))]
+pub struct ByteError(usize);
+
+#[derive(Error, Debug)]
+#[error("invalid digit at {0}")]
+#[naur(aggregate(container = ArrayVec<Self, 1>))]
+pub struct DigitError(usize);
+
+#[test]
+fn test_collect() {
+    let aggregate: FieldErrorAggregate =
+        vec![FieldError::Missing("name"), FieldError::TooLong("email")]
+            .into_iter()
+            .collect();
+    assert_eq!(2, aggregate.len());
+    assert!(matches!(
+        aggregate.errors()[1],
+        FieldError::TooLong("email")
+    ));
+    assert_eq!(
+        "2 errors occurred: `name` is required; `email` is too long",
+        aggregate.to_string(),
+    );
+    let errors: Vec<FieldError> = aggregate.into_inner();
+    assert_eq!(2, errors.len());
+}
+
+#[test]
+fn test_push() {
+    let mut aggregate = FieldErrorAggregate::default();
+    assert!(aggregate.is_empty());
+    aggregate.push(FieldError::Missing("name"));
+    assert_eq!("`name` is required", aggregate.to_string());
+
+    let aggregate = FieldErrorAggregate::from(FieldError::TooLong("bio"));
+    let errors: Vec<_> = aggregate.into_iter().collect();
+    assert!(matches!(errors[..], [FieldError::TooLong("bio")]));
+}
+
+#[test]
+fn test_container() {
+    let mut aggregate = ByteErrorAggregate::default();
+    aggregate.extend([ByteError(3), ByteError(7)]);
+    assert_eq!(
+        "2 errors occurred: invalid byte at 3; invalid byte at 7",
+        aggregate.to_string(),
+    );
+    let inner: SmallVec<[ByteError; 2]> = aggregate.into_inner();
+    assert!(!inner.spilled());
+}
+
+#[test]
+#[should_panic(expected = "capacity exceeded")]
+fn test_push_full() {
+    let mut aggregate = DigitErrorAggregate::from(DigitError(0));
+    assert_eq!(1, aggregate.len());
+    aggregate.push(DigitError(5));
+}
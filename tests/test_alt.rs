@@ -0,0 +1,64 @@
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to read config")]
+#[error(alt = "failed to read config from {path} ({attempts} attempts)")]
+pub struct ConfigError {
+    path: PathBuf,
+    attempts: usize,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+pub enum RequestError {
+    #[error("request timed out")]
+    #[error(alt = "request to {url} timed out after {elapsed_ms} ms")]
+    Timeout { url: String, elapsed_ms: u64 },
+    #[error("bad status {0}")]
+    Status(u16),
+}
+
+#[derive(Error, Debug)]
+#[error("invalid value")]
+#[error(alt = "invalid value {0}")]
+pub struct InvalidValue<T>(T);
+
+#[test]
+fn test_struct() {
+    let error = ConfigError {
+        path: PathBuf::from("/etc/app.toml"),
+        attempts: 3,
+        source: io::Error::new(io::ErrorKind::NotFound, "no such file"),
+    };
+    assert_eq!("failed to read config", format!("{}", error));
+    assert_eq!(
+        "failed to read config from /etc/app.toml (3 attempts)",
+        format!("{:#}", error),
+    );
+}
+
+#[test]
+fn test_enum() {
+    let error = RequestError::Timeout {
+        url: "https://example.com".to_owned(),
+        elapsed_ms: 30000,
+    };
+    assert_eq!("request timed out", error.to_string());
+    assert_eq!(
+        "request to https://example.com timed out after 30000 ms",
+        format!("{:#}", error),
+    );
+
+    // Without an alternate message, `{:#}` is the regular one.
+    assert_eq!("bad status 502", format!("{:#}", RequestError::Status(502)));
+}
+
+#[test]
+fn test_generic() {
+    fn assert_display<T: Display>() {}
+    assert_display::<InvalidValue<u8>>();
+    assert_eq!("invalid value 7", format!("{:#}", InvalidValue(7)));
+}
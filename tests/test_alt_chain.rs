@@ -0,0 +1,44 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to load config")]
+#[naur(alt_chain)]
+pub struct ConfigError {
+    #[from]
+    source: ReadError,
+}
+
+#[derive(Error, Debug)]
+#[naur(alt_chain)]
+pub enum ReadError {
+    #[error("failed to read {path}")]
+    Io { path: String, source: io::Error },
+    #[error("file is empty")]
+    Empty,
+}
+
+#[derive(Error, Debug)]
+#[error("plain")]
+pub struct Plain(#[source] io::Error);
+
+#[test]
+fn test_alternate() {
+    let error = ConfigError::from(ReadError::Io {
+        path: "app.toml".to_owned(),
+        source: io::Error::new(io::ErrorKind::NotFound, "no such file"),
+    });
+    assert_eq!("failed to load config", format!("{}", error));
+    assert_eq!(
+        "failed to load config; caused by: failed to read app.toml; caused by: no such file",
+        format!("{:#}", error),
+    );
+
+    assert_eq!("file is empty", format!("{:#}", ReadError::Empty));
+}
+
+#[test]
+fn test_without_attribute() {
+    let error = Plain(io::Error::new(io::ErrorKind::Other, "oh no"));
+    assert_eq!("plain", format!("{:#}", error));
+}
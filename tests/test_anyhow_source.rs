@@ -0,0 +1,51 @@
+use anyhow::anyhow;
+use std::error::Error as _;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to load {path}")]
+pub struct LoadError {
+    path: String,
+    #[source]
+    cause: anyhow::Error,
+}
+
+#[derive(Error, Debug)]
+pub enum TaskError {
+    #[error("task failed")]
+    Failed(#[from] anyhow::Error),
+    #[error("task {0} was cancelled")]
+    Cancelled(u32, #[source] Option<anyhow::Error>),
+}
+
+#[test]
+fn test_source() {
+    let error = LoadError {
+        path: "/etc/app.toml".to_owned(),
+        cause: anyhow!("inner").context("outer"),
+    };
+    let source = error.source().unwrap();
+    assert_eq!("outer", source.to_string());
+    assert_eq!("inner", source.source().unwrap().to_string());
+
+    let error = TaskError::from(anyhow!("timed out"));
+    assert_eq!("timed out", error.source().unwrap().to_string());
+
+    let error = TaskError::Cancelled(7, None);
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn test_throw() {
+    let error = Err::<(), _>(anyhow!("inner").context("outer"))
+        .throw_load("/etc/app.toml".to_owned())
+        .unwrap_err();
+    assert_eq!("failed to load /etc/app.toml", error.to_string());
+    assert_eq!("outer", error.source().unwrap().to_string());
+
+    let error = Err::<(), _>(anyhow!("timed out"))
+        .throw_failed()
+        .unwrap_err();
+    assert_eq!("task failed", error.to_string());
+    assert_eq!("timed out", error.source().unwrap().to_string());
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use std::collections::BTreeSet;
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug, Default)]
+pub struct RequestId(u64);
+
+#[derive(Error, Debug)]
+#[naur(arbitrary)]
+#[error("invalid header {name}: {value:?}")]
+pub struct HeaderError {
+    name: String,
+    value: Vec<u8>,
+}
+
+#[derive(Error, Debug)]
+#[naur(arbitrary)]
+pub enum RequestError {
+    #[error("request {0:?} timed out after {1}ms")]
+    Timeout(RequestId, u32),
+    #[error("connection failed")]
+    Connect(#[from] io::Error),
+    #[error(transparent)]
+    Header(#[from] HeaderError),
+    #[error("upstream failed")]
+    Upstream {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+        retries: u8,
+    },
+    #[error("cancelled")]
+    Cancelled,
+}
+
+#[derive(Error, Debug)]
+#[naur(arbitrary)]
+#[error("unreachable")]
+pub enum Never {}
+
+fn variant(error: &RequestError) -> &'static str {
+    match error {
+        RequestError::Timeout(..) => "Timeout",
+        RequestError::Connect(_) => "Connect",
+        RequestError::Header(_) => "Header",
+        RequestError::Upstream { .. } => "Upstream",
+        RequestError::Cancelled => "Cancelled",
+    }
+}
+
+#[test]
+fn test_every_variant() {
+    let mut seen = BTreeSet::new();
+    for seed in 0..=255u8 {
+        let data: Vec<u8> = (0..64)
+            .map(|i| seed.wrapping_mul(31).wrapping_add(i))
+            .collect();
+        let mut u = Unstructured::new(&data);
+        let error = RequestError::arbitrary(&mut u).unwrap();
+        let _ = error.to_string();
+        seen.insert(variant(&error));
+    }
+    assert_eq!(5, seen.len(), "{:?}", seen);
+}
+
+#[test]
+fn test_default_field() {
+    // RequestId is neither Arbitrary nor a source type, so it is defaulted.
+    for seed in 0..=255u8 {
+        let data = [seed; 16];
+        let error = RequestError::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        if let RequestError::Timeout(id, _) = error {
+            assert_eq!(0, id.0);
+        }
+    }
+}
+
+#[test]
+fn test_sources() {
+    for seed in 0..=255u8 {
+        let data = [seed; 16];
+        let error = RequestError::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        match &error {
+            RequestError::Connect(source) => {
+                assert_eq!(io::ErrorKind::Other, source.kind());
+                assert!(error.source().is_some());
+            }
+            RequestError::Upstream { .. } => assert!(error.source().is_some()),
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_empty_enum() {
+    assert!(Never::arbitrary(&mut Unstructured::new(&[0; 8])).is_err());
+}
@@ -0,0 +1,98 @@
+use std::error::Error as StdError;
+use std::io;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+#[error("request {id} failed")]
+pub struct RequestError {
+    id: u32,
+    source: Arc<dyn StdError + Send + Sync + 'static>,
+}
+
+#[derive(Error, Debug)]
+#[error("{tag} failed")]
+pub struct TaggedError<T> {
+    tag: T,
+    source: Arc<dyn StdError + Send + Sync>,
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum CacheError {
+    #[error("miss")]
+    Miss,
+    #[error("load failed")]
+    Load(#[from] Arc<dyn StdError + Send + Sync>),
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum JobError {
+    #[error("invalid job")]
+    Invalid,
+    #[error(transparent)]
+    Other(
+        #[from]
+        #[naur(from_any)]
+        Arc<dyn StdError + Send + Sync>,
+    ),
+}
+
+impl JobErrorCatchAll for io::Error {}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "oh no!")
+}
+
+#[test]
+fn test_source_derefs_arc() {
+    let error = RequestError {
+        id: 1,
+        source: Arc::new(io_error()),
+    };
+    let source = error.source().unwrap();
+    assert!(source.is::<io::Error>());
+    assert_eq!("oh no!", source.to_string());
+}
+
+#[test]
+fn test_from_arc() {
+    let shared: Arc<dyn StdError + Send + Sync> = Arc::new(io_error());
+    let error = CacheError::from(shared.clone());
+    let copy = error.clone();
+    match (&error, &copy) {
+        (CacheError::Load(a), CacheError::Load(b)) => assert!(Arc::ptr_eq(a, b)),
+        _ => unreachable!(),
+    }
+    assert!(copy.source().unwrap().is::<io::Error>());
+}
+
+#[test]
+fn test_from_any() {
+    fn read() -> Result<(), JobError> {
+        Err(io_error())?;
+        Ok(())
+    }
+    let error = read().unwrap_err();
+    assert!(matches!(error, JobError::Other(_)));
+    assert_eq!("oh no!", error.to_string());
+}
+
+#[test]
+fn test_throw_boxes_into_arc() {
+    let result: Result<(), io::Error> = Err(io_error());
+    let error = result.throw_request(7).unwrap_err();
+    assert_eq!("request 7 failed", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let result: Result<(), &str> = Err("no connection");
+    let error = result.throw_load().unwrap_err();
+    assert_eq!("no connection", error.source().unwrap().to_string());
+}
+
+#[test]
+fn test_throw_generic() {
+    let result: Result<(), io::Error> = Err(io_error());
+    let error = result.throw_tagged("sync").unwrap_err();
+    assert_eq!("sync failed", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+}
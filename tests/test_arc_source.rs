@@ -0,0 +1,82 @@
+use std::error::Error as _;
+use std::io;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+#[error("failed to fetch {url}")]
+pub struct FetchError {
+    url: String,
+    #[source]
+    #[error(arc_source)]
+    source: Arc<io::Error>,
+}
+
+#[derive(Error, Debug, Clone)]
+#[error(arc_source)]
+pub enum LoadError {
+    #[error("read failed")]
+    Read(#[from] Arc<io::Error>),
+    #[error("fetch failed")]
+    Fetch {
+        #[from]
+        source: Arc<FetchError>,
+    },
+    #[error("cancelled")]
+    Cancelled,
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum QueueError {
+    #[error("job {id} failed")]
+    #[error(arc_source)]
+    Job { id: u32, source: Arc<io::Error> },
+    #[error("worker crashed")]
+    Crashed(#[from] Arc<io::Error>),
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "oh no!")
+}
+
+#[test]
+fn test_struct_clone() {
+    let error = FetchError {
+        url: "https://example.com".to_owned(),
+        source: Arc::new(io_error()),
+    };
+    let copy = error.clone();
+    assert!(Arc::ptr_eq(&error.source, &copy.source));
+    let source = copy.source().unwrap();
+    assert!(source.is::<io::Error>());
+    assert_eq!("oh no!", source.to_string());
+}
+
+#[test]
+fn test_from_plain_source() {
+    let error = LoadError::from(io_error());
+    assert!(matches!(error, LoadError::Read(_)));
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let fetch = FetchError {
+        url: "https://example.com".to_owned(),
+        source: Arc::new(io_error()),
+    };
+    let error = LoadError::from(fetch);
+    let copies = vec![error.clone(), error.clone()];
+    for copy in &copies {
+        assert!(copy.source().unwrap().is::<FetchError>());
+    }
+}
+
+#[test]
+fn test_throw_plain_source() {
+    let result: Result<(), io::Error> = Err(io_error());
+    let error = result.throw_job(7).unwrap_err();
+    assert_eq!("job 7 failed", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let result: Result<(), Arc<io::Error>> = Err(Arc::new(io_error()));
+    let error = result.throw_crashed().unwrap_err();
+    assert!(matches!(error, QueueError::Crashed(_)));
+}
@@ -0,0 +1,43 @@
+use std::fmt::Debug;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(assert = "Send + Sync + 'static")]
+#[error("failed to read {path}")]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(assert = "Send + Sync + 'static")]
+pub enum LoadError {
+    #[error(transparent)]
+    Read(#[from] ReadError),
+    #[error("invalid line {line}")]
+    Parse { line: usize },
+    #[error("empty")]
+    Empty,
+}
+
+#[derive(Error, Debug)]
+#[naur(assert = "Send")]
+#[error("{value:?} at {position}")]
+pub struct InvalidValue<'a, T: Debug> {
+    value: T,
+    position: &'a usize,
+}
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+#[test]
+fn test_assert() {
+    assert_send_sync::<ReadError>();
+    assert_send_sync::<LoadError>();
+    let error = InvalidValue {
+        value: 1,
+        position: &0,
+    };
+    assert_eq!("1 at 0", error.to_string());
+}
@@ -0,0 +1,48 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid config")]
+pub struct ConfigError;
+
+#[derive(Error, Debug)]
+#[naur(auto_transparent)]
+pub enum AppError {
+    Io(#[from] io::Error),
+    Config(#[from] ConfigError),
+    Fmt {
+        source: std::fmt::Error,
+    },
+    #[error("database unavailable")]
+    Database(#[source] io::Error),
+    #[error("shutting down")]
+    Shutdown,
+}
+
+#[test]
+fn test_auto_transparent() {
+    let error = AppError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("oh no!", error.to_string());
+    assert!(error.source().is_none());
+
+    let error = AppError::from(ConfigError);
+    assert_eq!("invalid config", error.to_string());
+
+    let error = AppError::Fmt {
+        source: std::fmt::Error,
+    };
+    assert_eq!(
+        "an error occurred when formatting an argument",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_own_message() {
+    let error = AppError::Database(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("database unavailable", error.to_string());
+    assert!(error.source().is_some());
+
+    assert_eq!("shutting down", AppError::Shutdown.to_string());
+}
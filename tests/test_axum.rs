@@ -0,0 +1,74 @@
+#![cfg(feature = "axum")]
+
+use axum_core::body::Body;
+use axum_core::response::IntoResponse;
+use http::header::CONTENT_TYPE;
+use http_body_util::BodyExt as _;
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ApiError {
+    #[error("user {0} not found")]
+    #[error(status = 404, code = "USER_NOT_FOUND")]
+    NotFound(u64),
+    #[error("bad \"input\"")]
+    #[error(status = 400)]
+    BadInput,
+}
+
+#[derive(Error, Debug)]
+#[error("teapot")]
+#[error(status = 418)]
+pub struct Teapot;
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn body_string(body: Body) -> String {
+    let mut future = Box::pin(body.collect());
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    match future.as_mut().poll(&mut cx) {
+        Poll::Ready(collected) => {
+            String::from_utf8(collected.unwrap().to_bytes().to_vec()).unwrap()
+        }
+        Poll::Pending => panic!("body is not ready"),
+    }
+}
+
+#[test]
+fn test_into_response() {
+    let response = ApiError::NotFound(7).into_response();
+    assert_eq!(404, response.status().as_u16());
+    assert_eq!("application/json", response.headers()[CONTENT_TYPE]);
+    assert_eq!(
+        r#"{"status":404,"code":"USER_NOT_FOUND","message":"user 7 not found"}"#,
+        body_string(response.into_body()),
+    );
+}
+
+#[test]
+fn test_escaping() {
+    let response = ApiError::BadInput.into_response();
+    assert_eq!(400, response.status().as_u16());
+    assert_eq!(
+        r#"{"status":400,"code":null,"message":"bad \"input\""}"#,
+        body_string(response.into_body()),
+    );
+}
+
+#[test]
+fn test_struct() {
+    let response = Teapot.into_response();
+    assert_eq!(418, response.status().as_u16());
+    assert_eq!(
+        r#"{"status":418,"code":null,"message":"teapot"}"#,
+        body_string(response.into_body()),
+    );
+}
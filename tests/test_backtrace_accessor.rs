@@ -0,0 +1,87 @@
+#![cfg_attr(thiserror_nightly_testing, feature(error_generic_member_access))]
+#![allow(clippy::incompatible_msrv)]
+
+use std::backtrace::Backtrace;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("query failed")]
+pub struct QueryError {
+    backtrace: Backtrace,
+}
+
+#[derive(Error, Debug)]
+#[error("not connected")]
+pub struct ConnectError {
+    #[backtrace]
+    backtrace: Option<Backtrace>,
+}
+
+#[derive(Error, Debug)]
+pub enum DbError {
+    #[error("query")]
+    Query(#[from] QueryError),
+    #[error("io")]
+    Io {
+        #[from]
+        source: io::Error,
+        backtrace: Backtrace,
+    },
+    #[error("connect")]
+    Connect(
+        #[from]
+        #[backtrace]
+        ConnectError,
+    ),
+    #[error("closed")]
+    Closed,
+}
+
+#[derive(Error, Debug)]
+#[error("request failed")]
+pub struct RequestError {
+    #[from]
+    #[backtrace]
+    source: Box<DbError>,
+}
+
+fn query_error() -> QueryError {
+    QueryError {
+        backtrace: Backtrace::force_capture(),
+    }
+}
+
+#[test]
+fn test_own_field() {
+    let error = query_error();
+    assert!(error.backtrace().is_some());
+
+    let error = ConnectError { backtrace: None };
+    assert!(error.backtrace().is_none());
+}
+
+#[test]
+fn test_delegate_to_source() {
+    let error = DbError::from(query_error());
+    let expected: *const Backtrace = match &error {
+        DbError::Query(source) => source.backtrace().unwrap(),
+        _ => unreachable!(),
+    };
+    assert!(std::ptr::eq(expected, error.backtrace().unwrap()));
+
+    let error = RequestError::from(Box::new(error));
+    let expected: *const Backtrace = error.source.backtrace().unwrap();
+    assert!(std::ptr::eq(expected, error.backtrace().unwrap()));
+
+    let error = DbError::Connect(ConnectError { backtrace: None });
+    assert!(error.backtrace().is_none());
+}
+
+#[test]
+fn test_source_without_backtrace() {
+    let error = DbError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert!(error.backtrace().is_some());
+
+    assert!(DbError::Closed.backtrace().is_none());
+}
@@ -0,0 +1,114 @@
+#![cfg_attr(thiserror_nightly_testing, feature(error_generic_member_access))]
+
+#[cfg(thiserror_nightly_testing)]
+pub mod nightly {
+    use std::backtrace::Backtrace;
+    use std::error;
+    use std::io;
+    use std::num::ParseIntError;
+    use thiserror::Error;
+
+    #[derive(Debug)]
+    pub struct Trace(&'static str);
+
+    fn capture() -> Trace {
+        Trace("captured")
+    }
+
+    fn disabled() -> Backtrace {
+        Backtrace::disabled()
+    }
+
+    #[derive(Error, Debug)]
+    #[error("failed to read {path}")]
+    pub struct ReadError {
+        path: String,
+        source: io::Error,
+        #[backtrace(capture = capture)]
+        trace: Trace,
+    }
+
+    #[derive(Error, Debug)]
+    #[error("...")]
+    pub struct FromError {
+        #[from]
+        source: io::Error,
+        #[backtrace(capture = disabled)]
+        backtrace: Option<Backtrace>,
+    }
+
+    #[derive(Error, Debug)]
+    pub enum ParseError {
+        #[error("line {0} is invalid")]
+        Line(
+            usize,
+            #[source] ParseIntError,
+            #[backtrace(capture = capture)] Trace,
+        ),
+        #[error("i/o failure")]
+        Io(#[from] io::Error, #[backtrace(capture = capture)] Trace),
+    }
+
+    #[derive(Error, Debug)]
+    #[naur(backtrace_capture = disabled)]
+    pub enum StoreError {
+        #[error("failed to load")]
+        Load {
+            source: io::Error,
+            backtrace: Backtrace,
+        },
+        #[error("failed to decode")]
+        Decode {
+            source: ParseIntError,
+            #[backtrace(capture = capture)]
+            trace: Trace,
+        },
+    }
+
+    fn read() -> Result<String, io::Error> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "oh no!"))
+    }
+
+    #[test]
+    fn test_throw() {
+        let error = read().throw_read("/etc/app.toml".to_owned()).unwrap_err();
+        assert_eq!("failed to read /etc/app.toml", error.to_string());
+        assert_eq!("captured", error.trace.0);
+        let trace = error::request_ref::<Trace>(&error).unwrap();
+        assert_eq!("captured", trace.0);
+        assert!(error::request_ref::<Backtrace>(&error).is_none());
+
+        let error = "x".parse::<i32>().throw_line(3).unwrap_err();
+        assert_eq!("line 3 is invalid", error.to_string());
+        let trace = error::request_ref::<Trace>(&error).unwrap();
+        assert_eq!("captured", trace.0);
+    }
+
+    #[test]
+    fn test_from() {
+        let error = FromError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+        let backtrace = error::request_ref::<Backtrace>(&error).unwrap();
+        assert_eq!(
+            std::backtrace::BacktraceStatus::Disabled,
+            backtrace.status(),
+        );
+
+        let error = ParseError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+        let trace = error::request_ref::<Trace>(&error).unwrap();
+        assert_eq!("captured", trace.0);
+    }
+
+    #[test]
+    fn test_default_capture() {
+        let error = read().throw_load().unwrap_err();
+        let backtrace = error::request_ref::<Backtrace>(&error).unwrap();
+        assert_eq!(
+            std::backtrace::BacktraceStatus::Disabled,
+            backtrace.status(),
+        );
+
+        let error = "x".parse::<i32>().throw_decode().unwrap_err();
+        let trace = error::request_ref::<Trace>(&error).unwrap();
+        assert_eq!("captured", trace.0);
+    }
+}
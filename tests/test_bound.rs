@@ -0,0 +1,55 @@
+use std::error::Error as _;
+use std::fmt::{self, Debug, Display};
+use thiserror::Error;
+
+// Without an explicit bound, the inferred `Box<Tree<T>>: Error` overflows when
+// checked, as it requires `Tree<T>: Error` again.
+#[derive(Error, Debug)]
+#[error(bound = "T: Debug + Display + 'static")]
+pub enum Tree<T> {
+    #[error("leaf {0}")]
+    Leaf(T),
+    #[error("node")]
+    Node(#[source] Box<Tree<T>>),
+}
+
+pub struct Label<T>(T);
+
+impl<T> Display for Label<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("label")
+    }
+}
+
+impl<T> Debug for Label<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Label")
+    }
+}
+
+// No bound at all, where the inferred `Label<T>: Display` would hold anyway.
+#[derive(Error, Debug)]
+#[error("{label} failed")]
+#[error(bound = "")]
+pub struct Tagged<T> {
+    label: Label<T>,
+    source: std::io::Error,
+}
+
+#[test]
+fn test_recursive() {
+    let error = Tree::Node(Box::new(Tree::Leaf(1)));
+    assert_eq!("node", error.to_string());
+    assert_eq!("leaf 1", error.source().unwrap().to_string());
+}
+
+#[test]
+fn test_empty_bound() {
+    #[derive(Debug)]
+    struct NotDisplay;
+    let result: Result<(), std::io::Error> =
+        Err(std::io::Error::new(std::io::ErrorKind::Other, "oh no!"));
+    let error = result.throw_tagged(Label(NotDisplay)).unwrap_err();
+    assert_eq!("label failed", error.to_string());
+    assert!(error.source().is_some());
+}
@@ -0,0 +1,149 @@
+use std::error::Error as _;
+use std::io;
+use std::mem;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("request to {url} timed out after {elapsed_ms}ms")]
+pub struct Timeout {
+    url: String,
+    method: String,
+    elapsed_ms: u64,
+    #[source]
+    cause: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[error("server rejected the request with {status}")]
+pub struct Rejected {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+#[derive(Error, Debug)]
+pub enum RequestError {
+    #[error(boxed)]
+    Timeout(Box<Timeout>),
+    #[error(boxed)]
+    Rejected { details: Box<Rejected> },
+    #[error("cancelled")]
+    Cancelled,
+}
+
+fn timeout() -> Timeout {
+    Timeout {
+        url: "https://example.com".to_owned(),
+        method: "GET".to_owned(),
+        elapsed_ms: 500,
+        cause: io::Error::new(io::ErrorKind::TimedOut, "deadline elapsed"),
+    }
+}
+
+#[test]
+fn test_size() {
+    assert!(mem::size_of::<RequestError>() <= 2 * mem::size_of::<usize>());
+    assert!(mem::size_of::<Timeout>() > mem::size_of::<RequestError>());
+}
+
+#[test]
+fn test_boxed_display_and_source() {
+    let error = RequestError::from(timeout());
+    assert_eq!(
+        "request to https://example.com timed out after 500ms",
+        error.to_string(),
+    );
+    let source = error.source().unwrap();
+    assert_eq!("deadline elapsed", source.to_string());
+
+    let error = RequestError::from(Rejected {
+        status: 403,
+        headers: Vec::new(),
+        body: String::new(),
+    });
+    assert_eq!("server rejected the request with 403", error.to_string());
+    assert!(error.source().is_none());
+    assert!(matches!(error, RequestError::Rejected { .. }));
+}
+
+#[test]
+fn test_boxed_question_mark() {
+    fn send() -> Result<(), RequestError> {
+        Err(timeout())?
+    }
+    let error = send().unwrap_err();
+    match error {
+        RequestError::Timeout(timeout) => assert_eq!("GET", timeout.method),
+        _ => panic!("expected a timeout"),
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum FetchError {
+    #[error("fetching {url} stalled after {elapsed_ms}ms")]
+    #[error(boxed {
+        pub url: String,
+        pub headers: Vec<(String, String)>,
+        pub elapsed_ms: u64,
+        #[source]
+        pub cause: io::Error,
+    })]
+    Stalled(Box<FetchStalled>),
+    #[error("cancelled")]
+    Cancelled,
+}
+
+fn deadline() -> Result<(), io::Error> {
+    Err(io::Error::new(io::ErrorKind::TimedOut, "deadline elapsed"))
+}
+
+#[test]
+fn test_generated_payload() {
+    assert!(mem::size_of::<FetchError>() <= 2 * mem::size_of::<usize>());
+    assert!(mem::size_of::<FetchStalled>() > mem::size_of::<FetchError>());
+
+    let payload = FetchStalled {
+        url: "https://example.com".to_owned(),
+        headers: Vec::new(),
+        elapsed_ms: 500,
+        cause: io::Error::new(io::ErrorKind::TimedOut, "deadline elapsed"),
+    };
+    assert_eq!(
+        "fetching https://example.com stalled after 500ms",
+        payload.to_string(),
+    );
+    assert_eq!("deadline elapsed", payload.source().unwrap().to_string());
+
+    let error = FetchError::from(payload);
+    assert_eq!(
+        "fetching https://example.com stalled after 500ms",
+        error.to_string(),
+    );
+    assert_eq!("deadline elapsed", error.source().unwrap().to_string());
+}
+
+#[test]
+fn test_generated_throw_methods() {
+    let error = deadline()
+        .throw_stalled("https://example.com".to_owned(), Vec::new(), 500)
+        .unwrap_err();
+    assert_eq!(
+        "fetching https://example.com stalled after 500ms",
+        error.to_string(),
+    );
+    match error {
+        FetchError::Stalled(stalled) => {
+            assert_eq!(500, stalled.elapsed_ms);
+            assert_eq!(io::ErrorKind::TimedOut, stalled.cause.kind());
+        }
+        FetchError::Cancelled => panic!("expected a stall"),
+    }
+
+    let error = deadline()
+        .throw_stalled_with(|| ("https://example.org".to_owned(), Vec::new(), 20))
+        .unwrap_err();
+    assert_eq!(
+        "fetching https://example.org stalled after 20ms",
+        error.to_string(),
+    );
+}
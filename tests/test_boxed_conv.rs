@@ -0,0 +1,54 @@
+use std::error::Error as StdError;
+use std::fmt::Debug;
+use std::io;
+use thiserror::Error;
+
+type BoxError = Box<dyn StdError + Send + Sync + 'static>;
+
+#[derive(Error, Debug)]
+#[naur(boxed_conv)]
+#[error("failed to read {path}")]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(boxed_conv)]
+pub enum ParseError<T: Debug> {
+    #[error("unexpected {0:?}")]
+    Unexpected(T),
+    #[error("empty input")]
+    Empty,
+}
+
+fn take(error: BoxError) -> String {
+    error.to_string()
+}
+
+#[test]
+fn test_boxed() {
+    let error = ReadError {
+        path: "/etc/app.toml".to_owned(),
+        source: io::Error::new(io::ErrorKind::NotFound, "oh no!"),
+    };
+    let boxed = error.boxed();
+    assert_eq!("failed to read /etc/app.toml", boxed.to_string());
+    assert!(boxed.source().unwrap().is::<io::Error>());
+    assert!(boxed.downcast::<ReadError>().is_ok());
+
+    let boxed = ParseError::Unexpected('}').boxed();
+    assert_eq!("unexpected '}'", take(boxed));
+}
+
+#[test]
+fn test_into() {
+    fn parse() -> Result<(), BoxError> {
+        Err(ParseError::<char>::Empty)?
+    }
+    let error = parse().unwrap_err();
+    assert!(error.is::<ParseError<char>>());
+
+    let boxed: BoxError = ParseError::<char>::Empty.into();
+    assert_eq!("empty input", take(boxed));
+}
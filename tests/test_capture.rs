@@ -0,0 +1,92 @@
+use std::io;
+use std::thread::{self, ThreadId};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("worker failed")]
+pub struct WorkerError {
+    #[from]
+    source: io::Error,
+    #[capture(thread_name)]
+    thread: String,
+    #[capture(thread_id)]
+    thread_id: ThreadId,
+    #[capture(pid)]
+    pid: u32,
+}
+
+#[derive(Error, Debug)]
+#[naur(constructors)]
+pub enum JobError {
+    #[error("job {id} failed on {thread:?}")]
+    Failed {
+        id: u64,
+        #[source]
+        source: io::Error,
+        #[capture(thread_name)]
+        thread: Option<String>,
+    },
+    #[error("job {0} was cancelled")]
+    Cancelled(u64, #[capture(pid)] u32),
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "oh no")
+}
+
+#[test]
+fn test_from() {
+    let error = thread::Builder::new()
+        .name("worker-1".to_owned())
+        .spawn(|| (WorkerError::from(io_error()), thread::current().id()))
+        .unwrap()
+        .join()
+        .unwrap();
+    let (error, thread_id) = error;
+    assert_eq!("worker-1", error.thread);
+    assert_eq!(thread_id, error.thread_id);
+    assert_ne!(thread::current().id(), error.thread_id);
+    assert_eq!(std::process::id(), error.pid);
+}
+
+#[test]
+fn test_unnamed_thread() {
+    let error = thread::spawn(|| WorkerError::from(io_error()))
+        .join()
+        .unwrap();
+    assert_eq!("<unnamed>", error.thread);
+
+    let error = thread::spawn(|| {
+        let result: Result<(), io::Error> = Err(io_error());
+        result.throw_failed(1).unwrap_err()
+    })
+    .join()
+    .unwrap();
+    assert_eq!("job 1 failed on None", error.to_string());
+}
+
+#[test]
+fn test_throw() {
+    let error = thread::Builder::new()
+        .name("scheduler".to_owned())
+        .spawn(|| {
+            let result: Result<(), io::Error> = Err(io_error());
+            result.throw_failed(9).unwrap_err()
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert_eq!("job 9 failed on Some(\"scheduler\")", error.to_string());
+}
+
+#[test]
+fn test_constructor() {
+    let error = JobError::cancelled(4);
+    match error {
+        JobError::Cancelled(id, pid) => {
+            assert_eq!(4, id);
+            assert_eq!(std::process::id(), pid);
+        }
+        JobError::Failed { .. } => panic!("expected JobError::Cancelled"),
+    }
+}
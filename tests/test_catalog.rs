@@ -0,0 +1,100 @@
+use std::io;
+use thiserror::{Error, ErrorDescriptor};
+
+#[derive(Error, Debug)]
+#[naur(catalog)]
+#[error("failed to read {path}")]
+#[error(code = "E_READ", help = "check that {path} exists")]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(catalog)]
+#[error(code = "E_FETCH")]
+pub enum FetchError {
+    #[error("timed out after {0} ms")]
+    Timeout(u64),
+    #[error("user {id} not found")]
+    #[error(code = "E_NOT_FOUND", help = "look the user up first")]
+    NotFound { id: u64 },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[cfg(feature = "miette")]
+#[derive(Error, Debug)]
+#[naur(catalog)]
+#[diagnostic(code(app::parse), severity(Warning))]
+pub enum ParseError {
+    #[error("unexpected end of input")]
+    Eof,
+    #[error("invalid token {0:?}")]
+    #[diagnostic(severity(Error), help("quote the value"))]
+    Token(String),
+}
+
+#[test]
+fn test_struct() {
+    assert_eq!(
+        ReadError::CATALOG,
+        [ErrorDescriptor {
+            name: "ReadError",
+            code: Some("E_READ"),
+            template: Some("failed to read {path}"),
+            severity: None,
+            help: Some("check that {path} exists"),
+        }],
+    );
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(
+        FetchError::CATALOG,
+        [
+            ErrorDescriptor {
+                name: "Timeout",
+                code: Some("E_FETCH"),
+                template: Some("timed out after {0} ms"),
+                severity: None,
+                help: None,
+            },
+            ErrorDescriptor {
+                name: "NotFound",
+                code: Some("E_NOT_FOUND"),
+                template: Some("user {id} not found"),
+                severity: None,
+                help: Some("look the user up first"),
+            },
+            ErrorDescriptor {
+                name: "Io",
+                code: Some("E_FETCH"),
+                template: None,
+                severity: None,
+                help: None,
+            },
+        ],
+    );
+}
+
+#[cfg(feature = "miette")]
+#[test]
+fn test_diagnostic() {
+    let severities: Vec<_> = ParseError::CATALOG.iter().map(|d| d.severity).collect();
+    assert_eq!([Some("warning"), Some("error")], *severities);
+    assert_eq!(Some("app::parse"), ParseError::CATALOG[0].code);
+    assert_eq!(None, ParseError::CATALOG[0].help);
+    assert_eq!(Some("quote the value"), ParseError::CATALOG[1].help);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize() {
+    let json = serde_json::to_string(&ReadError::CATALOG).unwrap();
+    assert_eq!(
+        r#"[{"name":"ReadError","code":"E_READ","template":"failed to read {path}","severity":null,"help":"check that {path} exists"}]"#,
+        json,
+    );
+}
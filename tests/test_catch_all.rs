@@ -0,0 +1,74 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("not found")]
+    NotFound,
+    #[error(catch_all)]
+    Other(Box<dyn StdError + Send + Sync>),
+}
+
+#[derive(Error, Debug)]
+pub enum LabeledError {
+    #[error("something else went wrong")]
+    #[error(catch_all)]
+    Unexpected {
+        source: Box<dyn StdError + Send + Sync>,
+    },
+}
+
+#[derive(Debug)]
+struct Outer(io::Error);
+
+impl Display for Outer {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("outer")
+    }
+}
+
+impl StdError for Outer {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn outer() -> Outer {
+    Outer(io::Error::new(io::ErrorKind::Other, "inner"))
+}
+
+#[test]
+fn test_from_box() {
+    let boxed: Box<dyn StdError + Send + Sync> = Box::new(outer());
+    let error = AppError::from(boxed);
+    assert_eq!("outer", error.to_string());
+    assert_eq!("inner", error.source().unwrap().to_string());
+
+    let error = AppError::from(Box::<dyn StdError + Send + Sync>::from("plain text"));
+    assert_eq!("plain text", error.to_string());
+    assert_eq!("not found", AppError::NotFound.to_string());
+}
+
+#[test]
+fn test_throw() {
+    let error = Err::<(), _>(outer()).throw_other().unwrap_err();
+    assert!(matches!(error, AppError::Other(_)));
+    assert_eq!("outer", error.to_string());
+
+    let error = "x".parse::<i32>().throw_other().unwrap_err();
+    assert_eq!("invalid digit found in string", error.to_string());
+
+    let boxed: Box<dyn StdError + Send + Sync> = Box::new(outer());
+    let error = Err::<(), _>(boxed).throw_other().unwrap_err();
+    assert_eq!("outer", error.to_string());
+}
+
+#[test]
+fn test_message() {
+    let error = Err::<(), _>(outer()).throw_unexpected().unwrap_err();
+    assert!(matches!(error, LabeledError::Unexpected { .. }));
+    assert_eq!("something else went wrong", error.to_string());
+    assert_eq!("outer", error.source().unwrap().to_string());
+}
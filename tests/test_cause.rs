@@ -0,0 +1,88 @@
+#![cfg_attr(thiserror_nightly_testing, feature(error_generic_member_access))]
+
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to merge {left} into {right}")]
+pub struct MergeError {
+    left: &'static str,
+    right: &'static str,
+    #[source]
+    read_left: io::Error,
+    #[cause]
+    read_right: io::Error,
+}
+
+#[derive(Error, Debug)]
+pub enum CompareError {
+    #[error("comparison failed")]
+    Failed {
+        source: io::Error,
+        #[cause]
+        expected: Option<io::Error>,
+        #[cause]
+        actual: io::Error,
+    },
+    #[error("nothing to compare")]
+    Empty,
+}
+
+fn io_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, message)
+}
+
+fn merge_error() -> MergeError {
+    MergeError {
+        left: "a.txt",
+        right: "b.txt",
+        read_left: io_error("a.txt is locked"),
+        read_right: io_error("b.txt is missing"),
+    }
+}
+
+#[test]
+fn test_causes() {
+    let error = merge_error();
+    assert_eq!("a.txt is locked", error.source().unwrap().to_string());
+    let causes: Vec<String> = error.causes().map(ToString::to_string).collect();
+    assert_eq!(["b.txt is missing"], causes.as_slice());
+}
+
+#[test]
+fn test_enum_causes() {
+    let error = CompareError::Failed {
+        source: io_error("diff failed"),
+        expected: None,
+        actual: io_error("actual is unreadable"),
+    };
+    let causes: Vec<String> = error.causes().map(ToString::to_string).collect();
+    assert_eq!(["actual is unreadable"], causes.as_slice());
+
+    let error = CompareError::Failed {
+        source: io_error("diff failed"),
+        expected: Some(io_error("expected is unreadable")),
+        actual: io_error("actual is unreadable"),
+    };
+    assert_eq!(2, error.causes().count());
+
+    assert_eq!(0, CompareError::Empty.causes().count());
+}
+
+#[cfg(thiserror_nightly_testing)]
+#[test]
+fn test_chain_formatter() {
+    use thiserror::ChainFormatter;
+
+    let error = merge_error();
+    let expected = "\
+failed to merge a.txt into b.txt
+
+Caused by:
+    0: a.txt is locked
+
+Also caused by:
+    0: b.txt is missing";
+    assert_eq!(expected, ChainFormatter::new(&error).to_string());
+}
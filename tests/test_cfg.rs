@@ -0,0 +1,50 @@
+// Variants and fields behind a disabled cfg are stripped by the compiler
+// before the derive sees them, so no generated item may refer to them.
+// `cfg(any())` is never enabled and `cfg(test)` always is here.
+
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DbError {
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+    #[cfg(any())]
+    #[error("postgres failed")]
+    Postgres {
+        #[from]
+        source: postgres::Error,
+    },
+    #[error("query {query} failed")]
+    Query {
+        query: String,
+        #[cfg(any())]
+        detail: postgres::Detail,
+        #[cfg_attr(test, source)]
+        cause: io::Error,
+    },
+    #[cfg(test)]
+    #[error("pool exhausted")]
+    Pool(#[cfg_attr(any(), from)] usize),
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "oh no!")
+}
+
+#[test]
+fn test_cfg_variant() {
+    let error = DbError::from(io_error());
+    assert_eq!("i/o failed", error.to_string());
+    assert_eq!("pool exhausted", DbError::Pool(8).to_string());
+}
+
+#[test]
+fn test_cfg_field() {
+    let error = Err::<(), _>(io_error())
+        .throw_query("SELECT 1".to_owned())
+        .unwrap_err();
+    assert_eq!("query SELECT 1 failed", error.to_string());
+    assert!(error.source().is_some());
+}
@@ -0,0 +1,180 @@
+use std::error::Error as _;
+use std::fmt::{self, Display};
+use std::io;
+use thiserror::{Chain, ChainFormatter, Error};
+
+#[derive(Error, Debug)]
+#[error("failed to load config")]
+#[naur(chain)]
+pub struct ConfigError {
+    #[from]
+    source: ReadError,
+}
+
+#[derive(Error, Debug)]
+#[naur(chain)]
+pub enum ReadError {
+    #[error("failed to read {1}")]
+    Io(#[source] io::Error, String),
+    #[error("file is empty")]
+    Empty,
+}
+
+#[derive(Error, Debug)]
+#[error("wrapped: {0}")]
+#[naur(chain)]
+pub struct Wrapped<E>(#[source] E);
+
+fn config_error() -> ConfigError {
+    let io = io::Error::new(io::ErrorKind::PermissionDenied, "permission denied");
+    ConfigError::from(ReadError::Io(io, "/etc/app.toml".to_owned()))
+}
+
+#[test]
+fn test_chain_len() {
+    assert_eq!(3, config_error().chain_len());
+    assert_eq!(1, ReadError::Empty.chain_len());
+}
+
+#[test]
+fn test_chain_summary() {
+    assert_eq!(
+        "failed to load config: permission denied",
+        config_error().chain_summary(),
+    );
+    assert_eq!("file is empty", ReadError::Empty.chain_summary());
+}
+
+#[test]
+fn test_chain() {
+    let error = config_error();
+    let messages: Vec<String> = error.chain().map(|error| error.to_string()).collect();
+    assert_eq!(
+        [
+            "failed to load config",
+            "failed to read /etc/app.toml",
+            "permission denied",
+        ],
+        messages.as_slice(),
+    );
+    let root = error.chain().last().unwrap();
+    assert!(root.is::<io::Error>());
+    assert!(root.source().is_none());
+
+    let empty = ReadError::Empty;
+    assert_eq!(1, empty.chain().count());
+}
+
+#[test]
+fn test_root_cause() {
+    let error = config_error();
+    let root = error.root_cause();
+    assert!(root.is::<io::Error>());
+    assert_eq!("permission denied", root.to_string());
+
+    let empty = ReadError::Empty;
+    assert!(empty.root_cause().is::<ReadError>());
+    assert_eq!("file is empty", empty.root_cause().to_string());
+
+    let wrapped = Wrapped(ReadError::Empty);
+    assert!(wrapped.root_cause().is::<ReadError>());
+}
+
+#[test]
+fn test_chain_new() {
+    let error = config_error();
+    let source = error.source().unwrap();
+    assert_eq!(2, Chain::new(source).count());
+    assert_eq!(
+        "[Io(Custom { kind: PermissionDenied, error: \"permission denied\" }, \"/etc/app.toml\"), Custom { kind: PermissionDenied, error: \"permission denied\" }]",
+        format!("{:?}", Chain::new(source)),
+    );
+}
+
+#[test]
+fn test_generic() {
+    let error = Wrapped(ReadError::Empty);
+    assert_eq!(2, error.chain_len());
+    assert_eq!(2, error.chain().count());
+    assert_eq!(
+        "wrapped: file is empty: file is empty",
+        error.chain_summary()
+    );
+}
+
+#[test]
+fn test_source_downcast() {
+    let error = config_error();
+    let io = error.source_downcast::<io::Error>().unwrap();
+    assert_eq!(io::ErrorKind::PermissionDenied, io.kind());
+    assert!(error.source_downcast::<ReadError>().is_some());
+    assert!(error.source_downcast::<ConfigError>().is_none());
+
+    assert!(error.direct_source_downcast::<ReadError>().is_some());
+    assert!(error.direct_source_downcast::<io::Error>().is_none());
+    assert!(ReadError::Empty
+        .direct_source_downcast::<io::Error>()
+        .is_none());
+
+    let wrapped = Wrapped(config_error());
+    assert!(wrapped.source_downcast::<io::Error>().is_some());
+    assert!(wrapped.direct_source_downcast::<ConfigError>().is_some());
+}
+
+// An error whose source is the other error of a pair, which in turn has this
+// one as its source.
+#[derive(Debug)]
+pub struct Cycle(&'static str, &'static Cycle);
+
+static PING: Cycle = Cycle("ping", &PONG);
+static PONG: Cycle = Cycle("pong", &PING);
+
+impl Display for Cycle {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.0)
+    }
+}
+
+impl std::error::Error for Cycle {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.1)
+    }
+}
+
+// A zero-sized error which is its own source.
+#[derive(Debug)]
+pub struct Recursive;
+
+impl Display for Recursive {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("recursive")
+    }
+}
+
+impl std::error::Error for Recursive {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self)
+    }
+}
+
+#[test]
+fn test_cycle() {
+    let messages: Vec<String> = Chain::new(&PING).map(|error| error.to_string()).collect();
+    assert_eq!(["ping", "pong"], messages.as_slice());
+
+    let root = Chain::new(&PING).last().unwrap();
+    assert_eq!("pong", root.to_string());
+
+    let expected = "\
+ping
+
+Caused by:
+    0: pong";
+    assert_eq!(expected, ChainFormatter::new(&PING).to_string());
+}
+
+#[test]
+fn test_depth_limit() {
+    assert_eq!(256, Chain::new(&Recursive).count());
+    assert_eq!(256, Wrapped(Recursive).chain_len());
+}
@@ -0,0 +1,75 @@
+use std::io;
+use thiserror::{ChainFormatter, Error, Report};
+
+#[derive(Error, Debug)]
+#[error("failed to start the service")]
+pub struct StartError {
+    #[from]
+    source: ConfigError,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to load the configuration from {path}")]
+pub struct ConfigError {
+    source: io::Error,
+    path: &'static str,
+}
+
+fn error() -> StartError {
+    StartError::from(ConfigError {
+        source: io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "permission denied\nthe file is owned by root",
+        ),
+        path: "/etc/service/config.toml",
+    })
+}
+
+#[test]
+fn test_default() {
+    let expected = "\
+failed to start the service
+
+Caused by:
+    0: failed to load the configuration from /etc/service/config.toml
+    1: permission denied
+       the file is owned by root";
+    assert_eq!(expected, ChainFormatter::new(&error()).to_string());
+}
+
+#[test]
+fn test_width() {
+    let expected = "\
+failed to start the
+service
+
+Caused by:
+  0: failed to load the
+     configuration from
+     /etc/service/config.toml
+  1: permission denied
+     the file is owned by
+     root";
+    let error = error();
+    let formatter = ChainFormatter::new(&error).indent(2).width(25);
+    assert_eq!(expected, formatter.to_string());
+}
+
+#[test]
+fn test_format_width() {
+    let error = error();
+    let wrapped = format!("{:40}", ChainFormatter::new(&error));
+    assert!(wrapped.contains(
+        "\n    0: failed to load the configuration\n       from /etc/service/config.toml\n"
+    ));
+
+    let report = Report::new(error);
+    assert!(format!("{:40?}", report).starts_with(&wrapped));
+}
+
+#[test]
+fn test_no_source() {
+    let error = io::Error::new(io::ErrorKind::Other, "oh no!");
+    assert_eq!("oh no!", ChainFormatter::new(&error).to_string());
+    assert_eq!("oh\nno!", ChainFormatter::new(&error).width(1).to_string());
+}
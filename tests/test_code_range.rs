@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(code_range = 4000..5000)]
+#[error(code = "4000")]
+pub enum BillingError {
+    #[error("card declined")]
+    #[error(code = "E4001")]
+    CardDeclined,
+    #[error("invoice {0} not found")]
+    #[error(code = "E4002")]
+    InvoiceNotFound(u64),
+    #[error("billing unavailable")]
+    Unavailable,
+}
+
+#[derive(Error, Debug)]
+#[naur(code_range = 100..=199)]
+#[error("quota exceeded")]
+#[error(code = "199")]
+pub struct QuotaError;
+
+#[test]
+fn test_codes() {
+    assert_eq!(Some("E4001"), BillingError::CardDeclined.code());
+    assert_eq!(Some("E4002"), BillingError::InvoiceNotFound(1).code());
+    assert_eq!(Some("4000"), BillingError::Unavailable.code());
+    assert_eq!(Some("199"), QuotaError.code());
+}
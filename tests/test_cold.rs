@@ -0,0 +1,66 @@
+use std::error::Error as _;
+use std::io;
+use std::num::ParseIntError;
+use thiserror::{Error, Pipeline};
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+#[naur(cold)]
+#[throws(pipe)]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(cold)]
+pub enum ParseError {
+    #[error("invalid number")]
+    Number(#[from] ParseIntError),
+    #[error("line {line} is invalid")]
+    Line {
+        line: usize,
+        #[source]
+        source: ParseIntError,
+    },
+    #[error("{msg}")]
+    #[error(context)]
+    Context {
+        msg: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+fn read(path: &str) -> Result<String, io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path),
+    ))
+}
+
+#[test]
+fn test_cold_from() {
+    let error = ParseError::from("x".parse::<i32>().unwrap_err());
+    assert_eq!("invalid number", error.to_string());
+}
+
+#[test]
+fn test_cold_throw() {
+    let error = read("/etc/app.toml")
+        .throw_read("/etc/app.toml".to_owned())
+        .unwrap_err();
+    assert_eq!("failed to read /etc/app.toml", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let error = "x".parse::<i32>().throw_line_with(|| 3).unwrap_err();
+    assert_eq!("line 3 is invalid", error.to_string());
+
+    let result = Pipeline::new("/etc/app.toml").pipe_throw_read(read, "/etc/app.toml".to_owned());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cold_context() {
+    let error = read("/etc/app.toml").context("loading").unwrap_err();
+    assert_eq!("loading", error.to_string());
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "color")]
+
+use std::io;
+use thiserror::{ChainFormatter, Error, Report};
+
+#[derive(Error, Debug)]
+#[error("failed to load config")]
+pub struct ConfigError {
+    #[from]
+    source: io::Error,
+}
+
+fn error() -> ConfigError {
+    ConfigError::from(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "permission denied",
+    ))
+}
+
+#[test]
+fn test_chain_formatter() {
+    let expected = "\
+\x1b[1m\x1b[31mfailed to load config\x1b[0m
+
+\x1b[1mCaused by:\x1b[0m
+    \x1b[33m0:\x1b[0m permission denied";
+    let error = error();
+    assert_eq!(
+        expected,
+        ChainFormatter::new(&error).color(true).to_string()
+    );
+}
+
+#[test]
+fn test_wrapped_headline() {
+    let expected = "\
+\x1b[1m\x1b[31mfailed to\x1b[0m
+\x1b[1m\x1b[31mload config\x1b[0m
+
+\x1b[1mCaused by:\x1b[0m
+    \x1b[33m0:\x1b[0m permission
+       denied";
+    let error = error();
+    let formatter = ChainFormatter::new(&error).color(true).width(12);
+    assert_eq!(expected, formatter.to_string());
+}
+
+#[test]
+fn test_uncolored() {
+    let error = error();
+    let rendered = ChainFormatter::new(&error).color(false).to_string();
+    assert!(!rendered.contains('\x1b'));
+
+    let report = Report::new(error).color(false);
+    assert!(!format!("{:?}", report).contains('\x1b'));
+}
+
+#[test]
+fn test_report() {
+    let report = Report::new(error()).color(true);
+    let rendered = format!("{:?}", report);
+    assert!(rendered.starts_with("\x1b[1m\x1b[31mfailed to load config\x1b[0m"));
+    assert!(rendered.contains("\n\n\x1b[1mLocation:\x1b[0m\n    tests/test_color.rs:"));
+}
@@ -0,0 +1,60 @@
+use std::io;
+use thiserror::Error;
+
+mod messages {
+    pub const IO_FAILURE: &str = "I/O failure while accessing {path}";
+    pub const TIMEOUT: &str = "timed out after {0}ms ({{ms}})";
+    pub const UNAVAILABLE: &str = "service unavailable";
+}
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error(const = messages::IO_FAILURE)]
+    Read {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error(const = messages::IO_FAILURE)]
+    Write { path: String, attempts: usize },
+    #[error(const = messages::TIMEOUT)]
+    Timeout(u64),
+    #[error(const = messages::UNAVAILABLE)]
+    Unavailable,
+}
+
+#[derive(Error, Debug)]
+#[error(const = crate::messages::IO_FAILURE)]
+pub struct ConfigError {
+    path: &'static str,
+}
+
+#[test]
+fn test_shared_constant() {
+    let error = StorageError::Read {
+        path: "/var/data".to_owned(),
+        source: io::Error::new(io::ErrorKind::Other, "oh no"),
+    };
+    assert_eq!("I/O failure while accessing /var/data", error.to_string());
+
+    let error = StorageError::Write {
+        path: "/var/log".to_owned(),
+        attempts: 3,
+    };
+    assert_eq!("I/O failure while accessing /var/log", error.to_string());
+
+    let error = ConfigError { path: "app.toml" };
+    assert_eq!("I/O failure while accessing app.toml", error.to_string());
+}
+
+#[test]
+fn test_tuple_field_and_escapes() {
+    let error = StorageError::Timeout(250);
+    assert_eq!("timed out after 250ms ({ms})", error.to_string());
+}
+
+#[test]
+fn test_no_fields() {
+    let error = StorageError::Unavailable;
+    assert_eq!("service unavailable", error.to_string());
+}
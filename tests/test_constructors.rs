@@ -0,0 +1,97 @@
+#![cfg_attr(thiserror_nightly_testing, feature(error_generic_member_access))]
+
+use std::error::Error as _;
+use std::io;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(constructors)]
+pub enum ConfigError {
+    #[error("invalid value {value} for {msg}")]
+    InvalidMsg {
+        msg: String,
+        value: i32,
+        #[source]
+        source: io::Error,
+    },
+    #[error("missing key {0}")]
+    Missing(String),
+    #[error("failed to read {1}")]
+    Read(#[source] io::Error, String),
+    #[error("shared failure")]
+    Shared {
+        #[source]
+        #[error(arc_source)]
+        source: Arc<io::Error>,
+    },
+    #[error("no configuration")]
+    Empty,
+}
+
+#[derive(Error, Debug)]
+#[naur(constructors)]
+#[error("request {id} failed")]
+pub struct RequestError {
+    id: u64,
+    source: io::Error,
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "not found")
+}
+
+#[test]
+fn test_source_first() {
+    let error = ConfigError::invalid_msg(io_error(), "retries".to_owned(), -1);
+    assert_eq!("invalid value -1 for retries", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let error = ConfigError::read(io_error(), "app.toml".to_owned());
+    assert_eq!("failed to read app.toml", error.to_string());
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn test_sourceless() {
+    let error = ConfigError::missing("port".to_owned());
+    assert_eq!("missing key port", error.to_string());
+    assert!(error.source().is_none());
+
+    let error = ConfigError::empty();
+    assert_eq!("no configuration", error.to_string());
+}
+
+#[test]
+fn test_arc_source() {
+    let error = ConfigError::shared(io_error());
+    assert_eq!("not found", error.source().unwrap().to_string());
+}
+
+#[test]
+fn test_struct() {
+    let error = RequestError::new(io_error(), 7);
+    assert_eq!("request 7 failed", error.to_string());
+    assert!(error.source().is_some());
+}
+
+#[cfg(thiserror_nightly_testing)]
+pub mod nightly {
+    use std::backtrace::{Backtrace, BacktraceStatus};
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    #[naur(constructors)]
+    pub enum TimeoutError {
+        #[error("timed out after {secs}s")]
+        Timeout { secs: u64, backtrace: Backtrace },
+    }
+
+    #[test]
+    fn test_backtrace_captured() {
+        let error = TimeoutError::timeout(30);
+        assert_eq!("timed out after 30s", error.to_string());
+        let TimeoutError::Timeout { backtrace, .. } = &error;
+        assert_ne!(BacktraceStatus::Unsupported, backtrace.status());
+    }
+}
@@ -0,0 +1,65 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("config is missing key {0}")]
+    MissingKey(String),
+    #[error("{msg}")]
+    #[error(context)]
+    Context {
+        msg: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+fn read(path: &str) -> Result<String, io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path),
+    ))
+}
+
+fn load() -> Result<String, AppError> {
+    let config = read("/etc/app.toml").context("reading config")?;
+    Ok(config)
+}
+
+#[test]
+fn test_context() {
+    let error = load().unwrap_err();
+    assert_eq!("reading config", error.to_string());
+    let source = error.source().unwrap();
+    assert_eq!("/etc/app.toml not found", source.to_string());
+    assert!(source.is::<io::Error>());
+}
+
+#[test]
+fn test_with_context() {
+    let path = "/etc/app.toml";
+    let error = read(path)
+        .with_context(|| format!("reading {}", path))
+        .unwrap_err();
+    assert_eq!("reading /etc/app.toml", error.to_string());
+
+    let mut called = false;
+    let ok: Result<i32, io::Error> = Ok(1);
+    let value = ok
+        .with_context(|| {
+            called = true;
+            "unused"
+        })
+        .unwrap();
+    assert_eq!(1, value);
+    assert!(!called);
+}
+
+#[test]
+fn test_nested() {
+    let error = load().map(drop).context("starting server").unwrap_err();
+    assert_eq!("starting server", error.to_string());
+    let source = error.source().unwrap();
+    assert_eq!("reading config", source.to_string());
+    assert!(source.is::<AppError>());
+}
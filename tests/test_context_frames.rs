@@ -0,0 +1,108 @@
+#![cfg_attr(thiserror_nightly_testing, feature(error_generic_member_access))]
+
+use std::io;
+use std::thread;
+use thiserror::context::{self, ContextFrames};
+use thiserror::Error;
+#[cfg(thiserror_nightly_testing)]
+use thiserror::Report;
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+    #[context_frames]
+    frames: ContextFrames,
+}
+
+#[derive(Error, Debug)]
+#[naur(constructors)]
+pub enum LoadError {
+    #[error("failed to load config")]
+    Read(#[from] ReadError),
+    #[error("invalid line {line}")]
+    Parse {
+        line: usize,
+        #[context_frames]
+        frames: ContextFrames,
+    },
+}
+
+fn read(path: &str) -> Result<String, io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path),
+    ))
+}
+
+fn load() -> Result<String, LoadError> {
+    let _frame = context::enter("loading config");
+    let config = read("/etc/app.toml").throw_read("/etc/app.toml".to_owned())?;
+    Ok(config)
+}
+
+#[test]
+fn test_throw() {
+    let _frame = context::enter("starting server");
+    let error = match load().unwrap_err() {
+        LoadError::Read(error) => error,
+        LoadError::Parse { .. } => unreachable!(),
+    };
+    let frames: Vec<&str> = error.frames.iter().collect();
+    assert_eq!(["starting server", "loading config"], *frames);
+    assert_eq!("starting server > loading config", error.frames.to_string());
+}
+
+#[test]
+fn test_guard() {
+    let outer = context::enter("outer");
+    let inner = context::enter(format!("step {}", 2));
+    assert_eq!("outer > step 2", ContextFrames::capture().to_string());
+    drop(inner);
+    assert_eq!("outer", ContextFrames::capture().to_string());
+
+    // Dropping a guard also removes the frames entered after it.
+    let inner = context::enter("inner");
+    drop(outer);
+    assert!(ContextFrames::capture().is_empty());
+    drop(inner);
+    assert!(ContextFrames::capture().is_empty());
+}
+
+#[test]
+fn test_constructor() {
+    let _frame = context::enter("parsing");
+    let error = LoadError::parse(3);
+    let LoadError::Parse { frames, .. } = &error else {
+        unreachable!()
+    };
+    assert_eq!(1, frames.len());
+
+    // Each thread has a context of its own.
+    thread::spawn(|| assert!(ContextFrames::capture().is_empty()))
+        .join()
+        .unwrap();
+}
+
+#[cfg(thiserror_nightly_testing)]
+#[test]
+fn test_report() {
+    let _frame = context::enter("starting server");
+    let report = Report::new(load().unwrap_err());
+    let rendered = format!("{:?}", report);
+    let expected = "\
+failed to load config
+
+Caused by:
+    0: failed to read /etc/app.toml
+    1: /etc/app.toml not found
+
+Context:
+    starting server
+    loading config
+
+Location:
+";
+    assert!(rendered.starts_with(expected), "{}", rendered);
+}
@@ -0,0 +1,103 @@
+use std::error::Error as _;
+use std::io;
+use std::num::ParseIntError;
+use thiserror::{Error, Pipeline};
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("invalid {msg}: {value}")]
+    #[throws(context_struct, pipe)]
+    InvalidMsg {
+        msg: String,
+        value: i32,
+        source: ParseIntError,
+    },
+    #[error("invalid line {0} in {1}")]
+    #[throws(context_struct)]
+    InvalidLine(usize, String, #[source] ParseIntError),
+    #[error("missing {key}")]
+    Missing {
+        key: String,
+        #[source]
+        source: ParseIntError,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+#[throws(context_struct)]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+fn read(path: &str) -> Result<String, io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path),
+    ))
+}
+
+#[test]
+fn test_named() {
+    let msg = "port".to_owned();
+    let error = "x"
+        .parse::<i32>()
+        .throw_invalid_msg(ConfigErrorInvalidMsgContext { msg, value: 7 })
+        .unwrap_err();
+    assert_eq!("invalid port: 7", error.to_string());
+    assert!(error.source().unwrap().is::<ParseIntError>());
+
+    let error = "x"
+        .parse::<i32>()
+        .throw_invalid_msg(("timeout".to_owned(), 30).into())
+        .unwrap_err();
+    assert_eq!("invalid timeout: 30", error.to_string());
+
+    let error = "x"
+        .parse::<i32>()
+        .throw_invalid_msg_with(|| ConfigErrorInvalidMsgContext::from(("retries".to_owned(), 3)))
+        .unwrap_err();
+    assert_eq!("invalid retries: 3", error.to_string());
+}
+
+#[test]
+fn test_tuple() {
+    let error = "x"
+        .parse::<i32>()
+        .throw_invalid_line(ConfigErrorInvalidLineContext(4, "app.toml".to_owned()))
+        .unwrap_err();
+    assert_eq!("invalid line 4 in app.toml", error.to_string());
+}
+
+#[test]
+fn test_pipe() {
+    let error = Pipeline::new("x")
+        .pipe_throw_invalid_msg(str::parse::<i32>, ("port".to_owned(), 0).into())
+        .unwrap_err();
+    assert_eq!("invalid port: 0", error.to_string());
+}
+
+#[test]
+fn test_unaffected_variant() {
+    let error = "x"
+        .parse::<i32>()
+        .throw_missing("port".to_owned())
+        .unwrap_err();
+    assert_eq!("missing port", error.to_string());
+}
+
+#[test]
+fn test_struct() {
+    let error = read("/etc/app.toml")
+        .throw_read(ReadErrorContext::from("/etc/app.toml".to_owned()))
+        .unwrap_err();
+    assert_eq!("failed to read /etc/app.toml", error.to_string());
+
+    let error = read("/etc/app.toml")
+        .throw_read(ReadErrorContext {
+            path: "/etc/app.toml".to_owned(),
+        })
+        .unwrap_err();
+    assert!(error.source().unwrap().is::<io::Error>());
+}
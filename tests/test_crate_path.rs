@@ -0,0 +1,55 @@
+mod facade {
+    pub use thiserror as naur;
+}
+
+mod app {
+    use crate::facade::naur::Error;
+    use std::io;
+
+    // Shadows the runtime crate, so that the derives below compile only if
+    // their expansion goes through the configured path.
+    #[allow(dead_code)]
+    mod thiserror {}
+
+    #[derive(Error, Debug)]
+    #[naur(crate = "crate::facade::naur", manifest)]
+    #[error("failed to read {path}")]
+    pub struct ReadError {
+        pub path: String,
+        pub source: io::Error,
+    }
+
+    #[derive(Error, Debug)]
+    #[naur(crate = "crate::facade::naur", kind)]
+    pub enum LoadError {
+        #[error("{0}")]
+        Read(#[from] ReadError),
+        #[error("invalid line {line}")]
+        Parse { line: usize },
+    }
+}
+
+use app::{LoadError, ReadError};
+use std::error::Error as _;
+use std::io;
+
+#[test]
+fn test_struct() {
+    let error = ReadError {
+        path: "naur.toml".to_owned(),
+        source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+    };
+    assert_eq!("failed to read naur.toml", error.to_string());
+    assert!(error.source().is_some());
+    assert_eq!("ReadError", ReadError::manifest().name);
+}
+
+#[test]
+fn test_enum() {
+    let error = LoadError::from(ReadError {
+        path: "naur.toml".to_owned(),
+        source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+    });
+    assert_eq!("failed to read naur.toml", error.to_string());
+    assert_eq!("invalid line 3", LoadError::Parse { line: 3 }.to_string());
+}
@@ -0,0 +1,69 @@
+#![cfg_attr(thiserror_nightly_testing, feature(error_generic_member_access))]
+
+use std::io;
+use thiserror::Error;
+
+#[derive(Error)]
+#[naur(debug_chain)]
+#[error("failed to load config")]
+pub struct ConfigError {
+    #[from]
+    source: ParseError,
+}
+
+#[derive(Error)]
+#[naur(debug_chain)]
+pub enum ParseError {
+    #[error("failed to read config")]
+    Read(#[source] io::Error),
+    #[error("invalid config")]
+    Invalid,
+}
+
+#[test]
+fn test_debug_chain() {
+    let error = ConfigError::from(ParseError::Read(io::Error::new(
+        io::ErrorKind::NotFound,
+        "no such file\nor directory",
+    )));
+    let expected = "\
+        failed to load config\n\
+        \n\
+        Caused by:\n    \
+            0: failed to read config\n    \
+            1: no such file\n       \
+               or directory";
+    assert_eq!(expected, format!("{:?}", error));
+}
+
+#[test]
+fn test_no_source() {
+    assert_eq!("invalid config", format!("{:?}", ParseError::Invalid));
+}
+
+#[cfg(thiserror_nightly_testing)]
+pub mod nightly {
+    use std::backtrace::Backtrace;
+    use thiserror::Error;
+
+    #[derive(Error)]
+    #[naur(debug_chain)]
+    #[error("invalid config")]
+    pub struct InvalidError {
+        backtrace: Backtrace,
+    }
+
+    #[test]
+    fn test_backtrace() {
+        let error = InvalidError {
+            backtrace: Backtrace::force_capture(),
+        };
+        let debug = format!("{:?}", error);
+        assert!(debug.starts_with("invalid config\n\nStack backtrace:\n"));
+
+        let error = InvalidError {
+            backtrace: Backtrace::disabled(),
+        };
+        assert_eq!("invalid config", format!("{:?}", error));
+    }
+}
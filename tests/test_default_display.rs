@@ -0,0 +1,40 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(default = "{variant} failed")]
+pub enum JobError {
+    Fetch,
+    Parse(String),
+    #[error("upload of {0} bytes failed")]
+    Upload(usize),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[test]
+fn test_default() {
+    assert_eq!("Fetch failed", JobError::Fetch.to_string());
+    assert_eq!("Parse failed", JobError::Parse(String::new()).to_string());
+}
+
+#[test]
+fn test_own_message() {
+    assert_eq!("upload of 9 bytes failed", JobError::Upload(9).to_string());
+
+    let error = JobError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("oh no!", error.to_string());
+}
+
+#[test]
+fn test_fields() {
+    #[derive(Error, Debug)]
+    #[error(default = "{variant}: {0} {{retry}}")]
+    pub enum Error {
+        Timeout(u64),
+        r#Refused(u16),
+    }
+
+    assert_eq!("Timeout: 30 {retry}", Error::Timeout(30).to_string());
+    assert_eq!("Refused: 80 {retry}", Error::Refused(80).to_string());
+}
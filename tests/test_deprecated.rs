@@ -8,3 +8,15 @@ pub enum Error {
     #[error("...")]
     Deprecated,
 }
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+    #[deprecated = "use ReadError::Io"]
+    #[error("failed to read {path}")]
+    Legacy {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("i/o failed")]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,55 @@
+use std::convert::TryFrom;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[repr(u16)]
+#[naur(kind)]
+pub enum WireError {
+    #[error("not found: {0}")]
+    NotFound(String) = 404,
+    #[error("gone")]
+    Gone,
+    #[error("unavailable, retry in {retry_after}s")]
+    Unavailable { retry_after: u64 } = 503,
+}
+
+#[test]
+fn test_discriminant() {
+    assert_eq!(404, WireError::NotFound("key".to_owned()).discriminant());
+    assert_eq!(405, WireError::Gone.discriminant());
+    let error = WireError::Unavailable { retry_after: 5 };
+    assert_eq!(503, error.discriminant());
+}
+
+#[test]
+fn test_kind_round_trip() {
+    assert_eq!(404, WireErrorKind::NotFound as u16);
+    assert_eq!(405, WireErrorKind::Gone as u16);
+
+    for error in [WireError::Gone, WireError::Unavailable { retry_after: 1 }] {
+        let kind = WireErrorKind::try_from(error.discriminant());
+        assert_eq!(Ok(error.kind()), kind);
+    }
+    assert_eq!(Err(500), WireErrorKind::try_from(500));
+}
+
+#[test]
+fn test_implicit() {
+    #[derive(Error, Debug)]
+    #[repr(i8)]
+    pub enum Error {
+        #[error("first")]
+        First,
+        #[error("second")]
+        Second(u8),
+        #[error("negative")]
+        Negative = -3,
+        #[error("after")]
+        After,
+    }
+
+    assert_eq!(0, Error::First.discriminant());
+    assert_eq!(1, Error::Second(0).discriminant());
+    assert_eq!(-3, Error::Negative.discriminant());
+    assert_eq!(-2, Error::After.discriminant());
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::uninlined_format_args)]
 
 use std::fmt::{self, Display};
+use std::path::PathBuf;
 use thiserror::Error;
 
 fn assert<T: Display>(expected: &str, value: T) {
@@ -79,6 +80,19 @@ fn test_constants() {
     assert("failed to do: \"\" (code 9)", Error { id: "" });
 }
 
+#[test]
+fn test_positional_args() {
+    #[derive(Error, Debug)]
+    #[error("{task} gave up after {0}ms", TIMEOUT_MS)]
+    struct Error {
+        task: &'static str,
+    }
+
+    const TIMEOUT_MS: u64 = 500;
+
+    assert("lookup gave up after 500ms", Error { task: "lookup" });
+}
+
 #[test]
 fn test_inherit() {
     #[derive(Error, Debug)]
@@ -174,7 +188,7 @@ fn test_nested_display() {
 
 #[test]
 fn test_void() {
-    #[allow(clippy::empty_enum)]
+    #[allow(clippy::empty_enums)]
     #[derive(Error, Debug)]
     #[error("...")]
     pub enum Error {}
@@ -282,6 +296,30 @@ fn test_raw_enum() {
     assert("braced raw error: T", Error::Braced { r#fn: "T" });
 }
 
+#[test]
+fn test_raw_unprefixed() {
+    #[derive(Error, Debug)]
+    enum Error {
+        #[error("{type} at {where}: [{move:>width$}]")]
+        Braced {
+            r#type: &'static str,
+            r#where: usize,
+            r#move: &'static str,
+            r#width: usize,
+        },
+    }
+
+    assert(
+        "T at 1: [  m]",
+        Error::Braced {
+            r#type: "T",
+            r#where: 1,
+            r#move: "m",
+            r#width: 3,
+        },
+    );
+}
+
 #[test]
 fn test_raw_conflict() {
     #[derive(Error, Debug)]
@@ -301,3 +339,234 @@ fn test_keyword() {
 
     assert("error: 1", Error);
 }
+
+#[test]
+fn test_nested_field() {
+    #[derive(Debug)]
+    struct Config {
+        path: PathBuf,
+        retries: (u8, u8),
+    }
+
+    #[derive(Error, Debug)]
+    enum Error {
+        #[error("bad config at {config.path}, {config.retries.1:?} retries left")]
+        Braced { config: Config },
+        #[error("bad config at {0.path:?}")]
+        Tuple(Config),
+    }
+
+    let config = || Config {
+        path: PathBuf::from("/etc/app.toml"),
+        retries: (3, 2),
+    };
+    assert(
+        "bad config at /etc/app.toml, 2 retries left",
+        Error::Braced { config: config() },
+    );
+    assert("bad config at \"/etc/app.toml\"", Error::Tuple(config()));
+}
+
+#[test]
+fn test_nested_field_conflict() {
+    #[derive(Debug)]
+    struct Inner {
+        kind: &'static str,
+    }
+
+    #[derive(Error, Debug)]
+    #[error("{inner.kind} {nested__inner__kind}")]
+    #[allow(non_snake_case)]
+    struct Error {
+        inner: Inner,
+        nested__inner__kind: &'static str,
+    }
+
+    let error = Error {
+        inner: Inner { kind: "T" },
+        nested__inner__kind: "U",
+    };
+    assert("T U", error);
+}
+
+#[test]
+fn test_expression() {
+    #[derive(Error, Debug)]
+    enum Error {
+        #[error("failed to open {path.display()}")]
+        Open { path: PathBuf },
+        #[error("expected {len + 1} items, got {len}")]
+        Length { len: usize },
+        #[error("{0.to_uppercase():>6}|{0.len()}")]
+        Tuple(String),
+        #[error("{items.iter().map(|item| item * 2).sum::<u32>()} {items[0]:?}")]
+        Closure { items: Vec<u32> },
+        #[error("{name.split(\":\").next().unwrap()}")]
+        Colon { name: &'static str },
+    }
+
+    assert(
+        "failed to open /etc/app.toml",
+        Error::Open {
+            path: PathBuf::from("/etc/app.toml"),
+        },
+    );
+    assert("expected 4 items, got 3", Error::Length { len: 3 });
+    assert("   ABC|3", Error::Tuple("abc".to_owned()));
+    assert(
+        "12 1",
+        Error::Closure {
+            items: vec![1, 2, 3],
+        },
+    );
+    assert("host", Error::Colon { name: "host:80" });
+}
+
+mod formatters {
+    use std::fmt;
+
+    pub fn missing(names: &[&str], limit: &usize, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match names.len() {
+            0 => formatter.write_str("nothing is missing"),
+            1 => write!(formatter, "{} is missing", names[0]),
+            n if n > *limit => write!(formatter, "{} fields are missing", n),
+            _ => write!(formatter, "{} are missing", names.join(", ")),
+        }
+    }
+
+    pub fn unit(formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("unit")
+    }
+}
+
+#[test]
+fn test_width_precision() {
+    #[derive(Error, Debug)]
+    #[error("[{name:>width$}] {ratio:.precision$} in {width}")]
+    struct Error {
+        name: &'static str,
+        width: usize,
+        ratio: f64,
+        precision: usize,
+    }
+
+    #[derive(Error, Debug)]
+    enum Table {
+        #[error("|{0:<1$}|{2:^1$.3$}|")]
+        Row(&'static str, usize, f64, usize),
+        #[error("|{:>1$}|", .0.len())]
+        Len(&'static str, usize),
+    }
+
+    assert(
+        "[   ab] 0.12 in 5",
+        Error {
+            name: "ab",
+            width: 5,
+            ratio: 0.12345,
+            precision: 2,
+        },
+    );
+    assert("|ab   | 1.5 |", Table::Row("ab", 5, 1.5, 1));
+    assert("|    2|", Table::Len("ab", 5));
+}
+
+#[test]
+fn test_fmt_path() {
+    #[derive(Error, Debug)]
+    #[error(fmt = formatters::missing)]
+    struct Missing {
+        names: Vec<&'static str>,
+        limit: usize,
+    }
+
+    #[derive(Error, Debug)]
+    enum Error {
+        #[error(fmt = formatters::missing)]
+        Missing(Vec<&'static str>, usize),
+        #[error(fmt = formatters::unit)]
+        Unit,
+        #[error("other")]
+        Other,
+    }
+
+    let missing = |names: &[&'static str]| Missing {
+        names: names.to_vec(),
+        limit: 2,
+    };
+    assert("nothing is missing", missing(&[]));
+    assert("a is missing", missing(&["a"]));
+    assert("a, b are missing", missing(&["a", "b"]));
+    assert("3 fields are missing", missing(&["a", "b", "c"]));
+
+    assert("a is missing", Error::Missing(vec!["a"], 2));
+    assert("unit", Error::Unit);
+    assert("other", Error::Other);
+}
+
+#[test]
+fn test_doc() {
+    /// Failed to read {path:?}
+    ///
+    /// Not part of the message.
+    #[derive(Error, Debug)]
+    #[error(doc)]
+    struct ReadError {
+        path: PathBuf,
+    }
+
+    #[derive(Error, Debug)]
+    enum Error {
+        /** attempt {0} of {1} timed out */
+        #[error(doc)]
+        Timeout(usize, usize),
+        /// Disconnected
+        #[error(doc)]
+        Disconnected,
+        /// Ignored, the message is given explicitly.
+        #[error("unknown")]
+        Unknown,
+    }
+
+    assert(
+        "Failed to read \"/etc/hosts\"",
+        ReadError {
+            path: PathBuf::from("/etc/hosts"),
+        },
+    );
+    assert("attempt 2 of 3 timed out", Error::Timeout(2, 3));
+    assert("Disconnected", Error::Disconnected);
+    assert("unknown", Error::Unknown);
+}
+
+#[test]
+fn test_self() {
+    #[derive(Error, Debug)]
+    #[error("operation failed: {self:?}")]
+    enum Error {
+        Timeout,
+        Refused { port: u16 },
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum Kind {
+        Busy,
+    }
+
+    #[derive(Error, Debug)]
+    #[error("{self.kind():?} for {0}")]
+    struct BusyError(&'static str);
+
+    impl BusyError {
+        fn kind(&self) -> Kind {
+            Kind::Busy
+        }
+    }
+
+    assert("operation failed: Timeout", Error::Timeout);
+    assert(
+        "operation failed: Refused { port: 80 }",
+        Error::Refused { port: 80 },
+    );
+    assert("Busy for db", BusyError("db"));
+}
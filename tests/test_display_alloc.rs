@@ -0,0 +1,152 @@
+// Rendering a derived Display writes each interpolated value straight into
+// the formatter. Counts the allocations made by the current thread while
+// writing messages into a sink which itself never allocates.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::{self, Write};
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+struct Sink;
+
+impl Write for Sink {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+fn allocations(error: &dyn fmt::Display) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    write!(Sink, "{}", error).unwrap();
+    write!(Sink, "{:#}", error).unwrap();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+pub struct Config {
+    path: PathBuf,
+    retries: u32,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to open {path} after {attempts} attempts")]
+pub struct OpenError<'a> {
+    path: &'a Path,
+    attempts: u32,
+}
+
+#[derive(Error, Debug)]
+#[error("{name:>12} is invalid: {value:?} ({len + 1} bytes) at {path}")]
+pub struct FieldError {
+    name: &'static str,
+    value: Vec<u8>,
+    len: usize,
+    path: PathBuf,
+}
+
+#[derive(Error, Debug)]
+#[error("cannot load {config.path} with {config.retries} retries: {source}")]
+pub struct LoadError {
+    config: Config,
+    source: io::Error,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Config")
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("login failed for {user} with {password}")]
+pub struct LoginError {
+    user: &'static str,
+    #[sensitive]
+    password: &'static str,
+}
+
+#[derive(Error, Debug)]
+#[naur(single_line)]
+#[error(prefix = "store: ")]
+pub enum StoreError {
+    #[error("write failed:\n{0}")]
+    Write(&'static str),
+    #[error(transparent)]
+    Open(OpenError<'static>),
+    #[error("batch failed")]
+    Batch(#[related] Vec<io::Error>),
+}
+
+#[test]
+fn test_no_allocations() {
+    let open = OpenError {
+        path: Path::new("/etc/app.toml"),
+        attempts: 3,
+    };
+    assert_eq!(0, allocations(&open));
+
+    let field = FieldError {
+        name: "token",
+        value: b"abc".to_vec(),
+        len: 3,
+        path: PathBuf::from("/srv/input"),
+    };
+    assert_eq!(0, allocations(&field));
+
+    let load = LoadError {
+        config: Config {
+            path: PathBuf::from("/etc/app.toml"),
+            retries: 2,
+        },
+        source: io::Error::from(io::ErrorKind::NotFound),
+    };
+    assert_eq!(0, allocations(&load));
+
+    let login = LoginError {
+        user: "admin",
+        password: "hunter2",
+    };
+    assert_eq!(0, allocations(&login));
+
+    assert_eq!(0, allocations(&StoreError::Write("disk\n  full")));
+    let open = OpenError {
+        path: Path::new("/var/db"),
+        attempts: 1,
+    };
+    assert_eq!(0, allocations(&StoreError::Open(open)));
+    let related = vec![io::Error::from(io::ErrorKind::TimedOut)];
+    assert_eq!(0, allocations(&StoreError::Batch(related)));
+}
+
+#[test]
+fn test_counting() {
+    let before = ALLOCATIONS.with(Cell::get);
+    let message = OpenError {
+        path: Path::new("/etc/app.toml"),
+        attempts: 3,
+    }
+    .to_string();
+    assert!(ALLOCATIONS.with(Cell::get) > before);
+    assert_eq!("failed to open /etc/app.toml after 3 attempts", message);
+}
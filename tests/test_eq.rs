@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(eq = "kind")]
+pub enum FetchError {
+    #[error("request timed out after {0}s")]
+    Timeout(u64),
+    #[error("connection failed")]
+    Connect(#[from] io::Error),
+    #[error("cancelled")]
+    Cancelled,
+}
+
+#[derive(Error, Debug)]
+#[naur(eq = "fields")]
+pub enum QueryError {
+    #[error("table {table} not found")]
+    NotFound { table: String },
+    #[error("query on {table} failed")]
+    Failed {
+        table: String,
+        #[source]
+        cause: io::Error,
+    },
+    #[error("timed out after {0}ms")]
+    Timeout(u64),
+    #[error(transparent)]
+    Other(io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("bad row {row}")]
+#[naur(eq = "fields")]
+pub struct RowError {
+    row: usize,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(eq = "fields")]
+pub enum Wrapped<T> {
+    #[error("invalid value")]
+    Invalid(T),
+}
+
+#[derive(Error, Debug)]
+#[naur(eq = "code")]
+pub enum UploadError {
+    #[error("file {name} is too large")]
+    #[error(code = "E413")]
+    TooLarge { name: String, size: u64 },
+    #[error("storage unavailable")]
+    #[error(code = "E503")]
+    Unavailable(#[source] io::Error),
+    #[error("quota exceeded")]
+    #[error(code = "E429")]
+    Quota,
+}
+
+fn io_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, message)
+}
+
+fn assert_eq_impl<T: Eq>() {}
+
+#[test]
+fn test_eq_kind() {
+    assert_eq_impl::<FetchError>();
+    assert_eq!(FetchError::Timeout(1), FetchError::Timeout(30));
+    assert_eq!(
+        FetchError::Connect(io_error("refused")),
+        FetchError::Connect(io::Error::from(io::ErrorKind::TimedOut)),
+    );
+    assert_ne!(FetchError::Timeout(1), FetchError::Cancelled);
+}
+
+#[test]
+fn test_eq_fields() {
+    let not_found = |table: &str| QueryError::NotFound {
+        table: table.to_owned(),
+    };
+    assert_eq!(not_found("users"), not_found("users"));
+    assert_ne!(not_found("users"), not_found("orders"));
+
+    let failed = |table: &str, message: &str| QueryError::Failed {
+        table: table.to_owned(),
+        cause: io_error(message),
+    };
+    assert_eq!(failed("users", "disk full"), failed("users", "reset"));
+    assert_ne!(failed("users", "disk full"), failed("orders", "disk full"));
+    assert_ne!(failed("users", "disk full"), not_found("users"));
+
+    assert_eq!(QueryError::Timeout(5), QueryError::Timeout(5));
+    assert_ne!(QueryError::Timeout(5), QueryError::Timeout(6));
+    assert_eq!(
+        QueryError::Other(io_error("a")),
+        QueryError::Other(io_error("b")),
+    );
+}
+
+#[test]
+fn test_eq_fields_struct() {
+    let row = |row: usize, message: &str| RowError {
+        row,
+        source: io_error(message),
+    };
+    assert_eq!(row(1, "a"), row(1, "b"));
+    assert_ne!(row(1, "a"), row(2, "a"));
+}
+
+#[test]
+fn test_eq_generic() {
+    assert_eq!(Wrapped::Invalid(1), Wrapped::Invalid(1));
+    assert_ne!(Wrapped::Invalid(1), Wrapped::Invalid(2));
+}
+
+#[test]
+fn test_eq_code() {
+    assert_eq_impl::<UploadError>();
+    let too_large = |name: &str, size| UploadError::TooLarge {
+        name: name.to_owned(),
+        size,
+    };
+    assert_eq!(too_large("a.png", 1 << 30), too_large("b.mp4", 1 << 40));
+    assert_ne!(too_large("a.png", 1 << 30), UploadError::Quota);
+
+    let errors = vec![
+        too_large("a.png", 1 << 30),
+        UploadError::Unavailable(io_error("disk full")),
+        too_large("b.mp4", 1 << 40),
+        UploadError::Quota,
+        UploadError::Unavailable(io_error("read-only")),
+    ];
+    let mut counts = HashMap::new();
+    for error in errors {
+        *counts.entry(error).or_insert(0) += 1;
+    }
+    assert_eq!(3, counts.len());
+    assert_eq!(1, counts[&UploadError::Quota]);
+    assert_eq!(2, counts[&too_large("", 0)]);
+    assert_eq!(2, counts[&UploadError::Unavailable(io_error(""))]);
+}
@@ -0,0 +1,100 @@
+use std::io;
+use thiserror::{Error, ErrorExt};
+
+#[derive(Error, Debug)]
+#[error("quota exceeded")]
+#[error(code = "QUOTA")]
+#[error(help = "raise the limit above {limit}")]
+pub struct QuotaError {
+    limit: u32,
+}
+
+#[derive(Error, Debug)]
+pub enum FetchError {
+    #[error("timed out")]
+    #[error(code = "FETCH-TIMEOUT")]
+    #[error(transient)]
+    Timeout,
+    #[error("not found")]
+    NotFound,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("plain failure")]
+pub struct PlainError;
+
+#[derive(Error, Debug)]
+pub enum Wrapped<E> {
+    #[error("inner failed")]
+    #[error(code = "INNER")]
+    Inner(E),
+}
+
+fn describe(error: &dyn ErrorExt) -> String {
+    format!(
+        "{} {} {} {} {}",
+        error.kind_name().unwrap_or("-"),
+        error.code().unwrap_or("-"),
+        error.severity().unwrap_or("-"),
+        error.help().as_deref().unwrap_or("-"),
+        error.is_transient(),
+    )
+}
+
+#[test]
+fn test_struct() {
+    let error = QuotaError { limit: 10 };
+    assert_eq!(
+        "QuotaError QUOTA - raise the limit above 10 false",
+        describe(&error),
+    );
+    assert_eq!("PlainError - - - false", describe(&PlainError));
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(
+        "Timeout FETCH-TIMEOUT - - true",
+        describe(&FetchError::Timeout),
+    );
+    assert_eq!("NotFound - - - false", describe(&FetchError::NotFound));
+}
+
+#[test]
+fn test_generic() {
+    let error = Wrapped::Inner(7);
+    assert_eq!("Inner INNER - - false", describe(&error));
+}
+
+#[test]
+fn test_boxed() {
+    let errors: Vec<Box<dyn ErrorExt>> = vec![
+        Box::new(FetchError::Timeout),
+        Box::new(QuotaError { limit: 3 }),
+    ];
+    let retryable = errors.iter().filter(|error| error.is_transient()).count();
+    assert_eq!(1, retryable);
+}
+
+#[cfg(feature = "miette")]
+#[derive(Error, Debug)]
+#[diagnostic(code(app::parse), severity(Warning))]
+pub enum ParseError {
+    #[error("unexpected end of input")]
+    Eof,
+    #[error("invalid token")]
+    #[diagnostic(severity(Error))]
+    Token,
+}
+
+#[cfg(feature = "miette")]
+#[test]
+fn test_diagnostic() {
+    assert_eq!("Eof app::parse warning - false", describe(&ParseError::Eof));
+    assert_eq!(
+        "Token app::parse error - false",
+        describe(&ParseError::Token)
+    );
+}
@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("upload failed, reference {reference}")]
+pub struct UploadError {
+    #[from]
+    source: io::Error,
+    #[error_id]
+    reference: String,
+}
+
+#[derive(Error, Debug)]
+#[naur(constructors)]
+pub enum PaymentError {
+    #[error("card declined ({0})")]
+    Declined(String, #[error_id] u128),
+    #[error("gateway unavailable")]
+    Unavailable {
+        #[source]
+        source: io::Error,
+        #[error_id]
+        id: u128,
+    },
+}
+
+#[derive(Error, Debug)]
+pub enum LoginError {
+    #[error("wrong password")]
+    WrongPassword {
+        #[error_id(generate = next_ticket)]
+        ticket: u64,
+    },
+    #[error("account locked")]
+    Locked,
+}
+
+fn next_ticket() -> u64 {
+    1234
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "oh no")
+}
+
+#[test]
+fn test_display() {
+    let error = UploadError::from(io_error());
+    let reference = error.error_id().clone();
+    assert_eq!(14, reference.len());
+    assert_eq!(
+        format!("upload failed, reference {}", reference),
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_unique() {
+    let ids: HashSet<u128> = (0..1000)
+        .map(|_| *PaymentError::declined("insufficient funds".to_owned()).error_id())
+        .collect();
+    assert_eq!(1000, ids.len());
+
+    let result: Result<(), io::Error> = Err(io_error());
+    let error = result.throw_unavailable().unwrap_err();
+    assert!(!ids.contains(error.error_id()));
+}
+
+#[test]
+fn test_generate() {
+    let error = LoginError::WrongPassword {
+        ticket: next_ticket(),
+    };
+    assert_eq!(Some(&1234), error.error_id());
+    assert_eq!(None, LoginError::Locked.error_id());
+}
+
+#[cfg(feature = "uuid")]
+mod uuid {
+    use std::io;
+    use thiserror::Error;
+    use uuid::Uuid;
+
+    #[derive(Error, Debug)]
+    #[error("request failed, reference {id}")]
+    pub struct RequestError {
+        #[from]
+        source: io::Error,
+        #[error_id]
+        id: Uuid,
+    }
+
+    #[test]
+    fn test_uuid() {
+        let error = RequestError::from(super::io_error());
+        assert_eq!(Some(uuid::Version::Random), error.error_id().get_version());
+        assert_ne!(
+            error.error_id(),
+            RequestError::from(super::io_error()).error_id()
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::{Error, ErrorSet};
+
+#[derive(Error, Debug)]
+pub enum DbError {
+    #[error("connection lost")]
+    Disconnected,
+    #[error("query failed: {query}")]
+    Query {
+        query: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("cache miss for {0}")]
+pub struct CacheMiss(String);
+
+#[derive(ErrorSet, Debug)]
+pub enum ApiError {
+    #[set]
+    Db(DbError),
+    #[set]
+    Cache(CacheMiss),
+    #[set]
+    Io(io::Error),
+    #[error("unauthorized")]
+    Unauthorized,
+}
+
+fn query() -> Result<(), DbError> {
+    let source = io::Error::new(io::ErrorKind::Other, "socket closed");
+    Err(DbError::Query {
+        query: "select 1".to_owned(),
+        source,
+    })
+}
+
+fn handle() -> Result<(), ApiError> {
+    query()?;
+    Ok(())
+}
+
+#[test]
+fn test_from() {
+    let error = handle().unwrap_err();
+    assert!(matches!(error, ApiError::Db(DbError::Query { .. })));
+    let error = ApiError::from(CacheMiss("users".to_owned()));
+    assert!(matches!(error, ApiError::Cache(_)));
+}
+
+#[test]
+fn test_transparent() {
+    let error = handle().unwrap_err();
+    assert_eq!("query failed: select 1", error.to_string());
+    assert_eq!("socket closed", error.source().unwrap().to_string());
+
+    let error = ApiError::from(DbError::Disconnected);
+    assert_eq!("connection lost", error.to_string());
+    assert!(error.source().is_none());
+
+    assert_eq!("unauthorized", ApiError::Unauthorized.to_string());
+}
+
+#[test]
+fn test_throw() {
+    let error = query().throw_db().unwrap_err();
+    assert!(matches!(error, ApiError::Db(DbError::Query { .. })));
+}
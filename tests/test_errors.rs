@@ -0,0 +1,73 @@
+use std::error::Error as _;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("`{0}` is required")]
+pub struct MissingField(&'static str);
+
+#[derive(Error, Debug)]
+pub struct ValidationErrors {
+    #[errors]
+    errors: Vec<MissingField>,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to import {path}")]
+pub struct ImportError {
+    path: &'static str,
+    #[errors]
+    rows: Vec<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+fn validation_errors(fields: &[&'static str]) -> ValidationErrors {
+    ValidationErrors {
+        errors: fields.iter().copied().map(MissingField).collect(),
+    }
+}
+
+#[test]
+fn test_display() {
+    let error = validation_errors(&["name", "email"]);
+    assert_eq!(
+        "2 errors occurred: `name` is required; `email` is required",
+        error.to_string(),
+    );
+    assert_eq!(
+        "2 errors occurred:\n- `name` is required\n- `email` is required",
+        format!("{:#}", error),
+    );
+
+    let error = validation_errors(&["name"]);
+    assert_eq!("`name` is required", error.to_string());
+
+    let error = validation_errors(&[]);
+    assert_eq!("no errors occurred", error.to_string());
+}
+
+#[test]
+fn test_source() {
+    let error = validation_errors(&["name", "email"]);
+    let source = error.source().unwrap();
+    assert_eq!("`name` is required", source.to_string());
+    assert!(source.is::<MissingField>());
+
+    assert!(validation_errors(&[]).source().is_none());
+}
+
+#[test]
+fn test_errors() {
+    let error = validation_errors(&["name", "email"]);
+    let fields: Vec<&str> = error.errors().map(|error| error.0).collect();
+    assert_eq!(["name", "email"], fields.as_slice());
+}
+
+#[test]
+fn test_message() {
+    let error = ImportError {
+        path: "users.csv",
+        rows: vec!["row 3 is empty".into(), "row 7 is empty".into()],
+    };
+    assert_eq!("failed to import users.csv", error.to_string());
+    assert_eq!("row 3 is empty", error.source().unwrap().to_string());
+    assert_eq!(2, error.errors().count());
+}
@@ -0,0 +1,119 @@
+use std::io;
+use thiserror::Error;
+
+pub trait Audit {
+    fn audit_code(&self) -> Option<&'static str>;
+    fn message_template(&self) -> Option<&'static str>;
+    fn source_member(&self) -> Option<&'static str>;
+    fn field_count(&self) -> usize;
+}
+
+macro_rules! impl_audit {
+    (
+        kind = $kind:ident,
+        ident = $ident:ident,
+        impl_generics = [$($impl_generics:tt)*],
+        ty_generics = [$($ty_generics:tt)*],
+        where_clause = [$($where_clause:tt)*],
+        variants = [$({
+            ident = $variant:ident,
+            path = [$($path:tt)*],
+            message = $message:expr,
+            code = $code:expr,
+            status = $status:expr,
+            fields = [$({
+                member = $member:tt,
+                ty = [$($ty:tt)*],
+                source = $source:tt,
+                from = $from:tt,
+                backtrace = $backtrace:tt,
+            }),*],
+        }),*],
+    ) => {
+        impl $($impl_generics)* Audit for $ident $($ty_generics)* $($where_clause)* {
+            fn audit_code(&self) -> Option<&'static str> {
+                match self {
+                    $($($path)* {..} => $code,)*
+                }
+            }
+
+            fn message_template(&self) -> Option<&'static str> {
+                match self {
+                    $($($path)* {..} => $message,)*
+                }
+            }
+
+            fn source_member(&self) -> Option<&'static str> {
+                match self {
+                    $($($path)* {..} => {
+                        None
+                        $(.or(if $source { Some(stringify!($member)) } else { None }))*
+                    })*
+                }
+            }
+
+            fn field_count(&self) -> usize {
+                match self {
+                    $($($path)* {..} => <[&str]>::len(&[$(stringify!($member)),*]),)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_kind {
+    (kind = $kind:ident, ident = $ident:ident, $($rest:tt)*) => {
+        impl $ident {
+            const KIND: &'static str = stringify!($kind);
+        }
+    };
+}
+
+#[derive(Error, Debug)]
+#[error(code = "STORE")]
+#[naur(extend_with = impl_audit, extend_with = impl_kind)]
+pub enum StoreError {
+    #[error("failed to read {path}")]
+    #[error(code = "STORE_READ")]
+    Read { path: String, source: io::Error },
+    #[error("store is locked")]
+    Locked,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("invalid {0}")]
+#[naur(extend_with = impl_audit)]
+pub struct Generic<T: std::fmt::Debug>(T);
+
+#[test]
+fn test_enum() {
+    assert_eq!("enum", StoreError::KIND);
+
+    let error = StoreError::Read {
+        path: "/data".to_owned(),
+        source: io::Error::new(io::ErrorKind::Other, "disk on fire"),
+    };
+    assert_eq!(Some("STORE_READ"), error.audit_code());
+    assert_eq!(Some("failed to read {path}"), error.message_template());
+    assert_eq!(Some("source"), error.source_member());
+    assert_eq!(2, error.field_count());
+
+    let error = StoreError::Locked;
+    assert_eq!(Some("STORE"), error.audit_code());
+    assert_eq!(None, error.source_member());
+    assert_eq!(0, error.field_count());
+
+    let error = StoreError::from(io::Error::new(io::ErrorKind::Other, "oh no"));
+    assert_eq!(None, error.message_template());
+    assert_eq!(Some("0"), error.source_member());
+}
+
+#[test]
+fn test_generic_struct() {
+    let error = Generic(1);
+    assert_eq!(None, error.audit_code());
+    assert_eq!(Some("invalid {0}"), error.message_template());
+    assert_eq!(1, error.field_count());
+}
@@ -0,0 +1,44 @@
+use eyre::{eyre, Report, WrapErr as _};
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("failed to open {path}")]
+    Open {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("migration failed")]
+    Migrate(#[from] Report),
+}
+
+fn open(path: &str) -> Result<(), StoreError> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "missing")).throw_open(path.to_owned())
+}
+
+#[test]
+fn test_into_report() {
+    let report = Report::from(open("/data").unwrap_err());
+    assert_eq!("failed to open /data", report.to_string());
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(["failed to open /data", "missing"], *chain);
+
+    let report = open("/data").wrap_err("startup failed").unwrap_err();
+    assert_eq!(3, report.chain().count());
+    assert!(report.downcast_ref::<StoreError>().is_some());
+}
+
+#[test]
+fn test_report_source() {
+    let error = StoreError::from(eyre!("inner").wrap_err("outer"));
+    assert_eq!("migration failed", error.to_string());
+    let source = error.source().unwrap();
+    assert_eq!("outer", source.to_string());
+    assert_eq!("inner", source.source().unwrap().to_string());
+
+    let error = Err::<(), _>(eyre!("locked")).throw_migrate().unwrap_err();
+    assert_eq!("locked", error.source().unwrap().to_string());
+}
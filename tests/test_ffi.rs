@@ -0,0 +1,72 @@
+use std::ffi::CStr;
+use std::io;
+use std::os::raw::c_char;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(ffi)]
+pub enum StoreError {
+    #[error("blob {0} not found")]
+    NotFound(u64),
+    #[error("store is read-only")]
+    ReadOnly,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[naur(ffi)]
+pub enum ParseError<T: std::fmt::Debug> {
+    #[error("unexpected {0:?}")]
+    Unexpected(T),
+}
+
+#[test]
+fn test_code() {
+    assert_eq!(
+        StoreErrorCode::NotFound,
+        StoreError::NotFound(3).to_ffi_code()
+    );
+    assert_eq!(StoreErrorCode::ReadOnly, StoreError::ReadOnly.to_ffi_code());
+    assert_eq!(1, StoreErrorCode::NotFound as i32);
+    assert_eq!(2, StoreErrorCode::ReadOnly as i32);
+    assert_eq!(3, StoreErrorCode::Io as i32);
+    assert_eq!(
+        std::mem::size_of::<std::os::raw::c_int>(),
+        std::mem::size_of::<StoreErrorCode>(),
+    );
+    assert_eq!(
+        ParseErrorCode::Unexpected,
+        ParseError::Unexpected('}').to_ffi_code(),
+    );
+}
+
+#[test]
+fn test_message_into() {
+    let mut buf = [0x7f as c_char; 32];
+    let len = unsafe { StoreError::NotFound(3).message_into(buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(16, len);
+    let message = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    assert_eq!("blob 3 not found", message.to_str().unwrap());
+
+    let len = unsafe { ParseError::Unexpected('}').message_into(buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(14, len);
+}
+
+#[test]
+fn test_truncated() {
+    let mut buf = [0x7f as c_char; 8];
+    let len = unsafe { StoreError::ReadOnly.message_into(buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(18, len);
+    let message = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    assert_eq!("store i", message.to_str().unwrap());
+
+    let error = StoreError::Io(io::Error::new(io::ErrorKind::Other, "héllo"));
+    let mut buf = [0x7f as c_char; 3];
+    unsafe { error.message_into(buf.as_mut_ptr(), buf.len()) };
+    let message = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    assert_eq!("h", message.to_str().unwrap());
+
+    let len = unsafe { StoreError::ReadOnly.message_into(std::ptr::null_mut(), 0) };
+    assert_eq!(18, len);
+}
@@ -0,0 +1,68 @@
+use std::fmt::Debug;
+use std::io;
+use thiserror::{Error, FieldValue};
+
+#[derive(Debug)]
+pub struct Opaque(pub u8);
+
+#[derive(Error, Debug)]
+#[naur(fields)]
+pub enum QueryError {
+    #[error("query {query} failed after {attempts} attempts")]
+    Failed {
+        query: String,
+        attempts: u32,
+        opaque: Opaque,
+        source: io::Error,
+    },
+    #[error("table {0} not found")]
+    NotFound(&'static str),
+    #[error("timed out")]
+    Timeout,
+}
+
+#[derive(Error, Debug)]
+#[error("bad value {value:?}")]
+#[naur(fields)]
+pub struct BadValue<T> {
+    value: T,
+}
+
+fn render<'a>(fields: impl Iterator<Item = (&'static str, FieldValue<'a>)>) -> Vec<String> {
+    fields
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect()
+}
+
+#[test]
+fn test_enum() {
+    let error = QueryError::Failed {
+        query: "SELECT 1".to_owned(),
+        attempts: 3,
+        opaque: Opaque(9),
+        source: io::Error::new(io::ErrorKind::Other, "reset"),
+    };
+    assert_eq!(
+        ["query=SELECT 1", "attempts=3", "opaque=Opaque(9)"],
+        render(error.fields())[..],
+    );
+
+    let error = QueryError::NotFound("users");
+    assert_eq!(["0=users"], render(error.fields())[..]);
+
+    assert_eq!(0, QueryError::Timeout.fields().count());
+}
+
+#[test]
+fn test_generic() {
+    let error = BadValue { value: "x" };
+    assert_eq!(["value=\"x\""], render(error.fields())[..]);
+}
+
+#[test]
+fn test_field_value() {
+    let error = QueryError::NotFound("users");
+    let (_, value) = error.fields().next().unwrap();
+    assert!(matches!(value, FieldValue::Display(_)));
+    assert_eq!("Display(users)", format!("{:?}", value));
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "fluent")]
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::FluentResource;
+use std::sync::Once;
+use thiserror::Error;
+
+const FTL: &str = "
+errors-open = Impossible d'ouvrir { $path }
+errors-retry = { $attempts ->
+    [one] Réessayer une fois
+   *[other] Réessayer { $attempts } fois
+}
+";
+
+fn register() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        let resource = FluentResource::try_new(FTL.to_owned()).unwrap();
+        let mut bundle = FluentBundle::new_concurrent(vec!["fr".parse().unwrap()]);
+        bundle.set_use_isolating(false);
+        bundle.add_resource(resource).unwrap();
+        thiserror::set_fluent_bundle(bundle);
+    });
+}
+
+#[derive(Error, Debug)]
+#[error("failed to open {path}")]
+#[error(i18n = "errors-open")]
+pub struct OpenError {
+    path: String,
+}
+
+#[derive(Error, Debug)]
+pub enum EnumError {
+    #[error("retry {attempts} times")]
+    #[error(i18n = "errors-retry")]
+    Retry { attempts: usize },
+    #[error("not translated")]
+    #[error(i18n = "errors-missing")]
+    Missing,
+    #[error("no key")]
+    Plain,
+}
+
+#[test]
+fn test_localized() {
+    register();
+
+    let error = OpenError {
+        path: "/etc/hosts".to_owned(),
+    };
+    assert_eq!("Impossible d'ouvrir /etc/hosts", error.to_string());
+    assert_eq!(Some("errors-open"), error.message_key());
+
+    let error = EnumError::Retry { attempts: 1 };
+    assert_eq!("Réessayer une fois", error.to_string());
+    let error = EnumError::Retry { attempts: 3 };
+    assert_eq!("Réessayer 3 fois", error.to_string());
+    assert_eq!(Some("errors-retry"), error.message_key());
+}
+
+#[test]
+fn test_fallback() {
+    register();
+
+    let error = EnumError::Missing;
+    assert_eq!("not translated", error.to_string());
+    assert_eq!(Some("errors-missing"), error.message_key());
+
+    let error = EnumError::Plain;
+    assert_eq!("no key", error.to_string());
+    assert_eq!(None, error.message_key());
+}
@@ -0,0 +1,68 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("not found")]
+    NotFound,
+    #[error("invalid number")]
+    Parse(#[from] std::num::ParseIntError),
+    #[error(transparent)]
+    Other(
+        #[from]
+        #[naur(from_any)]
+        Box<dyn StdError + Send + Sync>,
+    ),
+}
+
+#[derive(Debug)]
+pub struct Timeout;
+
+impl Display for Timeout {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("timed out")
+    }
+}
+
+impl StdError for Timeout {}
+
+impl AppErrorCatchAll for io::Error {}
+impl AppErrorCatchAll for Timeout {}
+
+fn read() -> Result<(), AppError> {
+    Err(io::Error::new(io::ErrorKind::Other, "disk on fire"))?;
+    Ok(())
+}
+
+fn wait() -> Result<(), AppError> {
+    Err(Timeout)?;
+    Ok(())
+}
+
+fn parse() -> Result<i32, AppError> {
+    Ok("x".parse::<i32>()?)
+}
+
+#[test]
+fn test_catch_all() {
+    let error = read().unwrap_err();
+    assert!(matches!(error, AppError::Other(_)));
+    assert_eq!("disk on fire", error.to_string());
+
+    let error = wait().unwrap_err();
+    assert_eq!("timed out", error.to_string());
+    match error {
+        AppError::Other(source) => assert!(source.is::<Timeout>()),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_specific_from() {
+    assert!(matches!(parse(), Err(AppError::Parse(_))));
+
+    let boxed: Box<dyn StdError + Send + Sync> = Box::new(Timeout);
+    assert!(matches!(AppError::from(boxed), AppError::Other(_)));
+}
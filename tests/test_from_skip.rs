@@ -0,0 +1,46 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("failed to read")]
+    Read(#[from] io::Error),
+    #[error("failed to write")]
+    Write(#[from(skip)] io::Error),
+    #[error("failed to sync {path}")]
+    Sync {
+        path: String,
+        #[from(skip)]
+        source: io::Error,
+    },
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, "denied")
+}
+
+fn read() -> Result<(), StorageError> {
+    Err(io_error())?;
+    Ok(())
+}
+
+#[test]
+fn test_from() {
+    let error = read().unwrap_err();
+    assert!(matches!(error, StorageError::Read(_)));
+    assert!(error.source().unwrap().is::<io::Error>());
+}
+
+#[test]
+fn test_skipped_source() {
+    let error = Err::<(), _>(io_error()).throw_write().unwrap_err();
+    assert_eq!("failed to write", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let error = Err::<(), _>(io_error())
+        .throw_sync("/data".to_owned())
+        .unwrap_err();
+    assert_eq!("failed to sync /data", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+}
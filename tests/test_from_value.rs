@@ -0,0 +1,51 @@
+use std::num::ParseIntError;
+use std::time::SystemTime;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("invalid port {0}")]
+    InvalidPort(#[from_value] u16),
+    #[error("unknown host {host}")]
+    UnknownHost {
+        #[from_value]
+        host: String,
+    },
+    #[error("bad number")]
+    Parse(#[from] ParseIntError),
+}
+
+#[derive(Error, Debug)]
+#[error("retry after {seconds:?}s")]
+pub struct RetryAfter {
+    #[from_value]
+    seconds: Option<u64>,
+    #[timestamp]
+    at: SystemTime,
+}
+
+fn check_port(port: u16) -> Result<u16, ConfigError> {
+    if port < 1024 {
+        return Err(port.into());
+    }
+    Ok(port)
+}
+
+#[test]
+fn test_enum() {
+    let error = check_port(80).unwrap_err();
+    assert!(matches!(error, ConfigError::InvalidPort(80)));
+    assert_eq!("invalid port 80", error.to_string());
+    assert!(std::error::Error::source(&error).is_none());
+
+    let error = ConfigError::from("db.local".to_owned());
+    assert_eq!("unknown host db.local", error.to_string());
+}
+
+#[test]
+fn test_struct() {
+    let error = RetryAfter::from(30);
+    assert_eq!(Some(30), error.seconds);
+    assert_eq!("retry after Some(30)s", error.to_string());
+    assert!(error.at <= SystemTime::now());
+}
@@ -159,3 +159,39 @@ pub struct StructFromGeneric<E> {
 #[derive(Error, Debug)]
 #[error(transparent)]
 pub struct StructTransparentGeneric<E>(E);
+
+#[derive(Error, Debug)]
+#[error("buffer of {N} bytes failed at {at}")]
+pub struct StructConstGeneric<const N: usize> {
+    source: std::io::Error,
+    at: usize,
+}
+
+#[derive(Error, Debug)]
+pub enum EnumConstGeneric<T: Debug + Display, const N: usize = 8> {
+    #[error("ring of {N} is full of {0}")]
+    Full(T),
+    #[error("read of {len} failed")]
+    Read { source: std::io::Error, len: usize },
+}
+
+fn io_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, "oh no!")
+}
+
+#[test]
+fn test_throw_struct_const_generic() {
+    let result: Result<(), std::io::Error> = Err(io_error());
+    let error: StructConstGeneric<64> = result.throw_struct_const_generic(3).unwrap_err();
+    assert_eq!("buffer of 64 bytes failed at 3", error.to_string());
+}
+
+#[test]
+fn test_throw_enum_const_generic() {
+    let result: Result<(), std::io::Error> = Err(io_error());
+    let error: EnumConstGeneric<DebugAndDisplay, 4> = result.throw_read(16).unwrap_err();
+    assert_eq!("read of 16 failed", error.to_string());
+
+    let error: EnumConstGeneric<u8> = EnumConstGeneric::Full(1);
+    assert_eq!("ring of 8 is full of 1", error.to_string());
+}
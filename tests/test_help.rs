@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("{path:?} already exists")]
+#[error(help = "try running with --force to overwrite {path}")]
+pub struct AlreadyExists {
+    path: PathBuf,
+}
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("unknown command {0}")]
+    #[error(help = "{0:?} is not a command; run `tool help` to list them")]
+    Unknown(String),
+    #[error("missing argument")]
+    #[error(help = "try running with --force")]
+    Missing,
+    #[error("interrupted")]
+    Interrupted,
+    #[error("too many retries")]
+    #[error(help = "lower {retries} to at most {max.saturating_sub(1)}")]
+    Retries { retries: usize, max: usize },
+}
+
+#[derive(Error, Debug)]
+#[error("invalid value")]
+#[error(help = "expected {0}")]
+pub struct Generic<T>(T);
+
+#[test]
+fn test_help() {
+    let error = AlreadyExists {
+        path: PathBuf::from("out.txt"),
+    };
+    assert_eq!(
+        Some("try running with --force to overwrite out.txt"),
+        error.help().as_deref(),
+    );
+
+    assert!(matches!(
+        CliError::Missing.help(),
+        Some(Cow::Borrowed("try running with --force")),
+    ));
+    assert_eq!(None, CliError::Interrupted.help());
+
+    let error = CliError::Unknown("fetch".to_owned());
+    assert_eq!(
+        "\"fetch\" is not a command; run `tool help` to list them",
+        error.help().unwrap(),
+    );
+    let error = CliError::Retries { retries: 9, max: 5 };
+    assert_eq!("lower 9 to at most 4", error.help().unwrap());
+
+    assert_eq!("expected 42", Generic(42).help().unwrap());
+}
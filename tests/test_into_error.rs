@@ -0,0 +1,95 @@
+use std::error::Error as _;
+use std::fmt::Display;
+use std::io;
+use std::sync::Arc;
+use thiserror::{Error, IntoError, OrThrow};
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("invalid value {value} for {msg}")]
+    InvalidMsg {
+        msg: String,
+        value: i32,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to read {1}")]
+    Read(#[source] io::Error, String),
+    #[error("failed to parse")]
+    Parse(#[from] std::num::ParseIntError),
+    #[error("shared failure")]
+    Shared {
+        #[source]
+        #[error(arc_source)]
+        source: Arc<std::fmt::Error>,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("failed to load {path}")]
+pub struct AssetError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+pub enum AmbiguousError {
+    #[error("failed to open {0}")]
+    Open(#[source] io::Error, String),
+    #[error("failed to close {0}")]
+    Close(#[source] io::Error, String),
+    #[error("failed to flush")]
+    Flush(#[source] io::Error),
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "not found")
+}
+
+// Generic over every error which an io::Error and a path convert into.
+fn load<E>(path: &str) -> Result<(), E>
+where
+    io::Error: IntoError<E, String>,
+{
+    Err(io_error()).or_throw_with(path.to_owned())
+}
+
+#[test]
+fn test_generic_helper() {
+    let error: ConfigError = load("app.toml").unwrap_err();
+    assert_eq!("failed to read app.toml", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let error: AssetError = load("logo.png").unwrap_err();
+    assert_eq!("failed to load logo.png", error.to_string());
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn test_context() {
+    let error: ConfigError = io_error().into_error_with(("retries".to_owned(), -1));
+    assert_eq!("invalid value -1 for retries", error.to_string());
+
+    let error: ConfigError = "x".parse::<u8>().or_throw().unwrap_err();
+    assert!(matches!(error, ConfigError::Parse(_)));
+}
+
+#[test]
+fn test_arc_source() {
+    let error: ConfigError = std::fmt::Error.into_error_with(());
+    assert!(matches!(error, ConfigError::Shared { .. }));
+    assert!(error.source().unwrap().is::<std::fmt::Error>());
+}
+
+#[test]
+fn test_shared_context_left_out() {
+    // Open and Close take the same context, so only Flush has an impl.
+    fn message<E: Display>(error: io::Error) -> String
+    where
+        io::Error: IntoError<E>,
+    {
+        error.into_error_with(()).to_string()
+    }
+
+    assert_eq!("failed to flush", message::<AmbiguousError>(io_error()));
+}
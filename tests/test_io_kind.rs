@@ -0,0 +1,89 @@
+use std::error::Error as _;
+use std::fmt::Debug;
+use std::io::{self, ErrorKind};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("blob {0} not found")]
+    #[error(io_kind = NotFound)]
+    Missing(u64),
+    #[error("blob {0} is locked")]
+    #[error(io_kind = PermissionDenied)]
+    Locked(u64),
+    #[error("something broke")]
+    Broken,
+}
+
+#[derive(Error, Debug)]
+#[error("write would block")]
+#[error(io_kind = WouldBlock)]
+pub struct Busy;
+
+#[derive(Error, Debug)]
+#[error(io_kind = InvalidData)]
+pub enum DecodeError<T: Debug> {
+    #[error("unexpected token {0:?}")]
+    Unexpected(T),
+    #[error("stream ended early")]
+    #[error(io_kind = UnexpectedEof)]
+    Truncated,
+}
+
+#[test]
+fn test_enum() {
+    let error = io::Error::from(StoreError::Missing(3));
+    assert_eq!(ErrorKind::NotFound, error.kind());
+    assert_eq!("blob 3 not found", error.to_string());
+    assert!(error.get_ref().unwrap().is::<StoreError>());
+    assert_eq!(StoreError::Missing(3).io_kind(), Some(error.kind()));
+
+    let error = io::Error::from(StoreError::Locked(3));
+    assert_eq!(ErrorKind::PermissionDenied, error.kind());
+
+    let error = io::Error::from(StoreError::Broken);
+    assert_eq!(ErrorKind::Other, error.kind());
+}
+
+#[test]
+fn test_struct() {
+    let error = io::Error::from(Busy);
+    assert_eq!(ErrorKind::WouldBlock, error.kind());
+    assert_eq!("write would block", error.to_string());
+    let inner = error.into_inner().unwrap();
+    assert!(inner.downcast::<Busy>().is_ok());
+}
+
+#[test]
+fn test_generic() {
+    let error = io::Error::from(DecodeError::Unexpected('}'));
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!("unexpected token '}'", error.to_string());
+
+    let error = io::Error::from(DecodeError::<char>::Truncated);
+    assert_eq!(ErrorKind::UnexpectedEof, error.kind());
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn test_question_mark() {
+    fn read() -> io::Result<()> {
+        Err(StoreError::Missing(7))?
+    }
+    assert_eq!(ErrorKind::NotFound, read().unwrap_err().kind());
+}
+
+#[test]
+fn test_io_kind_method() {
+    assert_eq!(Some(ErrorKind::NotFound), StoreError::Missing(3).io_kind());
+    assert_eq!(None, StoreError::Broken.io_kind());
+    assert_eq!(Some(ErrorKind::WouldBlock), Busy.io_kind());
+    assert_eq!(
+        Some(ErrorKind::InvalidData),
+        DecodeError::Unexpected('}').io_kind(),
+    );
+    assert_eq!(
+        Some(ErrorKind::UnexpectedEof),
+        DecodeError::<char>::Truncated.io_kind(),
+    );
+}
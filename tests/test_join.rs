@@ -0,0 +1,111 @@
+use std::future::Future;
+use std::num::ParseIntError;
+use std::pin::Pin;
+use std::str::Utf8Error;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use thiserror::{try_join_typed, Error};
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("bad number")]
+    Parse(#[from] ParseIntError),
+    #[error("bad text")]
+    Utf8(#[from] Utf8Error),
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+// Returns Pending the given number of times before completing.
+struct Delay<T> {
+    polls: usize,
+    output: Option<T>,
+}
+
+impl<T: Unpin> Future for Delay<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<T> {
+        if self.polls == 0 {
+            Poll::Ready(self.output.take().unwrap())
+        } else {
+            self.polls -= 1;
+            Poll::Pending
+        }
+    }
+}
+
+fn delay<T>(polls: usize, output: T) -> Delay<T> {
+    Delay {
+        polls,
+        output: Some(output),
+    }
+}
+
+#[test]
+fn test_ok() {
+    let number = delay(3, "42".parse::<i32>());
+    let text = delay(1, std::str::from_utf8(b"hi"));
+    let joined = block_on(try_join_typed!(number, text => LoadError));
+    assert_eq!((42, "hi"), joined.unwrap());
+}
+
+#[test]
+fn test_first_error() {
+    let number = delay(3, "4x".parse::<i32>());
+    let bytes = vec![b'h', 0xff];
+    let text = delay(1, std::str::from_utf8(&bytes));
+    let joined = block_on(try_join_typed!(number, text => LoadError));
+    assert!(matches!(joined, Err(LoadError::Utf8(_))));
+}
+
+#[test]
+fn test_inferred() {
+    async fn load() -> Result<i32, LoadError> {
+        let joined: Result<_, LoadError> = try_join_typed!(
+            async { "1".parse::<i32>() },
+            async { "2".parse::<i32>() },
+            async { std::str::from_utf8(b"3").map(str::len) },
+        )
+        .await;
+        let (a, b, c) = joined?;
+        Ok(a + b + c as i32)
+    }
+
+    assert_eq!(4, block_on(load()).unwrap());
+}
+
+thiserror::join_error! {
+    /// The combined error of `test_generated`.
+    #[derive(PartialEq)]
+    pub enum JoinedError {
+        /// Not a number.
+        Parse(ParseIntError),
+        Utf8(Utf8Error),
+    }
+}
+
+#[test]
+fn test_generated() {
+    let number = delay(3, "4x".parse::<i32>());
+    let text = delay(1, std::str::from_utf8(b"hi"));
+    let joined = block_on(try_join_typed!(number, text => JoinedError));
+    let expected = "4x".parse::<i32>().unwrap_err();
+    assert_eq!(Err(JoinedError::Parse(expected.clone())), joined);
+    assert_eq!(expected.to_string(), joined.unwrap_err().to_string());
+}
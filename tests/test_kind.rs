@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(kind)]
+pub enum FetchError {
+    #[error("request timed out after {0}s")]
+    Timeout(u64),
+    #[error("server returned {status}")]
+    Status { status: u16 },
+    #[error("connection failed")]
+    Connect(#[from] io::Error),
+    #[error("cancelled")]
+    Cancelled,
+}
+
+#[derive(Error, Debug)]
+#[naur(kind)]
+pub enum Wrapped<E> {
+    #[error("inner: {0}")]
+    Inner(E),
+    #[error("other")]
+    Other,
+}
+
+fn is_retryable(error: &FetchError) -> bool {
+    matches!(
+        error.kind(),
+        FetchErrorKind::Timeout | FetchErrorKind::Connect
+    )
+}
+
+#[test]
+fn test_kind() {
+    assert_eq!(FetchErrorKind::Timeout, FetchError::Timeout(30).kind());
+    assert_eq!(
+        FetchErrorKind::Status,
+        FetchError::Status { status: 503 }.kind()
+    );
+    let io = io::Error::new(io::ErrorKind::Other, "refused");
+    assert!(is_retryable(&FetchError::from(io)));
+    assert!(!is_retryable(&FetchError::Cancelled));
+}
+
+#[test]
+fn test_traits() {
+    let kind = FetchError::Status { status: 404 }.kind();
+    assert_eq!("Status", kind.to_string());
+    assert_eq!("Status", format!("{:?}", kind));
+
+    let kinds: HashSet<FetchErrorKind> = [
+        FetchError::Timeout(1).kind(),
+        FetchError::Timeout(2).kind(),
+        FetchError::Cancelled.kind(),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(2, kinds.len());
+}
+
+#[test]
+fn test_generic() {
+    assert_eq!(WrappedKind::Inner, Wrapped::Inner(1).kind());
+    assert_eq!(WrappedKind::Other, Wrapped::<i32>::Other.kind());
+}
+
+#[derive(Error, Debug)]
+#[naur(kind)]
+pub enum AuthError {
+    #[error("token expired")]
+    #[error(code = "AUTH-001")]
+    Expired,
+    #[error("token revoked")]
+    #[error(code = "AUTH-002")]
+    Revoked,
+    #[error("unknown failure")]
+    Unknown,
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!(Ok(FetchErrorKind::Timeout), "Timeout".parse());
+    assert_eq!(Ok(AuthErrorKind::Revoked), "AUTH-002".parse());
+    assert_eq!(Ok(AuthErrorKind::Revoked), "Revoked".parse());
+
+    let error = "timeout".parse::<FetchErrorKind>().unwrap_err();
+    assert_eq!("timeout", error.input());
+    assert_eq!("unrecognized error kind `timeout`", error.to_string());
+}
+
+#[test]
+fn test_round_trip() {
+    for error in [AuthError::Expired, AuthError::Revoked, AuthError::Unknown] {
+        let kind = error.kind();
+        assert_eq!(Ok(kind), kind.to_string().parse());
+        if let Some(code) = error.code() {
+            assert_eq!(Ok(kind), code.parse());
+        }
+    }
+}
+
+#[test]
+fn test_from_kind() {
+    let error = AuthError::from(AuthErrorKind::Revoked);
+    assert_eq!("token revoked", error.to_string());
+    assert_eq!(AuthErrorKind::Revoked, error.kind());
+
+    let error = FetchError::try_from(FetchErrorKind::Cancelled).unwrap();
+    assert_eq!(FetchErrorKind::Cancelled, error.kind());
+    let kind = FetchError::try_from(FetchErrorKind::Timeout).unwrap_err();
+    assert_eq!(FetchErrorKind::Timeout, kind);
+
+    let error = Wrapped::<i32>::try_from(WrappedKind::Other).unwrap();
+    assert_eq!("other", error.to_string());
+}
+
+#[test]
+fn test_all() {
+    assert_eq!(
+        [
+            FetchErrorKind::Timeout,
+            FetchErrorKind::Status,
+            FetchErrorKind::Connect,
+            FetchErrorKind::Cancelled,
+        ],
+        FetchErrorKind::ALL,
+    );
+    let names: Vec<&str> = FetchErrorKind::iter().map(FetchErrorKind::name).collect();
+    assert_eq!(["Timeout", "Status", "Connect", "Cancelled"], *names);
+    assert_eq!(Some("AUTH-002"), AuthErrorKind::Revoked.code());
+
+    // Every kind round-trips through its name and its code.
+    for kind in AuthErrorKind::iter() {
+        assert_eq!(Ok(kind), kind.name().parse());
+        if let Some(code) = kind.code() {
+            assert_eq!(Ok(kind), code.parse());
+        }
+    }
+}
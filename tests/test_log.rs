@@ -0,0 +1,108 @@
+#![cfg(feature = "log")]
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::cell::RefCell;
+use std::io;
+use std::sync::Once;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[throws(log = "warn")]
+pub enum StoreError {
+    #[error("failed to read {path}")]
+    Read { path: String, source: io::Error },
+    #[error("store is locked")]
+    #[throws(log = "info")]
+    Locked,
+    #[error("store is corrupt")]
+    Corrupt(#[from] std::num::ParseIntError),
+}
+
+#[derive(Error, Debug)]
+#[error("request failed")]
+#[throws(log)]
+pub struct RequestError {
+    #[from]
+    source: StoreError,
+}
+
+#[derive(Error, Debug)]
+#[error("login failed for {user} with password {password}")]
+#[throws(log)]
+pub struct LoginError {
+    user: String,
+    #[sensitive]
+    password: String,
+    source: io::Error,
+}
+
+thread_local! {
+    static RECORDS: RefCell<Vec<(Level, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Recorder;
+
+impl Log for Recorder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let entry = (record.level(), record.args().to_string());
+        RECORDS.with(|records| records.borrow_mut().push(entry));
+    }
+
+    fn flush(&self) {}
+}
+
+fn record(f: impl FnOnce()) -> Vec<(Level, String)> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&Recorder).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+    });
+    RECORDS.with(|records| records.borrow_mut().clear());
+    f();
+    RECORDS.with(|records| records.borrow_mut().split_off(0))
+}
+
+#[test]
+fn test_throw_method() {
+    let records = record(|| {
+        let result = Err::<(), _>(io::Error::new(io::ErrorKind::NotFound, "gone"))
+            .throw_read("/var/db".to_owned());
+        assert!(result.is_err());
+    });
+    let expected = (Level::Warn, "failed to read /var/db: gone".to_owned());
+    assert_eq!(vec![expected], records);
+}
+
+#[test]
+fn test_from() {
+    let records = record(|| {
+        let source = "x".parse::<i32>().unwrap_err();
+        let _ = RequestError::from(StoreError::from(source));
+    });
+    let expected = vec![
+        (
+            Level::Warn,
+            "store is corrupt: invalid digit found in string".to_owned(),
+        ),
+        (Level::Error, "request failed: store is corrupt".to_owned()),
+    ];
+    assert_eq!(expected, records);
+}
+
+#[test]
+fn test_sensitive() {
+    let records = record(|| {
+        let result = Err::<(), _>(io::Error::new(io::ErrorKind::PermissionDenied, "denied"))
+            .throw_login("admin".to_owned(), "hunter2".to_owned());
+        assert!(result.is_err());
+    });
+    let expected = (
+        Level::Error,
+        "login failed for admin with password [REDACTED]: denied".to_owned(),
+    );
+    assert_eq!(vec![expected], records);
+}
@@ -0,0 +1,140 @@
+use std::io;
+use thiserror::{Error, FieldManifest, TypeManifest, VariantManifest};
+
+#[derive(Error, Debug)]
+#[naur(manifest)]
+#[error("failed to read {path}")]
+#[error(code = "E_READ", status = 404, help = "check that {path} exists")]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(manifest)]
+#[error(code = "E_FETCH")]
+pub enum FetchError {
+    #[error("timed out after {0} ms")]
+    #[error(status = 504)]
+    Timeout(u64),
+    #[error("bad header {name}")]
+    Header {
+        name: &'static str,
+        values: Vec<Option<String>>,
+    },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[naur(manifest)]
+#[error("empty")]
+pub struct Empty;
+
+#[test]
+fn test_struct() {
+    assert_eq!(
+        ReadError::manifest(),
+        TypeManifest {
+            name: "ReadError",
+            is_enum: false,
+            variants: &[VariantManifest {
+                name: "ReadError",
+                code: Some("E_READ"),
+                status: Some(404),
+                template: Some("failed to read {path}"),
+                help: Some("check that {path} exists"),
+                fields: &[
+                    FieldManifest {
+                        name: "path",
+                        ty: "String",
+                        source: false,
+                        backtrace: false,
+                    },
+                    FieldManifest {
+                        name: "source",
+                        ty: "io::Error",
+                        source: true,
+                        backtrace: false,
+                    },
+                ],
+            }],
+        },
+    );
+}
+
+#[test]
+fn test_enum() {
+    let manifest = FetchError::manifest();
+    assert_eq!("FetchError", manifest.name);
+    assert!(manifest.is_enum);
+    assert_eq!(
+        manifest.variants,
+        [
+            VariantManifest {
+                name: "Timeout",
+                code: Some("E_FETCH"),
+                status: Some(504),
+                template: Some("timed out after {0} ms"),
+                help: None,
+                fields: &[FieldManifest {
+                    name: "0",
+                    ty: "u64",
+                    source: false,
+                    backtrace: false,
+                }],
+            },
+            VariantManifest {
+                name: "Header",
+                code: Some("E_FETCH"),
+                status: Some(500),
+                template: Some("bad header {name}"),
+                help: None,
+                fields: &[
+                    FieldManifest {
+                        name: "name",
+                        ty: "&'static str",
+                        source: false,
+                        backtrace: false,
+                    },
+                    FieldManifest {
+                        name: "values",
+                        ty: "Vec<Option<String>>",
+                        source: false,
+                        backtrace: false,
+                    },
+                ],
+            },
+            VariantManifest {
+                name: "Io",
+                code: Some("E_FETCH"),
+                status: Some(500),
+                template: None,
+                help: None,
+                fields: &[FieldManifest {
+                    name: "0",
+                    ty: "io::Error",
+                    source: true,
+                    backtrace: false,
+                }],
+            },
+        ],
+    );
+}
+
+#[test]
+fn test_unit() {
+    let manifest = Empty::manifest();
+    assert_eq!(None, manifest.variants[0].status);
+    assert!(manifest.variants[0].fields.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize() {
+    let json = serde_json::to_string(&Empty::manifest()).unwrap();
+    assert_eq!(
+        r#"{"name":"Empty","is_enum":false,"variants":[{"name":"Empty","code":null,"status":null,"template":"empty","help":null,"fields":[]}]}"#,
+        json,
+    );
+}
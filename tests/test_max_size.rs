@@ -0,0 +1,29 @@
+use std::io;
+use std::mem;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to parse {input:?}")]
+#[naur(max_size = 32)]
+pub struct ParseError {
+    input: String,
+}
+
+#[derive(Error, Debug)]
+#[naur(max_size = 16)]
+pub enum StoreError {
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+    #[error("key {0} not found")]
+    NotFound(u64),
+    #[error(boxed)]
+    Parse(Box<ParseError>),
+    #[error("closed")]
+    Closed,
+}
+
+#[test]
+fn test_max_size() {
+    assert!(mem::size_of::<ParseError>() <= 32);
+    assert!(mem::size_of::<StoreError>() <= 16);
+}
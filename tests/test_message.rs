@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to open {path}")]
+pub struct OpenError {
+    path: &'static str,
+}
+
+#[derive(Error, Debug)]
+pub enum EnumError {
+    #[error("retry in {0}s")]
+    Retry(u64),
+    #[error(transparent)]
+    Open(#[from] OpenError),
+}
+
+#[derive(Error, Debug)]
+#[error("invalid {0}")]
+pub struct Generic<T>(T);
+
+#[derive(Error, Debug)]
+#[error("connection reset {{by peer}}")]
+pub struct Reset;
+
+#[derive(Error, Debug)]
+#[error(prefix = "db: ")]
+pub enum DbError {
+    #[error("connection lost")]
+    Lost,
+    #[error("query failed: {0}")]
+    Query(String),
+    #[error("timed out")]
+    #[error(no_prefix)]
+    Timeout,
+}
+
+// A fixed-capacity buffer that fails instead of growing.
+struct Buffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Buffer<N> {
+    fn new() -> Self {
+        Buffer {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl<const N: usize> Write for Buffer<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > N {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_message() {
+    let error = OpenError { path: "/etc/hosts" };
+    let mut buffer = Buffer::<64>::new();
+    error.write_message(&mut buffer).unwrap();
+    assert_eq!("failed to open /etc/hosts", buffer.as_str());
+
+    let mut buffer = Buffer::<8>::new();
+    assert!(error.write_message(&mut buffer).is_err());
+
+    let mut string = String::new();
+    EnumError::Retry(5).write_message(&mut string).unwrap();
+    assert_eq!("retry in 5s", string);
+
+    let mut string = String::new();
+    Generic(1).write_message(&mut string).unwrap();
+    assert_eq!("invalid 1", string);
+}
+
+#[test]
+fn test_message_len_hint() {
+    let error = OpenError { path: "/etc/hosts" };
+    assert_eq!(error.to_string().len(), error.message_len_hint());
+
+    let error = EnumError::from(error);
+    assert_eq!(25, error.message_len_hint());
+    assert_eq!(11, EnumError::Retry(5).message_len_hint());
+    assert_eq!(9, Generic("x").message_len_hint());
+}
+
+#[test]
+fn test_message() {
+    let message = Reset.message();
+    assert!(matches!(
+        message,
+        Cow::Borrowed("connection reset {by peer}")
+    ));
+    assert_eq!(Reset.to_string(), message);
+
+    assert!(matches!(
+        DbError::Lost.message(),
+        Cow::Borrowed("db: connection lost")
+    ));
+    assert!(matches!(
+        DbError::Timeout.message(),
+        Cow::Borrowed("timed out")
+    ));
+    let error = DbError::Query("syntax".to_owned());
+    assert!(matches!(error.message(), Cow::Owned(_)));
+    assert_eq!("db: query failed: syntax", error.message());
+
+    let error = OpenError { path: "/tmp/x" };
+    assert_eq!(error.to_string(), error.message());
+    assert!(matches!(EnumError::from(error).message(), Cow::Owned(_)));
+    assert_eq!("invalid x", Generic("x").message());
+}
+
+#[test]
+fn test_static_message() {
+    assert_eq!(Some("connection reset {by peer}"), Reset.static_message());
+    assert_eq!(Some("db: connection lost"), DbError::Lost.static_message());
+    assert_eq!(Some("timed out"), DbError::Timeout.static_message());
+    assert_eq!(None, DbError::Query("syntax".to_owned()).static_message());
+    assert_eq!(None, OpenError { path: "/tmp/x" }.static_message());
+    assert_eq!(None, Generic("x").static_message());
+}
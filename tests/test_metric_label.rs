@@ -0,0 +1,55 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("user {user} is not allowed")]
+pub struct Forbidden {
+    user: String,
+}
+
+#[derive(Error, Debug)]
+pub enum QueryError {
+    #[error("table {0} does not exist")]
+    MissingTable(String),
+    #[error("query timed out after {secs}s")]
+    Timeout { secs: u64 },
+    #[error(transparent)]
+    Forbidden(#[from] Forbidden),
+}
+
+#[derive(Error, Debug)]
+#[error("invalid {0}")]
+pub struct Generic<T>(T);
+
+#[test]
+fn test_metric_label() {
+    let forbidden = Forbidden {
+        user: "mallory".to_owned(),
+    };
+    assert_eq!("Forbidden", forbidden.metric_label());
+
+    let error = QueryError::MissingTable("users; DROP TABLE".to_owned());
+    assert_eq!("MissingTable", error.metric_label());
+    assert_eq!("Timeout", QueryError::Timeout { secs: 30 }.metric_label());
+    assert_eq!("Forbidden", QueryError::from(forbidden).metric_label());
+
+    assert_eq!("Generic", Generic(1).metric_label());
+}
+
+#[derive(Error, Debug)]
+pub enum Raw {
+    #[error("type error")]
+    r#Type,
+}
+
+#[test]
+fn test_variant_name() {
+    let forbidden = Forbidden {
+        user: "mallory".to_owned(),
+    };
+    assert_eq!("Forbidden", forbidden.variant_name());
+    let error = QueryError::Timeout { secs: 30 };
+    assert_eq!("Timeout", error.variant_name());
+    assert_eq!(error.metric_label(), error.variant_name());
+    assert_eq!("Type", Raw::r#Type.variant_name());
+    assert_eq!("Generic", Generic("x").variant_name());
+}
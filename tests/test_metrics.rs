@@ -0,0 +1,121 @@
+#![cfg(feature = "metrics")]
+
+use metrics::{
+    Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+};
+use std::io;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(metric = "db.query.failed")]
+pub enum QueryError {
+    #[error("failed to connect")]
+    Connect { source: io::Error },
+    #[error("malformed row")]
+    #[error(metric = "db.row.malformed")]
+    Malformed(#[from] std::num::ParseIntError),
+}
+
+#[derive(Error, Debug)]
+#[error("request failed")]
+#[error(metric = "http.request.failed")]
+pub struct RequestError {
+    #[from]
+    source: QueryError,
+}
+
+type Recorded = Vec<(String, Vec<(String, String)>, u64)>;
+type Increments = Arc<Mutex<Recorded>>;
+
+#[derive(Default)]
+struct Collector {
+    increments: Increments,
+}
+
+struct Increment {
+    key: Key,
+    increments: Increments,
+}
+
+impl CounterFn for Increment {
+    fn increment(&self, value: u64) {
+        let name = self.key.name().to_owned();
+        let labels = self
+            .key
+            .labels()
+            .map(|label| (label.key().to_owned(), label.value().to_owned()))
+            .collect();
+        self.increments.lock().unwrap().push((name, labels, value));
+    }
+
+    fn absolute(&self, _value: u64) {
+        unimplemented!()
+    }
+}
+
+impl Recorder for Collector {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata) -> Counter {
+        Counter::from_arc(Arc::new(Increment {
+            key: key.clone(),
+            increments: Arc::clone(&self.increments),
+        }))
+    }
+
+    fn register_gauge(&self, _key: &Key, _metadata: &Metadata) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, _key: &Key, _metadata: &Metadata) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+fn record(f: impl FnOnce()) -> Recorded {
+    let collector = Collector::default();
+    metrics::with_local_recorder(&collector, f);
+    let increments = collector.increments.lock().unwrap();
+    increments.clone()
+}
+
+fn variant(name: &str) -> Vec<(String, String)> {
+    vec![("variant".to_owned(), name.to_owned())]
+}
+
+#[test]
+fn test_throw_method() {
+    let increments = record(|| {
+        let result =
+            Err::<(), _>(io::Error::from(io::ErrorKind::ConnectionRefused)).throw_connect();
+        assert!(result.is_err());
+    });
+    let expected = vec![("db.query.failed".to_owned(), variant("Connect"), 1)];
+    assert_eq!(expected, increments);
+}
+
+#[test]
+fn test_from() {
+    let increments = record(|| {
+        let source = "x".parse::<i32>().unwrap_err();
+        let _ = RequestError::from(QueryError::from(source));
+    });
+    let expected = vec![
+        ("db.row.malformed".to_owned(), variant("Malformed"), 1),
+        ("http.request.failed".to_owned(), variant("RequestError"), 1),
+    ];
+    assert_eq!(expected, increments);
+}
+
+#[test]
+fn test_direct_construction() {
+    let increments = record(|| {
+        let _ = QueryError::Connect {
+            source: io::Error::from(io::ErrorKind::ConnectionRefused),
+        };
+    });
+    assert!(increments.is_empty());
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "miette")]
+
+use miette::{Diagnostic, LabeledSpan, Severity, SourceSpan};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("unexpected token")]
+#[diagnostic(code(parser::unexpected), help("remove the stray {token:?}"))]
+pub struct ParseError {
+    token: char,
+    #[diagnostic(source_code)]
+    input: String,
+    #[diagnostic(label = "here")]
+    at: SourceSpan,
+}
+
+#[derive(Error, Debug)]
+#[diagnostic(code(config::invalid), url("https://example.com/config"))]
+pub enum ConfigError {
+    #[error("missing key {0}")]
+    #[diagnostic(severity(Warning))]
+    Missing(String),
+    #[error("bad value")]
+    #[error(code = "CONFIG_VALUE", help = "values must be positive")]
+    #[diagnostic(code(config::value))]
+    Value,
+    #[error("deprecated")]
+    #[error(help = "use the new format")]
+    Deprecated,
+}
+
+fn display(value: Option<Box<dyn std::fmt::Display + '_>>) -> Option<String> {
+    value.map(|value| value.to_string())
+}
+
+#[test]
+fn test_struct() {
+    let error = ParseError {
+        token: ';',
+        input: "let x = 1;;".to_owned(),
+        at: SourceSpan::from((10, 1)),
+    };
+    assert_eq!(
+        Some("parser::unexpected".to_owned()),
+        display(Diagnostic::code(&error))
+    );
+    assert_eq!(
+        Some("remove the stray ';'".to_owned()),
+        display(Diagnostic::help(&error))
+    );
+    assert_eq!(None, Diagnostic::severity(&error));
+    assert!(error.source_code().is_some());
+    let labels: Vec<LabeledSpan> = error.labels().unwrap().collect();
+    assert_eq!(
+        vec![LabeledSpan::new(Some("here".to_owned()), 10, 1)],
+        labels,
+    );
+}
+
+#[test]
+fn test_enum() {
+    let error = ConfigError::Missing("port".to_owned());
+    assert_eq!(
+        Some("config::invalid".to_owned()),
+        display(Diagnostic::code(&error))
+    );
+    assert_eq!(Some(Severity::Warning), Diagnostic::severity(&error));
+    assert_eq!(
+        Some("https://example.com/config".to_owned()),
+        display(Diagnostic::url(&error)),
+    );
+    assert_eq!(None, display(Diagnostic::help(&error)));
+    assert!(error.source_code().is_none());
+    assert!(error.labels().is_none());
+
+    let error = ConfigError::Value;
+    assert_eq!(
+        Some("config::value".to_owned()),
+        display(Diagnostic::code(&error))
+    );
+    assert_eq!(
+        Some("values must be positive".to_owned()),
+        display(Diagnostic::help(&error)),
+    );
+    assert_eq!(None, Diagnostic::severity(&error));
+
+    let error = ConfigError::Deprecated;
+    assert_eq!(
+        Some("use the new format".to_owned()),
+        display(Diagnostic::help(&error))
+    );
+}
+
+#[derive(Error, Debug)]
+#[error("failed to parse the batch")]
+#[diagnostic(code(parser::batch))]
+pub struct BatchError {
+    #[related]
+    errors: Vec<ParseError>,
+}
+
+#[test]
+fn test_related() {
+    let parse_error = |token| ParseError {
+        token,
+        input: String::new(),
+        at: SourceSpan::from(0..0),
+    };
+    let error = BatchError {
+        errors: vec![parse_error(';'), parse_error(',')],
+    };
+    let related: Vec<Option<String>> = Diagnostic::related(&error)
+        .unwrap()
+        .map(|diagnostic| display(diagnostic.help()))
+        .collect();
+    assert_eq!(
+        [
+            Some("remove the stray ';'".to_owned()),
+            Some("remove the stray ','".to_owned()),
+        ],
+        related.as_slice(),
+    );
+    assert!(Diagnostic::related(&ConfigError::Value).is_none());
+}
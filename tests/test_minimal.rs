@@ -0,0 +1,53 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(minimal)]
+pub enum StoreError {
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+    #[error("key {key} not found")]
+    NotFound { key: String },
+    #[error("corrupt record")]
+    Corrupt {
+        #[source]
+        source: io::Error,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("failed to open {path}")]
+#[naur(minimal)]
+pub struct OpenError {
+    path: String,
+    source: io::Error,
+}
+
+// Stand-ins which would conflict with the generated trait and methods, if
+// there were any.
+#[allow(dead_code)]
+trait StoreErrorCorruptThrows {}
+#[allow(dead_code)]
+trait OpenErrorThrows {}
+
+impl StoreError {
+    #[allow(dead_code)]
+    fn variant_name(&self) -> &'static str {
+        "store"
+    }
+}
+
+#[test]
+fn test_minimal() {
+    let error = StoreError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("i/o failed", error.to_string());
+    assert_eq!("oh no!", error.source().unwrap().to_string());
+
+    let error = OpenError {
+        path: "/etc/app.toml".to_owned(),
+        source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+    };
+    assert_eq!("failed to open /etc/app.toml", error.to_string());
+    assert!(error.source().is_some());
+}
@@ -0,0 +1,45 @@
+#![deny(deprecated)]
+
+use std::error::Error as _;
+use std::io;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("invalid port: {0}")]
+    Port(ParseIntError),
+    #[error("invalid timeout: {0:?}")]
+    Timeout(ParseIntError),
+    #[error("failed to read config")]
+    #[error(no_source)]
+    Io(io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("invalid retry count: {error}")]
+pub struct RetryError {
+    error: ParseIntError,
+}
+
+#[test]
+fn test_interpolated() {
+    let error = ConfigError::Port("x".parse::<u16>().unwrap_err());
+    assert_eq!(
+        "invalid port: invalid digit found in string",
+        error.to_string(),
+    );
+    assert!(error.source().is_none());
+
+    let error = RetryError {
+        error: "x".parse::<u8>().unwrap_err(),
+    };
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn test_no_source() {
+    let error = ConfigError::Io(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("failed to read config", error.to_string());
+    assert!(error.source().is_none());
+}
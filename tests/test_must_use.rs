@@ -0,0 +1,60 @@
+#![deny(unused_must_use)]
+
+use std::error::Error as _;
+use std::io;
+use std::num::ParseIntError;
+use thiserror::{Error, Pipeline};
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+#[throws(must_use, pipe, doc = "Raises a `ReadError` from an i/o failure.")]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[throws(must_use, doc(hidden))]
+pub enum ParseError {
+    #[error("line {line} is invalid")]
+    Line {
+        line: usize,
+        #[source]
+        source: ParseIntError,
+    },
+    #[error("invalid field {name}")]
+    #[throws(doc = "Raises `ParseError::Field`.")]
+    Field {
+        name: String,
+        #[source]
+        source: ParseIntError,
+    },
+}
+
+fn read(path: &str) -> Result<String, io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path),
+    ))
+}
+
+#[test]
+fn test_must_use_throw() {
+    let error = read("/etc/app.toml")
+        .throw_read("/etc/app.toml".to_owned())
+        .unwrap_err();
+    assert_eq!("failed to read /etc/app.toml", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let error = "x".parse::<i32>().throw_line_with(|| 3).unwrap_err();
+    assert_eq!("line 3 is invalid", error.to_string());
+
+    let error = "x"
+        .parse::<i32>()
+        .throw_field("port".to_owned())
+        .unwrap_err();
+    assert_eq!("invalid field port", error.to_string());
+
+    let result = Pipeline::new("/etc/app.toml").pipe_throw_read(read, "/etc/app.toml".to_owned());
+    assert!(result.is_err());
+}
@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+use std::io;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+thread_local! {
+    static CREATED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(error: &dyn std::error::Error) {
+    CREATED.with(|created| created.borrow_mut().push(error.to_string()));
+}
+
+fn on_read_error(error: &ReadError) {
+    record(error);
+}
+
+fn created() -> Vec<String> {
+    CREATED.with(|created| created.borrow_mut().split_off(0))
+}
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+#[throws(on_create = on_read_error)]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(constructors)]
+#[throws(on_create = record)]
+pub enum ParseError {
+    #[error("invalid number")]
+    Number(#[from] ParseIntError),
+    #[error("line {line} is invalid")]
+    Line {
+        line: usize,
+        #[source]
+        source: ParseIntError,
+    },
+}
+
+fn read(path: &str) -> Result<String, io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path),
+    ))
+}
+
+#[test]
+fn test_throw() {
+    let error = read("/etc/app.toml")
+        .throw_read("/etc/app.toml".to_owned())
+        .unwrap_err();
+    assert_eq!(vec![error.to_string()], created());
+}
+
+#[test]
+fn test_from() {
+    let _error = ParseError::from("x".parse::<i32>().unwrap_err());
+    assert_eq!(vec!["invalid number".to_owned()], created());
+}
+
+#[test]
+fn test_constructor() {
+    let source = "x".parse::<i32>().unwrap_err();
+    let _error = ParseError::line(source, 3);
+    assert_eq!(vec!["line 3 is invalid".to_owned()], created());
+}
+
+#[test]
+fn test_literal() {
+    // Building the error by hand bypasses the hook.
+    let _error = ParseError::Line {
+        line: 3,
+        source: "x".parse::<i32>().unwrap_err(),
+    };
+    assert!(created().is_empty());
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "otel")]
+
+use opentelemetry::trace::{Span, SpanContext, Status, TraceContextExt};
+use opentelemetry::{Context, KeyValue, Value};
+use std::borrow::Cow;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(code = "QUERY_FAILED")]
+pub enum QueryError {
+    #[error("failed to connect")]
+    Connect { source: io::Error },
+    #[error("malformed row")]
+    #[error(code = "MALFORMED_ROW")]
+    Malformed(#[from] std::num::ParseIntError),
+}
+
+#[derive(Error, Debug)]
+#[error("request failed")]
+pub struct RequestError {
+    #[from]
+    source: QueryError,
+}
+
+#[derive(Error, Debug)]
+#[error("lookup of {key} failed")]
+pub struct LookupError<K> {
+    key: K,
+    source: io::Error,
+}
+
+type Events = Arc<Mutex<Vec<(String, Vec<(String, String)>)>>>;
+
+struct RecordingSpan {
+    events: Events,
+}
+
+impl Span for RecordingSpan {
+    fn add_event_with_timestamp<T>(
+        &mut self,
+        name: T,
+        _timestamp: SystemTime,
+        attributes: Vec<KeyValue>,
+    ) where
+        T: Into<Cow<'static, str>>,
+    {
+        let attributes = attributes
+            .into_iter()
+            .map(|kv| match kv.value {
+                Value::String(value) => (kv.key.to_string(), value.to_string()),
+                _ => unimplemented!(),
+            })
+            .collect();
+        self.events
+            .lock()
+            .unwrap()
+            .push((name.into().into_owned(), attributes));
+    }
+
+    fn span_context(&self) -> &SpanContext {
+        &SpanContext::NONE
+    }
+
+    fn is_recording(&self) -> bool {
+        true
+    }
+
+    fn set_attribute(&mut self, _attribute: KeyValue) {}
+    fn set_status(&mut self, _status: Status) {}
+    fn update_name<T>(&mut self, _new_name: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+    }
+    fn add_link(&mut self, _span_context: SpanContext, _attributes: Vec<KeyValue>) {}
+    fn end_with_timestamp(&mut self, _timestamp: SystemTime) {}
+}
+
+fn record(f: impl FnOnce()) -> Vec<(String, Vec<(String, String)>)> {
+    let events = Events::default();
+    let span = RecordingSpan {
+        events: Arc::clone(&events),
+    };
+    let guard = Context::current_with_span(span).attach();
+    f();
+    drop(guard);
+    let events = events.lock().unwrap();
+    events.clone()
+}
+
+fn exception(attributes: &[(&str, &str)]) -> (String, Vec<(String, String)>) {
+    let attributes = attributes
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    ("exception".to_owned(), attributes)
+}
+
+#[test]
+fn test_throw_method() {
+    let events = record(|| {
+        let result =
+            Err::<(), _>(io::Error::from(io::ErrorKind::ConnectionRefused)).throw_connect();
+        assert!(result.is_err());
+    });
+    let expected = vec![exception(&[
+        ("exception.type", "test_otel::QueryError::Connect"),
+        ("exception.message", "failed to connect"),
+        ("error.code", "QUERY_FAILED"),
+    ])];
+    assert_eq!(expected, events);
+}
+
+#[test]
+fn test_from() {
+    let events = record(|| {
+        let source = "x".parse::<i32>().unwrap_err();
+        let _ = RequestError::from(QueryError::from(source));
+    });
+    let expected = vec![
+        exception(&[
+            ("exception.type", "test_otel::QueryError::Malformed"),
+            ("exception.message", "malformed row"),
+            ("error.code", "MALFORMED_ROW"),
+        ]),
+        exception(&[
+            ("exception.type", "test_otel::RequestError"),
+            ("exception.message", "request failed"),
+        ]),
+    ];
+    assert_eq!(expected, events);
+}
+
+#[test]
+fn test_generic() {
+    let events = record(|| {
+        let result = Err::<(), _>(io::Error::from(io::ErrorKind::NotFound)).throw_lookup("user");
+        assert!(result.is_err());
+    });
+    let expected = vec![exception(&[("exception.type", "test_otel::LookupError")])];
+    assert_eq!(expected, events);
+}
+
+#[test]
+fn test_no_active_span() {
+    let error = QueryError::from("x".parse::<i32>().unwrap_err());
+    assert_eq!("malformed row", error.to_string());
+}
@@ -0,0 +1,63 @@
+use std::any::Any;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PluginError {
+    #[error("plugin failed to load")]
+    Load(#[from] io::Error),
+    #[error(panic)]
+    #[error("plugin panicked at {location}: {message}")]
+    Panicked { message: String, location: String },
+}
+
+#[derive(Error, Debug)]
+pub enum HostError {
+    #[error(panic)]
+    #[error("callback panicked")]
+    Panicked(Box<dyn Any + Send>, Option<String>),
+}
+
+#[derive(Error, Debug)]
+pub enum TaskError {
+    #[error(panic)]
+    #[error("task panicked: {0}")]
+    Panicked(String),
+}
+
+#[test]
+fn test_ok() {
+    let value = PluginError::catch_unwind(|| 1 + 1).unwrap();
+    assert_eq!(2, value);
+}
+
+#[test]
+fn test_message_and_location() {
+    thiserror::install_panic_hook();
+    let line = line!() + 1;
+    let error = PluginError::catch_unwind(|| panic!("bad input {}", 7)).unwrap_err();
+    let expected = format!("{}:{}:", file!(), line);
+    match &error {
+        PluginError::Panicked { message, location } => {
+            assert_eq!("bad input 7", message);
+            assert!(location.starts_with(&expected), "{}", location);
+        }
+        PluginError::Load(_) => panic!("expected PluginError::Panicked"),
+    }
+    assert!(error.to_string().starts_with("plugin panicked at "));
+}
+
+#[test]
+fn test_payload() {
+    thiserror::install_panic_hook();
+    let error = HostError::catch_unwind(|| std::panic::panic_any(42_u8)).unwrap_err();
+    let HostError::Panicked(payload, location) = error;
+    assert_eq!(Some(&42), payload.downcast_ref::<u8>());
+    assert!(location.is_some());
+
+    let error = TaskError::catch_unwind(|| std::panic::panic_any(42_u8)).unwrap_err();
+    assert_eq!("task panicked: Box<dyn Any>", error.to_string());
+
+    let error = TaskError::catch_unwind(|| panic!("static message")).unwrap_err();
+    assert_eq!("task panicked: static message", error.to_string());
+}
@@ -0,0 +1,29 @@
+// Without thiserror::install_panic_hook(), which no test in this binary calls,
+// the location of a panic is not recorded.
+
+use std::any::Any;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HostError {
+    #[error(panic)]
+    #[error("callback panicked")]
+    Panicked(Box<dyn Any + Send>, Option<String>),
+}
+
+#[derive(Error, Debug)]
+pub enum PluginError {
+    #[error(panic)]
+    #[error("plugin panicked at {location}: {message}")]
+    Panicked { message: String, location: String },
+}
+
+#[test]
+fn test_no_location() {
+    let HostError::Panicked(_payload, location) =
+        HostError::catch_unwind(|| panic!("no hook")).unwrap_err();
+    assert_eq!(None, location);
+
+    let error = PluginError::catch_unwind(|| panic!("no hook")).unwrap_err();
+    assert_eq!("plugin panicked at <unknown>: no hook", error.to_string());
+}
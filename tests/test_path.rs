@@ -22,6 +22,26 @@ enum EnumPathBuf {
     Read(PathBuf),
 }
 
+#[derive(Error, Debug)]
+#[error("failed to read [{file:>12}] into [{dir:.4}] via {}", .link.display())]
+struct StructPathSpec<'a> {
+    file: PathBuf,
+    dir: Box<Path>,
+    link: &'a Path,
+}
+
+#[derive(Debug)]
+struct Config {
+    path: PathBuf,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to load '{config.path}' from '{source_dir}'")]
+struct StructNestedPath<'a> {
+    config: Config,
+    source_dir: &'a Path,
+}
+
 fn assert<T: Display>(expected: &str, value: T) {
     assert_eq!(expected, value.to_string());
 }
@@ -34,4 +54,19 @@ fn test_display() {
     let file = path.to_owned();
     assert("failed to read '/thiserror'", EnumPathBuf::Read(file));
     assert("failed to read '/thiserror'", StructPath::ref_cast(path));
+
+    let error = StructPathSpec {
+        file: PathBuf::from("/thiserror"),
+        dir: Box::from(Path::new("/tmp/cache")),
+        link: Path::new("/link"),
+    };
+    assert("failed to read [  /thiserror] into [/tmp] via /link", error);
+
+    let error = StructNestedPath {
+        config: Config {
+            path: PathBuf::from("/etc/app.toml"),
+        },
+        source_dir: path,
+    };
+    assert("failed to load '/etc/app.toml' from '/thiserror'", error);
 }
@@ -0,0 +1,50 @@
+use std::io;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+pub struct Postgres;
+pub struct Sqlite;
+
+type Marker<T> = PhantomData<fn() -> T>;
+
+#[derive(Error, Debug)]
+#[error("query {query} failed")]
+pub struct QueryError<Db> {
+    query: String,
+    source: io::Error,
+    db: PhantomData<Db>,
+}
+
+#[derive(Error, Debug)]
+pub enum PoolError<Db> {
+    #[error("pool exhausted")]
+    Exhausted,
+    #[error("connect failed")]
+    Connect {
+        #[from]
+        source: io::Error,
+        #[throws(phantom)]
+        db: Marker<Db>,
+    },
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "oh no!")
+}
+
+#[test]
+fn test_throw_skips_phantom() {
+    let result: Result<(), io::Error> = Err(io_error());
+    let error: QueryError<Postgres> = result.throw_query("select 1".to_owned()).unwrap_err();
+    assert_eq!("query select 1 failed", error.to_string());
+
+    let result: Result<(), io::Error> = Err(io_error());
+    let error: PoolError<Sqlite> = result.throw_connect().unwrap_err();
+    assert!(matches!(error, PoolError::Connect { .. }));
+}
+
+#[test]
+fn test_from_fills_phantom() {
+    let error = PoolError::<Postgres>::from(io_error());
+    assert_eq!("connect failed", error.to_string());
+}
@@ -0,0 +1,83 @@
+use std::io;
+use std::num::{ParseIntError, TryFromIntError};
+use thiserror::{Error, Pipeline};
+
+#[derive(Error, Debug)]
+#[throws(pipe)]
+pub enum LoadError {
+    #[error("failed to read {path}")]
+    Read { path: String, source: io::Error },
+    #[error("invalid number")]
+    Parse(#[source] ParseIntError),
+    #[error("number out of range")]
+    Range(#[source] TryFromIntError),
+}
+
+#[derive(Error, Debug)]
+#[error("worker {id} failed")]
+#[throws(pipe)]
+pub struct WorkerError {
+    id: u32,
+    source: LoadError,
+}
+
+#[derive(Error, Debug)]
+#[error("lookup failed")]
+#[throws(pipe)]
+pub struct LookupError<K> {
+    key: K,
+    source: io::Error,
+}
+
+fn read(contents: Option<&str>) -> Result<String, io::Error> {
+    contents
+        .map(str::to_owned)
+        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+}
+
+fn load(contents: Option<&str>) -> Result<u8, LoadError> {
+    let value = Pipeline::new(contents)
+        .pipe_throw_read(read, "/etc/count".to_owned())?
+        .pipe_throw_parse(|text| text.trim().parse::<i32>())?
+        .pipe_throw_range(u8::try_from)?
+        .into_inner();
+    Ok(value)
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(42, load(Some("42\n")).unwrap());
+
+    let error = load(None).unwrap_err();
+    assert_eq!("failed to read /etc/count", error.to_string());
+    assert!(matches!(load(Some("four")), Err(LoadError::Parse(_))));
+    assert!(matches!(load(Some("256")), Err(LoadError::Range(_))));
+}
+
+#[test]
+fn test_struct() {
+    let result = Pipeline::new(None).pipe_throw_worker(load, 7);
+    let error = result.unwrap_err();
+    assert_eq!("worker 7 failed", error.to_string());
+    assert!(matches!(error.source, LoadError::Read { .. }));
+}
+
+#[test]
+fn test_generic() {
+    let result = Pipeline::new(None).pipe_throw_lookup(read, "user");
+    let error: LookupError<&str> = result.unwrap_err();
+    assert_eq!("user", error.key);
+}
+
+#[test]
+fn test_stage_skipped_after_error() {
+    let mut ran = false;
+    let result = Pipeline::new("x")
+        .pipe_throw_parse(str::parse::<i32>)
+        .and_then(|pipeline| {
+            ran = true;
+            pipeline.pipe_throw_range(u8::try_from)
+        });
+    assert!(matches!(result, Err(LoadError::Parse(_))));
+    assert!(!ran);
+}
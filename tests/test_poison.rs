@@ -0,0 +1,69 @@
+use std::error::Error as _;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use thiserror::{Error, Poisoned};
+
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("cache miss for {0}")]
+    Miss(String),
+    #[error("cache lock poisoned")]
+    Poisoned(#[source(poison)] Poisoned),
+}
+
+#[derive(Error, Debug)]
+#[error("registry lock poisoned")]
+pub struct RegistryError {
+    #[source(poison)]
+    source: Poisoned,
+}
+
+fn poisoned<T: Send + 'static>(value: T) -> Arc<Mutex<T>> {
+    let mutex = Arc::new(Mutex::new(value));
+    let clone = Arc::clone(&mutex);
+    let _ = thread::spawn(move || {
+        let _guard = clone.lock().unwrap();
+        panic!("poisoning the lock");
+    })
+    .join();
+    mutex
+}
+
+fn get(cache: &Mutex<Vec<String>>, i: usize) -> Result<String, CacheError> {
+    let cache = cache.lock()?;
+    cache
+        .get(i)
+        .cloned()
+        .ok_or_else(|| CacheError::Miss(i.to_string()))
+}
+
+#[test]
+fn test_enum() {
+    let cache = Mutex::new(vec!["a".to_owned()]);
+    assert_eq!("a", get(&cache, 0).unwrap());
+
+    let cache = poisoned(vec!["a".to_owned()]);
+    let error = get(&cache, 0).unwrap_err();
+    assert_eq!("cache lock poisoned", error.to_string());
+    let source = error.source().unwrap().downcast_ref::<Poisoned>().unwrap();
+    assert_eq!(
+        "poisoned lock: another task failed inside",
+        source.to_string(),
+    );
+    assert!(source.guard_type().contains("MutexGuard"));
+}
+
+#[test]
+fn test_struct() {
+    let lock = Arc::new(RwLock::new(0));
+    let clone = Arc::clone(&lock);
+    let _ = thread::spawn(move || {
+        let _guard = clone.write().unwrap();
+        panic!("poisoning the lock");
+    })
+    .join();
+
+    let read = || -> Result<i32, RegistryError> { Ok(*lock.read()?) };
+    let error = read().unwrap_err();
+    assert!(error.source.guard_type().contains("RwLockReadGuard"));
+}
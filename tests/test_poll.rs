@@ -0,0 +1,112 @@
+use std::error::Error as _;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+#[throws(poll)]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[throws(poll)]
+pub enum FetchError {
+    #[error("connection to {0} failed")]
+    Connect(String, #[source] io::Error),
+    #[error("other failure")]
+    Other(#[source] io::Error),
+}
+
+// A stand-in for a manual `AsyncRead` impl.
+struct Reader {
+    path: String,
+    ready: Poll<io::Result<usize>>,
+}
+
+impl Reader {
+    fn poll_read(&mut self) -> Poll<Result<usize, ReadError>> {
+        let ready = std::mem::replace(&mut self.ready, Poll::Pending);
+        ready.throw_read(self.path.clone())
+    }
+}
+
+struct Connect {
+    host: String,
+    result: Option<io::Result<()>>,
+}
+
+impl Future for Connect {
+    type Output = Result<(), FetchError>;
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Self::Output> {
+        let host = self.host.clone();
+        let ready = match self.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        };
+        ready.throw_connect(host)
+    }
+}
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "oh no!")
+}
+
+#[test]
+fn test_struct() {
+    let mut reader = Reader {
+        path: "/etc/app.toml".to_owned(),
+        ready: Poll::Ready(Err(io_error())),
+    };
+    match reader.poll_read() {
+        Poll::Ready(Err(error)) => {
+            assert_eq!("failed to read /etc/app.toml", error.to_string());
+            assert!(error.source().unwrap().is::<io::Error>());
+        }
+        _ => panic!("expected a ready error"),
+    }
+    assert!(reader.poll_read().is_pending());
+
+    reader.ready = Poll::Ready(Ok(4));
+    assert!(matches!(reader.poll_read(), Poll::Ready(Ok(4))));
+}
+
+#[test]
+fn test_enum() {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut connect = Connect {
+        host: "example.com".to_owned(),
+        result: None,
+    };
+    assert!(Pin::new(&mut connect).poll(&mut cx).is_pending());
+
+    connect.result = Some(Err(io_error()));
+    match Pin::new(&mut connect).poll(&mut cx) {
+        Poll::Ready(Err(error)) => {
+            assert_eq!("connection to example.com failed", error.to_string());
+        }
+        _ => panic!("expected a ready error"),
+    }
+
+    let ready: Poll<io::Result<()>> = Poll::Ready(Err(io_error()));
+    assert!(matches!(
+        ready.throw_other(),
+        Poll::Ready(Err(FetchError::Other(_)))
+    ));
+}
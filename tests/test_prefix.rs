@@ -0,0 +1,33 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(prefix = "config: ")]
+pub enum ConfigError {
+    #[error("missing key {0}")]
+    MissingKey(String),
+    #[error("invalid port {port}")]
+    InvalidPort { port: u16 },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("no configuration found")]
+    #[error(no_prefix)]
+    NotFound,
+}
+
+#[test]
+fn test_prefix() {
+    let error = ConfigError::MissingKey("port".to_owned());
+    assert_eq!("config: missing key port", error.to_string());
+
+    let error = ConfigError::InvalidPort { port: 0 };
+    assert_eq!("config: invalid port 0", error.to_string());
+
+    let error = ConfigError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("config: oh no!", error.to_string());
+}
+
+#[test]
+fn test_no_prefix() {
+    assert_eq!("no configuration found", ConfigError::NotFound.to_string());
+}
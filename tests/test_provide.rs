@@ -0,0 +1,74 @@
+#![cfg_attr(thiserror_nightly_testing, feature(error_generic_member_access))]
+
+#[cfg(thiserror_nightly_testing)]
+pub mod nightly {
+    use std::backtrace::Backtrace;
+    use std::error;
+    use std::io;
+    use thiserror::Error;
+
+    #[derive(Debug, PartialEq)]
+    pub struct RequestId(u64);
+
+    #[derive(Debug, PartialEq)]
+    pub struct Span(&'static str);
+
+    #[derive(Error, Debug)]
+    #[error("request failed")]
+    pub struct RequestError {
+        #[provide]
+        id: RequestId,
+        #[provide]
+        span: Option<Span>,
+        source: io::Error,
+    }
+
+    #[derive(Error, Debug)]
+    pub enum HandlerError {
+        #[error("request failed")]
+        Request(#[from] RequestError),
+        #[error("timed out")]
+        Timeout {
+            #[provide]
+            id: RequestId,
+            backtrace: Backtrace,
+        },
+        #[error("cancelled")]
+        Cancelled(#[provide] Span),
+        #[error("unknown")]
+        Unknown,
+    }
+
+    #[test]
+    fn test_struct() {
+        let error = RequestError {
+            id: RequestId(7),
+            span: None,
+            source: io::Error::new(io::ErrorKind::Other, "oh no!"),
+        };
+        assert_eq!(Some(&RequestId(7)), error::request_ref::<RequestId>(&error));
+        assert!(error::request_ref::<Span>(&error).is_none());
+
+        let error = RequestError {
+            span: Some(Span("handler")),
+            ..error
+        };
+        assert_eq!(Some(&Span("handler")), error::request_ref::<Span>(&error));
+    }
+
+    #[test]
+    fn test_enum() {
+        let error = HandlerError::Timeout {
+            id: RequestId(8),
+            backtrace: Backtrace::capture(),
+        };
+        assert_eq!(Some(&RequestId(8)), error::request_ref::<RequestId>(&error));
+        assert!(error::request_ref::<Backtrace>(&error).is_some());
+
+        let error = HandlerError::Cancelled(Span("handler"));
+        assert_eq!(Some(&Span("handler")), error::request_ref::<Span>(&error));
+        assert!(error::request_ref::<RequestId>(&error).is_none());
+
+        assert!(error::request_ref::<Span>(&HandlerError::Unknown).is_none());
+    }
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "pyo3")]
+
+use pyo3::exceptions::{PyKeyError, PyPermissionError, PyRuntimeError, PyValueError};
+use pyo3::{PyErr, Python};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("key {0:?} not found")]
+    #[error(py = pyo3::exceptions::PyKeyError)]
+    Missing(String),
+    #[error("invalid value for {0}")]
+    #[error(py = PyValueError)]
+    Invalid(String),
+    #[error("something broke")]
+    Broken,
+}
+
+#[derive(Error, Debug)]
+#[error("store is read-only")]
+#[error(py = PyPermissionError)]
+pub struct ReadOnly;
+
+#[derive(Error, Debug)]
+#[error(py = PyValueError)]
+pub enum ParseError<T: std::fmt::Display> {
+    #[error("unexpected {0}")]
+    Unexpected(T),
+    #[error("internal parser error")]
+    #[error(py = PyRuntimeError)]
+    Internal,
+}
+
+fn check<E>(error: PyErr, message: &str)
+where
+    E: pyo3::PyTypeInfo,
+{
+    Python::initialize();
+    Python::attach(|py| {
+        assert!(error.is_instance_of::<E>(py));
+        assert_eq!(message, error.value(py).to_string());
+    });
+}
+
+#[test]
+fn test_enum() {
+    let error = PyErr::from(StoreError::Missing("port".to_owned()));
+    check::<PyKeyError>(error, "'key \"port\" not found'");
+
+    let error = PyErr::from(StoreError::Invalid("port".to_owned()));
+    check::<PyValueError>(error, "invalid value for port");
+
+    let error = PyErr::from(StoreError::Broken);
+    check::<PyRuntimeError>(error, "something broke");
+}
+
+#[test]
+fn test_struct() {
+    check::<PyPermissionError>(PyErr::from(ReadOnly), "store is read-only");
+}
+
+#[test]
+fn test_generic() {
+    let error = PyErr::from(ParseError::Unexpected('}'));
+    check::<PyValueError>(error, "unexpected }");
+
+    let error = PyErr::from(ParseError::<char>::Internal);
+    check::<PyRuntimeError>(error, "internal parser error");
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "registry")]
+
+use std::collections::HashMap;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+#[error(code = "REG-READ")]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("store is full")]
+    #[error(code = "REG-FULL")]
+    Full,
+    #[error("key {0} is missing")]
+    Missing(String),
+}
+
+#[derive(Error, Debug)]
+pub enum Wrapped<E> {
+    #[error("wrapped")]
+    #[error(code = "REG-WRAPPED")]
+    Inner(E),
+}
+
+#[test]
+fn test_registered() {
+    let descriptors: HashMap<&str, _> = thiserror::registered_errors()
+        .map(|descriptor| (descriptor.name, descriptor))
+        .collect();
+
+    let read = descriptors["ReadError"];
+    assert_eq!(Some("REG-READ"), read.code);
+    assert_eq!(Some("failed to read {path}"), read.template);
+
+    assert_eq!(Some("REG-FULL"), descriptors["Full"].code);
+    assert_eq!(None, descriptors["Missing"].code);
+    assert_eq!(Some("key {0} is missing"), descriptors["Missing"].template);
+}
+
+#[test]
+fn test_generic_left_out() {
+    assert!(thiserror::registered_errors().all(|descriptor| descriptor.code != Some("REG-WRAPPED")));
+}
@@ -0,0 +1,82 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid row {row}")]
+pub struct RowError {
+    row: usize,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to import {path}")]
+pub struct ImportError {
+    path: String,
+    #[related]
+    errors: Vec<RowError>,
+}
+
+#[derive(Error, Debug)]
+pub enum BatchError {
+    #[error("batch was empty")]
+    Empty,
+    #[error("some jobs failed")]
+    Failed(#[related] Vec<Box<dyn std::error::Error + Send + Sync>>),
+    #[error("storage unavailable")]
+    Storage {
+        #[source]
+        source: io::Error,
+        #[related]
+        retries: Vec<io::Error>,
+    },
+}
+
+#[test]
+fn test_struct() {
+    let error = ImportError {
+        path: "users.csv".to_owned(),
+        errors: vec![RowError { row: 3 }, RowError { row: 7 }],
+    };
+    assert_eq!(
+        "failed to import users.csv (2 related errors)",
+        error.to_string()
+    );
+    let related: Vec<String> = error.related().map(ToString::to_string).collect();
+    assert_eq!(["invalid row 3", "invalid row 7"], related.as_slice());
+    assert!(error.related().all(|error| error.is::<RowError>()));
+    assert!(error.source().is_none());
+
+    let error = ImportError {
+        path: "users.csv".to_owned(),
+        errors: vec![RowError { row: 1 }],
+    };
+    assert_eq!(
+        "failed to import users.csv (1 related error)",
+        error.to_string()
+    );
+
+    let error = ImportError {
+        path: "users.csv".to_owned(),
+        errors: Vec::new(),
+    };
+    assert_eq!("failed to import users.csv", error.to_string());
+    assert_eq!(0, error.related().count());
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(0, BatchError::Empty.related().count());
+
+    let error = BatchError::Failed(vec![Box::new(RowError { row: 1 }), "timed out".into()]);
+    assert_eq!("some jobs failed (2 related errors)", error.to_string());
+    let related: Vec<String> = error.related().map(ToString::to_string).collect();
+    assert_eq!(["invalid row 1", "timed out"], related.as_slice());
+
+    let error = BatchError::Storage {
+        source: io::Error::new(io::ErrorKind::Other, "disk full"),
+        retries: vec![io::Error::new(io::ErrorKind::Other, "still full")],
+    };
+    assert_eq!("storage unavailable (1 related error)", error.to_string());
+    assert_eq!("disk full", error.source().unwrap().to_string());
+    assert_eq!("still full", error.related().next().unwrap().to_string());
+}
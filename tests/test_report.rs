@@ -0,0 +1,78 @@
+use std::io;
+use thiserror::{Error, Report};
+
+#[derive(Error, Debug)]
+#[error("failed to load config")]
+pub struct ConfigError {
+    #[from]
+    source: ReadError,
+}
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+    #[error("failed to read {path}")]
+    Io { source: io::Error, path: String },
+    #[error("file is empty")]
+    Empty,
+}
+
+fn read() -> Result<(), ReadError> {
+    let source = io::Error::new(io::ErrorKind::PermissionDenied, "permission\ndenied");
+    Err(ReadError::Io {
+        source,
+        path: "/etc/app.toml".to_owned(),
+    })
+}
+
+fn load() -> Result<(), ConfigError> {
+    read()?;
+    Ok(())
+}
+
+fn run() -> Result<(), Report<ConfigError>> {
+    load()?;
+    Ok(())
+}
+
+#[test]
+fn test_display() {
+    let report = run().unwrap_err();
+    assert_eq!("failed to load config", report.to_string());
+    assert_eq!(
+        "failed to load config: failed to read /etc/app.toml: permission\ndenied",
+        format!("{:#}", report),
+    );
+}
+
+#[test]
+fn test_debug() {
+    let report = run().unwrap_err();
+    let location = report.location();
+    assert_eq!(file!(), location.file());
+    assert_eq!(33, location.line());
+
+    let expected = format!(
+        "\
+failed to load config
+
+Caused by:
+    0: failed to read /etc/app.toml
+    1: permission
+       denied
+
+Location:
+    {}",
+        location,
+    );
+    assert!(format!("{:?}", report).starts_with(&expected));
+}
+
+#[test]
+fn test_no_source() {
+    let report = Report::new(ReadError::Empty);
+    let line = line!() - 1;
+    assert_eq!(line, report.location().line());
+    assert_eq!("file is empty", format!("{:#}", report));
+    assert!(format!("{:?}", report).starts_with("file is empty\n\nLocation:\n"));
+    assert!(matches!(report.into_inner(), ReadError::Empty));
+}
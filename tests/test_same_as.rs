@@ -0,0 +1,42 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("failed to read {key} from {backend}")]
+    Read { key: String, backend: &'static str },
+    #[error(same_as = Read)]
+    ReadReplica { backend: &'static str, key: String },
+    #[error("write of {0} failed")]
+    Write(String, #[source] io::Error),
+    #[error(same_as = Write)]
+    WriteReplica(String, #[source] io::Error),
+    #[error("store is closed")]
+    Closed,
+    #[error(same_as = Closed)]
+    Draining,
+}
+
+#[test]
+fn test_named() {
+    let error = StoreError::ReadReplica {
+        key: "user:1".to_owned(),
+        backend: "redis",
+    };
+    assert_eq!("failed to read user:1 from redis", error.to_string());
+}
+
+#[test]
+fn test_positional() {
+    let error = StoreError::WriteReplica(
+        "user:1".to_owned(),
+        io::Error::new(io::ErrorKind::Other, "disk full"),
+    );
+    assert_eq!("write of user:1 failed", error.to_string());
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn test_unit() {
+    assert_eq!("store is closed", StoreError::Draining.to_string());
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "schemars")]
+
+use schemars::{JsonSchema, SchemaGenerator};
+use serde_json::{json, Value};
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(serialize)]
+pub enum StoreError {
+    #[error("failed to read {path}")]
+    #[error(code = "STORE_READ")]
+    Read { path: String, source: io::Error },
+    #[error("store is locked")]
+    Locked,
+}
+
+#[derive(Error, Debug)]
+#[error("request failed")]
+#[error(code = "REQUEST")]
+#[naur(serialize)]
+pub struct RequestError {
+    #[from]
+    source: StoreError,
+}
+
+fn alternative(variant: &str, code: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "variant": { "const": variant },
+            "message": { "type": "string" },
+            "code": { "const": code },
+            "sources": { "type": "array", "items": { "type": "string" } },
+        },
+        "required": ["variant", "message", "code", "sources"],
+    })
+}
+
+fn schema<T: JsonSchema>() -> Value {
+    let schema = T::json_schema(&mut SchemaGenerator::default());
+    serde_json::to_value(schema).unwrap()
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!("StoreError", StoreError::schema_name());
+    let expected = json!({
+        "oneOf": [
+            alternative("Read", json!("STORE_READ")),
+            alternative("Locked", Value::Null),
+        ],
+    });
+    assert_eq!(expected, schema::<StoreError>());
+}
+
+#[test]
+fn test_struct() {
+    assert_eq!("RequestError", RequestError::schema_name());
+    let expected = json!({
+        "oneOf": [alternative("RequestError", json!("REQUEST"))],
+    });
+    assert_eq!(expected, schema::<RequestError>());
+}
@@ -0,0 +1,84 @@
+#![cfg_attr(thiserror_nightly_testing, feature(error_generic_member_access))]
+
+use std::io;
+use thiserror::{Error, Report};
+
+#[derive(Error, Debug)]
+#[error("failed to connect to {host}")]
+#[error(suggestion = "check that {host} is reachable")]
+#[error(url = "https://docs.example.com/errors/connect")]
+pub struct ConnectError {
+    host: &'static str,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+pub enum DeployError {
+    #[error("deployment failed")]
+    Connect(#[from] ConnectError),
+    #[error("quota exceeded")]
+    #[error(suggestion = "raise the quota to at least {0}")]
+    Quota(u32),
+    #[error("cancelled")]
+    Cancelled,
+}
+
+fn connect_error() -> ConnectError {
+    ConnectError {
+        host: "db.internal",
+        source: io::Error::new(io::ErrorKind::TimedOut, "timed out"),
+    }
+}
+
+#[test]
+fn test_accessors() {
+    let error = connect_error();
+    assert_eq!(
+        Some("check that db.internal is reachable"),
+        error.suggestion().as_deref(),
+    );
+    assert_eq!(
+        Some("https://docs.example.com/errors/connect"),
+        error.url().as_deref(),
+    );
+
+    assert_eq!(
+        Some("raise the quota to at least 10"),
+        DeployError::Quota(10).suggestion().as_deref(),
+    );
+    assert_eq!(None, DeployError::Cancelled.suggestion());
+}
+
+#[test]
+fn test_report_section() {
+    let error = DeployError::Quota(10);
+    let suggestion = error.suggestion().unwrap();
+    let report = Report::new(error).section("Suggestion", suggestion);
+    let rendered = format!("{:?}", report);
+    assert!(rendered.starts_with(
+        "quota exceeded\n\nSuggestion:\n    raise the quota to at least 10\n\nLocation:\n",
+    ));
+}
+
+#[cfg(thiserror_nightly_testing)]
+#[test]
+fn test_provided_sections() {
+    let report = Report::new(DeployError::from(connect_error()));
+    let rendered = format!("{:?}", report);
+    let expected = "\
+deployment failed
+
+Caused by:
+    0: failed to connect to db.internal
+    1: timed out
+
+Suggestion:
+    check that db.internal is reachable
+
+More information:
+    https://docs.example.com/errors/connect
+
+Location:
+";
+    assert!(rendered.starts_with(expected), "{}", rendered);
+}
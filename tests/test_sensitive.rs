@@ -0,0 +1,135 @@
+use std::fmt::Debug;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(fields)]
+#[error("login failed for {user} with password {password}")]
+pub struct LoginError {
+    user: String,
+    #[sensitive]
+    password: String,
+}
+
+#[derive(Error)]
+#[naur(redacted_debug, fields)]
+pub enum TokenError {
+    #[error("token {0:?} expired")]
+    Expired(#[sensitive = "***"] String),
+    #[error("token for {account} rejected: {reason}")]
+    Rejected {
+        account: u64,
+        #[sensitive]
+        token: String,
+        reason: &'static str,
+    },
+    #[error("no token given")]
+    Missing,
+}
+
+#[derive(Error)]
+#[naur(redacted_debug, fields)]
+#[error("request to {url} failed with key {key}")]
+pub struct RequestError<K> {
+    url: String,
+    #[sensitive]
+    key: K,
+}
+
+// Not Debug, nor Display, which the redacted field never needs.
+pub struct ApiKey;
+
+#[test]
+fn test_display() {
+    let error = LoginError {
+        user: "admin".to_owned(),
+        password: "hunter2".to_owned(),
+    };
+    assert_eq!(
+        "login failed for admin with password [REDACTED]",
+        error.to_string(),
+    );
+
+    let error = TokenError::Expired("abc123".to_owned());
+    assert_eq!("token *** expired", error.to_string());
+}
+
+#[test]
+fn test_derived_debug() {
+    let error = LoginError {
+        user: "admin".to_owned(),
+        password: "hunter2".to_owned(),
+    };
+    assert!(format!("{:?}", error).contains("hunter2"));
+}
+
+#[test]
+fn test_redacted_debug() {
+    let error = TokenError::Expired("abc123".to_owned());
+    assert_eq!("Expired(***)", format!("{:?}", error));
+
+    let error = TokenError::Rejected {
+        account: 7,
+        token: "abc123".to_owned(),
+        reason: "revoked",
+    };
+    assert_eq!("token for 7 rejected: revoked", error.to_string(),);
+    assert_eq!(
+        "Rejected { account: 7, token: [REDACTED], reason: \"revoked\" }",
+        format!("{:?}", error),
+    );
+    assert_eq!(
+        "Rejected {\n    account: 7,\n    token: [REDACTED],\n    reason: \"revoked\",\n}",
+        format!("{:#?}", error),
+    );
+
+    assert_eq!("Missing", format!("{:?}", TokenError::Missing));
+}
+
+#[test]
+fn test_generic() {
+    fn assert_debug<T: Debug>(_: &T) {}
+
+    let error = RequestError {
+        url: "https://example.com".to_owned(),
+        key: ApiKey,
+    };
+    assert_debug(&error);
+    assert_eq!(
+        "request to https://example.com failed with key [REDACTED]",
+        error.to_string(),
+    );
+    assert_eq!(
+        "RequestError { url: \"https://example.com\", key: [REDACTED] }",
+        format!("{:?}", error),
+    );
+}
+
+#[test]
+fn test_fields() {
+    fn fields<'a>(
+        fields: impl Iterator<Item = (&'static str, thiserror::FieldValue<'a>)>,
+    ) -> String {
+        let fields: Vec<String> = fields
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        fields.join(" ")
+    }
+
+    let error = LoginError {
+        user: "admin".to_owned(),
+        password: "hunter2".to_owned(),
+    };
+    assert_eq!("user=admin password=[REDACTED]", fields(error.fields()));
+
+    let error = TokenError::Expired("abc123".to_owned());
+    assert_eq!("0=***", fields(error.fields()));
+
+    let error = RequestError {
+        url: "https://example.com".to_owned(),
+        key: ApiKey,
+    };
+    assert_eq!(
+        "url=https://example.com key=[REDACTED]",
+        fields(error.fields()),
+    );
+}
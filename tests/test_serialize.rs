@@ -0,0 +1,79 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(serialize)]
+pub enum StoreError {
+    #[error("failed to read {path}")]
+    #[error(code = "STORE_READ")]
+    Read { path: String, source: io::Error },
+    #[error("store is locked")]
+    Locked,
+}
+
+#[derive(Error, Debug)]
+#[error("request failed")]
+#[naur(serialize)]
+pub struct RequestError {
+    #[from]
+    source: StoreError,
+}
+
+#[test]
+fn test_enum() {
+    let error = StoreError::Read {
+        path: "/var/db".to_owned(),
+        source: io::Error::new(io::ErrorKind::NotFound, "no such file"),
+    };
+    let expected = json!({
+        "variant": "Read",
+        "message": "failed to read /var/db",
+        "code": "STORE_READ",
+        "sources": ["no such file"],
+    });
+    assert_eq!(expected, serde_json::to_value(&error).unwrap());
+
+    let expected = json!({
+        "variant": "Locked",
+        "message": "store is locked",
+        "code": null,
+        "sources": [],
+    });
+    assert_eq!(expected, serde_json::to_value(StoreError::Locked).unwrap());
+}
+
+#[test]
+fn test_chain() {
+    let error = RequestError::from(StoreError::Read {
+        path: "/var/db".to_owned(),
+        source: io::Error::new(io::ErrorKind::NotFound, "no such file"),
+    });
+    let expected = json!({
+        "variant": "RequestError",
+        "message": "request failed",
+        "code": null,
+        "sources": ["failed to read /var/db", "no such file"],
+    });
+    assert_eq!(expected, serde_json::to_value(&error).unwrap());
+}
+
+#[test]
+fn test_problem() {
+    #[derive(Error, Debug)]
+    #[error("user {0} not found")]
+    #[error(status = 404, code = "USER_NOT_FOUND")]
+    pub struct NotFound(u64);
+
+    let expected = json!({
+        "type": "USER_NOT_FOUND",
+        "status": 404,
+        "detail": "user 7 not found",
+    });
+    assert_eq!(
+        expected,
+        serde_json::to_value(NotFound(7).to_problem()).unwrap()
+    );
+}
@@ -0,0 +1,46 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(single_line)]
+#[error("failed to parse {path}:\n{detail}")]
+pub struct ParseError {
+    path: &'static str,
+    detail: String,
+}
+
+#[derive(Error, Debug)]
+#[naur(single_line, alt_chain)]
+pub enum LoadError {
+    #[error("load failed: {0}")]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[test]
+fn test_collapse() {
+    let error = ParseError {
+        path: "app.toml",
+        detail: "line 3\n    unexpected `=`\r\n\n    expected a key\n".to_owned(),
+    };
+    assert_eq!(
+        "failed to parse app.toml: line 3 unexpected `=` expected a key",
+        error.to_string(),
+    );
+}
+
+#[test]
+fn test_source() {
+    let error = LoadError::from(io::Error::new(io::ErrorKind::Other, "\nno\nsuch\nfile\n"));
+    assert_eq!("no such file", error.to_string());
+
+    let error = LoadError::from(ParseError {
+        path: "app.toml",
+        detail: "missing\nkey".to_owned(),
+    });
+    assert_eq!(
+        "load failed: failed to parse app.toml: missing key; caused by: failed to parse app.toml: missing key",
+        format!("{:#}", error),
+    );
+}
@@ -0,0 +1,59 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+pub trait Backend {
+    type Error: std::error::Error + Send + Sync + 'static;
+}
+
+pub struct Disk;
+
+impl Backend for Disk {
+    type Error = io::Error;
+}
+
+type DiskError = <Disk as Backend>::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to store {key}")]
+pub struct StoreError {
+    key: String,
+    #[source(ty = io::Error)]
+    source: <Disk as Backend>::Error,
+}
+
+#[derive(Error, Debug)]
+pub enum SyncError {
+    #[error("disk failure")]
+    Disk(
+        #[from]
+        #[source(ty = io::Error)]
+        DiskError,
+    ),
+    #[error("sync of {0} failed")]
+    Sync(String, #[source(ty = io::Error)] DiskError),
+    // Seen as the same source type as `Sync`'s, rather than as a second,
+    // conflicting IntoError impl for io::Error.
+    #[error("read of {0} failed")]
+    Read(String, #[source] io::Error),
+}
+
+fn write() -> Result<(), io::Error> {
+    Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+}
+
+#[test]
+fn test_throw() {
+    let error = write().throw_store("config".to_owned()).unwrap_err();
+    assert_eq!("failed to store config", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+
+    let error = write().throw_sync("journal".to_owned()).unwrap_err();
+    assert_eq!("sync of journal failed", error.to_string());
+}
+
+#[test]
+fn test_from() {
+    let error = SyncError::from(write().unwrap_err());
+    assert_eq!("disk failure", error.to_string());
+}
@@ -0,0 +1,93 @@
+use std::error::Error as StdError;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error)]
+#[naur(stable_debug)]
+#[error("failed to open {path}")]
+pub struct OpenError {
+    path: String,
+    attempt: u32,
+    #[sensitive]
+    token: String,
+    source: io::Error,
+}
+
+#[derive(Error)]
+#[naur(stable_debug)]
+pub enum SyncError {
+    #[error("no replica for {0}")]
+    NoReplica(String, #[source] Option<io::Error>),
+    #[error("sync failed")]
+    Failed {
+        shard: u32,
+        #[source]
+        source: Box<dyn StdError + Send + Sync>,
+        #[cause]
+        cleanup: io::Error,
+    },
+    #[error("closed")]
+    Closed,
+}
+
+#[derive(Error)]
+#[naur(stable_debug)]
+#[error("wrapped")]
+pub struct Wrapped<E: StdError + 'static> {
+    source: E,
+}
+
+fn os_error() -> io::Error {
+    io::Error::from_raw_os_error(2)
+}
+
+#[test]
+fn test_struct() {
+    let error = OpenError {
+        path: "/etc/app.toml".to_owned(),
+        attempt: 2,
+        token: "hunter2".to_owned(),
+        source: os_error(),
+    };
+    let debug = format!("{:?}", error);
+    assert!(!debug.contains("os error"), "{}", debug);
+    assert!(
+        debug.starts_with(r#"OpenError { attempt: 2, path: "/etc/app.toml", source: io::Error("#)
+    );
+    assert!(debug.ends_with(r#"), token: [REDACTED] }"#), "{}", debug);
+}
+
+#[test]
+fn test_enum() {
+    let error = SyncError::NoReplica("eu".to_owned(), None);
+    assert_eq!(r#"NoReplica("eu", None)"#, format!("{:?}", error));
+
+    let error = SyncError::NoReplica(
+        "eu".to_owned(),
+        Some(io::Error::new(io::ErrorKind::Other, "unreachable")),
+    );
+    assert_eq!(
+        r#"NoReplica("eu", Some(io::Error("unreachable")))"#,
+        format!("{:?}", error),
+    );
+
+    let error = SyncError::Failed {
+        shard: 3,
+        source: "timed out".into(),
+        cleanup: io::Error::new(io::ErrorKind::Other, "lock held"),
+    };
+    assert_eq!(
+        r#"Failed { cleanup: io::Error("lock held"), shard: 3, source: Box<dyn StdError + Send + Sync>("timed out") }"#,
+        format!("{:?}", error),
+    );
+
+    assert_eq!("Closed", format!("{:?}", SyncError::Closed));
+}
+
+#[test]
+fn test_generic() {
+    let error = Wrapped {
+        source: io::Error::new(io::ErrorKind::Other, "inner"),
+    };
+    assert_eq!(r#"Wrapped { source: E("inner") }"#, format!("{:?}", error));
+}
@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PoolError {
+    #[error("pool is closed")]
+    Closed,
+    #[error("timed out waiting for a connection")]
+    Timeout,
+    #[error("braces {{ and }} are escaped")]
+    Escaped,
+    #[error("pool {0} is exhausted")]
+    Exhausted(String),
+}
+
+#[derive(Error, Debug)]
+#[error("no connection available")]
+pub struct Unavailable;
+
+#[test]
+fn test_static_messages() {
+    assert_eq!("pool is closed", PoolError::Closed.to_string());
+    assert_eq!("braces { and } are escaped", PoolError::Escaped.to_string());
+    assert_eq!(
+        "pool main is exhausted",
+        PoolError::Exhausted("main".to_owned()).to_string(),
+    );
+    assert_eq!("no connection available", Unavailable.to_string());
+}
+
+#[test]
+fn test_messages_const() {
+    assert_eq!(
+        &[
+            ("Closed", "pool is closed"),
+            ("Timeout", "timed out waiting for a connection"),
+            ("Escaped", "braces { and } are escaped"),
+        ],
+        PoolError::MESSAGES,
+    );
+}
@@ -0,0 +1,72 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("unauthorized")]
+#[error(status = 401, code = "UNAUTHORIZED")]
+pub struct Unauthorized;
+
+#[derive(Error, Debug)]
+pub enum ApiError {
+    #[error("user {0} not found")]
+    #[error(status = 404, code = "USER_NOT_FOUND")]
+    NotFound(u64),
+    #[error("invalid input: {reason}")]
+    #[error(status = 400)]
+    InvalidInput { reason: String },
+    #[error("internal error")]
+    Internal,
+}
+
+#[derive(Error, Debug)]
+#[error(status = 503)]
+pub enum Unavailable {
+    #[error("maintenance")]
+    Maintenance,
+    #[error("overloaded")]
+    #[error(status = 429, code = "SLOW_DOWN")]
+    Overloaded,
+}
+
+#[test]
+fn test_struct() {
+    assert_eq!(401, Unauthorized.status_code());
+    assert_eq!(Some("UNAUTHORIZED"), Unauthorized.code());
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(404, ApiError::NotFound(1).status_code());
+    assert_eq!(Some("USER_NOT_FOUND"), ApiError::NotFound(1).code());
+
+    let invalid = ApiError::InvalidInput {
+        reason: "empty name".to_owned(),
+    };
+    assert_eq!(400, invalid.status_code());
+    assert_eq!(None, invalid.code());
+
+    assert_eq!(500, ApiError::Internal.status_code());
+    assert_eq!(None, ApiError::Internal.code());
+}
+
+#[test]
+fn test_type_level_default() {
+    assert_eq!(503, Unavailable::Maintenance.status_code());
+    assert_eq!(None, Unavailable::Maintenance.code());
+    assert_eq!(429, Unavailable::Overloaded.status_code());
+    assert_eq!(Some("SLOW_DOWN"), Unavailable::Overloaded.code());
+}
+
+#[test]
+fn test_problem() {
+    let problem = ApiError::NotFound(7).to_problem();
+    assert_eq!("USER_NOT_FOUND", problem.problem_type);
+    assert_eq!(Some(404), problem.status);
+    assert_eq!("user 7 not found", problem.detail);
+    assert_eq!(None, problem.title);
+    assert_eq!(None, problem.instance);
+
+    let problem = ApiError::Internal.to_problem();
+    assert_eq!("about:blank", problem.problem_type);
+    assert_eq!(Some(500), problem.status);
+    assert_eq!("internal error", problem.detail);
+}
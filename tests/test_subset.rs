@@ -0,0 +1,82 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(subset(PublicError: InvalidInput, NotFound, Internal))]
+pub enum MyError {
+    #[error("invalid input: {reason}")]
+    InvalidInput { reason: String },
+    #[error("user {0} not found")]
+    NotFound(u64),
+    #[error("database connection to {host} failed")]
+    Database { host: String, source: io::Error },
+    #[error("io error")]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[naur(subset(Reduced: Internal, Timeout))]
+#[naur(subset(Minimal: Internal))]
+pub enum WithInternal {
+    #[error("timed out")]
+    Timeout(#[from] io::Error),
+    #[error("secret {0}")]
+    Secret(&'static str),
+    #[error("something went wrong")]
+    Internal,
+}
+
+#[test]
+fn test_listed_variants() {
+    let error = PublicError::from(MyError::InvalidInput {
+        reason: "empty name".to_owned(),
+    });
+    assert!(matches!(&error, PublicError::InvalidInput { reason } if reason == "empty name"));
+    assert_eq!("invalid input: empty name", error.to_string());
+
+    let error = PublicError::from(MyError::NotFound(7));
+    assert!(matches!(error, PublicError::NotFound(7)));
+    assert_eq!("user 7 not found", error.to_string());
+}
+
+#[test]
+fn test_unlisted_fold_into_internal() {
+    let error = PublicError::from(MyError::Database {
+        host: "db.internal:5432".to_owned(),
+        source: io::Error::new(io::ErrorKind::Other, "refused"),
+    });
+    assert!(matches!(error, PublicError::Internal));
+    assert_eq!("internal error", error.to_string());
+
+    let error = PublicError::from(MyError::Io(io::Error::new(io::ErrorKind::Other, "oh no")));
+    assert!(matches!(error, PublicError::Internal));
+}
+
+#[test]
+fn test_existing_internal() {
+    let error = Reduced::from(WithInternal::Secret("hunter2"));
+    assert!(matches!(error, Reduced::Internal));
+    assert_eq!("something went wrong", error.to_string());
+
+    let error = Minimal::from(WithInternal::Internal);
+    assert!(matches!(error, Minimal::Internal));
+}
+
+#[test]
+fn test_from_becomes_source() {
+    let error = Reduced::from(WithInternal::Timeout(io::Error::new(
+        io::ErrorKind::TimedOut,
+        "deadline",
+    )));
+    assert_eq!("deadline", error.source().unwrap().to_string());
+}
+
+#[test]
+fn test_parent_throw_methods() {
+    // The subset's Timeout variant holds an io::Error source too, and must not
+    // make the parent's throw method ambiguous.
+    let result: Result<(), io::Error> = Err(io::Error::new(io::ErrorKind::TimedOut, "deadline"));
+    let error = result.throw_timeout().unwrap_err();
+    assert!(matches!(error, WithInternal::Timeout(_)));
+}
@@ -0,0 +1,59 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to open {path:?}: {source}")]
+#[naur(templates)]
+pub struct OpenError {
+    path: String,
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(templates)]
+pub enum DataStoreError {
+    #[error("the data for key `{0}` is not available")]
+    Redaction(String),
+    #[error("invalid header (expected {expected:?}, found {found:?})")]
+    InvalidHeader { expected: String, found: String },
+    #[error(transparent)]
+    Other(#[from] io::Error),
+    #[error("unknown data store error")]
+    Unknown,
+}
+
+#[derive(Error, Debug)]
+#[error("{0} went wrong")]
+#[naur(templates)]
+pub enum Fallback {
+    First(String),
+    #[error("second: {0}")]
+    Second(String),
+}
+
+#[test]
+fn test_struct() {
+    assert_eq!(
+        "failed to open {path:?}: {source}",
+        OpenError::MESSAGE_TEMPLATE
+    );
+}
+
+#[test]
+fn test_enum() {
+    let expected: &[(&str, &str)] = &[
+        ("Redaction", "the data for key `{0}` is not available"),
+        (
+            "InvalidHeader",
+            "invalid header (expected {expected:?}, found {found:?})",
+        ),
+        ("Unknown", "unknown data store error"),
+    ];
+    assert_eq!(expected, DataStoreError::MESSAGE_TEMPLATES);
+}
+
+#[test]
+fn test_enum_display() {
+    let expected: &[(&str, &str)] = &[("First", "{0} went wrong"), ("Second", "second: {0}")];
+    assert_eq!(expected, Fallback::MESSAGE_TEMPLATES);
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "test-helpers")]
+
+use std::io;
+use std::panic;
+use thiserror::{assert_variant, Error};
+
+#[derive(Error, Debug)]
+pub enum UploadError {
+    #[error("invalid name {0:?}")]
+    InvalidName(String),
+    #[error("file {name} is {size} bytes, over the limit")]
+    TooLarge { name: String, size: u64 },
+    #[error("storage failed")]
+    Storage(#[from] io::Error),
+    #[error("cancelled")]
+    Cancelled,
+}
+
+fn too_large() -> UploadError {
+    UploadError::TooLarge {
+        name: "video.mp4".to_owned(),
+        size: 1 << 32,
+    }
+}
+
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(f).unwrap_err();
+    payload.downcast::<String>().map(|s| *s).unwrap()
+}
+
+#[test]
+fn test_assert_variant() {
+    let error = too_large();
+    assert_variant!(error, UploadError::TooLarge { .. });
+    assert_variant!(error, UploadError::TooLarge { name, size } if name == "video.mp4" && *size > 1024);
+    assert_variant!(
+        UploadError::Cancelled,
+        UploadError::InvalidName(_) | UploadError::Cancelled,
+    );
+}
+
+#[test]
+fn test_assert_variant_fails() {
+    let message = panic_message(|| assert_variant!(too_large(), UploadError::Cancelled));
+    assert_eq!(
+        "assertion failed: error matches `UploadError::Cancelled`\n  error: TooLarge { name: \"video.mp4\", size: 4294967296 }",
+        message,
+    );
+
+    let message = panic_message(|| {
+        assert_variant!(too_large(), UploadError::TooLarge { size, .. } if *size == 0);
+    });
+    assert!(message.starts_with("assertion failed: error matches `UploadError::TooLarge"));
+    assert!(message.ends_with("error: TooLarge { name: \"video.mp4\", size: 4294967296 }"));
+}
+
+#[test]
+fn test_expect_methods() {
+    let error = too_large();
+    let (name, size) = error.expect_too_large();
+    assert_eq!("video.mp4", name);
+    assert_eq!(1 << 32, *size);
+
+    let error = UploadError::InvalidName("..".to_owned());
+    assert_eq!("..", error.expect_invalid_name());
+
+    let error = UploadError::from(io::Error::new(io::ErrorKind::Other, "disk full"));
+    assert_eq!("disk full", error.expect_storage().to_string());
+
+    UploadError::Cancelled.expect_cancelled();
+}
+
+#[test]
+fn test_expect_wrong_variant() {
+    let message = panic_message(|| {
+        UploadError::Cancelled.expect_too_large();
+    });
+    assert_eq!("expected `UploadError::TooLarge`, found Cancelled", message);
+}
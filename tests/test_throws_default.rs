@@ -0,0 +1,51 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SyncError {
+    #[error("read failed after {attempts} attempts")]
+    Read {
+        #[from]
+        source: io::Error,
+        #[throws(default)]
+        attempts: u32,
+        #[throws(default)]
+        path: Option<String>,
+    },
+    #[error("conflict")]
+    Conflict,
+}
+
+#[derive(Error, Debug)]
+#[error("fetch failed ({1:?})")]
+pub struct FetchError(#[from] io::Error, #[throws(default)] Vec<String>);
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "oh no!")
+}
+
+#[test]
+fn test_from_fills_defaults() {
+    let error = SyncError::from(io_error());
+    assert_eq!("read failed after 0 attempts", error.to_string());
+    assert!(matches!(error, SyncError::Read { path: None, .. }));
+
+    let error = FetchError::from(io_error());
+    assert_eq!("fetch failed ([])", error.to_string());
+}
+
+#[test]
+fn test_throw_fills_defaults() {
+    let result: Result<(), io::Error> = Err(io_error());
+    let error = result.throw_read().unwrap_err();
+    assert_eq!("read failed after 0 attempts", error.to_string());
+}
+
+#[test]
+fn test_fields_are_kept() {
+    let mut error = SyncError::from(io_error());
+    if let SyncError::Read { attempts, .. } = &mut error {
+        *attempts = 3;
+    }
+    assert_eq!("read failed after 3 attempts", error.to_string());
+}
@@ -0,0 +1,83 @@
+use std::io;
+use thiserror::Error;
+
+fn fail() -> Result<(), io::Error> {
+    Err(io::Error::new(io::ErrorKind::Other, "oh no!"))
+}
+
+#[derive(Error, Debug)]
+#[error("read failed")]
+#[throws(feature = "color")]
+pub struct ReadError {
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[throws(feature = "color", context_struct)]
+pub enum StoreError {
+    #[error("cannot open {path}")]
+    Open { path: String, source: io::Error },
+    #[error("cannot sync")]
+    #[throws(feature = "serde")]
+    Sync(#[source] io::Error),
+}
+
+// Stands in for the throw methods when their feature is off. Were the
+// generated traits compiled in as well, calls to these would be ambiguous.
+#[cfg(not(feature = "color"))]
+trait Fallback: Sized {
+    fn throw_read(self) -> &'static str {
+        "fallback"
+    }
+
+    fn throw_open(self, _ctx: (String,)) -> &'static str {
+        "fallback"
+    }
+}
+
+#[cfg(not(feature = "color"))]
+impl Fallback for Result<(), io::Error> {}
+
+#[cfg(not(feature = "serde"))]
+trait SyncFallback: Sized {
+    fn throw_sync(self) -> &'static str {
+        "fallback"
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+impl SyncFallback for Result<(), io::Error> {}
+
+#[cfg(feature = "color")]
+#[test]
+fn test_enabled() {
+    let error = fail().throw_read().unwrap_err();
+    assert_eq!("read failed", error.to_string());
+
+    let error = fail()
+        .throw_open(StoreErrorOpenContext {
+            path: "/etc/app.toml".to_owned(),
+        })
+        .unwrap_err();
+    assert_eq!("cannot open /etc/app.toml", error.to_string());
+}
+
+#[cfg(not(feature = "color"))]
+#[test]
+fn test_disabled() {
+    assert_eq!("fallback", fail().throw_read());
+    assert_eq!("fallback", fail().throw_open(("/etc/app.toml".to_owned(),)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_variant_override() {
+    let error = fail().throw_sync().unwrap_err();
+    assert_eq!("cannot sync", error.to_string());
+}
+
+#[cfg(not(feature = "serde"))]
+#[test]
+fn test_variant_override() {
+    assert_eq!("fallback", fail().throw_sync());
+}
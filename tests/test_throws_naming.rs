@@ -0,0 +1,107 @@
+use std::io;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+fn fail() -> Result<(), io::Error> {
+    Err(io::Error::new(io::ErrorKind::Other, "oh no!"))
+}
+
+#[derive(Error, Debug)]
+#[error("i/o failure")]
+pub struct IOError {
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(throws_naming(acronyms))]
+#[error("request failed")]
+pub struct HTTPRequestError {
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(throws_naming(keep_suffix))]
+#[error("lookup failed")]
+pub struct LookupError {
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[naur(throws_naming(full, acronyms))]
+pub enum StoreError {
+    #[error("i/o failure")]
+    IOError(#[source] io::Error),
+    #[error("error")]
+    Error(#[source] ParseIntError),
+}
+
+#[derive(Error, Debug)]
+#[naur(throws_naming(full, keep_suffix))]
+pub enum CacheError {
+    #[error("i/o failure")]
+    IoError(#[source] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[naur(throws_prefix = "or_")]
+pub enum ConfigError {
+    #[error("invalid {0}")]
+    InvalidIo(String, #[source] io::Error),
+    #[error("bad number")]
+    #[throws(pipe)]
+    Number(#[source] ParseIntError),
+}
+
+#[derive(Error, Debug)]
+#[naur(throws_prefix = "raise_", throws_naming(keep_suffix))]
+#[error("sync failed")]
+pub struct SyncError {
+    source: io::Error,
+}
+
+#[test]
+fn test_default() {
+    let error = fail().throw_i_o().unwrap_err();
+    assert_eq!("i/o failure", error.to_string());
+}
+
+#[test]
+fn test_acronyms() {
+    let error = fail().throw_http_request().unwrap_err();
+    assert_eq!("request failed", error.to_string());
+}
+
+#[test]
+fn test_keep_suffix() {
+    let error = fail().throw_lookup_error().unwrap_err();
+    assert_eq!("lookup failed", error.to_string());
+}
+
+#[test]
+fn test_full() {
+    let error = fail().throw_store_io().unwrap_err();
+    assert_eq!("i/o failure", error.to_string());
+
+    let error = "x".parse::<i32>().throw_store_error().unwrap_err();
+    assert_eq!("error", error.to_string());
+
+    let error = fail().throw_cache_error_io_error().unwrap_err();
+    assert_eq!("i/o failure", error.to_string());
+}
+
+#[test]
+fn test_prefix() {
+    let error = fail().or_invalid_io("path".to_owned()).unwrap_err();
+    assert_eq!("invalid path", error.to_string());
+
+    let error = fail().or_invalid_io_with(|| "path".to_owned()).unwrap_err();
+    assert_eq!("invalid path", error.to_string());
+
+    let error = thiserror::Pipeline::new("x")
+        .pipe_or_number(str::parse::<i32>)
+        .unwrap_err();
+    assert_eq!("bad number", error.to_string());
+
+    let error = fail().raise_sync_error().unwrap_err();
+    assert_eq!("sync failed", error.to_string());
+}
@@ -0,0 +1,69 @@
+use std::io;
+use thiserror::Error;
+
+mod errors {
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    #[throws(vis = "pub(crate)")]
+    #[error("failed to read {path}")]
+    pub struct ReadError {
+        pub path: String,
+        pub source: std::io::Error,
+    }
+}
+
+#[derive(Error, Debug)]
+#[throws(vis = "pub")]
+pub enum StoreError {
+    #[error("disconnected")]
+    Disconnected(#[source] io::Error),
+}
+
+mod caller {
+    use super::errors::ReadErrorThrows;
+    use super::StoreErrorDisconnectedThrows;
+    use std::io;
+
+    pub fn read() -> Result<(), super::errors::ReadError> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "oh no!"))
+            .throw_read("/etc/app.toml".to_owned())
+    }
+
+    pub fn connect() -> Result<(), super::StoreError> {
+        Err(io::Error::new(io::ErrorKind::Other, "oh no!")).throw_disconnected()
+    }
+}
+
+#[test]
+fn test_vis() {
+    let error = caller::read().unwrap_err();
+    assert_eq!("failed to read /etc/app.toml", error.to_string());
+
+    let error = caller::connect().unwrap_err();
+    assert_eq!("disconnected", error.to_string());
+}
+
+mod context {
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    #[throws(vis = "pub(crate)", context_struct)]
+    #[error("failed to parse {key}")]
+    pub struct ParseError {
+        pub key: String,
+        pub source: std::num::ParseIntError,
+    }
+}
+
+#[test]
+fn test_context_struct_vis() {
+    use context::{ParseErrorContext, ParseErrorThrows};
+
+    let key = "port".to_owned();
+    let error = "x"
+        .parse::<u16>()
+        .throw_parse(ParseErrorContext { key })
+        .unwrap_err();
+    assert_eq!("failed to parse port", error.to_string());
+}
@@ -0,0 +1,71 @@
+use std::io;
+use std::time::{Instant, SystemTime};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to read config")]
+pub struct ConfigError {
+    #[from]
+    source: io::Error,
+    #[timestamp]
+    at: SystemTime,
+}
+
+#[derive(Error, Debug)]
+#[naur(constructors)]
+pub enum RequestError {
+    #[error("request {id} timed out")]
+    Timeout {
+        id: u64,
+        #[source]
+        source: io::Error,
+        #[timestamp]
+        started: Instant,
+    },
+    #[error("request failed")]
+    Failed(#[from] io::Error, #[timestamp] Option<SystemTime>),
+    #[error("request {0} was cancelled")]
+    Cancelled(u64, #[timestamp] Instant),
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::TimedOut, "timed out")
+}
+
+#[test]
+fn test_from() {
+    let before = SystemTime::now();
+    let error = ConfigError::from(io_error());
+    assert!(error.at >= before && error.at <= SystemTime::now());
+
+    let error = RequestError::from(io_error());
+    match error {
+        RequestError::Failed(_, at) => assert!(at.unwrap() >= before),
+        _ => panic!("expected RequestError::Failed"),
+    }
+}
+
+#[test]
+fn test_throw() {
+    let before = Instant::now();
+    let result: Result<(), io::Error> = Err(io_error());
+    let error = result.throw_timeout(7).unwrap_err();
+    match error {
+        RequestError::Timeout { id, started, .. } => {
+            assert_eq!(7, id);
+            assert!(started >= before && started <= Instant::now());
+        }
+        _ => panic!("expected RequestError::Timeout"),
+    }
+}
+
+#[test]
+fn test_constructor() {
+    let before = Instant::now();
+    let error = RequestError::cancelled(3);
+    assert_eq!("request 3 was cancelled", error.to_string());
+    match error {
+        RequestError::Cancelled(_, at) => assert!(at >= before),
+        _ => panic!("expected RequestError::Cancelled"),
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "tonic")]
+
+use thiserror::Error;
+use tonic::{Code, Status};
+
+#[derive(Error, Debug)]
+pub enum ServiceError {
+    #[error("user {0} not found")]
+    #[error(grpc = NotFound)]
+    NotFound(u64),
+    #[error("invalid argument: {0}")]
+    #[error(grpc = InvalidArgument)]
+    InvalidArgument(String),
+    #[error("something broke")]
+    Broken,
+}
+
+#[derive(Error, Debug)]
+#[error("try again later")]
+#[error(grpc = Unavailable)]
+pub struct Overloaded;
+
+#[derive(Error, Debug)]
+#[error(grpc = PermissionDenied)]
+pub enum AccessError<T: std::fmt::Display> {
+    #[error("cannot access {0}")]
+    Denied(T),
+    #[error("session expired")]
+    #[error(grpc = Unauthenticated)]
+    Expired,
+}
+
+#[test]
+fn test_enum() {
+    let status = Status::from(ServiceError::NotFound(3));
+    assert_eq!(Code::NotFound, status.code());
+    assert_eq!("user 3 not found", status.message());
+
+    let status = Status::from(ServiceError::InvalidArgument("name".to_owned()));
+    assert_eq!(Code::InvalidArgument, status.code());
+    assert_eq!("invalid argument: name", status.message());
+
+    let status = Status::from(ServiceError::Broken);
+    assert_eq!(Code::Internal, status.code());
+}
+
+#[test]
+fn test_struct() {
+    let status = Status::from(Overloaded);
+    assert_eq!(Code::Unavailable, status.code());
+    assert_eq!("try again later", status.message());
+}
+
+#[test]
+fn test_generic() {
+    let status = Status::from(AccessError::Denied("/etc/shadow"));
+    assert_eq!(Code::PermissionDenied, status.code());
+    assert_eq!("cannot access /etc/shadow", status.message());
+
+    let status = Status::from(AccessError::<&str>::Expired);
+    assert_eq!(Code::Unauthenticated, status.code());
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+use std::io;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Error, Debug)]
+#[error("login failed for {user} with password {password}")]
+#[throws(trace)]
+pub struct LoginError {
+    user: String,
+    #[sensitive]
+    password: String,
+    source: io::Error,
+}
+
+// Records the fields of every event as `name=value`.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl Visit for Recorder {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        let entry = format!("{}={:?}", field.name(), value);
+        self.0.lock().unwrap().push(entry);
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event) {
+        event.record(&mut self.clone());
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_sensitive() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let result = Err::<(), _>(io::Error::new(io::ErrorKind::PermissionDenied, "denied"))
+            .throw_login("admin".to_owned(), "hunter2".to_owned());
+        assert!(result.is_err());
+    });
+    let fields = recorder.0.lock().unwrap();
+    assert!(fields.contains(&"user=admin".to_owned()), "{:?}", fields);
+    assert!(
+        fields.contains(&"password=[REDACTED]".to_owned()),
+        "{:?}",
+        fields
+    );
+    assert!(
+        !fields.iter().any(|field| field.contains("hunter2")),
+        "{:?}",
+        fields
+    );
+}
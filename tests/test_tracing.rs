@@ -0,0 +1,129 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+use std::io;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+#[derive(Error, Debug)]
+#[throws(trace)]
+pub enum StoreError {
+    #[error("failed to read {path}")]
+    #[error(code = "STORE_READ")]
+    Read { path: String, source: io::Error },
+    #[error("store is locked")]
+    #[throws(trace = "warn")]
+    Locked(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("parse failed on line {line}")]
+#[throws(trace = "info")]
+pub struct ParseError {
+    line: usize,
+    source: std::num::ParseIntError,
+}
+
+type Recorded = Vec<(Level, Vec<(String, String)>)>;
+
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Recorded>>,
+}
+
+struct Fields<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push((field.name().to_owned(), format!("{:?}", value)));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event) {
+        let mut fields = Vec::new();
+        event.record(&mut Fields(&mut fields));
+        let level = *event.metadata().level();
+        self.events.lock().unwrap().push((level, fields));
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+fn record(f: impl FnOnce()) -> Recorded {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), f);
+    let events = recorder.events.lock().unwrap().clone();
+    events
+}
+
+fn pairs(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+        .collect()
+}
+
+#[test]
+fn test_throw_method() {
+    let events = record(|| {
+        let result = Err::<(), _>(io::Error::new(io::ErrorKind::NotFound, "gone"))
+            .throw_read("/var/db".to_owned());
+        assert!(result.is_err());
+    });
+    let expected = pairs(&[
+        ("message", "failed to read /var/db"),
+        ("variant", "\"Read\""),
+        ("code", "\"STORE_READ\""),
+        ("path", "/var/db"),
+    ]);
+    assert_eq!(vec![(Level::ERROR, expected)], events);
+}
+
+#[test]
+fn test_from() {
+    let events = record(|| {
+        let _ = StoreError::from(io::Error::new(io::ErrorKind::Other, "busy"));
+    });
+    let expected = pairs(&[("message", "store is locked"), ("variant", "\"Locked\"")]);
+    assert_eq!(vec![(Level::WARN, expected)], events);
+}
+
+#[test]
+fn test_struct() {
+    let events = record(|| {
+        let _ = "x".parse::<i32>().throw_parse(7);
+    });
+    let expected = pairs(&[
+        ("message", "parse failed on line 7"),
+        ("variant", "\"ParseError\""),
+        ("line", "7"),
+    ]);
+    assert_eq!(vec![(Level::INFO, expected)], events);
+}
+
+#[test]
+fn test_ok_is_silent() {
+    let events = record(|| {
+        let _ = Ok::<i32, io::Error>(1).throw_read("/var/db".to_owned());
+    });
+    assert!(events.is_empty());
+}
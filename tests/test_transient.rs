@@ -0,0 +1,99 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("connection reset")]
+#[error(transient)]
+pub struct ConnectionReset;
+
+#[derive(Error, Debug)]
+pub enum FetchError {
+    #[error("timed out")]
+    #[error(transient)]
+    Timeout,
+    #[error("rate limited")]
+    #[error(transient)]
+    RateLimited { retry_after: u64 },
+    #[error("not found")]
+    #[error(permanent)]
+    NotFound,
+    #[error("malformed response")]
+    Malformed,
+    #[error(transparent)]
+    #[error(transient = source)]
+    Connection(#[from] ConnectionReset),
+    #[error("request failed")]
+    #[error(transient = source)]
+    Request {
+        source: RequestError,
+        attempt: usize,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error(transient)]
+pub enum RequestError {
+    #[error("server busy")]
+    Busy,
+    #[error("bad request")]
+    #[error(permanent)]
+    BadRequest,
+}
+
+#[derive(Error, Debug)]
+#[error("fetch failed")]
+#[error(transient = source)]
+pub struct Retried {
+    #[source]
+    last: FetchError,
+}
+
+#[derive(Error, Debug)]
+#[error(transparent)]
+#[error(permanent)]
+pub struct Fatal(io::Error);
+
+#[test]
+fn test_struct() {
+    assert!(ConnectionReset.is_transient());
+    assert!(!Fatal(io::Error::new(io::ErrorKind::Other, "oh no!")).is_transient());
+}
+
+#[test]
+fn test_enum() {
+    assert!(FetchError::Timeout.is_transient());
+    assert!(FetchError::RateLimited { retry_after: 1 }.is_transient());
+    assert!(!FetchError::NotFound.is_transient());
+    assert!(!FetchError::Malformed.is_transient());
+}
+
+#[test]
+fn test_enum_default() {
+    assert!(RequestError::Busy.is_transient());
+    assert!(!RequestError::BadRequest.is_transient());
+}
+
+#[test]
+fn test_source() {
+    assert!(FetchError::from(ConnectionReset).is_transient());
+
+    let error = FetchError::Request {
+        source: RequestError::Busy,
+        attempt: 1,
+    };
+    assert!(error.is_transient());
+    let error = FetchError::Request {
+        source: RequestError::BadRequest,
+        attempt: 1,
+    };
+    assert!(!error.is_transient());
+
+    let error = Retried {
+        last: FetchError::Timeout,
+    };
+    assert!(error.is_transient());
+    let error = Retried {
+        last: FetchError::NotFound,
+    };
+    assert!(!error.is_transient());
+}
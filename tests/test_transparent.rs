@@ -76,3 +76,41 @@ fn test_non_static() {
     assert_eq!("unexpected token: \"error\"", error.to_string());
     assert!(error.source().is_none());
 }
+
+#[test]
+fn test_context_fields() {
+    #[derive(Error, Debug)]
+    #[error(transparent)]
+    struct RequestError {
+        source: anyhow::Error,
+        location: &'static str,
+        request_id: u64,
+    }
+
+    let error = Err::<(), _>(anyhow!("inner").context("outer"))
+        .throw_request("handler.rs", 7)
+        .unwrap_err();
+    assert_eq!("outer", error.to_string());
+    assert_eq!("inner", error.source().unwrap().to_string());
+    assert_eq!("handler.rs", error.location);
+    assert_eq!(7, error.request_id);
+}
+
+#[test]
+fn test_context_fields_enum() {
+    #[derive(Error, Debug)]
+    enum Error {
+        #[error("this failed")]
+        This,
+        #[error(transparent)]
+        Other(u64, #[source] anyhow::Error),
+    }
+
+    let error = Err::<(), _>(anyhow!("inner").context("outer"))
+        .throw_other(7)
+        .unwrap_err();
+    assert_eq!("outer", error.to_string());
+    assert_eq!("inner", error.source().unwrap().to_string());
+    assert!(matches!(error, Error::Other(7, _)));
+    assert_eq!("this failed", Error::This.to_string());
+}
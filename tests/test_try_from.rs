@@ -0,0 +1,55 @@
+use std::io;
+use thiserror::Error;
+
+fn is_error_status(status: &u16) -> bool {
+    (400..600).contains(status)
+}
+
+fn is_not_found(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::NotFound
+}
+
+#[derive(Error, Debug)]
+pub enum FetchError {
+    #[error("server responded with {0}")]
+    Status(#[naur(try_from(validate = is_error_status))] u16),
+    #[error("missing resource")]
+    Missing {
+        #[naur(try_from(validate = is_not_found))]
+        #[source]
+        error: io::Error,
+    },
+}
+
+pub mod rules {
+    pub fn is_printable(ch: &char) -> bool {
+        !ch.is_control()
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("unexpected {0:?}")]
+pub struct Unexpected(#[naur(try_from(validate = rules::is_printable))] char);
+
+#[test]
+fn test_accepted() {
+    let error = FetchError::try_from(503).unwrap();
+    assert_eq!("server responded with 503", error.to_string());
+
+    let error = FetchError::try_from(io::Error::from(io::ErrorKind::NotFound)).unwrap();
+    assert!(matches!(error, FetchError::Missing { .. }));
+    assert!(std::error::Error::source(&error).is_some());
+
+    let error = Unexpected::try_from('$').unwrap();
+    assert_eq!("unexpected '$'", error.to_string());
+}
+
+#[test]
+fn test_rejected() {
+    assert_eq!(200, FetchError::try_from(200).unwrap_err());
+
+    let error = FetchError::try_from(io::Error::from(io::ErrorKind::TimedOut)).unwrap_err();
+    assert_eq!(io::ErrorKind::TimedOut, error.kind());
+
+    assert_eq!('\0', Unexpected::try_from('\0').unwrap_err());
+}
@@ -0,0 +1,61 @@
+use std::error::Error as _;
+use std::fmt::Debug;
+use std::io;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[throws(tuple_from)]
+pub enum ConfigError {
+    #[error("invalid {msg}: {value}")]
+    InvalidMsg {
+        msg: String,
+        value: i32,
+        source: io::Error,
+    },
+    #[error("invalid line {1}")]
+    InvalidLine(#[source] ParseIntError, usize),
+    #[error("parse error")]
+    Parse(#[from] ParseIntError),
+}
+
+#[derive(Error, Debug)]
+#[error("failed to read {path:?}")]
+#[throws(tuple_from)]
+pub struct GenericReadError<P: Debug> {
+    path: P,
+    source: io::Error,
+}
+
+fn convert<T, E>(tuple: T) -> E
+where
+    T: Into<E>,
+{
+    tuple.into()
+}
+
+#[test]
+fn test_named() {
+    let io_error = io::Error::new(io::ErrorKind::InvalidData, "oops");
+    let error: ConfigError = (io_error, "port".to_owned(), 7).into();
+    assert_eq!("invalid port: 7", error.to_string());
+    assert!(error.source().unwrap().is::<io::Error>());
+}
+
+#[test]
+fn test_tuple() {
+    let parse_error = "x".parse::<i32>().unwrap_err();
+    let error: ConfigError = convert((parse_error, 4));
+    assert_eq!("invalid line 4", error.to_string());
+
+    // A variant without other fields keeps its usual conversions only.
+    let error = ConfigError::from("x".parse::<i32>().unwrap_err());
+    assert_eq!("parse error", error.to_string());
+}
+
+#[test]
+fn test_generic() {
+    let io_error = io::Error::new(io::ErrorKind::NotFound, "missing");
+    let error: GenericReadError<&str> = convert((io_error, "/etc/app.toml"));
+    assert_eq!("failed to read \"/etc/app.toml\"", error.to_string());
+}
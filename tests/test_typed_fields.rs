@@ -0,0 +1,78 @@
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(typed_fields)]
+pub enum StoreError {
+    #[error("failed to read {path:?}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("failed to write {path:?}: {msg}")]
+    Write { path: PathBuf, msg: String },
+    #[error("store is locked")]
+    Locked,
+    #[error("unknown")]
+    Unknown(String),
+}
+
+#[derive(Error, Debug)]
+#[error("{msg}")]
+#[naur(typed_fields = parse_fields)]
+pub struct ParseError<T> {
+    msg: String,
+    value: T,
+}
+
+#[test]
+fn test_enum() {
+    let error = StoreError::Write {
+        path: PathBuf::from("/data"),
+        msg: "disk full".to_owned(),
+    };
+    assert_eq!(
+        Some(&PathBuf::from("/data")),
+        error.get::<store_error_fields::Path>(),
+    );
+    assert_eq!(
+        Some("disk full"),
+        error.get::<store_error_fields::Msg>().map(String::as_str),
+    );
+
+    let error = StoreError::Read {
+        path: PathBuf::from("/data"),
+        source: io::Error::new(io::ErrorKind::Other, "oh no"),
+    };
+    assert!(error.get::<store_error_fields::Path>().is_some());
+    assert!(error.get::<store_error_fields::Msg>().is_none());
+    assert!(error.get::<store_error_fields::Source>().is_some());
+
+    assert!(StoreError::Locked
+        .get::<store_error_fields::Path>()
+        .is_none());
+}
+
+#[test]
+fn test_generic_struct() {
+    let error = ParseError {
+        msg: "out of range".to_owned(),
+        value: 300u32,
+    };
+    assert_eq!(Some(&300), error.get::<parse_fields::Value>());
+    assert_eq!(
+        Some("out of range"),
+        error.get::<parse_fields::Msg>().map(String::as_str),
+    );
+}
+
+#[test]
+fn test_private() {
+    #[derive(Error, Debug)]
+    #[naur(typed_fields)]
+    enum Private {
+        #[error("status {status}")]
+        Status { status: u16 },
+    }
+
+    let error = Private::Status { status: 503 };
+    assert_eq!(Some(&503), error.get::<private_fields::Status>());
+}
@@ -0,0 +1,74 @@
+use std::io;
+use thiserror::Error;
+
+mod app {
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum AppError {
+        #[error("database error")]
+        Database(#[source] super::DbError),
+        #[error("cache error")]
+        Cache(#[source] super::CacheError),
+        #[error("i/o error")]
+        Io(#[source] std::io::Error),
+        #[error("request timed out")]
+        Timeout(#[source] super::Timeout),
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("query failed: {query}")]
+#[error(up(app::AppError::Database))]
+pub struct DbError {
+    query: String,
+}
+
+#[derive(Error, Debug)]
+#[error(up(app::AppError::Cache))]
+pub enum CacheError {
+    #[error("cache miss for {0}")]
+    Miss(String),
+    #[error("cache i/o")]
+    #[error(up(app::AppError::Io))]
+    Io(#[source] io::Error),
+    #[error("cache timed out")]
+    #[error(up(app::AppError::Timeout))]
+    Timeout { timeout: Timeout },
+}
+
+#[derive(Error, Debug)]
+#[error("timed out after {0}ms")]
+pub struct Timeout(u64);
+
+fn query() -> Result<(), DbError> {
+    Err(DbError {
+        query: "select 1".to_owned(),
+    })
+}
+
+fn run() -> Result<(), app::AppError> {
+    query()?;
+    Ok(())
+}
+
+#[test]
+fn test_struct() {
+    let error = run().unwrap_err();
+    assert!(matches!(&error, app::AppError::Database(db) if db.query == "select 1"));
+}
+
+#[test]
+fn test_enum() {
+    let error = app::AppError::from(CacheError::Miss("users".to_owned()));
+    assert!(matches!(error, app::AppError::Cache(CacheError::Miss(_))));
+
+    let io = io::Error::new(io::ErrorKind::Other, "disk full");
+    let error = app::AppError::from(CacheError::Io(io));
+    assert!(matches!(&error, app::AppError::Io(io) if io.to_string() == "disk full"));
+
+    let error = app::AppError::from(CacheError::Timeout {
+        timeout: Timeout(30),
+    });
+    assert!(matches!(error, app::AppError::Timeout(Timeout(30))));
+}
@@ -32,51 +32,8 @@ enum EnumError {
     },
     #[error("another error: {1}")]
     AnotherError(#[source] ParseIntError, String),
-}
-
-trait EnumErrorInvalidMsgThrows<__RETURN> {
-    fn throw_invalid_msg(self, msg: String, value: i32) -> Result<__RETURN, EnumError>;
-    fn throw_invalid_msg_with<F: FnOnce() -> (String, i32)>(
-        self,
-        f: F,
-    ) -> Result<__RETURN, EnumError>;
-}
-impl<__RETURN> EnumErrorInvalidMsgThrows<__RETURN> for Result<__RETURN, io::Error> {
-    fn throw_invalid_msg(self, msg: String, value: i32) -> Result<__RETURN, EnumError> {
-        self.map_err(|e| EnumError::InvalidMsg {
-            source: e,
-            msg,
-            value,
-        })
-    }
-    fn throw_invalid_msg_with<F: FnOnce() -> (String, i32)>(
-        self,
-        f: F,
-    ) -> Result<__RETURN, EnumError> {
-        self.map_err(|e| {
-            let (msg, value) = f();
-            EnumError::InvalidMsg {
-                source: e,
-                msg,
-                value,
-            }
-        })
-    }
-}
-trait EnumErrorAnotherErrorThrows<__RETURN> {
-    fn throw_another(self, _0: String) -> Result<__RETURN, EnumError>;
-    fn throw_another_with<F: FnOnce() -> (String)>(self, f: F) -> Result<__RETURN, EnumError>;
-}
-impl<__RETURN> EnumErrorAnotherErrorThrows<__RETURN> for Result<__RETURN, ParseIntError> {
-    fn throw_another(self, _0: String) -> Result<__RETURN, EnumError> {
-        self.map_err(|e| EnumError::AnotherError(e, _0))
-    }
-    fn throw_another_with<F: FnOnce() -> (String)>(self, f: F) -> Result<__RETURN, EnumError> {
-        self.map_err(|e| {
-            let (_0) = f();
-            EnumError::AnotherError(e, _0)
-        })
-    }
+    #[error("only source")]
+    OnlySource(#[source] TryFromIntError),
 }
 
 #[test]
@@ -128,3 +85,28 @@ fn test_generic_enum() -> Result<(), GenericEnumError<i32, u32>> {
 
     Ok(())
 }
+
+#[derive(Error, Debug)]
+enum RawError {
+    #[error("invalid {type} in {r#where}")]
+    Invalid {
+        r#type: String,
+        r#where: &'static str,
+        source: io::Error,
+    },
+}
+
+#[test]
+fn test_raw_params() {
+    let result = Err::<(), _>(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    let error = result
+        .throw_invalid("token".to_owned(), "header")
+        .unwrap_err();
+    assert_eq!("invalid token in header", error.to_string());
+
+    let result = Err::<(), _>(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    let error = result
+        .throw_invalid_with(|| ("token".to_owned(), "body"))
+        .unwrap_err();
+    assert_eq!("invalid token in body", error.to_string());
+}
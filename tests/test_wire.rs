@@ -0,0 +1,66 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(serialize, wire)]
+pub enum StoreError {
+    #[error("failed to read {path}")]
+    #[error(code = "STORE_READ")]
+    Read { path: String, source: io::Error },
+    #[error("store is locked")]
+    Locked,
+}
+
+#[derive(Error, Debug)]
+#[error("invalid {0}")]
+#[naur(wire)]
+pub struct Generic<T>(T);
+
+#[test]
+fn test_from_error() {
+    let error = StoreError::Read {
+        path: "/data".to_owned(),
+        source: io::Error::new(io::ErrorKind::Other, "disk on fire"),
+    };
+    let wire = StoreErrorWire::from(&error);
+    assert_eq!("Read", wire.variant);
+    assert_eq!("failed to read /data", wire.message);
+    assert_eq!(Some("STORE_READ".to_owned()), wire.code);
+    assert_eq!(vec!["disk on fire".to_owned()], wire.sources);
+    assert_eq!("failed to read /data", wire.to_string());
+
+    let wire = StoreErrorWire::from(&StoreError::Locked);
+    assert_eq!(None, wire.code);
+    assert!(wire.sources.is_empty());
+
+    let wire = GenericWire::from(&Generic(1));
+    assert_eq!("Generic", wire.variant);
+    assert_eq!("invalid 1", wire.message);
+}
+
+#[test]
+fn test_round_trip() {
+    let error = StoreError::Read {
+        path: "/data".to_owned(),
+        source: io::Error::new(io::ErrorKind::Other, "disk on fire"),
+    };
+    let expected = json!({
+        "variant": "Read",
+        "message": "failed to read /data",
+        "code": "STORE_READ",
+        "sources": ["disk on fire"],
+    });
+
+    // The wire type serializes the same as the error it was copied from.
+    assert_eq!(expected, serde_json::to_value(&error).unwrap());
+    let wire = StoreErrorWire::from(&error);
+    assert_eq!(expected, serde_json::to_value(&wire).unwrap());
+
+    let received: StoreErrorWire = serde_json::from_value(expected).unwrap();
+    assert_eq!(wire, received);
+    let source: &dyn std::error::Error = &received;
+    assert_eq!("failed to read /data", source.to_string());
+}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed")]
+#[error(alt = "failed in detail")]
+#[naur(alt_chain)]
+pub struct Error;
+
+fn main() {}
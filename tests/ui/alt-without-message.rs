@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    #[error(alt = "failed in detail")]
+    Other(std::io::Error),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(arbitrary)]
+pub enum Error<T> {
+    #[error("invalid value {0}")]
+    Invalid(T),
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use std::error::Error as StdError;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct Error {
+    #[source]
+    #[error(arc_source)]
+    source: Arc<dyn StdError + Send + Sync>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::rc::Rc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(assert = "Send + Sync")]
+#[error("failed to read {path}")]
+pub struct ReadError {
+    path: Rc<str>,
+}
+
+fn main() {}
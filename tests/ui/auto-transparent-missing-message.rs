@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(auto_transparent)]
+pub enum Error {
+    Io(#[from] std::io::Error),
+    Timeout(u64),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(auto_transparent)]
+#[error("...")]
+pub struct Error(#[from] std::io::Error);
+
+fn main() {}
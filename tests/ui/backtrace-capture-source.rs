@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+fn capture() -> std::backtrace::Backtrace {
+    std::backtrace::Backtrace::force_capture()
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct Error {
+    #[from]
+    #[backtrace(capture = capture)]
+    source: std::io::Error,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("timed out")]
+pub struct Timeout;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(boxed)]
+    Timeout(Timeout),
+}
+
+fn main() {}
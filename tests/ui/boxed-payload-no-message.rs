@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RequestError {
+    #[error(boxed { url: String, source: std::io::Error })]
+    Timeout(Box<TimeoutDetails>),
+}
+
+fn main() {}
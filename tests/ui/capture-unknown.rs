@@ -0,0 +1,13 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed")]
+pub struct Error {
+    #[from]
+    source: io::Error,
+    #[capture(hostname)]
+    host: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use std::error::Error as StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(catch_all)]
+    Other(Box<dyn StdError + Send + Sync>),
+    #[error(catch_all)]
+    Unknown(Box<dyn StdError>),
+}
+
+fn main() {}
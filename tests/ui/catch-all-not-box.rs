@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(catch_all)]
+    Other(std::io::Error),
+}
+
+fn main() {}
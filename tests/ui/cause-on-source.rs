@@ -0,0 +1,13 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("merge failed")]
+pub struct MergeError {
+    #[source]
+    #[cause]
+    left: io::Error,
+    right: io::Error,
+}
+
+fn main() {}
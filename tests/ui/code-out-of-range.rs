@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(code_range = 4000..5000)]
+pub enum Error {
+    #[error("card declined")]
+    #[error(code = "E4001")]
+    CardDeclined,
+    #[error("card expired")]
+    #[error(code = "E5000")]
+    CardExpired,
+}
+
+#[derive(Error, Debug)]
+#[naur(code_range = 4000..5000)]
+#[error("quota exceeded")]
+#[error(code = "QUOTA")]
+pub struct QuotaError;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    #[error(context)]
+    Context(String, #[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+#[throws(context_struct)]
+pub struct ReadError<T> {
+    path: T,
+    source: std::io::Error,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error)]
+#[naur(debug_chain)]
+#[error("failed to load {0}")]
+pub struct LoadError<T>(T);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(default = "{variant} failed")]
+    Failed,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("job failed")]
+#[error(default = "{variant} failed")]
+pub enum Error {
+    Fetch,
+    Parse,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+#![deny(deprecated)]
+
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+    #[deprecated = "use ReadError::Io"]
+    #[error("failed to read {path}")]
+    Legacy { path: String, source: io::Error },
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+}
+
+fn main() {
+    let result: Result<(), io::Error> = Ok(());
+    let _ = result.throw_legacy("/etc/app.toml".to_owned());
+}
@@ -0,0 +1,7 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("operation failed: {self}")]
+pub struct Error;
+
+fn main() {}
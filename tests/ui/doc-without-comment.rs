@@ -0,0 +1,7 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(doc)]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("card declined")]
+    #[error(code = "CARD_DECLINED")]
+    CardDeclined,
+    #[error("card expired")]
+    #[error(code = "CARD_DECLINED")]
+    CardExpired,
+}
+
+fn main() {}
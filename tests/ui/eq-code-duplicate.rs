@@ -0,0 +1,14 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(eq = "code")]
+pub enum Error {
+    #[error("not found")]
+    #[error(code = "E404")]
+    NotFound,
+    #[error("gone")]
+    #[error(code = "E404")]
+    Gone,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed")]
+#[naur(eq = "kind")]
+pub struct Error {
+    code: u16,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PaymentError {
+    #[error("card declined")]
+    Declined(#[error_id] u128),
+    #[error("gateway unavailable")]
+    Unavailable(#[error_id] String),
+}
+
+fn main() {}
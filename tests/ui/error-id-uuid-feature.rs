@@ -0,0 +1,19 @@
+use std::io;
+use thiserror::Error;
+
+mod uuid {
+    #[derive(Debug)]
+    pub struct Uuid;
+}
+
+#[derive(Error, Debug)]
+#[naur(constructors)]
+#[error("request failed")]
+pub struct RequestError {
+    #[from]
+    source: io::Error,
+    #[error_id]
+    id: uuid::Uuid,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid row")]
+pub struct RowError;
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("invalid rows")]
+    Rows(#[errors] Vec<RowError>),
+}
+
+fn main() {}
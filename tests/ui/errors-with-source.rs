@@ -0,0 +1,16 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid row")]
+pub struct RowError;
+
+#[derive(Error, Debug)]
+#[error("failed to import")]
+pub struct ImportError {
+    source: io::Error,
+    #[errors]
+    rows: Vec<RowError>,
+}
+
+fn main() {}
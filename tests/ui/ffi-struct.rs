@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(ffi)]
+#[error("...")]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,13 @@
+use std::fmt;
+use thiserror::Error;
+
+fn render(formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("rendered")
+}
+
+#[derive(Error, Debug)]
+#[error("message")]
+#[error(fmt = render)]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct Error {
+    #[from]
+    #[from(skip)]
+    source: std::io::Error,
+}
+
+fn main() {}
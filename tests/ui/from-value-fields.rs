@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("invalid port {port}")]
+    InvalidPort {
+        #[from_value]
+        port: u16,
+        attempt: u32,
+    },
+}
+
+fn main() {}
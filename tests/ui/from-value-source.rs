@@ -0,0 +1,13 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("failed")]
+    Io {
+        #[from_value]
+        source: io::Error,
+    },
+}
+
+fn main() {}
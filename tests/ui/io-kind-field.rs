@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct Error {
+    #[error(io_kind = NotFound)]
+    path: String,
+}
+
+fn main() {}
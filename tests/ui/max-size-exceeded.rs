@@ -0,0 +1,12 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(max_size = 16)]
+pub enum Error {
+    #[error("key {0} not found")]
+    NotFound(u64),
+    #[error("query {query} failed on {table}")]
+    Query { query: String, table: String },
+}
+
+fn main() {}
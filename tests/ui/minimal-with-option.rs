@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(minimal, accessors)]
+pub enum Error {
+    #[error("closed")]
+    Closed,
+}
+
+fn main() {}
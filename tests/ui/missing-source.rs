@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("failed to read config")]
+    Io(io::Error),
+}
+
+fn main() {}
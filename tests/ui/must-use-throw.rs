@@ -0,0 +1,17 @@
+#![deny(unused_must_use)]
+
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+#[throws(must_use)]
+pub struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+fn main() {
+    let result: Result<(), io::Error> = Ok(());
+    result.throw_read("/etc/app.toml".to_owned());
+}
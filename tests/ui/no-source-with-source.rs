@@ -0,0 +1,11 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to read config")]
+#[error(no_source)]
+pub struct ReadError {
+    source: io::Error,
+}
+
+fn main() {}
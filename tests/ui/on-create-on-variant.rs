@@ -0,0 +1,12 @@
+use thiserror::Error;
+
+fn hook(_error: &Error) {}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("failed")]
+    #[throws(on_create = hook)]
+    Failed,
+}
+
+fn main() {}
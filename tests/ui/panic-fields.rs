@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PluginError {
+    #[error(panic)]
+    #[error("plugin panicked: {reason}")]
+    Panicked { reason: String },
+}
+
+fn main() {}
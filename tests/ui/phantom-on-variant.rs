@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("failed")]
+    #[throws(phantom)]
+    Failed,
+}
+
+fn main() {}
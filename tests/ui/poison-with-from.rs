@@ -0,0 +1,11 @@
+use thiserror::{Error, Poisoned};
+
+#[derive(Error, Debug)]
+#[error("lock poisoned")]
+pub struct LockError {
+    #[from]
+    #[source(poison)]
+    source: Poisoned,
+}
+
+fn main() {}
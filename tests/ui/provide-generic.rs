@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("request failed")]
+pub struct RequestError<T> {
+    #[provide]
+    id: T,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed")]
+pub struct Error {
+    #[source]
+    #[related]
+    source: Vec<io::Error>,
+}
+
+fn main() {}
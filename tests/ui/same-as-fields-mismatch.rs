@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("failed to read {key}")]
+    Read { key: String },
+    #[error(same_as = Read)]
+    ReadReplica { name: String },
+}
+
+fn main() {}
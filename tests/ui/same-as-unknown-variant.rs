@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("store is closed")]
+    Closed,
+    #[error(same_as = Close)]
+    Draining,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(transparent)]
+pub struct Error {
+    #[sensitive]
+    source: io::Error,
+}
+
+fn main() {}
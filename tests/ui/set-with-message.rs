@@ -0,0 +1,14 @@
+use thiserror::{Error, ErrorSet};
+
+#[derive(Error, Debug)]
+#[error("cache miss")]
+pub struct CacheMiss;
+
+#[derive(ErrorSet, Debug)]
+pub enum ApiError {
+    #[set]
+    #[error("cache")]
+    Cache(CacheMiss),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error)]
+#[naur(redacted_debug, stable_debug)]
+#[error("login failed")]
+pub struct Error {
+    #[sensitive]
+    password: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(suggestion = "try again later")]
+pub enum Error {
+    #[error("busy")]
+    Busy,
+}
+
+fn main() {}
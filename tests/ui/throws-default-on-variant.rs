@@ -0,0 +1,11 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("read failed")]
+    #[throws(default)]
+    Read(#[from] io::Error),
+}
+
+fn main() {}
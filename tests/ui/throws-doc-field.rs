@@ -0,0 +1,12 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("failed to read {path}")]
+pub struct ReadError {
+    #[throws(doc(hidden))]
+    path: String,
+    source: io::Error,
+}
+
+fn main() {}
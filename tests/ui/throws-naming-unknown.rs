@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(throws_naming(kebab))]
+#[error("...")]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(throws_prefix = "or-")]
+#[error("...")]
+pub struct Error {
+    source: std::io::Error,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("timed out")]
+#[error(transient)]
+#[error(permanent)]
+pub struct Error;
+
+fn main() {}
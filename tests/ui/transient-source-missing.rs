@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("timed out after {0} ms")]
+    #[error(transient = source)]
+    Timeout(u64),
+}
+
+fn main() {}
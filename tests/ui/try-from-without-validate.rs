@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("server responded with {0}")]
+    Status(#[naur(try_from)] u16),
+}
+
+fn main() {}
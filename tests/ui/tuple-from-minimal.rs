@@ -0,0 +1,14 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[naur(minimal)]
+pub enum Error {
+    #[error("failed to read {path}")]
+    #[throws(tuple_from)]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+fn main() {}
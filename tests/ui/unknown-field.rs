@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid request: {msgg} (code {code})")]
+pub struct Error {
+    msg: String,
+    code: u16,
+}
+
+fn main() {}
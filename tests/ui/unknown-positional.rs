@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("expected {0}, found {2}")]
+    Mismatch(String, String),
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("timed out")]
+    Timeout(#[source] Timeout),
+}
+
+#[derive(Error, Debug)]
+#[error("timed out")]
+pub struct Timeout;
+
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("cache miss")]
+    Miss,
+    #[error("cache timed out")]
+    #[error(up(AppError::Timeout))]
+    Timeout(Timeout),
+}
+
+fn main() {}